@@ -6,10 +6,14 @@ pub mod bundle_command;
 pub mod create;
 pub mod extract_params;
 pub mod extract_processors;
+#[cfg(feature = "audio-dev")]
+pub mod render;
 pub mod start;
 pub mod update;
 
 // Public command re-exports
 pub use bundle_command::BundleCommand;
 pub use create::CreateCommand;
-pub use start::StartCommand;
+#[cfg(feature = "audio-dev")]
+pub use render::RenderCommand;
+pub use start::{SidechainInput, StartCommand};
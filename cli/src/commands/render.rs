@@ -0,0 +1,509 @@
+//! Offline render command - streams an audio file through the plugin.
+//!
+//! This command produces reproducible renders without a DAW or audio device:
+//! 1. Builds the plugin (or uses the dylib passed with `--plugin`)
+//! 2. Loads parameter metadata and the dev processor vtable via FFI
+//! 3. Streams the input file block by block, resampled to the render rate
+//! 4. Processes each block with the parameter values at its start
+//! 5. Writes each processed block to the output file
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use wavecraft_bridge::PluginParamLoader;
+use wavecraft_dev_server::{DevAudioProcessor, FfiProcessor};
+
+use crate::project::{find_plugin_dylib, read_engine_package_name, ProjectMarkers};
+
+mod audio_file;
+mod automation;
+mod resample;
+
+pub use audio_file::SampleFormat;
+pub use automation::{parse_param_override, ParamOverride};
+
+use audio_file::{AudioReader, AudioWriter, FileFormat, FrameSink, FrameSource};
+use automation::{AutomationFile, ParameterTimeline};
+use resample::Resampled;
+
+/// Options for the `render` command.
+#[derive(Debug)]
+pub struct RenderCommand {
+    /// Audio file to process (.wav or .flac)
+    pub input: PathBuf,
+    /// File to write (.wav or .flac)
+    pub output: PathBuf,
+    /// Prebuilt plugin dylib; builds the current project when `None`
+    pub plugin: Option<PathBuf>,
+    /// Processing sample rate; the input file's rate when `None`
+    pub sample_rate: Option<u32>,
+    /// Samples per processing block
+    pub block_size: usize,
+    /// Seconds of silence appended to the input to capture tails
+    pub tail: f64,
+    /// Fixed parameter values
+    pub params: Vec<ParamOverride>,
+    /// JSON automation file
+    pub automation: Option<PathBuf>,
+    /// Output sample encoding; the format's default when `None`
+    pub bit_depth: Option<SampleFormat>,
+}
+
+impl RenderCommand {
+    pub fn execute(&self) -> Result<()> {
+        self.validate()?;
+
+        // Read everything that can fail cheaply before building the plugin.
+        let automation = match &self.automation {
+            Some(path) => automation::read_automation_file(path)?,
+            None => AutomationFile::new(),
+        };
+        let input = AudioReader::open(&self.input)?;
+
+        let dylib_path = match &self.plugin {
+            Some(path) => path.clone(),
+            None => build_project_plugin()?,
+        };
+        let loader = PluginParamLoader::load(&dylib_path)
+            .with_context(|| format!("Failed to load plugin from {}", dylib_path.display()))?;
+        let timeline = ParameterTimeline::new(loader.parameters(), &self.params, &automation)?;
+
+        // Declared after `loader` so it is dropped first, while the vtable's
+        // library is still loaded.
+        let mut processor = FfiProcessor::new(loader.dev_processor_vtable())
+            .context("Audio processor create() returned null")?;
+        let (num_inputs, num_outputs) = processor.default_layout();
+        let layout = ChannelLayout {
+            inputs: num_inputs as usize,
+            outputs: num_outputs as usize,
+        };
+
+        let sample_rate = self.sample_rate.unwrap_or(input.sample_rate());
+        println!(
+            "{} Rendering {} ({} Hz, {} ch) at {} Hz, {} in / {} out, {} samples per block",
+            style("→").cyan(),
+            self.input.display(),
+            input.sample_rate(),
+            input.channels(),
+            sample_rate,
+            layout.inputs,
+            layout.outputs,
+            self.block_size
+        );
+
+        let mut input: Box<dyn FrameSource> = if sample_rate == input.sample_rate() {
+            Box::new(input)
+        } else {
+            Box::new(Resampled::new(input, sample_rate)?)
+        };
+        let sample_format = match self.bit_depth {
+            Some(format) => format,
+            None => FileFormat::from_path(&self.output)?.default_sample_format(),
+        };
+        let mut output =
+            AudioWriter::create(&self.output, layout.outputs, sample_rate, sample_format)?;
+
+        let frames = render(
+            &mut processor,
+            &timeline,
+            layout,
+            input.as_mut(),
+            &mut output,
+            self.block_size,
+            (self.tail * f64::from(sample_rate)).round() as usize,
+        )?;
+        drop(processor);
+        output
+            .finalize()
+            .with_context(|| format!("Failed to write {}", self.output.display()))?;
+
+        println!(
+            "{} Wrote {} ({:.2} s)",
+            style("✓").green(),
+            self.output.display(),
+            frames as f64 / f64::from(sample_rate)
+        );
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        FileFormat::from_path(&self.input)?;
+        let output_format = FileFormat::from_path(&self.output)?;
+
+        if self.block_size == 0 {
+            bail!("--block-size must be at least 1");
+        }
+        if self.sample_rate == Some(0) {
+            bail!("--sample-rate must be greater than 0");
+        }
+        if !self.tail.is_finite() || self.tail < 0.0 {
+            bail!("--tail must be a non-negative number of seconds");
+        }
+        if output_format == FileFormat::Flac && self.bit_depth == Some(SampleFormat::Float32) {
+            bail!("FLAC stores integer samples; use --bit-depth 16 or 24");
+        }
+        if let Some(plugin) = &self.plugin {
+            if !plugin.exists() {
+                bail!("Plugin dylib not found: {}", plugin.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Main-bus channel counts the plugin is rendered with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ChannelLayout {
+    inputs: usize,
+    outputs: usize,
+}
+
+/// Stream `input` through `processor` into `output` and return the number of
+/// frames written.
+///
+/// The processor runs with its declared main-bus layout. Each output channel
+/// starts from the input channel with the same index (the last one when the
+/// file has fewer channels), or from silence when the plugin has no inputs.
+/// `tail_frames` of silence follow the input.
+fn render(
+    processor: &mut dyn DevAudioProcessor,
+    timeline: &ParameterTimeline,
+    layout: ChannelLayout,
+    input: &mut dyn FrameSource,
+    output: &mut dyn FrameSink,
+    block_size: usize,
+    tail_frames: usize,
+) -> Result<u64> {
+    processor.set_channel_layout(layout.inputs as u32, layout.outputs as u32);
+    processor.set_sample_rate(input.sample_rate() as f32);
+    processor.reset();
+
+    let sample_rate = f64::from(input.sample_rate());
+    let mut values = vec![0.0; timeline.parameter_count()];
+    let mut input_block = vec![vec![0.0; block_size]; input.channels()];
+    let mut block = vec![vec![0.0; block_size]; layout.outputs];
+    let mut input_done = false;
+    let mut tail_remaining = tail_frames;
+    let mut position: u64 = 0;
+
+    loop {
+        let read = if input_done {
+            0
+        } else {
+            input.read(&mut input_block)?
+        };
+        input_done |= read < block_size;
+
+        for (channel, samples) in block.iter_mut().enumerate() {
+            if layout.inputs > 0 {
+                let source = &input_block[channel.min(input_block.len() - 1)];
+                samples[..read].copy_from_slice(&source[..read]);
+                samples[read..].fill(0.0);
+            } else {
+                samples.fill(0.0);
+            }
+        }
+
+        let tail = (block_size - read).min(tail_remaining);
+        tail_remaining -= tail;
+        let frames = read + tail;
+        if frames == 0 {
+            break;
+        }
+
+        timeline.values_at(position as f64 / sample_rate, &mut values);
+        processor.apply_plain_values(&values);
+        let mut channels: Vec<&mut [f32]> = block
+            .iter_mut()
+            .map(|samples| &mut samples[..frames])
+            .collect();
+        processor.process(&mut channels);
+
+        output.write(&block, frames)?;
+        position += frames as u64;
+    }
+
+    Ok(position)
+}
+
+/// Build the current project's plugin library and return its path.
+fn build_project_plugin() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    let project = ProjectMarkers::detect(&cwd)
+        .context("Run `wavecraft render` from a plugin project, or pass --plugin <dylib>")?;
+
+    println!("{} Building plugin...", style("→").cyan());
+    run_dev_build(&project.engine_dir)?;
+    find_plugin_dylib(&project.engine_dir).context("Failed to find plugin library after build")
+}
+
+/// Same build `wavecraft start` uses: the dev processor exports without the
+/// nih-plug plugin exports.
+fn run_dev_build(engine_dir: &Path) -> Result<()> {
+    let mut build_cmd = Command::new("cargo");
+    build_cmd.args(["build", "--lib", "--features", "_param-discovery"]);
+
+    if let Some(package_name) = read_engine_package_name(engine_dir) {
+        build_cmd.args(["--package", &package_name]);
+    }
+
+    let status = build_cmd
+        .current_dir(engine_dir)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("Failed to run cargo build")?;
+    if !status.success() {
+        bail!("Plugin build failed");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_protocol::{ParameterInfo, ParameterType};
+
+    /// Applies the first parameter as a gain and records block sizes.
+    #[derive(Default)]
+    struct GainProcessor {
+        gain: f32,
+        blocks: Vec<usize>,
+        sample_rate: f32,
+        layout: (u32, u32),
+    }
+
+    impl DevAudioProcessor for GainProcessor {
+        fn process(&mut self, channels: &mut [&mut [f32]]) {
+            assert_eq!(channels.len(), self.layout.1 as usize);
+            self.blocks.push(channels[0].len());
+            for channel in channels.iter_mut() {
+                channel.iter_mut().for_each(|sample| *sample *= self.gain);
+            }
+        }
+
+        fn apply_plain_values(&mut self, values: &[f32]) {
+            self.gain = values[0];
+        }
+
+        fn set_sample_rate(&mut self, sample_rate: f32) {
+            self.sample_rate = sample_rate;
+        }
+
+        fn set_channel_layout(&mut self, num_inputs: u32, num_outputs: u32) {
+            self.layout = (num_inputs, num_outputs);
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    /// In-memory input handing out `channels` in order.
+    struct MemorySource {
+        sample_rate: u32,
+        channels: Vec<Vec<f32>>,
+        position: usize,
+    }
+
+    impl MemorySource {
+        fn new(sample_rate: u32, channels: Vec<Vec<f32>>) -> Self {
+            Self {
+                sample_rate,
+                channels,
+                position: 0,
+            }
+        }
+    }
+
+    impl FrameSource for MemorySource {
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn channels(&self) -> usize {
+            self.channels.len()
+        }
+
+        fn read(&mut self, output: &mut [Vec<f32>]) -> Result<usize> {
+            let frames = output[0].len().min(self.channels[0].len() - self.position);
+            for (dest, source) in output.iter_mut().zip(&self.channels) {
+                dest[..frames].copy_from_slice(&source[self.position..self.position + frames]);
+            }
+            self.position += frames;
+            Ok(frames)
+        }
+    }
+
+    /// Collects the rendered channels.
+    #[derive(Default)]
+    struct MemorySink {
+        channels: Vec<Vec<f32>>,
+    }
+
+    impl FrameSink for MemorySink {
+        fn write(&mut self, channels: &[Vec<f32>], frames: usize) -> Result<()> {
+            self.channels.resize(channels.len(), Vec::new());
+            for (dest, source) in self.channels.iter_mut().zip(channels) {
+                dest.extend_from_slice(&source[..frames]);
+            }
+            Ok(())
+        }
+    }
+
+    const STEREO: ChannelLayout = ChannelLayout {
+        inputs: 2,
+        outputs: 2,
+    };
+
+    fn gain_parameter() -> Vec<ParameterInfo> {
+        vec![ParameterInfo {
+            id: "gain".to_string(),
+            name: "Gain".to_string(),
+            param_type: ParameterType::Float,
+            value: 1.0,
+            default: 1.0,
+            min: 0.0,
+            max: 2.0,
+            unit: None,
+            group: None,
+            variants: None,
+        }]
+    }
+
+    fn command(input: &str, output: &str) -> RenderCommand {
+        RenderCommand {
+            input: input.into(),
+            output: output.into(),
+            plugin: None,
+            sample_rate: None,
+            block_size: 512,
+            tail: 0.0,
+            params: Vec::new(),
+            automation: None,
+            bit_depth: None,
+        }
+    }
+
+    #[test]
+    fn renders_in_blocks_with_automation() {
+        let automation: AutomationFile = serde_json::from_str(
+            r#"{ "gain": [{ "time": 0, "value": 0.0 }, { "time": 1, "value": 2.0 }] }"#,
+        )
+        .unwrap();
+        let timeline = ParameterTimeline::new(&gain_parameter(), &[], &automation).unwrap();
+        let mut input = MemorySource::new(8, vec![vec![1.0; 10]]);
+        let mut output = MemorySink::default();
+
+        let mut processor = GainProcessor::default();
+        let frames = render(
+            &mut processor,
+            &timeline,
+            STEREO,
+            &mut input,
+            &mut output,
+            4,
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(frames, 12);
+        assert_eq!(processor.sample_rate, 8.0);
+        assert_eq!(processor.layout, (2, 2));
+        assert_eq!(processor.blocks, [4, 4, 4]);
+        // Gain is sampled at each block start: 0 s, 0.5 s, 1.0 s. The mono
+        // input feeds both channels; the last two samples are the silent tail.
+        let expected = [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 0.0, 0.0];
+        assert_eq!(output.channels, [expected.to_vec(), expected.to_vec()]);
+    }
+
+    #[test]
+    fn renders_with_the_plugin_channel_layout() {
+        let timeline =
+            ParameterTimeline::new(&gain_parameter(), &[], &AutomationFile::new()).unwrap();
+
+        // Stereo file through a mono effect: only the first channel is used.
+        let mut input = MemorySource::new(8, vec![vec![0.5; 6], vec![1.0; 6]]);
+        let mut output = MemorySink::default();
+        let mut processor = GainProcessor::default();
+        let mono = ChannelLayout {
+            inputs: 1,
+            outputs: 1,
+        };
+        render(
+            &mut processor,
+            &timeline,
+            mono,
+            &mut input,
+            &mut output,
+            4,
+            0,
+        )
+        .unwrap();
+        assert_eq!(processor.layout, (1, 1));
+        assert_eq!(output.channels, [vec![0.5; 6]]);
+
+        // Instruments get silence in, whatever the file holds.
+        let mut input = MemorySource::new(8, vec![vec![1.0; 6]]);
+        let mut output = MemorySink::default();
+        let mut processor = GainProcessor::default();
+        let instrument = ChannelLayout {
+            inputs: 0,
+            outputs: 2,
+        };
+        render(
+            &mut processor,
+            &timeline,
+            instrument,
+            &mut input,
+            &mut output,
+            4,
+            0,
+        )
+        .unwrap();
+        assert_eq!(processor.layout, (0, 2));
+        assert_eq!(output.channels, [vec![0.0; 6], vec![0.0; 6]]);
+    }
+
+    #[test]
+    fn renders_resampled_input() {
+        let timeline =
+            ParameterTimeline::new(&gain_parameter(), &[], &AutomationFile::new()).unwrap();
+        let source = MemorySource::new(44_100, vec![vec![0.0; 441], vec![0.0; 441]]);
+        let mut input = Resampled::new(source, 48_000).unwrap();
+        let mut output = MemorySink::default();
+
+        let mut processor = GainProcessor::default();
+        let frames = render(
+            &mut processor,
+            &timeline,
+            STEREO,
+            &mut input,
+            &mut output,
+            512,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(frames, 480);
+        assert_eq!(processor.sample_rate, 48_000.0);
+        assert_eq!(processor.blocks, [480]);
+    }
+
+    #[test]
+    fn validate_rejects_bad_options() {
+        assert!(command("in.wav", "out.wav").validate().is_ok());
+        assert!(command("in.mp3", "out.wav").validate().is_err());
+
+        let mut zero_block = command("in.wav", "out.wav");
+        zero_block.block_size = 0;
+        assert!(zero_block.validate().is_err());
+
+        let mut float_flac = command("in.wav", "out.flac");
+        float_flac.bit_depth = Some(SampleFormat::Float32);
+        assert!(float_flac.validate().is_err());
+
+        let mut missing_plugin = command("in.wav", "out.wav");
+        missing_plugin.plugin = Some("does/not/exist.so".into());
+        assert!(missing_plugin.validate().is_err());
+    }
+}
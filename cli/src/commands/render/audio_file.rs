@@ -0,0 +1,574 @@
+//! Audio file reading and writing for `wavecraft render`.
+//!
+//! Supports WAV (PCM and IEEE float, via `hound`) and FLAC (decoded with
+//! `claxon`, encoded with `flacenc`). The format is chosen from the file
+//! extension. Files are read and written block by block, so renders of long
+//! recordings never hold the whole file in memory.
+
+use anyhow::{anyhow, bail, Context, Result};
+use flacenc::bitsink::MemSink;
+use flacenc::component::{BitRepr, Stream};
+use flacenc::config;
+use flacenc::error::{Verified, Verify};
+use flacenc::source::{Context as FlacContext, Fill, FrameBuf};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Deinterleaved audio delivered block by block.
+pub trait FrameSource {
+    fn sample_rate(&self) -> u32;
+
+    fn channels(&self) -> usize;
+
+    /// Fill every channel of `output` from the start, up to the length of
+    /// its first channel, and return the number of frames written. Fewer
+    /// frames than requested are returned only at the end of the stream.
+    fn read(&mut self, output: &mut [Vec<f32>]) -> Result<usize>;
+}
+
+/// Destination for deinterleaved audio written block by block.
+pub trait FrameSink {
+    /// Append the first `frames` frames of `channels`.
+    fn write(&mut self, channels: &[Vec<f32>], frames: usize) -> Result<()>;
+}
+
+/// Container format of an audio file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Wav,
+    Flac,
+}
+
+impl FileFormat {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("wav" | "wave") => Ok(Self::Wav),
+            Some("flac") => Ok(Self::Flac),
+            _ => bail!(
+                "Unsupported audio file '{}': expected a .wav or .flac extension",
+                path.display()
+            ),
+        }
+    }
+
+    /// Sample format used when `--bit-depth` is not given.
+    pub fn default_sample_format(self) -> SampleFormat {
+        match self {
+            Self::Wav => SampleFormat::Float32,
+            Self::Flac => SampleFormat::Int24,
+        }
+    }
+}
+
+/// Sample encoding of a written file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SampleFormat {
+    /// 16-bit integer PCM
+    #[value(name = "16")]
+    Int16,
+    /// 24-bit integer PCM
+    #[value(name = "24")]
+    Int24,
+    /// 32-bit IEEE float (WAV only)
+    #[default]
+    #[value(name = "32f")]
+    Float32,
+}
+
+impl SampleFormat {
+    pub fn bits(self) -> u16 {
+        match self {
+            Self::Int16 => 16,
+            Self::Int24 => 24,
+            Self::Float32 => 32,
+        }
+    }
+}
+
+/// Streaming decoder for a WAV or FLAC file.
+pub struct AudioReader {
+    sample_rate: u32,
+    channels: usize,
+    decoder: Decoder,
+}
+
+enum Decoder {
+    WavFloat(hound::WavReader<BufReader<File>>),
+    WavInt {
+        reader: hound::WavReader<BufReader<File>>,
+        bits: u32,
+    },
+    Flac {
+        reader: claxon::FlacReader<File>,
+        bits: u32,
+        /// Decoded block being handed out and the next frame to read from it
+        block: claxon::Block,
+        position: u32,
+    },
+}
+
+impl AudioReader {
+    pub fn open(path: &Path) -> Result<Self> {
+        let reader = match FileFormat::from_path(path)? {
+            FileFormat::Wav => {
+                let reader = hound::WavReader::open(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let spec = reader.spec();
+                let decoder = match spec.sample_format {
+                    hound::SampleFormat::Float => Decoder::WavFloat(reader),
+                    hound::SampleFormat::Int => Decoder::WavInt {
+                        reader,
+                        bits: u32::from(spec.bits_per_sample),
+                    },
+                };
+                Self {
+                    sample_rate: spec.sample_rate,
+                    channels: usize::from(spec.channels),
+                    decoder,
+                }
+            }
+            FileFormat::Flac => {
+                let reader = claxon::FlacReader::open(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let info = reader.streaminfo();
+                Self {
+                    sample_rate: info.sample_rate,
+                    channels: info.channels as usize,
+                    decoder: Decoder::Flac {
+                        reader,
+                        bits: info.bits_per_sample,
+                        block: claxon::Block::empty(),
+                        position: 0,
+                    },
+                }
+            }
+        };
+
+        if reader.channels == 0 || reader.sample_rate == 0 {
+            bail!("{} contains no audio", path.display());
+        }
+        Ok(reader)
+    }
+
+    fn read_frames(&mut self, output: &mut [Vec<f32>]) -> Result<usize> {
+        let frames = output.first().map_or(0, Vec::len);
+        let channels = self.channels;
+
+        match &mut self.decoder {
+            Decoder::WavFloat(reader) => read_interleaved(
+                reader.samples::<f32>(),
+                channels,
+                output,
+                frames,
+                |sample| sample,
+            ),
+            Decoder::WavInt { reader, bits } => {
+                let bits = *bits;
+                read_interleaved(
+                    reader.samples::<i32>(),
+                    channels,
+                    output,
+                    frames,
+                    |sample| dequantize(sample, bits),
+                )
+            }
+            Decoder::Flac {
+                reader,
+                bits,
+                block,
+                position,
+            } => {
+                let mut written = 0;
+                while written < frames {
+                    if *position == block.duration() {
+                        let buffer = std::mem::replace(block, claxon::Block::empty()).into_buffer();
+                        match reader.blocks().read_next_or_eof(buffer)? {
+                            Some(next) => *block = next,
+                            None => break,
+                        }
+                        *position = 0;
+                        continue;
+                    }
+
+                    let start = *position as usize;
+                    let count = (block.duration() as usize - start).min(frames - written);
+                    for (channel, samples) in output.iter_mut().enumerate() {
+                        let source = &block.channel(channel as u32)[start..start + count];
+                        for (sample, &value) in samples[written..].iter_mut().zip(source) {
+                            *sample = dequantize(value, *bits);
+                        }
+                    }
+                    *position += count as u32;
+                    written += count;
+                }
+                Ok(written)
+            }
+        }
+    }
+}
+
+impl FrameSource for AudioReader {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn read(&mut self, output: &mut [Vec<f32>]) -> Result<usize> {
+        self.read_frames(output).context("Failed to decode input")
+    }
+}
+
+/// Deinterleave up to `frames` frames from `samples` into `output`.
+fn read_interleaved<S, E>(
+    mut samples: impl Iterator<Item = Result<S, E>>,
+    channels: usize,
+    output: &mut [Vec<f32>],
+    frames: usize,
+    convert: impl Fn(S) -> f32,
+) -> Result<usize>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    for frame in 0..frames {
+        for channel in output.iter_mut().take(channels) {
+            match samples.next() {
+                Some(sample) => channel[frame] = convert(sample?),
+                // A truncated final frame is dropped.
+                None => return Ok(frame),
+            }
+        }
+    }
+    Ok(frames)
+}
+
+/// Streaming encoder for a WAV or FLAC file.
+pub struct AudioWriter {
+    encoder: Encoder,
+}
+
+enum Encoder {
+    Wav {
+        writer: hound::WavWriter<BufWriter<File>>,
+        sample_format: SampleFormat,
+    },
+    Flac(Box<FlacWriter>),
+}
+
+impl AudioWriter {
+    pub fn create(
+        path: &Path,
+        channels: usize,
+        sample_rate: u32,
+        sample_format: SampleFormat,
+    ) -> Result<Self> {
+        let encoder = match FileFormat::from_path(path)? {
+            FileFormat::Wav => {
+                let spec = hound::WavSpec {
+                    channels: channels as u16,
+                    sample_rate,
+                    bits_per_sample: sample_format.bits(),
+                    sample_format: match sample_format {
+                        SampleFormat::Float32 => hound::SampleFormat::Float,
+                        SampleFormat::Int16 | SampleFormat::Int24 => hound::SampleFormat::Int,
+                    },
+                };
+                Encoder::Wav {
+                    writer: hound::WavWriter::create(path, spec)
+                        .with_context(|| format!("Failed to write {}", path.display()))?,
+                    sample_format,
+                }
+            }
+            FileFormat::Flac => {
+                if sample_format == SampleFormat::Float32 {
+                    bail!("FLAC stores integer samples; use --bit-depth 16 or 24");
+                }
+                let file = File::create(path)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                Encoder::Flac(Box::new(FlacWriter::new(
+                    file,
+                    channels,
+                    sample_rate,
+                    sample_format.bits(),
+                )?))
+            }
+        };
+        Ok(Self { encoder })
+    }
+
+    /// Flush buffered audio and complete the file's header.
+    pub fn finalize(self) -> Result<()> {
+        match self.encoder {
+            Encoder::Wav { writer, .. } => writer.finalize()?,
+            Encoder::Flac(writer) => writer.finalize()?,
+        }
+        Ok(())
+    }
+}
+
+impl FrameSink for AudioWriter {
+    fn write(&mut self, channels: &[Vec<f32>], frames: usize) -> Result<()> {
+        match &mut self.encoder {
+            Encoder::Wav {
+                writer,
+                sample_format,
+            } => {
+                for frame in 0..frames {
+                    for channel in channels {
+                        let sample = channel[frame];
+                        match sample_format {
+                            SampleFormat::Float32 => writer.write_sample(sample)?,
+                            SampleFormat::Int16 | SampleFormat::Int24 => {
+                                writer.write_sample(quantize(sample, sample_format.bits()))?
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Encoder::Flac(writer) => writer.write(channels, frames),
+        }
+    }
+}
+
+/// FLAC encoder that writes each frame as soon as it is complete.
+///
+/// `flacenc` encodes single frames; the STREAMINFO block is written up front
+/// and rewritten with the final length and MD5 once the stream ends.
+struct FlacWriter {
+    file: BufWriter<File>,
+    config: Verified<config::Encoder>,
+    stream: Stream,
+    bits: u16,
+    /// Interleaved samples waiting for a full frame
+    pending: Vec<i32>,
+    frame: (FrameBuf, FlacContext),
+}
+
+impl FlacWriter {
+    fn new(file: File, channels: usize, sample_rate: u32, bits: u16) -> Result<Self> {
+        let config = config::Encoder::default()
+            .into_verified()
+            .map_err(|(_, err)| anyhow!("Invalid FLAC encoder config: {err}"))?;
+        let mut stream = Stream::new(sample_rate as usize, channels, usize::from(bits))
+            .map_err(|err| anyhow!("Unsupported FLAC stream: {err}"))?;
+        // Every frame but the last has the configured size.
+        stream
+            .stream_info_mut()
+            .set_block_sizes(config.block_size, config.block_size)?;
+        let frame_buf = FrameBuf::with_size(channels, config.block_size)
+            .map_err(|err| anyhow!("Unsupported FLAC stream: {err}"))?;
+
+        let mut writer = Self {
+            file: BufWriter::new(file),
+            pending: Vec::with_capacity(config.block_size * channels),
+            frame: (frame_buf, FlacContext::new(usize::from(bits), channels)),
+            config,
+            stream,
+            bits,
+        };
+        writer.write_header()?;
+        Ok(writer)
+    }
+
+    fn channels(&self) -> usize {
+        self.stream.stream_info().channels()
+    }
+
+    fn write(&mut self, channels: &[Vec<f32>], frames: usize) -> Result<()> {
+        let frame_len = self.config.block_size * self.channels();
+        for frame in 0..frames {
+            for channel in channels {
+                self.pending.push(quantize(channel[frame], self.bits));
+            }
+            if self.pending.len() == frame_len {
+                self.encode_pending()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_pending(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        self.frame
+            .fill_interleaved(&self.pending)
+            .map_err(|err| anyhow!("Failed to buffer FLAC frame: {err}"))?;
+        self.pending.clear();
+
+        let frame_number = self.frame.1.current_frame_number().unwrap_or_default();
+        let frame = flacenc::encode_fixed_size_frame(
+            &self.config,
+            &self.frame.0,
+            frame_number,
+            self.stream.stream_info(),
+        )
+        .map_err(|err| anyhow!("Failed to encode FLAC frame: {err}"))?;
+        self.stream.stream_info_mut().update_frame_info(&frame);
+
+        let mut sink = MemSink::<u8>::with_capacity(frame.count_bits());
+        frame
+            .write(&mut sink)
+            .map_err(|err| anyhow!("Failed to encode FLAC frame: {err}"))?;
+        self.file.write_all(sink.as_slice())?;
+        Ok(())
+    }
+
+    /// Write the `fLaC` marker and the STREAMINFO block.
+    fn write_header(&mut self) -> Result<()> {
+        let header = Stream::with_stream_info(self.stream.stream_info().clone());
+        let mut sink = MemSink::<u8>::with_capacity(header.count_bits());
+        header
+            .write(&mut sink)
+            .map_err(|err| anyhow!("Failed to encode FLAC header: {err}"))?;
+        self.file.write_all(sink.as_slice())?;
+        Ok(())
+    }
+
+    fn finalize(mut self) -> Result<()> {
+        self.encode_pending()?;
+        let digest = self.frame.1.md5_digest();
+        let info = self.stream.stream_info_mut();
+        info.set_md5_digest(&digest);
+        if info.total_samples() == 0 {
+            // No frames: replace the "unknown" frame size sentinel.
+            info.set_frame_sizes(0, 0)?;
+        }
+
+        self.file.seek(SeekFrom::Start(0))?;
+        self.write_header()?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// Quantize a float sample to a signed integer of `bits` bits.
+fn quantize(sample: f32, bits: u16) -> i32 {
+    let scale = (1_i64 << (bits - 1)) as f64;
+    let max = scale - 1.0;
+    (f64::from(sample) * scale).round().clamp(-scale, max) as i32
+}
+
+/// Convert a signed integer sample of `bits` bits to float.
+fn dequantize(sample: i32, bits: u32) -> f32 {
+    (f64::from(sample) / (1_i64 << (bits - 1)) as f64) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frames: usize, channels: usize) -> Vec<Vec<f32>> {
+        (0..channels)
+            .map(|channel| {
+                (0..frames)
+                    .map(|n| (n as f32 * 0.01 * (channel + 1) as f32).sin() * 0.8)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Write `audio` in uneven blocks and read it back in other uneven blocks.
+    fn round_trip(path: &Path, audio: &[Vec<f32>], format: SampleFormat) -> Vec<Vec<f32>> {
+        let frames = audio[0].len();
+        let mut writer = AudioWriter::create(path, audio.len(), 44_100, format).unwrap();
+        let mut start = 0;
+        while start < frames {
+            let end = (start + 300).min(frames);
+            let block: Vec<Vec<f32>> = audio.iter().map(|c| c[start..end].to_vec()).collect();
+            writer.write(&block, end - start).unwrap();
+            start = end;
+        }
+        writer.finalize().unwrap();
+
+        let mut reader = AudioReader::open(path).unwrap();
+        assert_eq!(reader.sample_rate(), 44_100);
+        assert_eq!(reader.channels(), audio.len());
+        let mut decoded = vec![Vec::new(); audio.len()];
+        let mut block = vec![vec![0.0; 1_000]; audio.len()];
+        loop {
+            let read = reader.read(&mut block).unwrap();
+            for (channel, samples) in decoded.iter_mut().zip(&block) {
+                channel.extend_from_slice(&samples[..read]);
+            }
+            if read < 1_000 {
+                break;
+            }
+        }
+        decoded
+    }
+
+    fn assert_close(expected: &[Vec<f32>], actual: &[Vec<f32>], tolerance: f32) {
+        assert_eq!(expected.len(), actual.len());
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert_eq!(expected.len(), actual.len());
+            for (a, b) in expected.iter().zip(actual) {
+                assert!((a - b).abs() <= tolerance, "{a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(
+            FileFormat::from_path(Path::new("take.WAV")).unwrap(),
+            FileFormat::Wav
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("take.flac")).unwrap(),
+            FileFormat::Flac
+        );
+        assert!(FileFormat::from_path(Path::new("take.mp3")).is_err());
+    }
+
+    #[test]
+    fn quantize_clips_to_integer_range() {
+        assert_eq!(quantize(1.0, 16), i16::MAX as i32);
+        assert_eq!(quantize(-1.5, 16), i16::MIN as i32);
+        assert_eq!(quantize(0.5, 24), 1 << 22);
+        assert_eq!(dequantize(1 << 22, 24), 0.5);
+    }
+
+    #[test]
+    fn wav_round_trips_in_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = sine(2_500, 2);
+
+        let float = round_trip(&dir.path().join("float.wav"), &audio, SampleFormat::Float32);
+        assert_eq!(float, audio);
+
+        let int16 = round_trip(&dir.path().join("int16.wav"), &audio, SampleFormat::Int16);
+        assert_close(&audio, &int16, 1.0 / 32_768.0);
+    }
+
+    #[test]
+    fn flac_round_trips_across_several_frames() {
+        let dir = tempfile::tempdir().unwrap();
+        // Several full FLAC frames plus a short final one.
+        let audio = sine(10_000, 2);
+        let path = dir.path().join("take.flac");
+
+        let decoded = round_trip(&path, &audio, SampleFormat::Int24);
+        assert_close(&audio, &decoded, 1.0 / 8_388_608.0);
+
+        let info = claxon::FlacReader::open(&path).unwrap().streaminfo();
+        assert_eq!(info.samples, Some(10_000));
+        assert_eq!(info.bits_per_sample, 24);
+    }
+
+    #[test]
+    fn flac_rejects_float_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("take.flac");
+        assert!(AudioWriter::create(&path, 2, 44_100, SampleFormat::Float32).is_err());
+    }
+}
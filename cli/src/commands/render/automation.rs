@@ -0,0 +1,298 @@
+//! Parameter overrides and automation for offline renders.
+//!
+//! Automation files map parameter IDs to breakpoints in seconds:
+//!
+//! ```json
+//! {
+//!   "input_trim_level": [
+//!     { "time": 0.0, "value": 0.5 },
+//!     { "time": 4.0, "value": 1.0 }
+//!   ]
+//! }
+//! ```
+//!
+//! Values are plain (in the parameter's declared range) and interpolate
+//! linearly between breakpoints, holding the first and last values outside
+//! them.
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use wavecraft_protocol::ParameterInfo;
+
+/// A `--param id=value` override.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamOverride {
+    pub id: String,
+    pub value: f32,
+}
+
+/// Parse a `--param` argument of the form `id=value`.
+pub fn parse_param_override(arg: &str) -> Result<ParamOverride, String> {
+    let (id, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected `id=value`, got `{arg}`"))?;
+    let id = id.trim();
+    if id.is_empty() {
+        return Err(format!("missing parameter ID in `{arg}`"));
+    }
+    let value = value
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("invalid value for `{id}`: `{}`", value.trim()))?;
+
+    Ok(ParamOverride {
+        id: id.to_string(),
+        value,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AutomationPoint {
+    /// Time in seconds from the start of the render.
+    pub time: f64,
+    pub value: f32,
+}
+
+pub type AutomationFile = BTreeMap<String, Vec<AutomationPoint>>;
+
+pub fn read_automation_file(path: &Path) -> Result<AutomationFile> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read automation file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid automation file {}", path.display()))
+}
+
+enum Lane {
+    Constant(f32),
+    Automated(Vec<AutomationPoint>),
+}
+
+impl Lane {
+    fn value_at(&self, time: f64) -> f32 {
+        let points = match self {
+            Self::Constant(value) => return *value,
+            Self::Automated(points) => points,
+        };
+
+        let next = points.partition_point(|point| point.time <= time);
+        match (
+            next.checked_sub(1).map(|index| points[index]),
+            points.get(next),
+        ) {
+            (None, Some(first)) => first.value,
+            (Some(last), None) => last.value,
+            (Some(from), Some(to)) => {
+                let position = ((time - from.time) / (to.time - from.time)) as f32;
+                from.value + (to.value - from.value) * position
+            }
+            (None, None) => unreachable!("automation lanes are never empty"),
+        }
+    }
+}
+
+/// Plain parameter values over the course of a render, in the plugin's
+/// canonical parameter order.
+pub struct ParameterTimeline {
+    lanes: Vec<Lane>,
+}
+
+impl ParameterTimeline {
+    /// Build the timeline from parameter defaults, `--param` overrides and
+    /// automation lanes.
+    ///
+    /// Unknown IDs, values outside a parameter's range and parameters that
+    /// are both overridden and automated are rejected so a render never
+    /// silently differs from what was asked for.
+    pub fn new(
+        parameters: &[ParameterInfo],
+        overrides: &[ParamOverride],
+        automation: &AutomationFile,
+    ) -> Result<Self> {
+        let mut lanes: Vec<Lane> = parameters
+            .iter()
+            .map(|parameter| Lane::Constant(parameter.default))
+            .collect();
+
+        for param_override in overrides {
+            let (index, parameter) = find_parameter(parameters, &param_override.id)?;
+            check_range(parameter, param_override.value)?;
+            lanes[index] = Lane::Constant(param_override.value);
+        }
+
+        for (id, points) in automation {
+            let (index, parameter) = find_parameter(parameters, id)?;
+            ensure!(
+                !overrides.iter().any(|o| &o.id == id),
+                "Parameter '{id}' is both overridden with --param and automated"
+            );
+            ensure!(!points.is_empty(), "Automation for '{id}' has no points");
+            for pair in points.windows(2) {
+                ensure!(
+                    pair[0].time <= pair[1].time,
+                    "Automation points for '{id}' must be sorted by time"
+                );
+            }
+            for point in points {
+                ensure!(
+                    point.time.is_finite() && point.time >= 0.0,
+                    "Automation time {} for '{id}' must be a non-negative number of seconds",
+                    point.time
+                );
+                check_range(parameter, point.value)?;
+            }
+            lanes[index] = Lane::Automated(points.clone());
+        }
+
+        Ok(Self { lanes })
+    }
+
+    /// Number of parameters, and so of values written by `values_at`.
+    pub fn parameter_count(&self) -> usize {
+        self.lanes.len()
+    }
+
+    /// Write the value of every parameter at `time` seconds into `values`.
+    pub fn values_at(&self, time: f64, values: &mut [f32]) {
+        for (value, lane) in values.iter_mut().zip(&self.lanes) {
+            *value = lane.value_at(time);
+        }
+    }
+}
+
+fn find_parameter<'a>(
+    parameters: &'a [ParameterInfo],
+    id: &str,
+) -> Result<(usize, &'a ParameterInfo)> {
+    parameters
+        .iter()
+        .enumerate()
+        .find(|(_, parameter)| parameter.id == id)
+        .with_context(|| {
+            let known: Vec<&str> = parameters.iter().map(|p| p.id.as_str()).collect();
+            format!(
+                "Unknown parameter '{id}'. Available parameters: {}",
+                known.join(", ")
+            )
+        })
+}
+
+fn check_range(parameter: &ParameterInfo, value: f32) -> Result<()> {
+    if !(parameter.min..=parameter.max).contains(&value) {
+        bail!(
+            "Value {value} for '{}' is outside its range {}..={}",
+            parameter.id,
+            parameter.min,
+            parameter.max
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_protocol::ParameterType;
+
+    fn parameter(id: &str, default: f32, min: f32, max: f32) -> ParameterInfo {
+        ParameterInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            param_type: ParameterType::Float,
+            value: default,
+            default,
+            min,
+            max,
+            unit: None,
+            group: None,
+            variants: None,
+        }
+    }
+
+    fn parameters() -> Vec<ParameterInfo> {
+        vec![
+            parameter("input_trim_level", 1.0, 0.0, 2.0),
+            parameter("filter_cutoff", 1000.0, 20.0, 20_000.0),
+        ]
+    }
+
+    #[test]
+    fn parses_param_overrides() {
+        assert_eq!(
+            parse_param_override("input_trim_level=0.5").unwrap(),
+            ParamOverride {
+                id: "input_trim_level".to_string(),
+                value: 0.5
+            }
+        );
+        assert!(parse_param_override("input_trim_level").is_err());
+        assert!(parse_param_override("=0.5").is_err());
+        assert!(parse_param_override("gain=loud").is_err());
+        assert!(parse_param_override("gain=NaN").is_err());
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let overrides = [parse_param_override("filter_cutoff=440").unwrap()];
+        let timeline =
+            ParameterTimeline::new(&parameters(), &overrides, &AutomationFile::new()).unwrap();
+
+        let mut values = [0.0; 2];
+        timeline.values_at(3.0, &mut values);
+        assert_eq!(values, [1.0, 440.0]);
+    }
+
+    #[test]
+    fn automation_interpolates_and_holds_ends() {
+        let automation: AutomationFile = serde_json::from_str(
+            r#"{ "input_trim_level": [
+                { "time": 1.0, "value": 0.0 },
+                { "time": 3.0, "value": 2.0 },
+                { "time": 3.0, "value": 0.5 }
+            ] }"#,
+        )
+        .unwrap();
+        let timeline = ParameterTimeline::new(&parameters(), &[], &automation).unwrap();
+
+        let mut values = [0.0; 2];
+        let mut trim_at = |time| {
+            timeline.values_at(time, &mut values);
+            values[0]
+        };
+        assert_eq!(trim_at(0.0), 0.0);
+        assert_eq!(trim_at(2.0), 1.0);
+        // Equal times step to the later point.
+        assert_eq!(trim_at(3.0), 0.5);
+        assert_eq!(trim_at(10.0), 0.5);
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let params = parameters();
+        let empty = AutomationFile::new();
+        let unknown = [parse_param_override("drive=1").unwrap()];
+        let error = ParameterTimeline::new(&params, &unknown, &empty)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("input_trim_level, filter_cutoff"), "{error}");
+
+        let out_of_range = [parse_param_override("input_trim_level=3").unwrap()];
+        assert!(ParameterTimeline::new(&params, &out_of_range, &empty).is_err());
+
+        let unsorted: AutomationFile = serde_json::from_str(
+            r#"{ "filter_cutoff": [{ "time": 2, "value": 100 }, { "time": 1, "value": 200 }] }"#,
+        )
+        .unwrap();
+        assert!(ParameterTimeline::new(&params, &[], &unsorted).is_err());
+
+        let both = [parse_param_override("filter_cutoff=100").unwrap()];
+        let automated: AutomationFile =
+            serde_json::from_str(r#"{ "filter_cutoff": [{ "time": 0, "value": 100 }] }"#).unwrap();
+        assert!(ParameterTimeline::new(&params, &both, &automated).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! Sample rate conversion for renders at a rate other than the input file's.
+
+use anyhow::{Context, Result};
+use rubato::{FftFixedIn, Resampler as _};
+
+use super::audio_file::FrameSource;
+
+/// Input frames handed to the resampler per call.
+const CHUNK_FRAMES: usize = 1024;
+
+/// Streams a [`FrameSource`] through `rubato` at a new sample rate.
+///
+/// The resampler's delay is trimmed from the start and the output ends after
+/// `round(input_frames * ratio)` frames, so it lines up with the input.
+pub struct Resampled<S> {
+    source: S,
+    resampler: FftFixedIn<f32>,
+    sample_rate: u32,
+    ratio: f64,
+    input: Vec<Vec<f32>>,
+    output: Vec<Vec<f32>>,
+    /// Resampled frames in `output` not yet handed out
+    output_range: std::ops::Range<usize>,
+    /// Leading output frames still to drop
+    delay: usize,
+    input_frames: u64,
+    produced_frames: u64,
+    /// Output length, known once the source has ended
+    total_frames: Option<u64>,
+}
+
+impl<S: FrameSource> Resampled<S> {
+    pub fn new(source: S, sample_rate: u32) -> Result<Self> {
+        let channels = source.channels();
+        let resampler = FftFixedIn::new(
+            source.sample_rate() as usize,
+            sample_rate as usize,
+            CHUNK_FRAMES,
+            2,
+            channels,
+        )
+        .context("Failed to create resampler")?;
+
+        Ok(Self {
+            ratio: f64::from(sample_rate) / f64::from(source.sample_rate()),
+            input: vec![vec![0.0; resampler.input_frames_max()]; channels],
+            output: resampler.output_buffer_allocate(true),
+            output_range: 0..0,
+            delay: resampler.output_delay(),
+            input_frames: 0,
+            produced_frames: 0,
+            total_frames: None,
+            source,
+            resampler,
+            sample_rate,
+        })
+    }
+
+    /// Resample the next chunk of input. Returns `false` once the output is
+    /// complete.
+    fn refill(&mut self) -> Result<bool> {
+        if self
+            .total_frames
+            .is_some_and(|total| self.produced_frames >= total)
+        {
+            return Ok(false);
+        }
+
+        let needed = self.resampler.input_frames_next();
+        let read = if self.total_frames.is_none() {
+            self.input.iter_mut().for_each(|c| c.resize(needed, 0.0));
+            self.source.read(&mut self.input)?
+        } else {
+            0
+        };
+        self.input_frames += read as u64;
+        if read < needed {
+            // Past the end of the input: flush the resampler with silence.
+            self.input.iter_mut().for_each(|c| c[read..].fill(0.0));
+            self.total_frames
+                .get_or_insert((self.input_frames as f64 * self.ratio).round() as u64);
+        }
+
+        let (_, written) = self
+            .resampler
+            .process_into_buffer(&self.input, &mut self.output, None)
+            .context("Failed to resample input")?;
+
+        let skip = self.delay.min(written);
+        self.delay -= skip;
+        let mut end = written;
+        if let Some(total) = self.total_frames {
+            let remaining = total.saturating_sub(self.produced_frames) as usize;
+            end = end.min(skip + remaining);
+        }
+        self.produced_frames += (end - skip) as u64;
+        self.output_range = skip..end;
+        Ok(true)
+    }
+}
+
+impl<S: FrameSource> FrameSource for Resampled<S> {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn channels(&self) -> usize {
+        self.source.channels()
+    }
+
+    fn read(&mut self, output: &mut [Vec<f32>]) -> Result<usize> {
+        let frames = output.first().map_or(0, Vec::len);
+        let mut written = 0;
+        while written < frames {
+            if self.output_range.is_empty() {
+                if !self.refill()? {
+                    break;
+                }
+                continue;
+            }
+
+            let count = self.output_range.len().min(frames - written);
+            let source = self.output_range.start..self.output_range.start + count;
+            for (dest, resampled) in output.iter_mut().zip(&self.output) {
+                dest[written..written + count].copy_from_slice(&resampled[source.clone()]);
+            }
+            self.output_range.start += count;
+            written += count;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// In-memory source handing out `channels` in order.
+    struct Memory {
+        sample_rate: u32,
+        channels: Vec<Vec<f32>>,
+        position: usize,
+    }
+
+    impl FrameSource for Memory {
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn channels(&self) -> usize {
+            self.channels.len()
+        }
+
+        fn read(&mut self, output: &mut [Vec<f32>]) -> Result<usize> {
+            let frames = output[0].len().min(self.channels[0].len() - self.position);
+            for (dest, source) in output.iter_mut().zip(&self.channels) {
+                dest[..frames].copy_from_slice(&source[self.position..self.position + frames]);
+            }
+            self.position += frames;
+            Ok(frames)
+        }
+    }
+
+    fn sine(frequency: f64, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|n| (2.0 * PI * frequency * n as f64 / f64::from(sample_rate)).sin() as f32)
+            .collect()
+    }
+
+    fn resample(input: Vec<f32>, from: u32, to: u32) -> Vec<f32> {
+        let source = Memory {
+            sample_rate: from,
+            channels: vec![input],
+            position: 0,
+        };
+        let mut resampled = Resampled::new(source, to).unwrap();
+        let mut output = Vec::new();
+        let mut block = vec![vec![0.0; 300]];
+        loop {
+            let read = resampled.read(&mut block).unwrap();
+            output.extend_from_slice(&block[0][..read]);
+            if read < 300 {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn output_length_follows_the_ratio() {
+        assert_eq!(resample(vec![0.0; 441], 44_100, 48_000).len(), 480);
+        assert_eq!(resample(vec![0.0; 4_800], 48_000, 44_100).len(), 4_410);
+    }
+
+    #[test]
+    fn preserves_in_band_sine() {
+        for (from, to) in [(44_100, 48_000), (48_000, 44_100), (48_000, 96_000)] {
+            let output = resample(sine(1000.0, from, 4_410), from, to);
+            let expected = sine(1000.0, to, output.len());
+            // Skip the edges, where the filter runs off the input.
+            let error = output[400..output.len() - 400]
+                .iter()
+                .zip(&expected[400..])
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0_f32, f32::max);
+            assert!(error < 2e-3, "{from} -> {to}: max error {error}");
+        }
+    }
+
+    #[test]
+    fn removes_content_above_the_new_nyquist() {
+        let output = resample(sine(20_000.0, 48_000, 4_800), 48_000, 22_050);
+        let peak = output[400..output.len() - 400]
+            .iter()
+            .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+        assert!(peak < 0.01, "peak {peak}");
+    }
+}
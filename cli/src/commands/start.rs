@@ -35,6 +35,33 @@ pub struct StartCommand {
     pub install: bool,
     /// Fail if dependencies are missing (no prompt)
     pub no_install: bool,
+    /// Sidechain input fed to the audio processor
+    pub sidechain: SidechainInput,
+    /// Reset the audio processor after it outputs NaN or Inf
+    pub reset_on_non_finite: bool,
+}
+
+/// Sidechain input source for `wavecraft start`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SidechainInput {
+    /// No sidechain input
+    #[default]
+    None,
+    /// Input device channels 3 and 4
+    Input,
+    /// Built-in 120 BPM pulse for testing duckers and gates
+    Pulse,
+}
+
+/// Audio runtime options passed from the command to the audio runtime.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct AudioOptions {
+    /// Keep the dev servers running if audio fails to start
+    pub allow_no_audio: bool,
+    /// Sidechain input fed to the audio processor
+    pub sidechain: SidechainInput,
+    /// Reset the audio processor after it outputs NaN or Inf
+    pub reset_on_non_finite: bool,
 }
 
 pub(super) const ALLOW_NO_AUDIO_ENV: &str = "WAVECRAFT_ALLOW_NO_AUDIO";
@@ -61,7 +88,16 @@ impl StartCommand {
 
         // 3. Start servers
         tsconfig_paths::ensure_sdk_ui_paths_for_typescript(&project)?;
-        startup_pipeline::run_dev_servers(&project, self.port, self.ui_port)
+        startup_pipeline::run_dev_servers(
+            &project,
+            self.port,
+            self.ui_port,
+            AudioOptions {
+                allow_no_audio: startup_pipeline::allow_no_audio_runtime_fallback(),
+                sidechain: self.sidechain,
+                reset_on_non_finite: self.reset_on_non_finite,
+            },
+        )
     }
 }
 
@@ -90,7 +126,11 @@ fn resolve_execution_mode_with_guard(
 }
 
 fn is_xtask_compatible_start(command: &StartCommand) -> bool {
-    command.ui_port == 5173 && !command.install && !command.no_install
+    command.ui_port == 5173
+        && !command.install
+        && !command.no_install
+        && command.sidechain == SidechainInput::None
+        && !command.reset_on_non_finite
 }
 
 fn xtask_delegation_guard_enabled() -> bool {
@@ -134,6 +174,8 @@ mod tests {
             ui_port: 5173,
             install: false,
             no_install: false,
+            sidechain: SidechainInput::None,
+            reset_on_non_finite: false,
         }
     }
 
@@ -203,6 +245,21 @@ mod tests {
         assert_eq!(mode, StartExecutionMode::DelegateToSdkXtask);
     }
 
+    #[test]
+    fn sdk_root_with_sidechain_stays_on_direct_start_flow() {
+        let temp = TempDir::new().expect("temp dir");
+        create_sdk_repo_root(temp.path());
+
+        let project = ProjectMarkers::detect(temp.path()).expect("sdk markers");
+        let command = StartCommand {
+            sidechain: SidechainInput::Pulse,
+            ..default_command()
+        };
+
+        let mode = resolve_execution_mode_with_guard(&project, &command, false);
+        assert_eq!(mode, StartExecutionMode::Direct);
+    }
+
     #[test]
     fn unrelated_directory_still_fails_project_detection() {
         let temp = TempDir::new().expect("temp dir");
@@ -10,6 +10,8 @@ use std::sync::Arc;
 #[cfg(feature = "audio-dev")]
 use crate::project::find_plugin_dylib;
 #[cfg(feature = "audio-dev")]
+use wavecraft_bridge::PresetLibrary;
+#[cfg(feature = "audio-dev")]
 use wavecraft_dev_server::{AtomicParameterBridge, DevServerHost, WsHandle};
 #[cfg(feature = "audio-dev")]
 use wavecraft_protocol::{AudioDiagnosticCode, AudioRuntimePhase, AudioRuntimeStatus};
@@ -28,6 +30,11 @@ pub(super) struct AudioStartupFailure {
     hint: Option<&'static str>,
 }
 
+/// Minimum time between audio status updates for sanitized output, so a
+/// processor stuck producing NaN doesn't flood the UI.
+#[cfg(feature = "audio-dev")]
+const OUTPUT_SANITIZED_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 #[cfg(feature = "audio-dev")]
 pub(super) fn status_for_running_audio(sample_rate: f32, buffer_size: u32) -> AudioRuntimeStatus {
     wavecraft_dev_server::audio_status(
@@ -97,8 +104,9 @@ fn try_start_audio_in_process(
     host: Arc<DevServerHost>,
     ws_handle: WsHandle,
     param_bridge: Arc<AtomicParameterBridge>,
+    audio: super::AudioOptions,
 ) -> Result<AudioStartupSuccess, AudioStartupFailure> {
-    use wavecraft_dev_server::{AudioConfig, AudioServer, FfiProcessor};
+    use wavecraft_dev_server::{AudioConfig, AudioServer, FfiProcessor, SidechainSource};
 
     println!();
     println!("{} Checking for audio processor...", style("→").cyan());
@@ -129,6 +137,12 @@ fn try_start_audio_in_process(
     let config = AudioConfig {
         sample_rate: 44100.0,
         buffer_size: 512,
+        sidechain: match audio.sidechain {
+            super::SidechainInput::None => SidechainSource::None,
+            super::SidechainInput::Input => SidechainSource::InputChannels,
+            super::SidechainInput::Pulse => SidechainSource::Pulse,
+        },
+        reset_on_non_finite: audio.reset_on_non_finite,
     };
     let target_sample_rate = config.sample_rate;
     let target_buffer_size = config.buffer_size;
@@ -157,36 +171,62 @@ fn try_start_audio_in_process(
         }
     };
 
+    let output_stats = server.output_stats();
+
     // Start audio server. Returns lock-free ring buffer consumers for
-    // meter and oscilloscope data (RT-safe: audio thread writes without allocations).
-    let (handle, mut meter_consumer, mut oscilloscope_consumer) = match server.start() {
-        Ok((h, meter, oscilloscope)) => (h, meter, oscilloscope),
-        Err(e) => {
-            println!(
-                "{}",
-                style(format!("⚠ Failed to start audio: {}", e)).yellow()
-            );
-            println!(
+    // meter, oscilloscope and spectrum data (RT-safe: audio thread writes
+    // without allocations).
+    let (handle, mut meter_consumer, mut oscilloscope_consumer, mut spectrum_consumer) =
+        match server.start() {
+            Ok((h, meter, oscilloscope, spectrum)) => (h, meter, oscilloscope, spectrum),
+            Err(e) => {
+                println!(
+                    "{}",
+                    style(format!("⚠ Failed to start audio: {}", e)).yellow()
+                );
+                println!(
                 "  Audio runtime startup failed (strict mode aborts; set {}=1 to continue without audio).",
                 super::ALLOW_NO_AUDIO_ENV
             );
 
-            return Err(AudioStartupFailure {
-                code: AudioDiagnosticCode::StreamStartFailed,
-                message: e.to_string(),
-                hint: Some("Check current audio device availability and retry."),
-            });
-        }
-    };
+                return Err(AudioStartupFailure {
+                    code: AudioDiagnosticCode::StreamStartFailed,
+                    message: e.to_string(),
+                    hint: Some("Check current audio device availability and retry."),
+                });
+            }
+        };
 
-    // Spawn a task that drains the lock-free meter ring buffer and
-    // forwards updates to WebSocket clients.
+    // Spawn a task that drains the lock-free meter and spectrum ring buffers
+    // and forwards updates to WebSocket clients. It also reports output
+    // sanitizer events as a degraded audio status, at most once per interval.
     tokio::spawn(async move {
-        use wavecraft_protocol::{IpcNotification, NOTIFICATION_METER_UPDATE};
+        use wavecraft_protocol::{
+            IpcNotification, NOTIFICATION_METER_UPDATE, NOTIFICATION_SPECTRUM_UPDATE,
+        };
 
         let mut interval = tokio::time::interval(std::time::Duration::from_millis(16));
+        let mut reported_counts = output_stats.counts();
+        let mut last_report: Option<std::time::Instant> = None;
         loop {
             interval.tick().await;
+
+            let counts = output_stats.counts();
+            if counts != reported_counts
+                && last_report.is_none_or(|at| at.elapsed() >= OUTPUT_SANITIZED_REPORT_INTERVAL)
+            {
+                reported_counts = counts;
+                last_report = Some(std::time::Instant::now());
+                if let Some(status) = wavecraft_dev_server::audio_output_sanitized_status(
+                    counts,
+                    Some(target_sample_rate),
+                    Some(target_buffer_size),
+                ) {
+                    host.set_audio_status(status.clone());
+                    let _ = ws_handle.broadcast_audio_status_changed(&status).await;
+                }
+            }
+
             // Drain all available meter frames, keeping only the latest.
             let mut latest = None;
             while let Ok(notification) = meter_consumer.pop() {
@@ -206,6 +246,18 @@ fn try_start_audio_in_process(
             if let Some(frame) = oscilloscope_consumer.read_latest() {
                 host.set_latest_oscilloscope_frame(frame.to_protocol_frame());
             }
+
+            if let Some(frame) = spectrum_consumer.read_latest() {
+                let frame = frame.to_protocol_frame();
+                host.set_latest_spectrum_frame(frame.clone());
+
+                if let Ok(json) = serde_json::to_string(&IpcNotification::new(
+                    NOTIFICATION_SPECTRUM_UPDATE,
+                    frame,
+                )) {
+                    ws_handle.broadcast(&json).await;
+                }
+            }
         }
     });
 
@@ -264,7 +316,7 @@ pub(super) fn start_audio_runtime(
     host: Arc<DevServerHost>,
     ws_handle: WsHandle,
     param_bridge: Arc<AtomicParameterBridge>,
-    allow_no_audio: bool,
+    audio: super::AudioOptions,
 ) -> Result<(
     Option<wavecraft_dev_server::AudioHandle>,
     Option<super::PluginLoader>,
@@ -288,7 +340,10 @@ pub(super) fn start_audio_runtime(
     }
 
     let runtime_loader = match load_runtime_plugin_loader(engine_dir) {
-        Ok(loader) => Some(loader),
+        Ok(loader) => {
+            host.set_presets(PresetLibrary::from_catalog(loader.presets().clone()));
+            Some(loader)
+        }
         Err(error) => {
             let message = error.to_string();
             let (code, hint) = classify_runtime_loader_error(&message);
@@ -315,7 +370,7 @@ pub(super) fn start_audio_runtime(
                 );
             }
 
-            if allow_no_audio {
+            if audio.allow_no_audio {
                 println!(
                     "{}",
                     style(format!(
@@ -338,6 +393,7 @@ pub(super) fn start_audio_runtime(
                 host.clone(),
                 ws_handle.clone(),
                 param_bridge.clone(),
+                audio,
             )
         }) {
             Ok(started) => {
@@ -373,7 +429,7 @@ pub(super) fn start_audio_runtime(
                     );
                 }
 
-                if allow_no_audio {
+                if audio.allow_no_audio {
                     println!(
                         "{}",
                         style(format!(
@@ -23,14 +23,19 @@ pub(super) fn parse_allow_no_audio_env(value: &str) -> bool {
     )
 }
 
-fn allow_no_audio_runtime_fallback() -> bool {
+pub(super) fn allow_no_audio_runtime_fallback() -> bool {
     std::env::var(super::ALLOW_NO_AUDIO_ENV)
         .map(|value| parse_allow_no_audio_env(&value))
         .unwrap_or(false)
 }
 
 /// Run both development servers.
-pub(super) fn run_dev_servers(project: &ProjectMarkers, ws_port: u16, ui_port: u16) -> Result<()> {
+pub(super) fn run_dev_servers(
+    project: &ProjectMarkers,
+    ws_port: u16,
+    ui_port: u16,
+    audio: super::AudioOptions,
+) -> Result<()> {
     println!();
     println!(
         "{}",
@@ -136,7 +141,7 @@ pub(super) fn run_dev_servers(project: &ProjectMarkers, ws_port: u16, ui_port: u
         host.clone(),
         server.handle(),
         param_bridge.clone(),
-        allow_no_audio_runtime_fallback(),
+        audio,
     )?;
     #[cfg(feature = "audio-dev")]
     let has_audio = audio_handle.is_some();
@@ -160,7 +165,7 @@ pub(super) fn run_dev_servers(project: &ProjectMarkers, ws_port: u16, ui_port: u
     println!("  UI:        http://localhost:{}", ui_port);
     if has_audio {
         println!("  Audio:     Real-time OS input (in-process FFI)");
-    } else if allow_no_audio_runtime_fallback() {
+    } else if audio.allow_no_audio {
         println!(
             "  Audio:     Disabled (degraded mode via {}=1)",
             super::ALLOW_NO_AUDIO_ENV
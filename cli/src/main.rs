@@ -8,7 +8,12 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
-use commands::{BundleCommand, CreateCommand, StartCommand};
+#[cfg(feature = "audio-dev")]
+use commands::{
+    render::{ParamOverride, SampleFormat},
+    RenderCommand,
+};
+use commands::{BundleCommand, CreateCommand, SidechainInput, StartCommand};
 
 /// SDK version derived from CLI package version at compile time.
 /// Used for git tag dependencies in generated projects.
@@ -91,6 +96,14 @@ enum Commands {
         /// Fail if node_modules is missing (CI mode, no prompts)
         #[arg(long)]
         no_install: bool,
+
+        /// Sidechain input for processors that declare one
+        #[arg(long, value_enum, default_value_t = SidechainInput::None)]
+        sidechain: SidechainInput,
+
+        /// Reset the processor when it outputs NaN or Inf
+        #[arg(long)]
+        reset_on_non_finite: bool,
     },
 
     /// Build plugin bundles (macOS-first, optional install)
@@ -104,6 +117,52 @@ enum Commands {
         install: bool,
     },
 
+    /// Render an audio file through the plugin offline
+    #[cfg(feature = "audio-dev")]
+    #[command(
+        long_about = "Stream a WAV or FLAC file through the plugin's audio processor and \
+        write the result, without a DAW or audio device.\n\n\
+        Builds the current project unless --plugin is given. Parameter values are applied \
+        at the start of every block, from defaults, --param overrides and an optional \
+        JSON automation file."
+    )]
+    Render {
+        /// Audio file to process (.wav or .flac)
+        input: PathBuf,
+
+        /// Output file (.wav or .flac)
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Prebuilt plugin library to load instead of building the current project
+        #[arg(long)]
+        plugin: Option<PathBuf>,
+
+        /// Processing sample rate in Hz (defaults to the input file's rate)
+        #[arg(long)]
+        sample_rate: Option<u32>,
+
+        /// Samples per processing block
+        #[arg(long, default_value = "512")]
+        block_size: usize,
+
+        /// Seconds of silence to append so reverb and delay tails are captured
+        #[arg(long, default_value = "0")]
+        tail: f64,
+
+        /// Fixed parameter value as `id=value` (repeatable)
+        #[arg(long = "param", value_name = "ID=VALUE", value_parser = commands::render::parse_param_override)]
+        params: Vec<ParamOverride>,
+
+        /// JSON file with per-parameter automation breakpoints
+        #[arg(long)]
+        automation: Option<PathBuf>,
+
+        /// Output sample encoding (WAV defaults to 32f, FLAC to 24)
+        #[arg(long, value_enum)]
+        bit_depth: Option<SampleFormat>,
+    },
+
     /// Update the CLI and project dependencies (Rust crates + npm packages)
     #[command(
         long_about = "Update the Wavecraft CLI to the latest version, then update Rust crates \
@@ -152,8 +211,40 @@ fn dispatch(command: Commands) -> Result<()> {
             ui_port,
             install,
             no_install,
-        } => run_start(port, ui_port, install, no_install),
+            sidechain,
+            reset_on_non_finite,
+        } => run_start(
+            port,
+            ui_port,
+            install,
+            no_install,
+            sidechain,
+            reset_on_non_finite,
+        ),
         Commands::Bundle { install } => run_bundle(install),
+        #[cfg(feature = "audio-dev")]
+        Commands::Render {
+            input,
+            output,
+            plugin,
+            sample_rate,
+            block_size,
+            tail,
+            params,
+            automation,
+            bit_depth,
+        } => RenderCommand {
+            input,
+            output,
+            plugin,
+            sample_rate,
+            block_size,
+            tail,
+            params,
+            automation,
+            bit_depth,
+        }
+        .execute(),
         Commands::Update { skip_self } => commands::update::run(skip_self),
         Commands::ExtractParams { dylib_path } => commands::extract_params::execute(dylib_path),
         Commands::ExtractProcessors { dylib_path } => {
@@ -184,12 +275,21 @@ fn run_create(
     .execute()
 }
 
-fn run_start(port: u16, ui_port: u16, install: bool, no_install: bool) -> Result<()> {
+fn run_start(
+    port: u16,
+    ui_port: u16,
+    install: bool,
+    no_install: bool,
+    sidechain: SidechainInput,
+    reset_on_non_finite: bool,
+) -> Result<()> {
     StartCommand {
         port,
         ui_port,
         install,
         no_install,
+        sidechain,
+        reset_on_non_finite,
     }
     .execute()
 }
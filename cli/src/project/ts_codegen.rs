@@ -176,7 +176,8 @@ pub fn write_parameter_types(ui_dir: &Path, params: &[ParameterInfo]) -> Result<
 /// resolves to a typed union of processor IDs in both internal SDK source
 /// modules and user project TypeScript compilation. It also registers
 /// discovered processors at runtime for `useHasProcessor` and
-/// `useAvailableProcessors` hooks.
+/// `useAvailableProcessors` hooks, and exports each processor's reported
+/// latency as `PROCESSOR_LATENCY_SAMPLES`.
 pub fn write_processor_types(ui_dir: &Path, processors: &[ProcessorInfo]) -> Result<()> {
     let generated_dir = ensure_generated_types_dir(ui_dir)?;
 
@@ -194,9 +195,11 @@ pub fn write_processor_types(ui_dir: &Path, processors: &[ProcessorInfo]) -> Res
     content.push_str(PROCESSOR_ID_MAP_AUGMENTED_MARKER);
     content.push_str(": true;\n");
 
-    let mut typed_ids: BTreeMap<&str, bool> = BTreeMap::new();
+    let mut typed_ids: BTreeMap<&str, u32> = BTreeMap::new();
     for processor in processors {
-        typed_ids.entry(processor.id.as_str()).or_insert(true);
+        typed_ids
+            .entry(processor.id.as_str())
+            .or_insert(processor.latency_samples);
     }
 
     for id in typed_ids.keys() {
@@ -221,6 +224,17 @@ pub fn write_processor_types(ui_dir: &Path, processors: &[ProcessorInfo]) -> Res
     }
     content.push_str("] as const;\n\n");
     content.push_str("registerAvailableProcessors(PROCESSOR_IDS);\n\n");
+
+    content
+        .push_str("export const PROCESSOR_LATENCY_SAMPLES: Readonly<Record<string, number>> = {\n");
+    for (id, latency_samples) in &typed_ids {
+        content.push_str("  ");
+        content.push_str(&ts_string_literal(id)?);
+        content.push_str(": ");
+        content.push_str(&latency_samples.to_string());
+        content.push_str(",\n");
+    }
+    content.push_str("};\n\n");
     content.push_str("export {};\n");
 
     let out_file = generated_dir.join("processors.ts");
@@ -401,7 +415,32 @@ mod tests {
     }
 
     fn processor(id: &str) -> ProcessorInfo {
-        ProcessorInfo { id: id.to_string() }
+        ProcessorInfo {
+            id: id.to_string(),
+            latency_samples: 0,
+        }
+    }
+
+    #[test]
+    fn emits_processor_latency_map() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let ui_dir = temp.path();
+
+        let processors = vec![
+            ProcessorInfo {
+                id: "limiter".to_string(),
+                latency_samples: 64,
+            },
+            processor("output_gain"),
+        ];
+        write_processor_types(ui_dir, &processors).expect("write should succeed");
+
+        let output_path = ui_dir.join("src/generated/processors.ts");
+        let output = fs::read_to_string(output_path).expect("generated file should exist");
+
+        assert!(output.contains(
+            "export const PROCESSOR_LATENCY_SAMPLES: Readonly<Record<string, number>> = {\n  'limiter': 64,\n  'output_gain': 0,\n};"
+        ));
     }
 
     #[test]
@@ -5,7 +5,10 @@
 
 use std::ffi::c_void;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use wavecraft_protocol::DevProcessorVTable;
+use wavecraft_protocol::{DEV_PROCESSOR_MAX_CHANNELS, DevProcessorVTable};
+
+/// Channel pointer array size for vtable calls.
+const MAX_CHANNELS: usize = DEV_PROCESSOR_MAX_CHANNELS as usize;
 
 /// Simplified audio processor trait for dev mode.
 ///
@@ -16,12 +19,41 @@ pub trait DevAudioProcessor: Send + 'static {
     /// Process deinterleaved audio in-place.
     fn process(&mut self, channels: &mut [&mut [f32]]);
 
+    /// Process deinterleaved audio in-place with an auxiliary sidechain input.
+    ///
+    /// The sidechain is read-only from the processor's point of view; it is
+    /// borrowed mutably only so it can cross the FFI boundary. Processors
+    /// without sidechain support ignore it.
+    fn process_with_sidechain(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        sidechain: &mut [&mut [f32]],
+    ) {
+        let _ = sidechain;
+        self.process(channels);
+    }
+
     /// Apply plain parameter values in canonical generation order.
     fn apply_plain_values(&mut self, values: &[f32]);
 
     /// Update the processor's sample rate.
     fn set_sample_rate(&mut self, sample_rate: f32);
 
+    /// Main-bus `(inputs, outputs)` the processor runs in unless told
+    /// otherwise.
+    ///
+    /// Stereo by default, for processors that accept any channel count.
+    fn default_layout(&self) -> (u32, u32) {
+        (2, 2)
+    }
+
+    /// Select the main-bus channel layout. Call before `set_sample_rate`.
+    ///
+    /// No-op by default, for processors that accept any channel count.
+    fn set_channel_layout(&mut self, num_inputs: u32, num_outputs: u32) {
+        let _ = (num_inputs, num_outputs);
+    }
+
     /// Reset processor state.
     fn reset(&mut self);
 }
@@ -74,11 +106,14 @@ impl FfiProcessor {
         Some((num_channels, channels[0].len() as u32))
     }
 
-    fn prepare_channel_ptrs(&self, channels: &mut [&mut [f32]]) -> Option<[*mut f32; 2]> {
+    fn prepare_channel_ptrs(
+        &self,
+        channels: &mut [&mut [f32]],
+    ) -> Option<[*mut f32; MAX_CHANNELS]> {
         // Real-time safety: use a stack-allocated array instead of Vec.
-        // Wavecraft targets stereo (2 channels). Guard against unexpected
-        // multi-channel input to avoid out-of-bounds access.
-        if channels.len() > 2 {
+        // Guard against buses wider than the vtable supports to avoid
+        // out-of-bounds access.
+        if channels.len() > MAX_CHANNELS {
             // Real-time safe reporting: set a one-shot flag and count events.
             // A non-RT path can poll and report via `take_unsupported_channel_count`
             // and `take_unsupported_channel_flag` if needed.
@@ -90,7 +125,7 @@ impl FfiProcessor {
 
         // Build fixed-size array of channel pointers for the C-ABI call.
         // No heap allocation — this lives on the stack.
-        let mut ptrs: [*mut f32; 2] = [std::ptr::null_mut(); 2];
+        let mut ptrs: [*mut f32; MAX_CHANNELS] = [std::ptr::null_mut(); MAX_CHANNELS];
         for (index, channel) in channels.iter_mut().enumerate() {
             ptrs[index] = channel.as_mut_ptr();
         }
@@ -99,7 +134,8 @@ impl FfiProcessor {
     }
 
     /// Non-RT diagnostic hook: returns and resets the count of callback
-    /// invocations that were skipped due to receiving more than 2 channels.
+    /// invocations that were skipped due to receiving more than
+    /// `DEV_PROCESSOR_MAX_CHANNELS` channels.
     pub fn take_unsupported_channel_count(&self) -> u32 {
         self.unsupported_channel_count.swap(0, Ordering::Relaxed)
     }
@@ -135,6 +171,43 @@ impl DevAudioProcessor for FfiProcessor {
         (self.vtable.process)(self.instance, ptrs.as_mut_ptr(), num_channels, num_samples);
     }
 
+    fn process_with_sidechain(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        sidechain: &mut [&mut [f32]],
+    ) {
+        let Some((num_channels, num_samples)) = Self::process_dimensions(channels) else {
+            return;
+        };
+
+        // A sidechain shorter than the block would be read out of bounds by
+        // the plugin; fall back to plain processing instead.
+        if sidechain.is_empty()
+            || sidechain
+                .iter()
+                .any(|channel| channel.len() < num_samples as usize)
+        {
+            self.process(channels);
+            return;
+        }
+
+        let Some(mut ptrs) = self.prepare_channel_ptrs(channels) else {
+            return;
+        };
+        let Some(mut sidechain_ptrs) = self.prepare_channel_ptrs(sidechain) else {
+            return;
+        };
+
+        (self.vtable.process_with_sidechain)(
+            self.instance,
+            ptrs.as_mut_ptr(),
+            num_channels,
+            sidechain_ptrs.as_mut_ptr(),
+            sidechain.len() as u32,
+            num_samples,
+        );
+    }
+
     fn apply_plain_values(&mut self, values: &[f32]) {
         if !self.supports_plain_values {
             return;
@@ -152,6 +225,18 @@ impl DevAudioProcessor for FfiProcessor {
         (self.vtable.set_sample_rate)(self.instance, sample_rate);
     }
 
+    /// The plugin's default (first declared) layout.
+    fn default_layout(&self) -> (u32, u32) {
+        (
+            self.vtable.default_num_inputs,
+            self.vtable.default_num_outputs,
+        )
+    }
+
+    fn set_channel_layout(&mut self, num_inputs: u32, num_outputs: u32) {
+        (self.vtable.set_channel_layout)(self.instance, num_inputs, num_outputs);
+    }
+
     fn reset(&mut self) {
         (self.vtable.reset)(self.instance);
     }
@@ -179,23 +264,27 @@ mod tests {
     static CREATE_CALLED: AtomicBool = AtomicBool::new(false);
     static PROCESS_CALLED: AtomicBool = AtomicBool::new(false);
     static SET_SAMPLE_RATE_CALLED: AtomicBool = AtomicBool::new(false);
+    static LAYOUT_OUTPUTS: AtomicU32 = AtomicU32::new(0);
     static RESET_CALLED: AtomicBool = AtomicBool::new(false);
     static DROP_CALLED: AtomicBool = AtomicBool::new(false);
     static APPLY_PLAIN_VALUES_CALLED: AtomicBool = AtomicBool::new(false);
     static APPLY_PLAIN_VALUES_LEN: AtomicU32 = AtomicU32::new(0);
     static PROCESS_CHANNELS: AtomicU32 = AtomicU32::new(0);
     static PROCESS_SAMPLES: AtomicU32 = AtomicU32::new(0);
+    static SIDECHAIN_CHANNELS: AtomicU32 = AtomicU32::new(0);
 
     fn reset_flags() {
         CREATE_CALLED.store(false, Ordering::SeqCst);
         PROCESS_CALLED.store(false, Ordering::SeqCst);
         SET_SAMPLE_RATE_CALLED.store(false, Ordering::SeqCst);
+        LAYOUT_OUTPUTS.store(0, Ordering::SeqCst);
         RESET_CALLED.store(false, Ordering::SeqCst);
         DROP_CALLED.store(false, Ordering::SeqCst);
         APPLY_PLAIN_VALUES_CALLED.store(false, Ordering::SeqCst);
         APPLY_PLAIN_VALUES_LEN.store(0, Ordering::SeqCst);
         PROCESS_CHANNELS.store(0, Ordering::SeqCst);
         PROCESS_SAMPLES.store(0, Ordering::SeqCst);
+        SIDECHAIN_CHANNELS.store(0, Ordering::SeqCst);
     }
 
     extern "C" fn mock_create() -> *mut c_void {
@@ -220,10 +309,32 @@ mod tests {
         PROCESS_SAMPLES.store(num_samples, Ordering::SeqCst);
     }
 
+    extern "C" fn mock_process_with_sidechain(
+        _instance: *mut c_void,
+        _channels: *mut *mut f32,
+        num_channels: u32,
+        _sidechain: *mut *mut f32,
+        num_sidechain_channels: u32,
+        num_samples: u32,
+    ) {
+        PROCESS_CALLED.store(true, Ordering::SeqCst);
+        PROCESS_CHANNELS.store(num_channels, Ordering::SeqCst);
+        PROCESS_SAMPLES.store(num_samples, Ordering::SeqCst);
+        SIDECHAIN_CHANNELS.store(num_sidechain_channels, Ordering::SeqCst);
+    }
+
     extern "C" fn mock_set_sample_rate(_instance: *mut c_void, _sample_rate: f32) {
         SET_SAMPLE_RATE_CALLED.store(true, Ordering::SeqCst);
     }
 
+    extern "C" fn mock_set_channel_layout(
+        _instance: *mut c_void,
+        _num_inputs: u32,
+        num_outputs: u32,
+    ) {
+        LAYOUT_OUTPUTS.store(num_outputs, Ordering::SeqCst);
+    }
+
     extern "C" fn mock_reset(_instance: *mut c_void) {
         RESET_CALLED.store(true, Ordering::SeqCst);
     }
@@ -250,6 +361,10 @@ mod tests {
             set_sample_rate: mock_set_sample_rate,
             reset: mock_reset,
             drop: mock_drop,
+            process_with_sidechain: mock_process_with_sidechain,
+            default_num_inputs: 1,
+            default_num_outputs: 2,
+            set_channel_layout: mock_set_channel_layout,
         }
     }
 
@@ -276,6 +391,33 @@ mod tests {
         assert!(DROP_CALLED.load(Ordering::SeqCst));
     }
 
+    #[test]
+    fn test_ffi_processor_forwards_sidechain_channels() {
+        let _guard = TEST_LOCK.lock().unwrap();
+        reset_flags();
+        let vtable = mock_vtable();
+        let mut processor = FfiProcessor::new(&vtable).expect("create should succeed");
+
+        let mut left = vec![0.0f32; 64];
+        let mut right = vec![0.0f32; 64];
+        let mut key = vec![0.0f32; 64];
+        let mut channels: Vec<&mut [f32]> = vec![&mut left, &mut right];
+        let mut sidechain: Vec<&mut [f32]> = vec![&mut key];
+
+        processor.process_with_sidechain(&mut channels, &mut sidechain);
+        assert!(PROCESS_CALLED.load(Ordering::SeqCst));
+        assert_eq!(PROCESS_CHANNELS.load(Ordering::SeqCst), 2);
+        assert_eq!(SIDECHAIN_CHANNELS.load(Ordering::SeqCst), 1);
+
+        // A sidechain shorter than the block falls back to plain processing.
+        reset_flags();
+        let mut short_key = vec![0.0f32; 16];
+        let mut sidechain: Vec<&mut [f32]> = vec![&mut short_key];
+        processor.process_with_sidechain(&mut channels, &mut sidechain);
+        assert!(PROCESS_CALLED.load(Ordering::SeqCst));
+        assert_eq!(SIDECHAIN_CHANNELS.load(Ordering::SeqCst), 0);
+    }
+
     #[test]
     fn test_ffi_processor_set_sample_rate_and_reset() {
         let _guard = TEST_LOCK.lock().unwrap();
@@ -286,6 +428,15 @@ mod tests {
 
         processor.set_sample_rate(48000.0);
         assert!(SET_SAMPLE_RATE_CALLED.load(Ordering::SeqCst));
+        assert_eq!(
+            LAYOUT_OUTPUTS.load(Ordering::SeqCst),
+            0,
+            "a sample-rate change must not touch the layout"
+        );
+
+        assert_eq!(processor.default_layout(), (1, 2));
+        processor.set_channel_layout(2, 6);
+        assert_eq!(LAYOUT_OUTPUTS.load(Ordering::SeqCst), 6);
 
         processor.reset();
         assert!(RESET_CALLED.load(Ordering::SeqCst));
@@ -347,18 +498,17 @@ mod tests {
         let vtable = mock_vtable();
         let mut processor = FfiProcessor::new(&vtable).expect("create should succeed");
 
-        // More than 2 channels should skip processing and record diagnostics.
+        // More than MAX_CHANNELS channels should skip processing and record
+        // diagnostics.
         PROCESS_CALLED.store(false, Ordering::SeqCst);
-        let mut ch1 = vec![0.0f32; 16];
-        let mut ch2 = vec![0.0f32; 16];
-        let mut ch3 = vec![0.0f32; 16];
-        let mut channels: Vec<&mut [f32]> = vec![&mut ch1, &mut ch2, &mut ch3];
+        let mut storage = vec![vec![0.0f32; 16]; MAX_CHANNELS + 1];
+        let mut channels: Vec<&mut [f32]> = storage.iter_mut().map(Vec::as_mut_slice).collect();
 
         processor.process(&mut channels);
 
         assert!(
             !PROCESS_CALLED.load(Ordering::SeqCst),
-            "Should not call vtable.process when channel count > 2"
+            "Should not call vtable.process when channel count > MAX_CHANNELS"
         );
         assert!(processor.take_unsupported_channel_flag());
         assert_eq!(processor.take_unsupported_channel_count(), 1);
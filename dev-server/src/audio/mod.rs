@@ -15,5 +15,5 @@ pub mod status;
 // Public re-exports
 pub use atomic_params::AtomicParameterBridge;
 pub use ffi_processor::{DevAudioProcessor, FfiProcessor};
-pub use server::{AudioConfig, AudioHandle, AudioServer};
-pub use status::{status, status_with_diagnostic};
+pub use server::{AudioConfig, AudioHandle, AudioServer, SidechainSource};
+pub use status::{output_sanitized_status, status, status_with_diagnostic};
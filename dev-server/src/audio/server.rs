@@ -25,17 +25,23 @@ mod device_setup;
 mod input_pipeline;
 mod metering;
 mod output_routing;
+mod sidechain;
 mod startup_wiring;
 
 use std::sync::Arc;
 
 use anyhow::Result;
 use cpal::{Device, Stream, StreamConfig};
-use wavecraft_processors::OscilloscopeFrameConsumer;
+use wavecraft_processors::{OscilloscopeFrameConsumer, SpectrumFrameConsumer};
 use wavecraft_protocol::MeterUpdateNotification;
 
+use wavecraft_dsp::{OutputSanitizer, SanitizerStats};
+
 use super::atomic_params::AtomicParameterBridge;
 use super::ffi_processor::DevAudioProcessor;
+use input_pipeline::MainBus;
+
+pub use sidechain::SidechainSource;
 
 /// Configuration for audio server.
 #[derive(Debug, Clone)]
@@ -44,6 +50,11 @@ pub struct AudioConfig {
     pub sample_rate: f32,
     /// Buffer size in samples.
     pub buffer_size: u32,
+    /// Source of the sidechain input passed to sidechain-aware processors.
+    pub sidechain: SidechainSource,
+    /// Reset the processor after it outputs NaN or Inf. Non-finite samples
+    /// are always replaced with silence.
+    pub reset_on_non_finite: bool,
 }
 
 /// Handle returned by `AudioServer::start()` that keeps both audio
@@ -62,18 +73,26 @@ pub struct AudioServer {
     output_device: Device,
     input_config: StreamConfig,
     output_config: StreamConfig,
+    main_bus: MainBus,
     param_bridge: Arc<AtomicParameterBridge>,
+    output_sanitizer: OutputSanitizer,
 }
 
 impl AudioServer {
     /// Create a new audio server with the given processor, config, and
     /// parameter bridge for lock-free audio-thread parameter reads.
+    ///
+    /// Fails if the processor's default layout has no outputs or more
+    /// channels than dev audio supports.
     pub fn new(
         processor: Box<dyn DevAudioProcessor>,
         config: AudioConfig,
         param_bridge: Arc<AtomicParameterBridge>,
     ) -> Result<Self> {
+        let main_bus = MainBus::new(processor.default_layout())?;
         let negotiated = device_setup::negotiate_default_devices_and_configs()?;
+        let output_sanitizer =
+            OutputSanitizer::new().with_reset_on_non_finite(config.reset_on_non_finite);
 
         Ok(Self {
             processor,
@@ -82,15 +101,23 @@ impl AudioServer {
             output_device: negotiated.output_device,
             input_config: negotiated.input_config,
             output_config: negotiated.output_config,
+            main_bus,
             param_bridge,
+            output_sanitizer,
         })
     }
 
+    /// Counters of the output sanitizer that guards the processed audio,
+    /// for reporting NaN/Inf and clamped output to the UI.
+    pub fn output_stats(&self) -> Arc<SanitizerStats> {
+        self.output_sanitizer.stats()
+    }
+
     /// Start audio capture, processing, and playback.
     ///
-    /// Returns an `AudioHandle` that keeps both streams alive, plus a
-    /// `MeterConsumer` for draining meter frames from a lock-free ring
-    /// buffer (RT-safe: no allocations on the audio thread).
+    /// Returns an `AudioHandle` that keeps both streams alive, plus
+    /// consumers for draining meter, oscilloscope and spectrum frames from
+    /// lock-free ring buffers (RT-safe: no allocations on the audio thread).
     ///
     /// Drop the handle to stop audio.
     pub fn start(
@@ -99,7 +126,13 @@ impl AudioServer {
         AudioHandle,
         rtrb::Consumer<MeterUpdateNotification>,
         OscilloscopeFrameConsumer,
+        SpectrumFrameConsumer,
     )> {
+        // Run the plugin's default layout; the input pipeline maps it to
+        // and from the devices' stereo pair.
+        self.processor
+            .set_channel_layout(self.main_bus.inputs as u32, self.main_bus.outputs as u32);
+
         // Set sample rate from the actual input device config
         let actual_sample_rate = self.input_config.sample_rate.0 as f32;
         self.processor.set_sample_rate(actual_sample_rate);
@@ -119,8 +152,11 @@ impl AudioServer {
             buffer_size,
             input_channels,
             output_channels,
+            main_bus: self.main_bus,
             param_bridge,
             actual_sample_rate,
+            sidechain: self.config.sidechain,
+            output_sanitizer: self.output_sanitizer,
         })
     }
 
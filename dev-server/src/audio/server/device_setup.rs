@@ -3,11 +3,15 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::{Device, Stream, StreamConfig};
-use wavecraft_processors::OscilloscopeTap;
+use wavecraft_dsp::OutputSanitizer;
+use wavecraft_metering::MeterEngine;
+use wavecraft_processors::{OscilloscopeTap, SpectrumAnalyzer};
 use wavecraft_protocol::MeterUpdateNotification;
 
 use super::super::atomic_params::AtomicParameterBridge;
 use super::super::ffi_processor::DevAudioProcessor;
+use super::input_pipeline::MainBus;
+use super::sidechain::SidechainFeed;
 
 const INPUT_STREAM_LABEL: &str = "input";
 const OUTPUT_STREAM_LABEL: &str = "output";
@@ -23,10 +27,15 @@ pub(super) struct InputStreamBuildContext {
     pub(super) processor: Box<dyn DevAudioProcessor>,
     pub(super) buffer_size: usize,
     pub(super) input_channels: usize,
+    pub(super) main_bus: MainBus,
     pub(super) param_bridge: Arc<AtomicParameterBridge>,
     pub(super) ring_producer: rtrb::Producer<f32>,
     pub(super) meter_producer: rtrb::Producer<MeterUpdateNotification>,
+    pub(super) meters: MeterEngine,
     pub(super) oscilloscope_tap: OscilloscopeTap,
+    pub(super) spectrum_analyzer: SpectrumAnalyzer,
+    pub(super) sidechain: SidechainFeed,
+    pub(super) output_sanitizer: OutputSanitizer,
 }
 
 pub(super) fn negotiate_default_devices_and_configs() -> Result<NegotiatedAudioDeviceConfig> {
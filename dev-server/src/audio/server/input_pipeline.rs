@@ -1,17 +1,83 @@
 use std::sync::Arc;
 
-use wavecraft_processors::OscilloscopeTap;
-use wavecraft_protocol::MeterUpdateNotification;
+use anyhow::{Result, bail};
+use wavecraft_dsp::{DenormalGuard, OutputSanitizer};
+use wavecraft_metering::MeterEngine;
+use wavecraft_processors::{OscilloscopeTap, SpectrumAnalyzer};
+use wavecraft_protocol::{DEV_PROCESSOR_MAX_CHANNELS, MeterUpdateNotification};
 
 use super::super::atomic_params::AtomicParameterBridge;
 use super::super::ffi_processor::DevAudioProcessor;
 
 use super::device_setup::InputStreamBuildContext;
+use super::sidechain::SidechainFeed;
+
+const MAX_CHANNELS: usize = DEV_PROCESSOR_MAX_CHANNELS as usize;
+
+/// The processor's main bus, fed from and played back on the stereo pair of
+/// the audio devices.
+///
+/// The bus holds one buffer per output, processed in place. Each output
+/// starts as input `min(channel, inputs - 1)`, where a mono input is the
+/// downmix of the device pair, inputs beyond the pair are silent, and
+/// instruments (no inputs) start from silence. Outputs beyond the pair are
+/// processed but not played; a mono output plays on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct MainBus {
+    pub(super) inputs: usize,
+    pub(super) outputs: usize,
+}
+
+impl MainBus {
+    /// Bus for a processor's `(inputs, outputs)` layout, or an error if dev
+    /// audio can't run it.
+    pub(super) fn new((inputs, outputs): (u32, u32)) -> Result<Self> {
+        if outputs == 0
+            || inputs > DEV_PROCESSOR_MAX_CHANNELS
+            || outputs > DEV_PROCESSOR_MAX_CHANNELS
+        {
+            bail!(
+                "Dev audio can't run the plugin's default layout ({inputs} in, {outputs} out): \
+                 it needs 1 to {DEV_PROCESSOR_MAX_CHANNELS} outputs and at most \
+                 {DEV_PROCESSOR_MAX_CHANNELS} inputs"
+            );
+        }
+
+        Ok(Self {
+            inputs: inputs as usize,
+            outputs: outputs as usize,
+        })
+    }
+
+    /// Fill the bus from the device's `left` and `right` input.
+    fn fill(&self, left: &[f32], right: &[f32], bus: &mut [&mut [f32]]) {
+        for (channel, output) in bus.iter_mut().enumerate() {
+            match (self.inputs, channel.min(self.inputs.saturating_sub(1))) {
+                (0, _) => output.fill(0.0),
+                (1, _) => {
+                    for ((sample, l), r) in output.iter_mut().zip(left).zip(right) {
+                        *sample = 0.5 * (l + r);
+                    }
+                }
+                (_, 0) => output.copy_from_slice(left),
+                (_, 1) => output.copy_from_slice(right),
+                _ => output.fill(0.0),
+            }
+        }
+    }
+
+    /// The left and right signals to play from the processed bus.
+    fn playback<'a>(&self, bus: &'a [&mut [f32]]) -> (&'a [f32], &'a [f32]) {
+        (&*bus[0], &*bus[1.min(self.outputs - 1)])
+    }
+}
 
 pub(super) struct InputCallbackPipeline {
     frame_counter: u64,
     left_buf: Vec<f32>,
     right_buf: Vec<f32>,
+    bus_bufs: Vec<Vec<f32>>,
+    main_bus: MainBus,
     interleave_buf: Vec<f32>,
     plain_values_buf: Vec<f32>,
     processor: Box<dyn DevAudioProcessor>,
@@ -19,7 +85,11 @@ pub(super) struct InputCallbackPipeline {
     param_bridge: Arc<AtomicParameterBridge>,
     ring_producer: rtrb::Producer<f32>,
     meter_producer: rtrb::Producer<MeterUpdateNotification>,
+    meters: MeterEngine,
     oscilloscope_tap: OscilloscopeTap,
+    spectrum_analyzer: SpectrumAnalyzer,
+    sidechain: SidechainFeed,
+    output_sanitizer: OutputSanitizer,
 }
 
 impl InputCallbackPipeline {
@@ -28,6 +98,8 @@ impl InputCallbackPipeline {
             frame_counter: 0,
             left_buf: vec![0.0f32; context.buffer_size],
             right_buf: vec![0.0f32; context.buffer_size],
+            bus_bufs: vec![vec![0.0f32; context.buffer_size]; context.main_bus.outputs],
+            main_bus: context.main_bus,
             interleave_buf: vec![0.0f32; context.buffer_size * 2],
             plain_values_buf: vec![0.0f32; context.param_bridge.parameter_count()],
             processor: context.processor,
@@ -35,12 +107,18 @@ impl InputCallbackPipeline {
             param_bridge: context.param_bridge,
             ring_producer: context.ring_producer,
             meter_producer: context.meter_producer,
+            meters: context.meters,
             oscilloscope_tap: context.oscilloscope_tap,
+            spectrum_analyzer: context.spectrum_analyzer,
+            sidechain: context.sidechain,
+            output_sanitizer: context.output_sanitizer,
         }
     }
 
     pub(super) fn process_callback(&mut self, data: &[f32]) {
         self.frame_counter += 1;
+        // Flush subnormals to zero for the user's DSP and our own analysis.
+        let _denormals = DenormalGuard::new();
 
         let Some(actual_samples) =
             callback_sample_count(data.len(), self.input_channels, self.left_buf.len())
@@ -54,25 +132,43 @@ impl InputCallbackPipeline {
         // Zero-fill and deinterleave
         deinterleave_input(data, self.input_channels, left, right);
 
+        // Stack-local channel array over the processor's main bus
+        let mut channels: [&mut [f32]; MAX_CHANNELS] = Default::default();
+        for (slot, buffer) in channels.iter_mut().zip(self.bus_bufs.iter_mut()) {
+            *slot = &mut buffer[..actual_samples];
+        }
+        let channels = &mut channels[..self.main_bus.outputs];
+        self.main_bus.fill(left, right, channels);
+
         // Process through the user's DSP (stack-local channel array)
         let plain_values_len = self.param_bridge.copy_all_to(&mut self.plain_values_buf);
         self.processor
             .apply_plain_values(&self.plain_values_buf[..plain_values_len]);
 
+        match self
+            .sidechain
+            .fill(data, self.input_channels, actual_samples)
         {
-            let mut channels: [&mut [f32]; 2] = [left, right];
-            self.processor.process(&mut channels);
+            Some(mut sidechain) => self
+                .processor
+                .process_with_sidechain(channels, &mut sidechain),
+            None => self.processor.process(channels),
+        }
+
+        // Keep NaN/Inf and runaway levels away from the speakers.
+        if self.output_sanitizer.process(channels) {
+            self.processor.reset();
         }
 
-        // Re-borrow after process()
-        let left = &self.left_buf[..actual_samples];
-        let right = &self.right_buf[..actual_samples];
+        let (left, right) = self.main_bus.playback(channels);
 
-        // Observation-only waveform capture for oscilloscope UI.
+        // Observation-only waveform and spectrum capture for analyzer UIs.
         self.oscilloscope_tap.capture_stereo(left, right);
+        self.spectrum_analyzer.capture_stereo(left, right);
 
+        self.meters.process(&[left, right]);
         if let Some(notification) =
-            super::metering::maybe_build_meter_update(self.frame_counter, left, right)
+            super::metering::maybe_build_meter_update(self.frame_counter, &mut self.meters)
         {
             // Push to lock-free ring buffer — RT-safe, no allocation.
             // If the consumer is slow, older frames are silently
@@ -132,3 +228,51 @@ fn push_samples_to_ring(ring_producer: &mut rtrb::Producer<f32>, samples: &[f32]
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(inputs: u32, outputs: u32) -> Vec<Vec<f32>> {
+        let bus = MainBus::new((inputs, outputs)).expect("supported layout");
+        let mut buffers = vec![vec![9.0_f32; 2]; bus.outputs];
+        let mut channels: Vec<&mut [f32]> = buffers.iter_mut().map(Vec::as_mut_slice).collect();
+        bus.fill(&[1.0, 0.5], &[0.0, 0.5], &mut channels);
+        buffers
+    }
+
+    #[test]
+    fn main_bus_feeds_each_layout_from_the_device_pair() {
+        assert_eq!(fill(2, 2), [[1.0, 0.5], [0.0, 0.5]]);
+        assert_eq!(fill(1, 1), [[0.5, 0.5]]);
+        assert_eq!(fill(1, 2), [[0.5, 0.5], [0.5, 0.5]]);
+        assert_eq!(fill(0, 2), [[0.0, 0.0], [0.0, 0.0]]);
+
+        let surround = fill(6, 6);
+        assert_eq!(surround[..2], [[1.0, 0.5], [0.0, 0.5]]);
+        assert!(surround[2..].iter().flatten().all(|sample| *sample == 0.0));
+    }
+
+    #[test]
+    fn main_bus_plays_mono_on_both_sides_and_wider_buses_as_left_right() {
+        let mut mono = [0.25_f32];
+        let mono_bus = MainBus::new((1, 1)).unwrap();
+        assert_eq!(
+            mono_bus.playback(&[&mut mono[..]]),
+            (&[0.25][..], &[0.25][..])
+        );
+
+        let mut channels = [[1.0_f32], [2.0], [3.0], [4.0], [5.0], [6.0]];
+        let channels: Vec<&mut [f32]> = channels.iter_mut().map(|c| &mut c[..]).collect();
+        let surround = MainBus::new((6, 6)).unwrap();
+        assert_eq!(surround.playback(&channels), (&[1.0][..], &[2.0][..]));
+    }
+
+    #[test]
+    fn main_bus_rejects_layouts_dev_audio_cannot_play() {
+        let error = MainBus::new((2, 0)).unwrap_err().to_string();
+        assert!(error.contains("(2 in, 0 out)"), "{error}");
+        assert!(MainBus::new((2, DEV_PROCESSOR_MAX_CHANNELS + 1)).is_err());
+        assert!(MainBus::new((DEV_PROCESSOR_MAX_CHANNELS + 1, 2)).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Sidechain input feed for sidechain-aware processors in dev mode.
+
+use std::f32::consts::TAU;
+
+/// Where `wavecraft start` takes the sidechain input from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SidechainSource {
+    /// No sidechain input; processors run without one.
+    #[default]
+    None,
+    /// Input device channels 3 and 4 (e.g. a second input pair on an
+    /// audio interface). Channel 3 alone is used as mono.
+    InputChannels,
+    /// Built-in decaying pulse at 120 BPM, for auditioning duckers and
+    /// gates without routing a second source.
+    Pulse,
+}
+
+/// Pulse rate of [`SidechainSource::Pulse`].
+const PULSE_BPM: f32 = 120.0;
+/// Frequency of the pulse's sine burst.
+const PULSE_FREQUENCY_HZ: f32 = 60.0;
+/// Time constant of the pulse's exponential decay.
+const PULSE_DECAY_SECONDS: f32 = 0.05;
+
+/// Per-callback sidechain buffers, filled from the configured source.
+pub(super) struct SidechainFeed {
+    source: SidechainSource,
+    left: Vec<f32>,
+    right: Vec<f32>,
+    sample_rate: f32,
+    pulse_position: usize,
+}
+
+impl SidechainFeed {
+    pub(super) fn new(source: SidechainSource, buffer_size: usize, sample_rate: f32) -> Self {
+        // Buffers are only needed when a source is configured.
+        let capacity = if source == SidechainSource::None {
+            0
+        } else {
+            buffer_size
+        };
+
+        Self {
+            source,
+            left: vec![0.0; capacity],
+            right: vec![0.0; capacity],
+            sample_rate,
+            pulse_position: 0,
+        }
+    }
+
+    /// Fill the sidechain for one callback of `samples` frames.
+    ///
+    /// `data` is the interleaved input callback buffer. Returns `None` when no
+    /// sidechain source is configured.
+    pub(super) fn fill(
+        &mut self,
+        data: &[f32],
+        input_channels: usize,
+        samples: usize,
+    ) -> Option<[&mut [f32]; 2]> {
+        let samples = samples.min(self.left.len());
+        let left = &mut self.left[..samples];
+        let right = &mut self.right[..samples];
+
+        match self.source {
+            SidechainSource::None => return None,
+            SidechainSource::InputChannels => {
+                for (frame, (left, right)) in left.iter_mut().zip(right.iter_mut()).enumerate() {
+                    let base = frame * input_channels;
+                    *left = channel_sample(data, base, 2, input_channels);
+                    *right = if input_channels > 3 {
+                        channel_sample(data, base, 3, input_channels)
+                    } else {
+                        *left
+                    };
+                }
+            }
+            SidechainSource::Pulse => {
+                let period = ((self.sample_rate * 60.0 / PULSE_BPM) as usize).max(1);
+                for (left, right) in left.iter_mut().zip(right.iter_mut()) {
+                    let t = self.pulse_position as f32 / self.sample_rate;
+                    let sample =
+                        (-t / PULSE_DECAY_SECONDS).exp() * (TAU * PULSE_FREQUENCY_HZ * t).sin();
+                    *left = sample;
+                    *right = sample;
+                    self.pulse_position = (self.pulse_position + 1) % period;
+                }
+            }
+        }
+
+        Some([left, right])
+    }
+}
+
+fn channel_sample(data: &[f32], base: usize, channel: usize, input_channels: usize) -> f32 {
+    if channel < input_channels {
+        data.get(base + channel).copied().unwrap_or(0.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_source_provides_no_sidechain() {
+        let mut feed = SidechainFeed::new(SidechainSource::None, 64, 48_000.0);
+        assert!(feed.fill(&[0.5; 128], 2, 64).is_none());
+    }
+
+    #[test]
+    fn input_channels_source_reads_channels_three_and_four() {
+        let mut feed = SidechainFeed::new(SidechainSource::InputChannels, 4, 48_000.0);
+        // Two frames of four interleaved channels.
+        let data = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+
+        let [left, right] = feed.fill(&data, 4, 2).expect("sidechain configured");
+        assert_eq!(left, &[0.3, 0.7]);
+        assert_eq!(right, &[0.4, 0.8]);
+    }
+
+    #[test]
+    fn input_channels_source_is_silent_without_extra_channels() {
+        let mut feed = SidechainFeed::new(SidechainSource::InputChannels, 4, 48_000.0);
+
+        let [left, right] = feed.fill(&[1.0; 4], 2, 2).expect("sidechain configured");
+        assert_eq!(left, &[0.0, 0.0]);
+        assert_eq!(right, &[0.0, 0.0]);
+    }
+
+    #[test]
+    fn pulse_source_repeats_every_beat() {
+        let sample_rate = 1_000.0;
+        let beat = 500;
+        let mut feed = SidechainFeed::new(SidechainSource::Pulse, beat, sample_rate);
+
+        let first: Vec<f32> = feed.fill(&[], 0, beat).expect("sidechain configured")[0].to_vec();
+        let second: Vec<f32> = feed.fill(&[], 0, beat).expect("sidechain configured")[0].to_vec();
+
+        assert_eq!(first, second);
+        let peak_attack = first[..50]
+            .iter()
+            .fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        let peak_release = first[400..]
+            .iter()
+            .fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        assert!(peak_attack > 0.5, "pulse should start loud: {peak_attack}");
+        assert!(peak_release < 1.0e-3, "pulse should decay: {peak_release}");
+    }
+}
@@ -3,13 +3,18 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use cpal::traits::StreamTrait;
 use cpal::{Device, StreamConfig};
+use wavecraft_dsp::OutputSanitizer;
+use wavecraft_metering::MeterEngine;
 use wavecraft_processors::{
-    OscilloscopeFrameConsumer, OscilloscopeTap, create_oscilloscope_channel,
+    OscilloscopeFrameConsumer, OscilloscopeTap, SpectrumAnalyzer, SpectrumFrameConsumer,
+    create_oscilloscope_channel, create_spectrum_channel,
 };
 use wavecraft_protocol::MeterUpdateNotification;
 
 use super::super::atomic_params::AtomicParameterBridge;
 use super::super::ffi_processor::DevAudioProcessor;
+use super::input_pipeline::MainBus;
+use super::sidechain::{SidechainFeed, SidechainSource};
 use super::{AudioHandle, device_setup};
 
 pub(super) struct StartAudioIoContext<'a> {
@@ -21,8 +26,11 @@ pub(super) struct StartAudioIoContext<'a> {
     pub(super) buffer_size: usize,
     pub(super) input_channels: usize,
     pub(super) output_channels: usize,
+    pub(super) main_bus: MainBus,
     pub(super) param_bridge: Arc<AtomicParameterBridge>,
     pub(super) actual_sample_rate: f32,
+    pub(super) sidechain: SidechainSource,
+    pub(super) output_sanitizer: OutputSanitizer,
 }
 
 pub(super) fn start_audio_io(
@@ -31,6 +39,7 @@ pub(super) fn start_audio_io(
     AudioHandle,
     rtrb::Consumer<MeterUpdateNotification>,
     OscilloscopeFrameConsumer,
+    SpectrumFrameConsumer,
 )> {
     // --- SPSC ring buffer for input→output audio transfer ---
     // Capacity: buffer_size * num_channels * 4 blocks of headroom.
@@ -46,6 +55,21 @@ pub(super) fn start_audio_io(
     let (oscilloscope_producer, oscilloscope_consumer) = create_oscilloscope_channel(8);
     let mut oscilloscope_tap = OscilloscopeTap::with_output(oscilloscope_producer);
     oscilloscope_tap.set_sample_rate_hz(context.actual_sample_rate);
+    let (spectrum_producer, spectrum_consumer) = create_spectrum_channel(4);
+    let mut spectrum_analyzer = SpectrumAnalyzer::with_output(spectrum_producer);
+    spectrum_analyzer.set_sample_rate_hz(context.actual_sample_rate);
+
+    if context.sidechain == SidechainSource::InputChannels && context.input_channels < 3 {
+        tracing::warn!(
+            "Sidechain source is input channels 3/4, but the input device only has {} channel(s); the sidechain will be silent",
+            context.input_channels
+        );
+    }
+    let sidechain = SidechainFeed::new(
+        context.sidechain,
+        context.buffer_size,
+        context.actual_sample_rate,
+    );
 
     let input_stream = device_setup::build_input_stream(
         context.input_device,
@@ -54,10 +78,15 @@ pub(super) fn start_audio_io(
             processor: context.processor,
             buffer_size: context.buffer_size,
             input_channels: context.input_channels,
+            main_bus: context.main_bus,
             param_bridge: context.param_bridge,
             ring_producer,
             meter_producer,
+            meters: MeterEngine::new(context.actual_sample_rate, 2),
             oscilloscope_tap,
+            spectrum_analyzer,
+            sidechain,
+            output_sanitizer: context.output_sanitizer,
         },
     )?;
 
@@ -88,5 +117,6 @@ pub(super) fn start_audio_io(
         },
         meter_consumer,
         oscilloscope_consumer,
+        spectrum_consumer,
     ))
 }
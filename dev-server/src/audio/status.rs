@@ -4,6 +4,7 @@
 //! browser dev mode startup and diagnostics.
 
 use std::time::{SystemTime, UNIX_EPOCH};
+use wavecraft_dsp::SanitizerCounts;
 use wavecraft_protocol::{
     AudioDiagnostic, AudioDiagnosticCode, AudioRuntimePhase, AudioRuntimeStatus,
 };
@@ -32,12 +33,29 @@ pub fn status_with_diagnostic(
             code,
             message: message.into(),
             hint: hint.map(ToOwned::to_owned),
+            count: None,
         }),
         sample_rate,
         buffer_size,
     )
 }
 
+/// Build a degraded status reporting output sanitizer events, or `None` if
+/// no output has been sanitized.
+pub fn output_sanitized_status(
+    counts: SanitizerCounts,
+    sample_rate: Option<f32>,
+    buffer_size: Option<u32>,
+) -> Option<AudioRuntimeStatus> {
+    let diagnostic = counts.diagnostic()?;
+    Some(build_status(
+        AudioRuntimePhase::Degraded,
+        Some(diagnostic),
+        sample_rate,
+        buffer_size,
+    ))
+}
+
 fn build_status(
     phase: AudioRuntimePhase,
     diagnostic: Option<AudioDiagnostic>,
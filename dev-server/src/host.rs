@@ -7,12 +7,13 @@
 
 #[cfg(feature = "audio")]
 use std::sync::Arc;
-use std::sync::RwLock;
+use std::sync::{OnceLock, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
-use wavecraft_bridge::{BridgeError, InMemoryParameterHost, ParameterHost};
+use wavecraft_bridge::{BridgeError, InMemoryParameterHost, ParameterHost, PresetLibrary};
+use wavecraft_processors::eq_response_from_parameters;
 use wavecraft_protocol::{
-    AudioRuntimePhase, AudioRuntimeStatus, MeterFrame, MeterUpdateNotification, OscilloscopeFrame,
-    ParameterInfo,
+    AudioRuntimePhase, AudioRuntimeStatus, MeterFrame, MeterUpdateNotification, ModulationConfig,
+    OscilloscopeFrame, ParameterInfo, SpectrumFrame,
 };
 
 #[cfg(feature = "audio")]
@@ -38,7 +39,13 @@ pub struct DevServerHost {
     inner: InMemoryParameterHost,
     latest_meter_frame: Arc<RwLock<Option<MeterFrame>>>,
     latest_oscilloscope_frame: Arc<RwLock<Option<OscilloscopeFrame>>>,
+    latest_spectrum_frame: Arc<RwLock<Option<SpectrumFrame>>>,
     audio_status: Arc<RwLock<AudioRuntimeStatus>>,
+    /// Plugin presets, installed once the plugin library has been loaded.
+    presets: OnceLock<PresetLibrary>,
+    /// Modulation routings edited from the UI. Stored so the UI can be
+    /// developed against them; dev audio doesn't apply modulation.
+    modulation: RwLock<ModulationConfig>,
     #[cfg(feature = "audio")]
     param_bridge: Option<Arc<AtomicParameterBridge>>,
 }
@@ -46,6 +53,7 @@ pub struct DevServerHost {
 struct SharedState {
     latest_meter_frame: Arc<RwLock<Option<MeterFrame>>>,
     latest_oscilloscope_frame: Arc<RwLock<Option<OscilloscopeFrame>>>,
+    latest_spectrum_frame: Arc<RwLock<Option<SpectrumFrame>>>,
     audio_status: Arc<RwLock<AudioRuntimeStatus>>,
 }
 
@@ -53,6 +61,7 @@ impl DevServerHost {
     fn initialize_shared_state() -> SharedState {
         let latest_meter_frame = Arc::new(RwLock::new(None));
         let latest_oscilloscope_frame = Arc::new(RwLock::new(None));
+        let latest_spectrum_frame = Arc::new(RwLock::new(None));
         let audio_status = Arc::new(RwLock::new(AudioRuntimeStatus {
             phase: AudioRuntimePhase::Disabled,
             diagnostic: None,
@@ -64,6 +73,7 @@ impl DevServerHost {
         SharedState {
             latest_meter_frame,
             latest_oscilloscope_frame,
+            latest_spectrum_frame,
             audio_status,
         }
     }
@@ -85,7 +95,10 @@ impl DevServerHost {
             inner,
             latest_meter_frame: shared_state.latest_meter_frame,
             latest_oscilloscope_frame: shared_state.latest_oscilloscope_frame,
+            latest_spectrum_frame: shared_state.latest_spectrum_frame,
             audio_status: shared_state.audio_status,
+            presets: OnceLock::new(),
+            modulation: RwLock::default(),
             #[cfg(feature = "audio")]
             param_bridge: None,
         }
@@ -107,7 +120,10 @@ impl DevServerHost {
             inner,
             latest_meter_frame: shared_state.latest_meter_frame,
             latest_oscilloscope_frame: shared_state.latest_oscilloscope_frame,
+            latest_spectrum_frame: shared_state.latest_spectrum_frame,
             audio_status: shared_state.audio_status,
+            presets: OnceLock::new(),
+            modulation: RwLock::default(),
             param_bridge: Some(bridge),
         }
     }
@@ -154,6 +170,9 @@ impl DevServerHost {
             peak_r: update.right_peak,
             rms_l: update.left_rms,
             rms_r: update.right_rms,
+            true_peak_l: update.left_true_peak,
+            true_peak_r: update.right_true_peak,
+            loudness: update.loudness,
             timestamp: update.timestamp_us,
         });
     }
@@ -167,6 +186,23 @@ impl DevServerHost {
         *oscilloscope = Some(frame);
     }
 
+    /// Store the latest spectrum frame for polling-based consumers.
+    pub fn set_latest_spectrum_frame(&self, frame: SpectrumFrame) {
+        let mut spectrum = self
+            .latest_spectrum_frame
+            .write()
+            .expect("latest_spectrum_frame lock poisoned");
+        *spectrum = Some(frame);
+    }
+
+    /// Install the preset library served by the preset IPC methods.
+    ///
+    /// Only the first call takes effect; later calls (e.g. after a
+    /// hot-reload) return `false` and keep the existing library.
+    pub fn set_presets(&self, presets: PresetLibrary) -> bool {
+        self.presets.set(presets).is_ok()
+    }
+
     /// Update the shared audio runtime status.
     pub fn set_audio_status(&self, status: AudioRuntimeStatus) {
         let mut current = self
@@ -224,6 +260,28 @@ impl ParameterHost for DevServerHost {
             .clone()
     }
 
+    fn get_spectrum_frame(&self) -> Option<SpectrumFrame> {
+        self.latest_spectrum_frame
+            .read()
+            .expect("latest_spectrum_frame lock poisoned")
+            .clone()
+    }
+
+    fn get_eq_response(&self, eq_id: Option<&str>, frequencies_hz: &[f32]) -> Option<Vec<f32>> {
+        // Evaluated at the device rate once audio is running.
+        let sample_rate = self
+            .audio_status
+            .read()
+            .expect("audio_status lock poisoned")
+            .sample_rate;
+        eq_response_from_parameters(
+            &self.inner.get_all_parameters(),
+            eq_id,
+            sample_rate,
+            frequencies_hz,
+        )
+    }
+
     fn request_resize(&self, width: u32, height: u32) -> bool {
         self.inner.request_resize(width, height)
     }
@@ -236,6 +294,24 @@ impl ParameterHost for DevServerHost {
                 .clone(),
         )
     }
+
+    fn presets(&self) -> Option<&PresetLibrary> {
+        self.presets.get()
+    }
+
+    fn get_modulation(&self) -> Option<ModulationConfig> {
+        Some(
+            self.modulation
+                .read()
+                .expect("modulation lock poisoned")
+                .clone(),
+        )
+    }
+
+    fn set_modulation(&self, config: ModulationConfig) -> Result<(), BridgeError> {
+        *self.modulation.write().expect("modulation lock poisoned") = config;
+        Ok(())
+    }
 }
 
 fn now_millis() -> u64 {
@@ -247,7 +323,7 @@ fn now_millis() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wavecraft_protocol::ParameterType;
+    use wavecraft_protocol::{LoudnessFrame, ParameterType};
 
     fn test_params() -> Vec<ParameterInfo> {
         vec![
@@ -344,6 +420,12 @@ mod tests {
             left_rms: 0.4,
             right_peak: 0.8,
             right_rms: 0.3,
+            left_true_peak: 0.95,
+            right_true_peak: 0.85,
+            loudness: Some(LoudnessFrame {
+                short_term_lufs: Some(-14.0),
+                ..LoudnessFrame::default()
+            }),
         });
 
         let frame = host
@@ -351,6 +433,8 @@ mod tests {
             .expect("meter frame should be populated after update");
         assert!((frame.peak_l - 0.9).abs() < f32::EPSILON);
         assert!((frame.rms_r - 0.3).abs() < f32::EPSILON);
+        assert!((frame.true_peak_l - 0.95).abs() < f32::EPSILON);
+        assert_eq!(frame.loudness.unwrap().short_term_lufs, Some(-14.0));
         assert_eq!(frame.timestamp, 42);
     }
 
@@ -397,6 +481,117 @@ mod tests {
         assert_eq!(frame.timestamp, 777);
     }
 
+    #[test]
+    fn test_get_eq_response_from_parameters() {
+        let band_types = [
+            "Peak",
+            "Low Shelf",
+            "High Shelf",
+            "Notch",
+            "Low-pass 12",
+            "Low-pass 24",
+            "Low-pass 48",
+            "High-pass 12",
+            "High-pass 24",
+            "High-pass 48",
+        ];
+        let param = |suffix: &str, value: f32| ParameterInfo {
+            id: format!("eq_band1_{suffix}"),
+            name: suffix.to_string(),
+            param_type: ParameterType::Float,
+            value,
+            default: value,
+            min: -24.0,
+            max: 20_000.0,
+            unit: None,
+            group: Some("Band 1".to_string()),
+            variants: None,
+        };
+        let mut parameters = test_params();
+        parameters.extend([
+            param("enabled", 1.0),
+            ParameterInfo {
+                param_type: ParameterType::Enum,
+                variants: Some(band_types.map(str::to_string).to_vec()),
+                ..param("type", 0.0)
+            },
+            param("freq_hz", 1_000.0),
+            param("gain_db", 6.0),
+            param("q", 0.707),
+        ]);
+
+        assert!(
+            DevServerHost::new(test_params())
+                .get_eq_response(None, &[1_000.0])
+                .is_none()
+        );
+
+        let host = DevServerHost::new(parameters);
+        let response = host
+            .get_eq_response(Some("eq"), &[20.0, 1_000.0])
+            .expect("EQ response should be computed");
+        assert!(response[0].abs() < 0.1);
+        assert!((response[1] - 6.0).abs() < 0.01);
+
+        host.set_parameter("eq_band1_gain_db", -3.0)
+            .expect("gain should be set");
+        let response = host
+            .get_eq_response(None, &[1_000.0])
+            .expect("EQ response should be computed");
+        assert!((response[0] + 3.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_get_spectrum_frame() {
+        let host = DevServerHost::new(test_params());
+        assert!(host.get_spectrum_frame().is_none());
+
+        host.set_latest_spectrum_frame(SpectrumFrame {
+            frequencies: vec![100.0, 1_000.0],
+            magnitudes_l: vec![-48.0, -6.0],
+            magnitudes_r: vec![-50.0, -7.0],
+            sample_rate: 48_000.0,
+            fft_size: 2048,
+            timestamp: 42,
+        });
+
+        let frame = host
+            .get_spectrum_frame()
+            .expect("spectrum frame should be populated");
+        assert_eq!(frame.frequencies.len(), 2);
+        assert_eq!(frame.fft_size, 2048);
+        assert_eq!(frame.timestamp, 42);
+    }
+
+    #[test]
+    fn test_presets_installed_once() {
+        let host = DevServerHost::new(test_params());
+        assert!(host.presets().is_none());
+
+        assert!(host.set_presets(PresetLibrary::new("1.0.0", Vec::new())));
+        assert!(!host.set_presets(PresetLibrary::new("2.0.0", Vec::new())));
+
+        let presets = host.presets().expect("preset library should be installed");
+        assert_eq!(presets.plugin_version(), "1.0.0");
+    }
+
+    #[test]
+    fn test_modulation_config_round_trips_without_frames() {
+        let host = DevServerHost::new(test_params());
+        assert!(host.get_modulation().expect("config").routings.is_empty());
+
+        let config: ModulationConfig = serde_json::from_value(serde_json::json!({
+            "sources": [{ "type": "envelopeFollower", "attack_ms": 5.0, "release_ms": 80.0 }],
+            "routings": [{ "source": 0, "target": "gain", "depth": 0.3 }]
+        }))
+        .expect("config should parse");
+        host.set_modulation(config.clone())
+            .expect("set should succeed");
+
+        assert_eq!(host.get_modulation(), Some(config));
+        assert!(host.get_modulation_frame().is_none());
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn test_set_audio_status_inside_runtime_does_not_panic() {
         let host = DevServerHost::new(test_params());
@@ -55,6 +55,9 @@ pub use ws::{WsHandle, WsServer};
 pub use audio::{
     atomic_params::AtomicParameterBridge,
     ffi_processor::{DevAudioProcessor, FfiProcessor},
-    server::{AudioConfig, AudioHandle, AudioServer},
-    status::{status as audio_status, status_with_diagnostic as audio_status_with_diagnostic},
+    server::{AudioConfig, AudioHandle, AudioServer, SidechainSource},
+    status::{
+        output_sanitized_status as audio_output_sanitized_status, status as audio_status,
+        status_with_diagnostic as audio_status_with_diagnostic,
+    },
 };
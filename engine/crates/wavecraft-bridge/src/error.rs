@@ -18,6 +18,10 @@ pub enum BridgeError {
     #[error("Parameter value out of range: {id} = {value}")]
     ParameterOutOfRange { id: String, value: f32 },
 
+    /// Preset not found in the host's preset library
+    #[error("Preset not found: {0}")]
+    PresetNotFound(String),
+
     /// Method not supported by handler
     #[error("Unknown method: {0}")]
     UnknownMethod(String),
@@ -38,6 +42,7 @@ impl BridgeError {
             Self::JsonParse(_) => IpcError::parse_error(),
             Self::ParameterNotFound(id) => IpcError::param_not_found(id),
             Self::ParameterOutOfRange { id, value } => IpcError::param_out_of_range(id, *value),
+            Self::PresetNotFound(name) => IpcError::preset_not_found(name),
             Self::UnknownMethod(method) => IpcError::method_not_found(method),
             Self::InvalidParams { reason, .. } => IpcError::invalid_params(reason),
             Self::Internal(reason) => IpcError::internal_error(reason),
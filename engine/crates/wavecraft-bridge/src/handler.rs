@@ -2,14 +2,23 @@
 
 use crate::error::BridgeError;
 use crate::host::ParameterHost;
+use crate::presets::PresetLibrary;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use wavecraft_protocol::{
-    GetAllParametersResult, GetAudioStatusResult, GetMeterFrameResult, GetOscilloscopeFrameResult,
-    GetParameterParams, GetParameterResult, IpcRequest, IpcResponse, METHOD_GET_ALL_PARAMETERS,
-    METHOD_GET_AUDIO_STATUS, METHOD_GET_METER_FRAME, METHOD_GET_OSCILLOSCOPE_FRAME,
-    METHOD_GET_PARAMETER, METHOD_REQUEST_RESIZE, METHOD_SET_PARAMETER, RequestId,
-    RequestResizeParams, RequestResizeResult, SetParameterParams, SetParameterResult,
+    DeletePresetParams, DeletePresetResult, GetAllParametersResult, GetAudioStatusResult,
+    GetEqResponseParams, GetEqResponseResult, GetMeterFrameParams, GetMeterFrameResult,
+    GetModulationFrameResult, GetModulationResult, GetOscilloscopeFrameParams,
+    GetOscilloscopeFrameResult, GetParameterParams, GetParameterResult, GetSpectrumFrameParams,
+    GetSpectrumFrameResult, IpcRequest, IpcResponse, ListPresetsResult, LoadPresetParams,
+    LoadPresetResult, METHOD_DELETE_PRESET, METHOD_GET_ALL_PARAMETERS, METHOD_GET_AUDIO_STATUS,
+    METHOD_GET_EQ_RESPONSE, METHOD_GET_METER_FRAME, METHOD_GET_MODULATION,
+    METHOD_GET_MODULATION_FRAME, METHOD_GET_OSCILLOSCOPE_FRAME, METHOD_GET_PARAMETER,
+    METHOD_GET_SPECTRUM_FRAME, METHOD_LIST_PRESETS, METHOD_LOAD_PRESET, METHOD_REQUEST_RESIZE,
+    METHOD_SAVE_PRESET, METHOD_SET_MODULATION, METHOD_SET_PARAMETER, PRESET_FORMAT_VERSION, Preset,
+    PresetSource, RequestId, RequestResizeParams, RequestResizeResult, SavePresetParams,
+    SavePresetResult, SetModulationParams, SetModulationResult, SetParameterParams,
+    SetParameterResult,
 };
 
 /// IPC message handler that dispatches requests to a ParameterHost
@@ -34,8 +43,17 @@ impl<H: ParameterHost> IpcHandler<H> {
             METHOD_GET_ALL_PARAMETERS => self.handle_get_all_parameters(&request),
             METHOD_GET_METER_FRAME => self.handle_get_meter_frame(&request),
             METHOD_GET_OSCILLOSCOPE_FRAME => self.handle_get_oscilloscope_frame(&request),
+            METHOD_GET_SPECTRUM_FRAME => self.handle_get_spectrum_frame(&request),
+            METHOD_GET_EQ_RESPONSE => self.handle_get_eq_response(&request),
             METHOD_GET_AUDIO_STATUS => self.handle_get_audio_status(&request),
             METHOD_REQUEST_RESIZE => self.handle_request_resize(&request),
+            METHOD_LIST_PRESETS => self.handle_list_presets(&request),
+            METHOD_LOAD_PRESET => self.handle_load_preset(&request),
+            METHOD_SAVE_PRESET => self.handle_save_preset(&request),
+            METHOD_DELETE_PRESET => self.handle_delete_preset(&request),
+            METHOD_GET_MODULATION => self.handle_get_modulation(&request),
+            METHOD_SET_MODULATION => self.handle_set_modulation(&request),
+            METHOD_GET_MODULATION_FRAME => self.handle_get_modulation_frame(&request),
             "ping" => self.handle_ping(&request),
             _ => Err(BridgeError::UnknownMethod(request.method.clone())),
         };
@@ -90,6 +108,18 @@ impl<H: ParameterHost> IpcHandler<H> {
         }
     }
 
+    /// Parses params that may be omitted entirely, falling back to
+    /// `T::default()`.
+    fn parse_optional_params<T>(&self, request: &IpcRequest) -> Result<T, BridgeError>
+    where
+        T: DeserializeOwned + Default,
+    {
+        match &request.params {
+            Some(value) if !value.is_null() => Ok(serde_json::from_value(value.clone())?),
+            _ => Ok(T::default()),
+        }
+    }
+
     // ------------------------------------------------------------------------
     // Method Handlers
     // ------------------------------------------------------------------------
@@ -136,8 +166,13 @@ impl<H: ParameterHost> IpcHandler<H> {
     }
 
     fn handle_get_meter_frame(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
-        // Get meter frame from host
-        let frame = self.host.get_meter_frame();
+        let params: GetMeterFrameParams = self.parse_optional_params(request)?;
+
+        // Get meter frame from host (output meter unless a tap is selected)
+        let frame = match params.tap_id {
+            Some(tap_id) => self.host.get_tap_meter_frame(&tap_id),
+            None => self.host.get_meter_frame(),
+        };
 
         let result = GetMeterFrameResult { frame };
 
@@ -148,13 +183,44 @@ impl<H: ParameterHost> IpcHandler<H> {
         &self,
         request: &IpcRequest,
     ) -> Result<IpcResponse, BridgeError> {
-        let frame = self.host.get_oscilloscope_frame();
+        let params: GetOscilloscopeFrameParams = self.parse_optional_params(request)?;
+
+        let frame = match params.tap_id {
+            Some(tap_id) => self.host.get_tap_oscilloscope_frame(&tap_id),
+            None => self.host.get_oscilloscope_frame(),
+        };
 
         let result = GetOscilloscopeFrameResult { frame };
 
         Ok(IpcResponse::success(request.id.clone(), result))
     }
 
+    fn handle_get_spectrum_frame(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let params: GetSpectrumFrameParams = self.parse_optional_params(request)?;
+
+        let frame = match params.tap_id {
+            Some(tap_id) => self.host.get_tap_spectrum_frame(&tap_id),
+            None => self.host.get_spectrum_frame(),
+        };
+
+        let result = GetSpectrumFrameResult { frame };
+
+        Ok(IpcResponse::success(request.id.clone(), result))
+    }
+
+    fn handle_get_eq_response(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let params: GetEqResponseParams =
+            self.parse_required_params(request, METHOD_GET_EQ_RESPONSE)?;
+
+        let magnitudes_db = self
+            .host
+            .get_eq_response(params.eq_id.as_deref(), &params.frequencies);
+
+        let result = GetEqResponseResult { magnitudes_db };
+
+        Ok(IpcResponse::success(request.id.clone(), result))
+    }
+
     fn handle_request_resize(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
         let params: RequestResizeParams =
             self.parse_required_params(request, METHOD_REQUEST_RESIZE)?;
@@ -175,6 +241,146 @@ impl<H: ParameterHost> IpcHandler<H> {
         Ok(IpcResponse::success(request.id.clone(), result))
     }
 
+    fn preset_library(&self, method: &'static str) -> Result<&PresetLibrary, BridgeError> {
+        self.host.presets().ok_or_else(|| {
+            BridgeError::Internal(format!("{method} is not supported: host has no presets"))
+        })
+    }
+
+    fn handle_list_presets(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let presets = self.preset_library(METHOD_LIST_PRESETS)?.list();
+
+        Ok(IpcResponse::success(
+            request.id.clone(),
+            ListPresetsResult { presets },
+        ))
+    }
+
+    fn handle_load_preset(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let params: LoadPresetParams = self.parse_required_params(request, METHOD_LOAD_PRESET)?;
+
+        let preset = self
+            .preset_library(METHOD_LOAD_PRESET)?
+            .get(&params.name, params.source)
+            .ok_or_else(|| BridgeError::PresetNotFound(params.name.clone()))?;
+
+        // Apply what this plugin still has; values saved before a range
+        // change are clamped rather than rejected.
+        let mut skipped = Vec::new();
+        for (id, value) in preset.parameters {
+            match self.host.get_parameter(&id) {
+                Some(info) => self
+                    .host
+                    .set_parameter(&id, value.clamp(info.min, info.max))?,
+                None => skipped.push(id),
+            }
+        }
+
+        Ok(IpcResponse::success(
+            request.id.clone(),
+            LoadPresetResult { skipped },
+        ))
+    }
+
+    fn handle_save_preset(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let params: SavePresetParams = self.parse_required_params(request, METHOD_SAVE_PRESET)?;
+        if params.name.trim().is_empty() {
+            return Err(BridgeError::InvalidParams {
+                method: METHOD_SAVE_PRESET.to_string(),
+                reason: "Preset name must not be empty".to_string(),
+            });
+        }
+
+        let library = self.preset_library(METHOD_SAVE_PRESET)?;
+        let preset = Preset {
+            format_version: PRESET_FORMAT_VERSION,
+            name: params.name,
+            plugin_version: library.plugin_version().to_string(),
+            author: params.author,
+            category: params.category,
+            description: params.description,
+            parameters: self
+                .host
+                .get_all_parameters()
+                .into_iter()
+                .map(|param| (param.id, param.value))
+                .collect(),
+        };
+        let summary = preset.summary(PresetSource::User);
+        library.save(preset)?;
+
+        Ok(IpcResponse::success(
+            request.id.clone(),
+            SavePresetResult { preset: summary },
+        ))
+    }
+
+    fn handle_delete_preset(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let params: DeletePresetParams =
+            self.parse_required_params(request, METHOD_DELETE_PRESET)?;
+
+        self.preset_library(METHOD_DELETE_PRESET)?
+            .delete(&params.name)?;
+
+        Ok(IpcResponse::success(
+            request.id.clone(),
+            DeletePresetResult {},
+        ))
+    }
+
+    fn handle_get_modulation(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let config = self.host.get_modulation().ok_or_else(|| {
+            BridgeError::Internal(format!(
+                "{METHOD_GET_MODULATION} is not supported: host has no modulation"
+            ))
+        })?;
+
+        Ok(IpcResponse::success(
+            request.id.clone(),
+            GetModulationResult { config },
+        ))
+    }
+
+    fn handle_set_modulation(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
+        let params: SetModulationParams =
+            self.parse_required_params(request, METHOD_SET_MODULATION)?;
+
+        let invalid = |reason: String| BridgeError::InvalidParams {
+            method: METHOD_SET_MODULATION.to_string(),
+            reason,
+        };
+        params.config.validate().map_err(invalid)?;
+        if let Some(routing) = params
+            .config
+            .routings
+            .iter()
+            .find(|routing| self.host.get_parameter(&routing.target).is_none())
+        {
+            return Err(invalid(format!(
+                "Unknown modulation target: {}",
+                routing.target
+            )));
+        }
+
+        self.host.set_modulation(params.config)?;
+
+        Ok(IpcResponse::success(
+            request.id.clone(),
+            SetModulationResult {},
+        ))
+    }
+
+    fn handle_get_modulation_frame(
+        &self,
+        request: &IpcRequest,
+    ) -> Result<IpcResponse, BridgeError> {
+        let result = GetModulationFrameResult {
+            frame: self.host.get_modulation_frame(),
+        };
+
+        Ok(IpcResponse::success(request.id.clone(), result))
+    }
+
     fn handle_ping(&self, request: &IpcRequest) -> Result<IpcResponse, BridgeError> {
         // Simple ping/pong for testing connectivity
         #[derive(Serialize)]
@@ -198,12 +404,15 @@ mod tests {
     use super::*;
     use wavecraft_protocol::{
         AudioRuntimePhase, AudioRuntimeStatus, MeterFrame, OscilloscopeFrame, ParameterInfo,
-        ParameterType, RequestId,
+        ParameterType, RequestId, SpectrumFrame,
     };
 
     // Mock ParameterHost for testing
     struct MockHost {
         params: Vec<ParameterInfo>,
+        values: std::sync::Mutex<std::collections::HashMap<String, f32>>,
+        presets: Option<PresetLibrary>,
+        modulation: std::sync::Mutex<wavecraft_protocol::ModulationConfig>,
     }
 
     impl MockHost {
@@ -235,13 +444,27 @@ mod tests {
                         variants: None,
                     },
                 ],
+                values: Default::default(),
+                presets: None,
+                modulation: Default::default(),
+            }
+        }
+
+        fn with_presets(factory: Vec<Preset>) -> Self {
+            Self {
+                presets: Some(PresetLibrary::new("1.0.0", factory)),
+                ..Self::new()
             }
         }
     }
 
     impl ParameterHost for MockHost {
         fn get_parameter(&self, id: &str) -> Option<ParameterInfo> {
-            self.params.iter().find(|p| p.id == id).cloned()
+            let mut param = self.params.iter().find(|p| p.id == id).cloned()?;
+            if let Some(value) = self.values.lock().unwrap().get(id) {
+                param.value = *value;
+            }
+            Some(param)
         }
 
         fn set_parameter(&self, id: &str, value: f32) -> Result<(), BridgeError> {
@@ -256,12 +479,15 @@ mod tests {
                 });
             }
 
-            // In real implementation, would update atomic value
+            self.values.lock().unwrap().insert(id.to_string(), value);
             Ok(())
         }
 
         fn get_all_parameters(&self) -> Vec<ParameterInfo> {
-            self.params.clone()
+            self.params
+                .iter()
+                .filter_map(|p| self.get_parameter(&p.id))
+                .collect()
         }
 
         fn get_meter_frame(&self) -> Option<MeterFrame> {
@@ -273,6 +499,31 @@ mod tests {
             None
         }
 
+        fn get_tap_meter_frame(&self, tap_id: &str) -> Option<MeterFrame> {
+            (tap_id == "input").then(|| MeterFrame {
+                peak_l: 0.25,
+                ..MeterFrame::default()
+            })
+        }
+
+        fn get_tap_spectrum_frame(&self, tap_id: &str) -> Option<SpectrumFrame> {
+            (tap_id == "input").then(|| SpectrumFrame {
+                frequencies: vec![1000.0],
+                magnitudes_l: vec![-6.0],
+                magnitudes_r: vec![-6.0],
+                sample_rate: 48_000.0,
+                fft_size: 2048,
+                timestamp: 1,
+            })
+        }
+
+        fn get_eq_response(&self, eq_id: Option<&str>, frequencies_hz: &[f32]) -> Option<Vec<f32>> {
+            // A flat EQ registered as "eq".
+            eq_id
+                .is_none_or(|eq_id| eq_id == "eq")
+                .then(|| vec![0.0; frequencies_hz.len()])
+        }
+
         fn request_resize(&self, _width: u32, _height: u32) -> bool {
             // Mock always accepts resize requests
             true
@@ -287,6 +538,22 @@ mod tests {
                 updated_at_ms: 123,
             })
         }
+
+        fn presets(&self) -> Option<&PresetLibrary> {
+            self.presets.as_ref()
+        }
+
+        fn get_modulation(&self) -> Option<wavecraft_protocol::ModulationConfig> {
+            Some(self.modulation.lock().unwrap().clone())
+        }
+
+        fn set_modulation(
+            &self,
+            config: wavecraft_protocol::ModulationConfig,
+        ) -> Result<(), BridgeError> {
+            *self.modulation.lock().unwrap() = config;
+            Ok(())
+        }
     }
 
     #[test]
@@ -454,4 +721,244 @@ mod tests {
                 .expect("oscilloscope result should deserialize");
         assert!(result.frame.is_none());
     }
+
+    #[test]
+    fn test_get_meter_frame_selects_tap() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        let frame_for = |params| {
+            let request = IpcRequest::new(RequestId::Number(10), METHOD_GET_METER_FRAME, params);
+            let response = handler.handle_request(request);
+            let result: GetMeterFrameResult =
+                serde_json::from_value(response.result.expect("meter response should exist"))
+                    .expect("meter result should deserialize");
+            result.frame
+        };
+
+        assert!(frame_for(None).is_none());
+        let frame = frame_for(Some(serde_json::json!({"tap_id": "input"})))
+            .expect("tap frame should be returned");
+        assert_eq!(frame.peak_l, 0.25);
+        assert!(frame_for(Some(serde_json::json!({"tap_id": "missing"}))).is_none());
+    }
+
+    #[test]
+    fn test_get_spectrum_frame_defaults_to_none() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        let request = IpcRequest::new(RequestId::Number(9), METHOD_GET_SPECTRUM_FRAME, None);
+
+        let response = handler.handle_request(request);
+        assert!(response.error.is_none());
+
+        let result: GetSpectrumFrameResult =
+            serde_json::from_value(response.result.expect("spectrum response should exist"))
+                .expect("spectrum result should deserialize");
+        assert!(result.frame.is_none());
+    }
+
+    #[test]
+    fn test_get_spectrum_frame_selects_tap() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        let frame_for = |params| {
+            let request = IpcRequest::new(RequestId::Number(11), METHOD_GET_SPECTRUM_FRAME, params);
+            let response = handler.handle_request(request);
+            let result: GetSpectrumFrameResult =
+                serde_json::from_value(response.result.expect("spectrum response should exist"))
+                    .expect("spectrum result should deserialize");
+            result.frame
+        };
+
+        assert!(frame_for(None).is_none());
+        let frame = frame_for(Some(serde_json::json!({"tap_id": "input"})))
+            .expect("tap frame should be returned");
+        assert_eq!(frame.magnitudes_l, [-6.0]);
+        assert!(frame_for(Some(serde_json::json!({"tap_id": "missing"}))).is_none());
+    }
+
+    #[test]
+    fn test_get_eq_response() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        let response_for = |params| {
+            let request = IpcRequest::new(RequestId::Number(12), METHOD_GET_EQ_RESPONSE, params);
+            handler.handle_request(request)
+        };
+        let magnitudes_for = |params| {
+            let response = response_for(Some(params));
+            let result: GetEqResponseResult =
+                serde_json::from_value(response.result.expect("eq response should exist"))
+                    .expect("eq result should deserialize");
+            result.magnitudes_db
+        };
+
+        let magnitudes = magnitudes_for(serde_json::json!({"frequencies": [100.0, 1000.0]}))
+            .expect("default EQ should respond");
+        assert_eq!(magnitudes, [0.0, 0.0]);
+        assert!(
+            magnitudes_for(serde_json::json!({"frequencies": [100.0], "eq_id": "eq"})).is_some()
+        );
+        assert!(
+            magnitudes_for(serde_json::json!({"frequencies": [100.0], "eq_id": "missing"}))
+                .is_none()
+        );
+
+        let error = response_for(None).error.expect("frequencies are required");
+        assert_eq!(error.code, wavecraft_protocol::ERROR_INVALID_PARAMS);
+    }
+
+    fn factory_preset() -> Preset {
+        Preset {
+            format_version: PRESET_FORMAT_VERSION,
+            name: "Loud".to_string(),
+            plugin_version: "0.9.0".to_string(),
+            author: Some("Sound Design".to_string()),
+            category: None,
+            description: None,
+            parameters: [
+                ("gain".to_string(), 1.4),
+                ("removed_param".to_string(), 0.2),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn preset_request(id: i64, method: &str, params: serde_json::Value) -> IpcRequest {
+        IpcRequest::new(RequestId::Number(id), method, Some(params))
+    }
+
+    #[test]
+    fn test_load_preset_applies_clamped_values_and_reports_skipped() {
+        let handler = IpcHandler::new(MockHost::with_presets(vec![factory_preset()]));
+
+        let response = handler.handle_request(preset_request(
+            9,
+            METHOD_LOAD_PRESET,
+            serde_json::json!({"name": "Loud", "source": "factory"}),
+        ));
+
+        let result: LoadPresetResult =
+            serde_json::from_value(response.result.expect("load should succeed"))
+                .expect("load result should deserialize");
+        assert_eq!(result.skipped, ["removed_param"]);
+        assert_eq!(handler.host.get_parameter("gain").unwrap().value, 1.0);
+    }
+
+    #[test]
+    fn test_save_list_and_delete_user_preset() {
+        let handler = IpcHandler::new(MockHost::with_presets(vec![factory_preset()]));
+        handler.host.set_parameter("gain", 0.25).unwrap();
+
+        let response = handler.handle_request(preset_request(
+            10,
+            METHOD_SAVE_PRESET,
+            serde_json::json!({"name": "Quiet", "category": "Soft"}),
+        ));
+        assert!(response.error.is_none());
+
+        let saved = handler
+            .host
+            .presets()
+            .unwrap()
+            .get("Quiet", PresetSource::User)
+            .expect("saved preset should exist");
+        assert_eq!(saved.parameters.get("gain"), Some(&0.25));
+        assert_eq!(saved.plugin_version, "1.0.0");
+
+        let response = handler.handle_request(IpcRequest::new(
+            RequestId::Number(11),
+            METHOD_LIST_PRESETS,
+            None,
+        ));
+        let result: ListPresetsResult =
+            serde_json::from_value(response.result.expect("list should succeed")).unwrap();
+        let names: Vec<_> = result.presets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Loud", "Quiet"]);
+
+        let response = handler.handle_request(preset_request(
+            12,
+            METHOD_DELETE_PRESET,
+            serde_json::json!({"name": "Quiet"}),
+        ));
+        assert!(response.error.is_none());
+
+        let response = handler.handle_request(preset_request(
+            13,
+            METHOD_DELETE_PRESET,
+            serde_json::json!({"name": "Loud"}),
+        ));
+        assert_eq!(
+            response
+                .error
+                .expect("factory preset delete must fail")
+                .code,
+            wavecraft_protocol::ERROR_PRESET_NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_preset_methods_without_library_fail() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        let response = handler.handle_request(IpcRequest::new(
+            RequestId::Number(14),
+            METHOD_LIST_PRESETS,
+            None,
+        ));
+        assert_eq!(
+            response.error.expect("no preset library").code,
+            wavecraft_protocol::ERROR_INTERNAL
+        );
+    }
+
+    fn modulation_config(target: &str, depth: f32) -> serde_json::Value {
+        serde_json::json!({
+            "config": {
+                "sources": [{ "type": "lfo", "shape": "triangle", "rate": { "type": "hz", "hz": 0.5 } }],
+                "routings": [{ "source": 0, "target": target, "depth": depth }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_set_and_get_modulation() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        let response = handler.handle_request(preset_request(
+            15,
+            METHOD_SET_MODULATION,
+            modulation_config("gain", 0.5),
+        ));
+        assert!(response.error.is_none());
+
+        let response = handler.handle_request(IpcRequest::new(
+            RequestId::Number(16),
+            METHOD_GET_MODULATION,
+            None,
+        ));
+        let result: GetModulationResult =
+            serde_json::from_value(response.result.expect("get should succeed")).unwrap();
+        assert_eq!(result.config.routings[0].target, "gain");
+        assert_eq!(result.config.routings[0].depth, 0.5);
+    }
+
+    #[test]
+    fn test_set_modulation_rejects_unknown_targets_and_bad_depth() {
+        let handler = IpcHandler::new(MockHost::new());
+
+        for (id, params) in [
+            (17, modulation_config("missing", 0.5)),
+            (18, modulation_config("gain", 2.0)),
+        ] {
+            let response =
+                handler.handle_request(preset_request(id, METHOD_SET_MODULATION, params));
+            assert_eq!(
+                response.error.expect("config should be rejected").code,
+                wavecraft_protocol::ERROR_INVALID_PARAMS
+            );
+        }
+        assert!(handler.host.get_modulation().unwrap().routings.is_empty());
+    }
 }
@@ -4,8 +4,12 @@
 //! and the actual parameter storage (typically in the plugin or DAW host).
 
 use crate::error::BridgeError;
+use crate::presets::PresetLibrary;
 use std::sync::Arc;
-use wavecraft_protocol::{AudioRuntimeStatus, MeterFrame, OscilloscopeFrame, ParameterInfo};
+use wavecraft_protocol::{
+    AudioRuntimeStatus, MeterFrame, ModulationConfig, ModulationFrame, OscilloscopeFrame,
+    ParameterInfo, SpectrumFrame,
+};
 
 /// Trait for objects that store and manage parameters.
 ///
@@ -137,6 +141,47 @@ pub trait ParameterHost: Send + Sync {
     /// The latest oscilloscope frame, or `None` if no frame is available.
     fn get_oscilloscope_frame(&self) -> Option<OscilloscopeFrame>;
 
+    /// Get the latest meter frame of the named tap `tap_id`.
+    ///
+    /// Returns `None` (the default) if the host has no tap with that ID or
+    /// the tap hasn't published a frame since the last call.
+    fn get_tap_meter_frame(&self, _tap_id: &str) -> Option<MeterFrame> {
+        None
+    }
+
+    /// Get the latest oscilloscope frame of the named tap `tap_id`.
+    ///
+    /// Returns `None` (the default) if the host has no tap with that ID or
+    /// the tap hasn't published a frame since the last call.
+    fn get_tap_oscilloscope_frame(&self, _tap_id: &str) -> Option<OscilloscopeFrame> {
+        None
+    }
+
+    /// Get the latest spectrum analyzer frame for UI visualization.
+    ///
+    /// Returns `None` (the default) if the host has no spectrum analyzer or
+    /// no frame is available yet.
+    fn get_spectrum_frame(&self) -> Option<SpectrumFrame> {
+        None
+    }
+
+    /// Get the latest spectrum frame of the named tap `tap_id`.
+    ///
+    /// Returns `None` (the default) if the host has no tap with that ID or
+    /// the tap hasn't published a frame since the last call.
+    fn get_tap_spectrum_frame(&self, _tap_id: &str) -> Option<SpectrumFrame> {
+        None
+    }
+
+    /// Get the combined magnitude response in dB of a parametric EQ, one
+    /// value per entry of `frequencies_hz`.
+    ///
+    /// `eq_id` is the EQ's parameter ID prefix; `None` selects the first
+    /// EQ. Returns `None` (the default) if the host has no such EQ.
+    fn get_eq_response(&self, _eq_id: Option<&str>, _frequencies_hz: &[f32]) -> Option<Vec<f32>> {
+        None
+    }
+
     /// Request resize of the editor window.
     ///
     /// Asks the host (DAW or standalone window manager) to resize the plugin UI.
@@ -154,6 +199,48 @@ pub trait ParameterHost: Send + Sync {
     ///
     /// Implementers that do not expose runtime audio state should return `None`.
     fn get_audio_status(&self) -> Option<AudioRuntimeStatus>;
+
+    /// Get the factory and user presets served by the preset IPC methods.
+    ///
+    /// Loading a preset goes through [`ParameterHost::set_parameter`], so
+    /// hosts only need to provide the library.
+    ///
+    /// # Returns
+    /// The preset library, or `None` (the default) if presets are unsupported.
+    fn presets(&self) -> Option<&PresetLibrary> {
+        None
+    }
+
+    /// Get the modulation sources and routings.
+    ///
+    /// # Returns
+    /// The current configuration, or `None` (the default) if the host has no
+    /// modulation.
+    fn get_modulation(&self) -> Option<ModulationConfig> {
+        None
+    }
+
+    /// Replace the modulation sources and routings.
+    ///
+    /// The IPC handler validates `config` and its routing targets before
+    /// calling this.
+    ///
+    /// # Errors
+    /// The default implementation returns [`BridgeError::Internal`] because
+    /// modulation is unsupported.
+    fn set_modulation(&self, _config: ModulationConfig) -> Result<(), BridgeError> {
+        Err(BridgeError::Internal(
+            "host does not support modulation".to_string(),
+        ))
+    }
+
+    /// Get the latest modulated parameter values for UI modulation rings.
+    ///
+    /// Returns `None` (the default) if the host has no modulation or hasn't
+    /// processed audio yet.
+    fn get_modulation_frame(&self) -> Option<ModulationFrame> {
+        None
+    }
 }
 
 /// Blanket implementation for Arc<T> where T: ParameterHost.
@@ -183,6 +270,26 @@ impl<T: ParameterHost> ParameterHost for std::sync::Arc<T> {
         forward_host(self).get_oscilloscope_frame()
     }
 
+    fn get_tap_meter_frame(&self, tap_id: &str) -> Option<MeterFrame> {
+        forward_host(self).get_tap_meter_frame(tap_id)
+    }
+
+    fn get_tap_oscilloscope_frame(&self, tap_id: &str) -> Option<OscilloscopeFrame> {
+        forward_host(self).get_tap_oscilloscope_frame(tap_id)
+    }
+
+    fn get_spectrum_frame(&self) -> Option<SpectrumFrame> {
+        forward_host(self).get_spectrum_frame()
+    }
+
+    fn get_tap_spectrum_frame(&self, tap_id: &str) -> Option<SpectrumFrame> {
+        forward_host(self).get_tap_spectrum_frame(tap_id)
+    }
+
+    fn get_eq_response(&self, eq_id: Option<&str>, frequencies_hz: &[f32]) -> Option<Vec<f32>> {
+        forward_host(self).get_eq_response(eq_id, frequencies_hz)
+    }
+
     fn request_resize(&self, width: u32, height: u32) -> bool {
         forward_host(self).request_resize(width, height)
     }
@@ -190,6 +297,22 @@ impl<T: ParameterHost> ParameterHost for std::sync::Arc<T> {
     fn get_audio_status(&self) -> Option<AudioRuntimeStatus> {
         forward_host(self).get_audio_status()
     }
+
+    fn presets(&self) -> Option<&PresetLibrary> {
+        forward_host(self).presets()
+    }
+
+    fn get_modulation(&self) -> Option<ModulationConfig> {
+        forward_host(self).get_modulation()
+    }
+
+    fn set_modulation(&self, config: ModulationConfig) -> Result<(), BridgeError> {
+        forward_host(self).set_modulation(config)
+    }
+
+    fn get_modulation_frame(&self) -> Option<ModulationFrame> {
+        forward_host(self).get_modulation_frame()
+    }
 }
 
 #[inline]
@@ -324,7 +324,7 @@ mod tests {
             rms_l: 0.5,
             peak_r: 0.6,
             rms_r: 0.4,
-            timestamp: 0,
+            ..MeterFrame::default()
         };
         let provider = Arc::new(StaticMeterProvider { frame });
         let host = InMemoryParameterHost::with_meter_provider(test_params(), provider);
@@ -9,6 +9,7 @@
 //! - **ParameterHost** trait: Abstracts parameter storage (desktop POC, plugin, etc.)
 //! - **IpcHandler**: Dispatches JSON-RPC requests to appropriate handlers
 //! - **BridgeError**: Typed error handling with conversion to IPC error codes
+//! - **PresetLibrary**: Factory and user presets behind the preset IPC methods
 //!
 //! # Example
 //!
@@ -69,6 +70,7 @@ pub mod handler;
 pub mod host;
 pub mod in_memory_host;
 pub mod plugin_loader;
+pub mod presets;
 
 // Core bridge entrypoints and host abstraction.
 pub use error::BridgeError;
@@ -78,11 +80,12 @@ pub use host::ParameterHost;
 // Test/dev host implementations and plugin loader support.
 pub use in_memory_host::{InMemoryParameterHost, MeterProvider, OscilloscopeProvider};
 pub use plugin_loader::{PluginLoaderError, PluginParamLoader};
+pub use presets::{PresetLibrary, default_user_preset_dir};
 
 // Protocol contracts surfaced by the bridge API.
 pub use wavecraft_protocol::{
     GetAllParametersResult, GetParameterParams, GetParameterResult, IpcError, IpcNotification,
     IpcRequest, IpcResponse, MeterUpdateNotification, ParameterChangedNotification, ParameterInfo,
-    ParameterType, ProcessorInfo, RegisterAudioParams, RegisterAudioResult, RequestId,
-    SetParameterParams, SetParameterResult,
+    ParameterType, Preset, PresetCatalog, PresetSource, PresetSummary, ProcessorInfo,
+    RegisterAudioParams, RegisterAudioResult, RequestId, SetParameterParams, SetParameterResult,
 };
@@ -5,7 +5,7 @@ use std::ffi::CStr;
 use std::os::raw::c_char;
 use std::path::Path;
 use wavecraft_protocol::{
-    DEV_PROCESSOR_VTABLE_VERSION, DevProcessorVTable, ParameterInfo, ProcessorInfo,
+    DEV_PROCESSOR_VTABLE_VERSION, DevProcessorVTable, ParameterInfo, PresetCatalog, ProcessorInfo,
 };
 
 /// Errors that can occur during plugin loading.
@@ -59,6 +59,7 @@ impl std::error::Error for PluginLoaderError {
 
 type GetParamsJsonFn = unsafe extern "C" fn() -> *mut c_char;
 type GetProcessorsJsonFn = unsafe extern "C" fn() -> *mut c_char;
+type GetPresetsJsonFn = unsafe extern "C" fn() -> *mut c_char;
 type FreeStringFn = unsafe extern "C" fn(*mut c_char);
 type DevProcessorVTableFn = unsafe extern "C" fn() -> DevProcessorVTable;
 
@@ -142,6 +143,8 @@ where
 pub struct PluginParamLoader {
     parameters: Vec<ParameterInfo>,
     processors: Vec<ProcessorInfo>,
+    /// Factory presets and user preset location exported by the plugin.
+    presets: PresetCatalog,
     /// Audio processor vtable for in-process dev audio.
     dev_processor_vtable: DevProcessorVTable,
     /// Dynamic library handle — must be the last field so it is dropped
@@ -222,12 +225,15 @@ impl PluginParamLoader {
             "wavecraft_get_processors_json",
         )?;
 
+        let presets = Self::load_presets(&library, *free_string)?;
+
         // Load and validate audio processor vtable (required for current SDK dev mode)
         let dev_processor_vtable = Self::load_processor_vtable(&library)?;
 
         Ok(Self {
             parameters: params,
             processors,
+            presets,
             dev_processor_vtable,
             _library: library,
         })
@@ -320,6 +326,11 @@ impl PluginParamLoader {
         &self.processors
     }
 
+    /// Get the plugin's preset catalog.
+    pub fn presets(&self) -> &PresetCatalog {
+        &self.presets
+    }
+
     /// Get a parameter by ID.
     #[allow(dead_code)]
     pub fn get_parameter(&self, id: &str) -> Option<&ParameterInfo> {
@@ -331,6 +342,29 @@ impl PluginParamLoader {
         &self.dev_processor_vtable
     }
 
+    /// Load the preset catalog from the library.
+    ///
+    /// Plugins built before presets were added don't export
+    /// `wavecraft_get_presets_json`; they get an empty catalog.
+    fn load_presets(
+        library: &Library,
+        free_string: FreeStringFn,
+    ) -> Result<PresetCatalog, PluginLoaderError> {
+        // SAFETY: Symbol is generated by wavecraft_plugin! and uses GetPresetsJsonFn ABI.
+        let get_presets_json: Symbol<GetPresetsJsonFn> =
+            match unsafe { library.get(b"wavecraft_get_presets_json\0") } {
+                Ok(symbol) => symbol,
+                Err(_) => return Ok(PresetCatalog::default()),
+            };
+
+        // SAFETY: See load() for FFI safety rationale.
+        load_json_via_ffi::<PresetCatalog>(
+            *get_presets_json,
+            free_string,
+            "wavecraft_get_presets_json",
+        )
+    }
+
     /// Load and validate the dev audio processor vtable from the library.
     fn load_processor_vtable(library: &Library) -> Result<DevProcessorVTable, PluginLoaderError> {
         // SAFETY: `library` is a valid loaded Library handle. The symbol name
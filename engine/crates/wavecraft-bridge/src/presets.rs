@@ -0,0 +1,405 @@
+//! Factory and user preset storage backing the preset IPC methods.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use wavecraft_protocol::{Preset, PresetCatalog, PresetSource, PresetSummary};
+
+use crate::BridgeError;
+
+/// Where user presets are kept.
+enum UserPresets {
+    /// One JSON file per preset in a directory.
+    Directory(PathBuf),
+    /// Held in memory only (tests, hosts without a writable location).
+    Memory(Mutex<Vec<Preset>>),
+}
+
+/// Read-only factory presets plus a writable set of user presets.
+///
+/// Returned from [`ParameterHost::presets`](crate::ParameterHost::presets)
+/// so the IPC handler can serve `listPresets`, `loadPreset`, `savePreset`
+/// and `deletePreset`.
+pub struct PresetLibrary {
+    plugin_version: String,
+    factory: Vec<Preset>,
+    user: UserPresets,
+}
+
+impl PresetLibrary {
+    /// Create a library with in-memory user presets.
+    pub fn new(plugin_version: impl Into<String>, factory: Vec<Preset>) -> Self {
+        Self {
+            plugin_version: plugin_version.into(),
+            factory,
+            user: UserPresets::Memory(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Store user presets as JSON files in `dir` (created on first save).
+    pub fn with_user_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.user = UserPresets::Directory(dir.into());
+        self
+    }
+
+    /// Create a library from the catalog a plugin exports.
+    pub fn from_catalog(catalog: PresetCatalog) -> Self {
+        let library = Self::new(catalog.plugin_version, catalog.factory);
+        match catalog.user_dir {
+            Some(dir) => library.with_user_dir(dir),
+            None => library,
+        }
+    }
+
+    /// Plugin version stamped into saved presets.
+    pub fn plugin_version(&self) -> &str {
+        &self.plugin_version
+    }
+
+    /// List factory presets in declaration order, then user presets by name.
+    pub fn list(&self) -> Vec<PresetSummary> {
+        let mut user = self.user_presets();
+        user.sort_by(|a, b| a.name.cmp(&b.name));
+
+        self.factory
+            .iter()
+            .map(|preset| preset.summary(PresetSource::Factory))
+            .chain(user.iter().map(|preset| preset.summary(PresetSource::User)))
+            .collect()
+    }
+
+    /// Look up a preset by name.
+    pub fn get(&self, name: &str, source: PresetSource) -> Option<Preset> {
+        match source {
+            PresetSource::Factory => self
+                .factory
+                .iter()
+                .find(|preset| preset.name == name)
+                .cloned(),
+            PresetSource::User => self
+                .user_presets()
+                .into_iter()
+                .find(|preset| preset.name == name),
+        }
+    }
+
+    /// Save a user preset, replacing any user preset with the same name.
+    ///
+    /// In a preset directory, a preset is identified by the name stored in its
+    /// file, not by the file name: replacing one rewrites whichever file holds
+    /// it, and a new preset whose sanitized file name is taken gets a numbered
+    /// file name instead of overwriting the other preset.
+    pub fn save(&self, preset: Preset) -> Result<(), BridgeError> {
+        match &self.user {
+            UserPresets::Directory(dir) => {
+                let path = user_preset_path(dir, &preset.name)?;
+                let json = serde_json::to_string_pretty(&preset)?;
+                fs::create_dir_all(dir)
+                    .and_then(|()| fs::write(&path, json))
+                    .map_err(|err| storage_error(&path, err))
+            }
+            UserPresets::Memory(presets) => {
+                let mut presets = presets.lock().unwrap_or_else(PoisonError::into_inner);
+                presets.retain(|existing| existing.name != preset.name);
+                presets.push(preset);
+                Ok(())
+            }
+        }
+    }
+
+    /// Delete a user preset. Factory presets can't be deleted.
+    pub fn delete(&self, name: &str) -> Result<(), BridgeError> {
+        match &self.user {
+            UserPresets::Directory(dir) => {
+                let path = user_preset_files(dir)
+                    .find(|(_, preset)| preset.name == name)
+                    .map(|(path, _)| path)
+                    .ok_or_else(|| BridgeError::PresetNotFound(name.to_string()))?;
+                fs::remove_file(&path).map_err(|err| storage_error(&path, err))
+            }
+            UserPresets::Memory(presets) => {
+                let mut presets = presets.lock().unwrap_or_else(PoisonError::into_inner);
+                let count = presets.len();
+                presets.retain(|preset| preset.name != name);
+                if presets.len() == count {
+                    return Err(BridgeError::PresetNotFound(name.to_string()));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn user_presets(&self) -> Vec<Preset> {
+        match &self.user {
+            UserPresets::Directory(dir) => {
+                user_preset_files(dir).map(|(_, preset)| preset).collect()
+            }
+            UserPresets::Memory(presets) => presets
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+        }
+    }
+}
+
+/// Default per-user preset directory for a plugin.
+///
+/// - macOS: `~/Library/Audio/Presets/<vendor>/<plugin>`
+/// - Windows: `%APPDATA%\<vendor>\<plugin>\Presets`
+/// - Linux: `$XDG_DATA_HOME/<vendor>/<plugin>/presets` (or `~/.local/share/...`)
+pub fn default_user_preset_dir(vendor: &str, plugin_name: &str) -> Option<PathBuf> {
+    let vendor = sanitize_path_component(vendor);
+    let plugin_name = sanitize_path_component(plugin_name);
+
+    let dir = if cfg!(target_os = "macos") {
+        home_dir()?
+            .join("Library/Audio/Presets")
+            .join(vendor)
+            .join(plugin_name)
+    } else if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("APPDATA")?)
+            .join(vendor)
+            .join(plugin_name)
+            .join("Presets")
+    } else {
+        let data_dir = match std::env::var_os("XDG_DATA_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => home_dir()?.join(".local/share"),
+        };
+        data_dir.join(vendor).join(plugin_name).join("presets")
+    };
+
+    Some(dir)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Replace characters that aren't safe in file names on every platform.
+fn sanitize_path_component(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('.')
+        .to_string()
+}
+
+fn preset_file_name(name: &str) -> Result<String, BridgeError> {
+    let stem = sanitize_path_component(name);
+    if stem.is_empty() {
+        return Err(BridgeError::Internal(format!(
+            "preset name '{name}' can't be used as a file name"
+        )));
+    }
+    Ok(format!("{stem}.json"))
+}
+
+/// File for the user preset called `name`: the file that already holds it, or
+/// else the first free file name derived from `name`.
+fn user_preset_path(dir: &Path, name: &str) -> Result<PathBuf, BridgeError> {
+    if let Some((path, _)) = user_preset_files(dir).find(|(_, preset)| preset.name == name) {
+        return Ok(path);
+    }
+
+    let file_name = preset_file_name(name)?;
+    let stem = file_name.trim_end_matches(".json");
+    let path = (1..)
+        .map(|index| match index {
+            1 => dir.join(&file_name),
+            _ => dir.join(format!("{stem} ({index}).json")),
+        })
+        .find(|path| !path.exists())
+        .expect("an unbounded range always yields a free file name");
+    Ok(path)
+}
+
+/// Parse every `*.json` preset in `dir`, skipping unreadable files.
+fn user_preset_files(dir: &Path) -> impl Iterator<Item = (PathBuf, Preset)> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let json = fs::read_to_string(&path).ok()?;
+            match serde_json::from_str(&json) {
+                Ok(preset) => Some((path, preset)),
+                Err(err) => {
+                    tracing::warn!("Skipping unreadable preset {}: {}", path.display(), err);
+                    None
+                }
+            }
+        })
+}
+
+fn storage_error(path: &Path, err: std::io::Error) -> BridgeError {
+    BridgeError::Internal(format!("preset file {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use wavecraft_protocol::PRESET_FORMAT_VERSION;
+
+    fn preset(name: &str, gain: f32) -> Preset {
+        Preset {
+            format_version: PRESET_FORMAT_VERSION,
+            name: name.to_string(),
+            plugin_version: "1.0.0".to_string(),
+            author: None,
+            category: None,
+            description: None,
+            parameters: BTreeMap::from([("gain".to_string(), gain)]),
+        }
+    }
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("wavecraft-presets-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn lists_factory_presets_before_sorted_user_presets() {
+        let library = PresetLibrary::new("1.0.0", vec![preset("Zeta", 0.1), preset("Alpha", 0.2)]);
+        library.save(preset("Mine B", 0.3)).expect("save");
+        library.save(preset("Mine A", 0.4)).expect("save");
+
+        let names: Vec<_> = library
+            .list()
+            .into_iter()
+            .map(|summary| (summary.name, summary.source))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("Zeta".to_string(), PresetSource::Factory),
+                ("Alpha".to_string(), PresetSource::Factory),
+                ("Mine A".to_string(), PresetSource::User),
+                ("Mine B".to_string(), PresetSource::User),
+            ]
+        );
+    }
+
+    #[test]
+    fn directory_store_saves_replaces_and_deletes() {
+        let dir = temp_dir("directory");
+        let library = PresetLibrary::new("1.0.0", Vec::new()).with_user_dir(&dir);
+
+        library.save(preset("Lead / Bright", 0.5)).expect("save");
+        library
+            .save(preset("Lead / Bright", 0.75))
+            .expect("overwrite");
+        assert!(dir.join("Lead _ Bright.json").exists());
+
+        let loaded = library
+            .get("Lead / Bright", PresetSource::User)
+            .expect("saved preset should load");
+        assert_eq!(loaded.parameters.get("gain"), Some(&0.75));
+        assert_eq!(library.list().len(), 1);
+
+        library.delete("Lead / Bright").expect("delete");
+        assert!(library.get("Lead / Bright", PresetSource::User).is_none());
+        assert!(matches!(
+            library.delete("Lead / Bright"),
+            Err(BridgeError::PresetNotFound(_))
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn directory_store_keeps_names_that_sanitize_to_the_same_file() {
+        let dir = temp_dir("collision");
+        let library = PresetLibrary::new("1.0.0", Vec::new()).with_user_dir(&dir);
+
+        library.save(preset("Lead/Bright", 0.25)).expect("save");
+        library.save(preset("Lead:Bright", 0.5)).expect("save");
+        assert!(dir.join("Lead_Bright.json").exists());
+        assert!(dir.join("Lead_Bright (2).json").exists());
+
+        // Saving either again replaces only that preset.
+        library
+            .save(preset("Lead:Bright", 0.75))
+            .expect("overwrite");
+        let names: Vec<_> = library
+            .list()
+            .into_iter()
+            .map(|summary| summary.name)
+            .collect();
+        assert_eq!(names, ["Lead/Bright", "Lead:Bright"]);
+        assert_eq!(
+            library
+                .get("Lead/Bright", PresetSource::User)
+                .and_then(|preset| preset.parameters.get("gain").copied()),
+            Some(0.25)
+        );
+        assert_eq!(
+            library
+                .get("Lead:Bright", PresetSource::User)
+                .and_then(|preset| preset.parameters.get("gain").copied()),
+            Some(0.75)
+        );
+
+        library.delete("Lead/Bright").expect("delete");
+        assert!(!dir.join("Lead_Bright.json").exists());
+        assert!(library.get("Lead:Bright", PresetSource::User).is_some());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn directory_store_updates_renamed_files_in_place() {
+        let dir = temp_dir("renamed");
+        let library = PresetLibrary::new("1.0.0", Vec::new()).with_user_dir(&dir);
+
+        library.save(preset("Pad", 0.25)).expect("save");
+        fs::rename(dir.join("Pad.json"), dir.join("my pad.json")).expect("rename");
+
+        library.save(preset("Pad", 0.5)).expect("overwrite");
+        assert!(!dir.join("Pad.json").exists());
+        assert_eq!(library.list().len(), 1);
+        assert_eq!(
+            library
+                .get("Pad", PresetSource::User)
+                .and_then(|preset| preset.parameters.get("gain").copied()),
+            Some(0.5)
+        );
+
+        library.delete("Pad").expect("delete");
+        assert!(library.list().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn factory_presets_cannot_be_deleted() {
+        let library = PresetLibrary::new("1.0.0", vec![preset("Init", 0.5)]);
+
+        assert!(matches!(
+            library.delete("Init"),
+            Err(BridgeError::PresetNotFound(_))
+        ));
+        assert!(library.get("Init", PresetSource::Factory).is_some());
+    }
+
+    #[test]
+    fn sanitizes_path_components() {
+        assert_eq!(sanitize_path_component("  ../My:Plugin  "), "_My_Plugin");
+        assert!(preset_file_name("...").is_err());
+    }
+}
@@ -43,6 +43,12 @@
 /// - `Passthrough` → `wavecraft_processors::PassthroughDsp`
 /// - `Filter` → `wavecraft_processors::UnifiedFilterDsp`
 /// - `Saturator` → `wavecraft_processors::SaturatorDsp`
+/// - `Delay` → `wavecraft_processors::DelayDsp`
+/// - `Compressor` → `wavecraft_processors::CompressorDsp`
+/// - `Limiter` → `wavecraft_processors::LimiterDsp`
+/// - `Eq` → `wavecraft_processors::ParametricEqDsp` (four bands)
+/// - `Reverb` → `wavecraft_processors::ReverbDsp`
+/// - `StereoUtility` → `wavecraft_processors::StereoUtilityDsp`
 ///
 /// # Example
 ///
@@ -74,6 +80,30 @@ macro_rules! wavecraft_processor {
         $crate::wavecraft_processor!($name => $crate::wavecraft_processors::SaturatorDsp);
     };
 
+    ($name:ident => Delay) => {
+        $crate::wavecraft_processor!($name => $crate::wavecraft_processors::DelayDsp);
+    };
+
+    ($name:ident => Compressor) => {
+        $crate::wavecraft_processor!($name => $crate::wavecraft_processors::CompressorDsp);
+    };
+
+    ($name:ident => Limiter) => {
+        $crate::wavecraft_processor!($name => $crate::wavecraft_processors::LimiterDsp);
+    };
+
+    ($name:ident => Eq) => {
+        $crate::wavecraft_processor!($name => $crate::wavecraft_processors::ParametricEqDsp);
+    };
+
+    ($name:ident => Reverb) => {
+        $crate::wavecraft_processor!($name => $crate::wavecraft_processors::ReverbDsp);
+    };
+
+    ($name:ident => StereoUtility) => {
+        $crate::wavecraft_processor!($name => $crate::wavecraft_processors::StereoUtilityDsp);
+    };
+
     ($name:ident => $inner:path) => {
         #[derive(Default)]
         pub struct $name($inner);
@@ -90,13 +120,65 @@ macro_rules! wavecraft_processor {
                 self.0.process(buffer, transport, params)
             }
 
+            fn process_events(
+                &mut self,
+                buffer: &mut [&mut [f32]],
+                events: &[$crate::wavecraft_dsp::NoteEvent],
+                transport: &$crate::wavecraft_dsp::Transport,
+                params: &Self::Params,
+            ) {
+                self.0.process_events(buffer, events, transport, params)
+            }
+
+            fn process_with_sidechain(
+                &mut self,
+                buffer: &mut [&mut [f32]],
+                sidechain: &[&mut [f32]],
+                events: &[$crate::wavecraft_dsp::NoteEvent],
+                transport: &$crate::wavecraft_dsp::Transport,
+                params: &Self::Params,
+            ) {
+                self.0
+                    .process_with_sidechain(buffer, sidechain, events, transport, params)
+            }
+
             fn set_sample_rate(&mut self, sample_rate: f32) {
                 self.0.set_sample_rate(sample_rate)
             }
 
+            fn set_channel_layout(&mut self, layout: $crate::wavecraft_dsp::ChannelLayout) {
+                self.0.set_channel_layout(layout)
+            }
+
+            fn persistent_fields(
+                &self,
+                fields: &mut ::std::vec::Vec<
+                    ::std::sync::Arc<dyn $crate::wavecraft_dsp::PersistentField>,
+                >,
+            ) {
+                self.0.persistent_fields(fields)
+            }
+
+            fn metering_taps(
+                &self,
+                taps: &mut ::std::vec::Vec<
+                    ::std::sync::Arc<dyn $crate::wavecraft_dsp::MeteringTap>,
+                >,
+            ) {
+                self.0.metering_taps(taps)
+            }
+
             fn reset(&mut self) {
                 self.0.reset()
             }
+
+            fn latency_samples(&self) -> u32 {
+                self.0.latency_samples()
+            }
+
+            fn tail_samples(&self) -> $crate::wavecraft_dsp::TailLength {
+                self.0.tail_samples()
+            }
         }
     };
 }
@@ -17,12 +17,17 @@
 // Re-export Wavecraft DSP traits and types
 #[allow(deprecated)] // Chain! is deprecated but maintained for backward compatibility
 pub use wavecraft_dsp::{
-    Chain, ParamRange, ParamSpec, Processor, ProcessorParams, SignalChain, Transport,
+    Chain, ChannelLayout, MidSide, MidSideMode, Mix, NoteEvent, NoteEventKind, Oversampled,
+    Parallel, ParamRange, ParamSmoothing, ParamSpec, PersistentField, PersistentState, Processor,
+    ProcessorParams, SignalChain, Smoother, SmoothingStyle, TailLength, TapId, Transport,
+    note_to_frequency, tap_id,
 };
 
 // Re-export built-in processors
 pub use wavecraft_processors::{
-    GainDsp, PassthroughDsp, SaturatorDsp, UnifiedFilterDsp, UnifiedFilterMode,
+    CompressorDsp, DelayDsp, DelayMode, GainDsp, LimiterDsp, MeterTap, NoteDivision,
+    ParametricEqDsp, PassthroughDsp, ReverbDsp, SaturatorDsp, ScopeTap, SpectrumTap,
+    StereoUtilityDsp, UnifiedFilterDsp, UnifiedFilterMode,
 };
 
 // Re-export Wavecraft protocol types
@@ -1,7 +1,12 @@
 //! Tests for the wavecraft_processor! macro.
 
 use wavecraft_core::wavecraft_processor;
-use wavecraft_dsp::{Processor, Transport};
+use wavecraft_core::wavecraft_processors::{
+    DelayParams, EqBandType, LimiterParams, ParametricEqDsp, ParametricEqParams, ReverbParams,
+    StereoUtilityParams,
+};
+use wavecraft_dsp::testing::signals;
+use wavecraft_dsp::{Processor, ProcessorParams, Transport};
 
 // Generate wrapper types for built-in processors
 wavecraft_processor!(InputGain => Gain);
@@ -9,6 +14,15 @@ wavecraft_processor!(OutputGain => Gain);
 wavecraft_processor!(Bypass => Passthrough);
 wavecraft_processor!(ToneFilter => Filter);
 wavecraft_processor!(SoftClip => Saturator);
+wavecraft_processor!(Echo => Delay);
+wavecraft_processor!(BusCompressor => Compressor);
+wavecraft_processor!(MasterLimiter => Limiter);
+wavecraft_processor!(ToneShaper => Eq);
+wavecraft_processor!(Room => Reverb);
+wavecraft_processor!(Utility => StereoUtility);
+
+wavecraft_dsp::tap_id!(pub DrumBusReduction = "drum_bus_gain_reduction");
+wavecraft_processor!(DrumBus => wavecraft_core::wavecraft_processors::CompressorDsp<DrumBusReduction>);
 
 #[test]
 fn test_processor_macro_generates_default() {
@@ -17,6 +31,9 @@ fn test_processor_macro_generates_default() {
     let _bypass = Bypass::default();
     let _tone_filter = ToneFilter::default();
     let _soft_clip = SoftClip::default();
+    let _echo = Echo::default();
+    let _bus_compressor = BusCompressor::default();
+    let _master_limiter = MasterLimiter::default();
 }
 
 #[test]
@@ -101,3 +118,151 @@ fn test_saturator_wrapper_processes_audio() {
     assert!(buffer[0][0].abs() < 2.0);
     assert!(buffer[0][1].abs() < 2.0);
 }
+
+#[test]
+fn test_delay_wrapper_repeats_input() {
+    let mut echo = Echo::default();
+    echo.set_sample_rate(1_000.0);
+
+    let mut mono = signals::impulse(16);
+    let mut buffer = [&mut mono[..]];
+    let params = DelayParams {
+        time_ms: 10.0,
+        mix: 1.0,
+        ..Default::default()
+    };
+
+    echo.process(&mut buffer, &Transport::default(), &params);
+
+    assert_eq!(buffer[0][0], 0.0);
+    assert!((buffer[0][10] - 1.0).abs() < 1e-6);
+    assert!(matches!(echo.tail_samples(), wavecraft_dsp::TailLength::Samples(n) if n > 10));
+}
+
+#[test]
+fn test_dynamics_wrappers_publish_gain_reduction() {
+    let compressor = BusCompressor::default();
+    let drum_bus = DrumBus::default();
+    let mut limiter = MasterLimiter::default();
+    limiter.set_sample_rate(48_000.0);
+
+    let mut taps = Vec::new();
+    compressor.metering_taps(&mut taps);
+    limiter.metering_taps(&mut taps);
+    drum_bus.metering_taps(&mut taps);
+    let ids: Vec<_> = taps.iter().map(|tap| tap.id().to_string()).collect();
+    assert_eq!(
+        ids,
+        [
+            "compressor_gain_reduction",
+            "limiter_gain_reduction",
+            "drum_bus_gain_reduction"
+        ]
+    );
+
+    // The limiter's lookahead is forwarded as latency.
+    assert_eq!(limiter.latency_samples(), 240);
+
+    let mut mono = [1.0_f32; 512];
+    let params = LimiterParams {
+        ceiling_db: -6.0,
+        ..Default::default()
+    };
+    limiter.process(&mut [&mut mono[..]], &Transport::default(), &params);
+    assert!(mono.iter().all(|sample| sample.abs() <= 0.502));
+    assert!(taps[1].meter_frame().is_some());
+}
+
+#[test]
+fn test_eq_wrapper_exposes_four_bands() {
+    let mut shaper = ToneShaper::default();
+    shaper.set_sample_rate(48_000.0);
+
+    let specs = ParametricEqParams::<4>::param_specs();
+    assert_eq!(specs.len(), 20);
+    assert_eq!(specs[19].id_suffix, "band4_q");
+
+    let mut params = ParametricEqParams::<4>::default();
+    for band in &mut params.bands {
+        band.enabled = false;
+    }
+    params.bands[1].enabled = true;
+    params.bands[1].band_type = EqBandType::Peak;
+    params.bands[1].gain_db = -12.0;
+
+    let mut mono = [0.25_f32; 64];
+    shaper.process(&mut [&mut mono[..]], &Transport::default(), &params);
+    assert!(mono.iter().all(|sample| sample.is_finite()));
+    let response = params.magnitude_response_db(48_000.0, &[params.bands[1].frequency_hz]);
+    assert!((response[0] + 12.0).abs() < 0.01);
+
+    // Other band counts are available through the generic form.
+    let _: ParametricEqParams<8> = <ParametricEqDsp<8> as Processor>::Params::default();
+}
+
+#[test]
+fn test_reverb_wrapper_rings_out_and_resets() {
+    let mut room = Room::default();
+    room.set_sample_rate(48_000.0);
+
+    let params = ReverbParams {
+        pre_delay_ms: 0.0,
+        mix: 1.0,
+        ..Default::default()
+    };
+    let mut left = signals::impulse(4_800);
+    let mut right = vec![0.0_f32; 4_800];
+    room.process(&mut [&mut left, &mut right], &Transport::default(), &params);
+    assert!(left.iter().skip(1_000).any(|sample| sample.abs() > 1e-4));
+    assert!(matches!(room.tail_samples(), wavecraft_dsp::TailLength::Samples(n) if n > 48_000));
+
+    room.reset();
+    let mut silence = [0.0_f32; 512];
+    room.process(&mut [&mut silence[..]], &Transport::default(), &params);
+    assert!(silence.iter().all(|sample| *sample == 0.0));
+}
+
+#[test]
+fn test_stereo_utility_wrapper_swaps_and_narrows() {
+    let mut utility = Utility::default();
+    utility.set_sample_rate(48_000.0);
+
+    let mut left = [1.0_f32, 0.0];
+    let mut right = [0.0_f32, 1.0];
+    let params = StereoUtilityParams {
+        swap: true,
+        ..Default::default()
+    };
+    utility.process(&mut [&mut left, &mut right], &Transport::default(), &params);
+    assert_eq!((left, right), ([0.0, 1.0], [1.0, 0.0]));
+
+    let params = StereoUtilityParams {
+        mono: true,
+        ..Default::default()
+    };
+    utility.process(&mut [&mut left, &mut right], &Transport::default(), &params);
+    assert_eq!((left, right), ([0.5, 0.5], [0.5, 0.5]));
+}
+
+#[test]
+fn test_builtin_wrappers_compose_inside_mid_side() {
+    use wavecraft_dsp::{MidSide, MidSideMode};
+
+    type MsClip = MidSide<SoftClip>;
+    let specs = <MsClip as Processor>::Params::param_specs();
+    let suffixes: Vec<_> = specs.iter().map(|spec| spec.id_suffix).collect();
+    assert_eq!(suffixes, ["drive_db", "output_trim_db", "ms_mode"]);
+
+    let mut clip = MsClip::default();
+    clip.set_sample_rate(48_000.0);
+    let mut params = <MsClip as Processor>::Params::default();
+    // drive_db, output_trim_db, ms_mode
+    params.apply_plain_values(&[12.0, 0.0, 1.0]);
+    assert_eq!(params.mode, MidSideMode::Side);
+
+    // A mono-compatible signal has no side, so clipping the side leaves it alone.
+    let mut left = [0.8_f32, -0.6];
+    let mut right = [0.8_f32, -0.6];
+    clip.process(&mut [&mut left, &mut right], &Transport::default(), &params);
+    assert_eq!((left, right), ([0.8, -0.6], [0.8, -0.6]));
+}
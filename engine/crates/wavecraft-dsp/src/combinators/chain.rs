@@ -1,10 +1,22 @@
 //! Chain combinator for serial processor composition.
 
+use std::sync::Arc;
+
+use super::latency::DryDelay;
+use crate::events::NoteEvent;
+use crate::layout::ChannelLayout;
+use crate::state::PersistentField;
+use crate::tail::TailLength;
+use crate::taps::MeteringTap;
 use crate::traits::{ParamSpec, Processor, ProcessorParams, Transport};
 
 /// Processor wrapper that adds a standard per-instance bypass parameter.
+///
+/// While bypassed, the dry signal is delayed by the wrapped processor's
+/// [`Processor::latency_samples`], so toggling bypass never shifts timing.
 pub struct Bypassed<P> {
     pub processor: P,
+    dry_delay: DryDelay,
     source_bypassed: bool,
     target_bypassed: bool,
     transition_phase: BypassTransitionPhase,
@@ -28,6 +40,7 @@ impl<P> Bypassed<P> {
     pub fn new(processor: P) -> Self {
         Self {
             processor,
+            dry_delay: DryDelay::default(),
             source_bypassed: false,
             target_bypassed: false,
             transition_phase: BypassTransitionPhase::Stable,
@@ -72,6 +85,20 @@ impl<P> Bypassed<P> {
     }
 }
 
+impl<P> Bypassed<P>
+where
+    P: Processor,
+{
+    /// Size the dry delay for the wrapped processor's current latency.
+    ///
+    /// Allocates when the latency grows; only called outside the audio thread.
+    fn prepare_dry_delay(&mut self) {
+        let latency = self.processor.latency_samples() as usize;
+        self.dry_delay.ensure_capacity(latency);
+        self.dry_delay.set_delay(latency);
+    }
+}
+
 impl<P> Default for Bypassed<P>
 where
     P: Default,
@@ -173,6 +200,27 @@ where
     type Params = BypassedParams<P::Params>;
 
     fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.process_events(buffer, &[], transport, params);
+    }
+
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_with_sidechain(buffer, &[], events, transport, params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
         if params.bypassed != self.target_bypassed {
             self.target_bypassed = params.bypassed;
             if self.source_bypassed != self.target_bypassed {
@@ -182,8 +230,22 @@ where
             }
         }
 
-        if !self.source_bypassed {
-            self.processor.process(buffer, transport, &params.inner);
+        // Keep the dry history current even while active so switching to
+        // bypass outputs a latency-aligned signal immediately. Latency growth
+        // beyond the preallocated capacity is clamped (no audio-thread alloc).
+        self.dry_delay
+            .set_delay(self.processor.latency_samples() as usize);
+        if self.source_bypassed {
+            self.dry_delay.process(buffer);
+        } else {
+            self.dry_delay.write(buffer);
+            self.processor.process_with_sidechain(
+                buffer,
+                sidechain,
+                events,
+                transport,
+                &params.inner,
+            );
         }
 
         let samples = buffer
@@ -240,13 +302,35 @@ where
     fn set_sample_rate(&mut self, sample_rate: f32) {
         self.transition_samples = Self::transition_samples_for_rate(sample_rate);
         self.processor.set_sample_rate(sample_rate);
+        self.prepare_dry_delay();
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        self.processor.set_channel_layout(layout);
+    }
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        self.processor.persistent_fields(fields);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        self.processor.metering_taps(taps);
     }
 
     fn reset(&mut self) {
         self.source_bypassed = self.target_bypassed;
         self.transition_phase = BypassTransitionPhase::Stable;
+        self.dry_delay.clear();
         self.processor.reset();
     }
+
+    fn latency_samples(&self) -> u32 {
+        self.processor.latency_samples()
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        self.processor.tail_samples()
+    }
 }
 
 /// Combines two processors in series: A → B.
@@ -373,15 +457,67 @@ where
         self.second.process(buffer, transport, &params.second);
     }
 
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_with_sidechain(buffer, &[], events, transport, params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        // Both stages see the same events (e.g. a synth followed by a
+        // note-tracking filter) and the same sidechain input.
+        self.first
+            .process_with_sidechain(buffer, sidechain, events, transport, &params.first);
+        self.second
+            .process_with_sidechain(buffer, sidechain, events, transport, &params.second);
+    }
+
     fn set_sample_rate(&mut self, sample_rate: f32) {
         self.first.set_sample_rate(sample_rate);
         self.second.set_sample_rate(sample_rate);
     }
 
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        self.first.set_channel_layout(layout);
+        self.second.set_channel_layout(layout);
+    }
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        self.first.persistent_fields(fields);
+        self.second.persistent_fields(fields);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        self.first.metering_taps(taps);
+        self.second.metering_taps(taps);
+    }
+
     fn reset(&mut self) {
         self.first.reset();
         self.second.reset();
     }
+
+    fn latency_samples(&self) -> u32 {
+        // Serial stages: latencies add up.
+        self.first
+            .latency_samples()
+            .saturating_add(self.second.latency_samples())
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        self.first.tail_samples().then(self.second.tail_samples())
+    }
 }
 
 #[cfg(test)]
@@ -511,6 +647,7 @@ mod tests {
         set_sample_rate_calls: Arc<AtomicU32>,
         reset_calls: Arc<AtomicU32>,
         last_sample_rate_bits: Arc<AtomicU32>,
+        last_output_channels: Arc<AtomicU32>,
         touched_process: Arc<AtomicBool>,
     }
 
@@ -520,6 +657,7 @@ mod tests {
                 set_sample_rate_calls: Arc::new(AtomicU32::new(0)),
                 reset_calls: Arc::new(AtomicU32::new(0)),
                 last_sample_rate_bits: Arc::new(AtomicU32::new(0)),
+                last_output_channels: Arc::new(AtomicU32::new(0)),
                 touched_process: Arc::new(AtomicBool::new(false)),
             }
         }
@@ -543,6 +681,11 @@ mod tests {
                 .store(sample_rate.to_bits(), Ordering::SeqCst);
         }
 
+        fn set_channel_layout(&mut self, layout: ChannelLayout) {
+            self.last_output_channels
+                .store(layout.outputs, Ordering::SeqCst);
+        }
+
         fn reset(&mut self) {
             self.reset_calls.fetch_add(1, Ordering::SeqCst);
         }
@@ -665,6 +808,21 @@ mod tests {
         assert_eq!(f32::from_bits(second_sr.load(Ordering::SeqCst)), 48_000.0);
     }
 
+    #[test]
+    fn test_chain_propagates_channel_layout_to_both_processors() {
+        let first = LifecycleProbe::new();
+        let second = LifecycleProbe::new();
+
+        let first_outputs = Arc::clone(&first.last_output_channels);
+        let second_outputs = Arc::clone(&second.last_output_channels);
+
+        let mut chain = Chain { first, second };
+        chain.set_channel_layout(ChannelLayout::SURROUND_5_1);
+
+        assert_eq!(first_outputs.load(Ordering::SeqCst), 6);
+        assert_eq!(second_outputs.load(Ordering::SeqCst), 6);
+    }
+
     #[test]
     fn test_chain_propagates_reset_to_both_processors() {
         let first = LifecycleProbe::new();
@@ -881,4 +1039,231 @@ mod tests {
         assert!(max_step < 1.0);
         assert!(previous < -0.95);
     }
+
+    /// Counts how many events reach `process_events`.
+    #[derive(Default)]
+    struct TestEventCounter {
+        events_seen: usize,
+    }
+
+    impl Processor for TestEventCounter {
+        type Params = TestPassthroughParams;
+
+        fn process(
+            &mut self,
+            buffer: &mut [&mut [f32]],
+            transport: &Transport,
+            params: &Self::Params,
+        ) {
+            self.process_events(buffer, &[], transport, params);
+        }
+
+        fn process_events(
+            &mut self,
+            _buffer: &mut [&mut [f32]],
+            events: &[NoteEvent],
+            _transport: &Transport,
+            _params: &Self::Params,
+        ) {
+            self.events_seen += events.len();
+        }
+    }
+
+    #[test]
+    fn test_chain_forwards_events_to_both_stages() {
+        let mut chain = Chain {
+            first: Bypassed::new(TestEventCounter::default()),
+            second: TestEventCounter::default(),
+        };
+        let params = ChainParams {
+            first: BypassedParams::<TestPassthroughParams>::default(),
+            second: TestPassthroughParams,
+        };
+        let events = [
+            NoteEvent::note_on(0, 0, 60, 1.0),
+            NoteEvent::note_off(3, 0, 60, 0.0),
+        ];
+
+        let mut samples = [0.0_f32; 4];
+        let mut buffer = [&mut samples[..]];
+        chain.process_events(&mut buffer, &events, &Transport::default(), &params);
+
+        assert_eq!(chain.first.processor.events_seen, 2);
+        assert_eq!(chain.second.events_seen, 2);
+    }
+
+    /// Replaces its input with the first sidechain channel (test only).
+    struct TestSidechainCopyDsp;
+
+    impl Processor for TestSidechainCopyDsp {
+        type Params = TestPassthroughParams;
+
+        fn process(
+            &mut self,
+            _buffer: &mut [&mut [f32]],
+            _transport: &Transport,
+            _params: &Self::Params,
+        ) {
+        }
+
+        fn process_with_sidechain(
+            &mut self,
+            buffer: &mut [&mut [f32]],
+            sidechain: &[&mut [f32]],
+            _events: &[NoteEvent],
+            _transport: &Transport,
+            _params: &Self::Params,
+        ) {
+            if let Some(key) = sidechain.first() {
+                for channel in buffer.iter_mut() {
+                    channel.copy_from_slice(key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_forwards_sidechain_through_bypass_wrappers() {
+        let mut chain = Chain {
+            first: Bypassed::new(TestGainDsp),
+            second: Bypassed::new(TestSidechainCopyDsp),
+        };
+        let params = ChainParams {
+            first: BypassedParams {
+                inner: TestGainParams { level: 0.5 },
+                bypassed: false,
+            },
+            second: BypassedParams::default(),
+        };
+
+        let mut samples = [1.0_f32; 3];
+        let mut key = [0.25_f32, 0.5, 0.75];
+        chain.process_with_sidechain(
+            &mut [&mut samples[..]],
+            &[&mut key[..]],
+            &[],
+            &Transport::default(),
+            &params,
+        );
+        assert_eq!(samples, [0.25, 0.5, 0.75]);
+
+        // Without a sidechain the default path leaves the signal alone.
+        let mut samples = [1.0_f32; 3];
+        chain.process(&mut [&mut samples[..]], &Transport::default(), &params);
+        assert_eq!(samples, [0.5; 3]);
+    }
+
+    #[test]
+    fn test_default_process_events_falls_back_to_process() {
+        let mut processor = TestGainDsp;
+        let mut samples = [1.0_f32; 2];
+        let mut buffer = [&mut samples[..]];
+
+        processor.process_events(
+            &mut buffer,
+            &[NoteEvent::note_on(0, 0, 60, 1.0)],
+            &Transport::default(),
+            &TestGainParams { level: 0.5 },
+        );
+
+        assert!((samples[0] - 0.5).abs() < 1e-6);
+    }
+
+    /// Delays its input by a fixed number of samples (mono, test only).
+    struct TestLatencyDsp {
+        latency: usize,
+        history: Vec<f32>,
+    }
+
+    impl TestLatencyDsp {
+        fn new(latency: usize) -> Self {
+            Self {
+                latency,
+                history: vec![0.0; latency],
+            }
+        }
+    }
+
+    impl Processor for TestLatencyDsp {
+        type Params = TestPassthroughParams;
+
+        fn process(
+            &mut self,
+            buffer: &mut [&mut [f32]],
+            _transport: &Transport,
+            _params: &Self::Params,
+        ) {
+            for sample in buffer[0].iter_mut() {
+                self.history.push(*sample);
+                *sample = self.history.remove(0);
+            }
+        }
+
+        fn latency_samples(&self) -> u32 {
+            self.latency as u32
+        }
+
+        fn tail_samples(&self) -> TailLength {
+            // A pure delay keeps emitting its history after the input stops.
+            TailLength::Samples(self.latency as u32)
+        }
+    }
+
+    #[test]
+    fn test_chain_sums_latency() {
+        let chain = Chain {
+            first: TestLatencyDsp::new(3),
+            second: Chain {
+                first: TestGainDsp,
+                second: Bypassed::new(TestLatencyDsp::new(5)),
+            },
+        };
+
+        assert_eq!(chain.latency_samples(), 8);
+    }
+
+    #[test]
+    fn test_chain_sums_tails_and_tail_ends() {
+        let mut chain = Chain {
+            first: TestLatencyDsp::new(3),
+            second: Bypassed::new(TestLatencyDsp::new(5)),
+        };
+        chain.set_sample_rate(48_000.0);
+        assert_eq!(chain.tail_samples(), TailLength::Samples(8));
+
+        let params = ChainParams {
+            first: TestPassthroughParams,
+            second: BypassedParams::default(),
+        };
+        let mut impulse = [1.0_f32, 0.0];
+        chain.process(&mut [&mut impulse[..]], &Transport::default(), &params);
+
+        // The impulse leaves the chain 8 samples after it entered, which is the
+        // 7th sample of silence.
+        let measured = crate::testing::assert_tail_ends(&mut chain, &params, 1, 4);
+        assert_eq!(measured, 7);
+    }
+
+    #[test]
+    fn test_bypassed_delays_dry_signal_by_inner_latency() {
+        let mut wrapped = Bypassed::new(TestLatencyDsp::new(4));
+        wrapped.set_sample_rate(48_000.0);
+        let bypassed = BypassedParams {
+            inner: TestPassthroughParams,
+            bypassed: true,
+        };
+
+        // Settle into the stable bypassed state.
+        for _ in 0..64 {
+            let mut block = [0.0_f32; 16];
+            wrapped.process(&mut [&mut block[..]], &Transport::default(), &bypassed);
+        }
+
+        let mut impulse = [0.0_f32; 8];
+        impulse[0] = 1.0;
+        wrapped.process(&mut [&mut impulse[..]], &Transport::default(), &bypassed);
+
+        assert_eq!(wrapped.latency_samples(), 4);
+        assert_eq!(impulse, [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    }
 }
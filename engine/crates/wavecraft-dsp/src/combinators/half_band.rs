@@ -0,0 +1,192 @@
+//! Polyphase half-band FIR stages for 2x resampling.
+//!
+//! A half-band lowpass has every other tap equal to zero except the centre
+//! one, so each 2x stage splits into a short FIR branch and a pure delay
+//! branch. [`Oversampled`](super::Oversampled) cascades these stages for
+//! 4x and 8x.
+
+use std::f64::consts::PI;
+
+/// Index of the centre tap of the prototype filter (63 taps in total).
+///
+/// Odd, so the non-zero side taps land on the even indices.
+pub(crate) const HALF_BAND_CENTER: usize = 31;
+
+/// Number of non-zero taps besides the centre one.
+pub(crate) const HALF_BAND_TAPS: usize = HALF_BAND_CENTER + 1;
+
+/// Non-zero side taps of the half-band prototype, `h[0], h[2], ..., h[62]`.
+///
+/// The centre tap is always 0.5 and is applied as a plain delay.
+pub(crate) type HalfBandCoefficients = [f32; HALF_BAND_TAPS];
+
+/// Design the Blackman-windowed half-band prototype.
+///
+/// Side taps are normalized to sum to 0.5 so DC passes at unity gain.
+pub(crate) fn half_band_coefficients() -> HalfBandCoefficients {
+    let length = (2 * HALF_BAND_CENTER + 1) as f64;
+    let mut coefficients = [0.0_f64; HALF_BAND_TAPS];
+
+    for (index, coefficient) in coefficients.iter_mut().enumerate() {
+        let tap = 2 * index;
+        let offset = tap as f64 - HALF_BAND_CENTER as f64;
+        let sinc = (PI * offset / 2.0).sin() / (PI * offset);
+        // Window over length + 1 points so the outermost taps stay non-zero.
+        let phase = 2.0 * PI * (tap as f64 + 1.0) / (length + 1.0);
+        let window = 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+        *coefficient = sinc * window;
+    }
+
+    let sum: f64 = coefficients.iter().sum();
+    coefficients.map(|coefficient| (coefficient * 0.5 / sum) as f32)
+}
+
+/// Latency of one up + down stage pair, in samples at the stage's high rate.
+pub(crate) const STAGE_PAIR_LATENCY: usize = 2 * HALF_BAND_CENTER;
+
+/// Mirrored ring buffer so the most recent `HALF_BAND_TAPS` samples are
+/// always readable as one contiguous slice, newest first.
+#[derive(Debug, Clone)]
+struct History {
+    data: [f32; 2 * HALF_BAND_TAPS],
+    pos: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            data: [0.0; 2 * HALF_BAND_TAPS],
+            pos: 0,
+        }
+    }
+}
+
+impl History {
+    fn push(&mut self, sample: f32) -> &[f32] {
+        self.pos = self.pos.checked_sub(1).unwrap_or(HALF_BAND_TAPS - 1);
+        self.data[self.pos] = sample;
+        self.data[self.pos + HALF_BAND_TAPS] = sample;
+        &self.data[self.pos..self.pos + HALF_BAND_TAPS]
+    }
+
+    fn clear(&mut self) {
+        self.data.fill(0.0);
+        self.pos = 0;
+    }
+}
+
+fn dot(coefficients: &HalfBandCoefficients, history: &[f32]) -> f32 {
+    coefficients
+        .iter()
+        .zip(history)
+        .map(|(coefficient, sample)| coefficient * sample)
+        .sum()
+}
+
+/// One 2x interpolation stage.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Upsampler {
+    history: History,
+}
+
+impl Upsampler {
+    /// Consume one input sample and return the two output samples.
+    pub(crate) fn push(&mut self, coefficients: &HalfBandCoefficients, sample: f32) -> (f32, f32) {
+        let history = self.history.push(sample);
+        // Zero-stuffing halves the level, so both branches run at gain 2.
+        let filtered = 2.0 * dot(coefficients, history);
+        let delayed = history[(HALF_BAND_CENTER - 1) / 2];
+        (filtered, delayed)
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.history.clear();
+    }
+}
+
+/// One 2x decimation stage.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Downsampler {
+    even: History,
+    odd: History,
+    pending: Option<f32>,
+}
+
+impl Downsampler {
+    /// Consume one input sample; returns an output sample every second call.
+    pub(crate) fn push(&mut self, coefficients: &HalfBandCoefficients, sample: f32) -> Option<f32> {
+        let Some(even) = self.pending.take() else {
+            self.pending = Some(sample);
+            return None;
+        };
+
+        let filtered = dot(coefficients, self.even.push(even));
+        let delayed = self.odd.push(sample)[HALF_BAND_CENTER.div_ceil(2)];
+        Some(filtered + 0.5 * delayed)
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.even.clear();
+        self.odd.clear();
+        self.pending = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(input: &[f32]) -> Vec<f32> {
+        let coefficients = half_band_coefficients();
+        let mut up = Upsampler::default();
+        let mut down = Downsampler::default();
+
+        let mut output = Vec::new();
+        for &sample in input {
+            let (first, second) = up.push(&coefficients, sample);
+            output.extend(down.push(&coefficients, first));
+            output.extend(down.push(&coefficients, second));
+        }
+        output
+    }
+
+    #[test]
+    fn test_coefficients_are_symmetric_with_unity_dc_gain() {
+        let coefficients = half_band_coefficients();
+        let sum: f32 = coefficients.iter().sum();
+
+        assert!((sum - 0.5).abs() < 1e-6);
+        for (left, right) in coefficients.iter().zip(coefficients.iter().rev()) {
+            assert!((left - right).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_delays_by_stage_latency() {
+        let mut impulse = vec![0.0_f32; 128];
+        impulse[0] = 1.0;
+
+        let output = round_trip(&impulse);
+        let peak = output
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map(|(index, _)| index);
+
+        // 2 * 31 samples at the doubled rate = 31 samples at the input rate.
+        assert_eq!(peak, Some(STAGE_PAIR_LATENCY / 2));
+    }
+
+    #[test]
+    fn test_round_trip_passes_low_frequencies() {
+        let input: Vec<f32> = (0..512)
+            .map(|n| (2.0 * std::f32::consts::PI * n as f32 / 64.0).sin())
+            .collect();
+
+        let output = round_trip(&input);
+        let delay = STAGE_PAIR_LATENCY / 2;
+        for (n, sample) in output.iter().enumerate().skip(2 * delay) {
+            assert!((sample - input[n - delay]).abs() < 1e-3, "sample {n}");
+        }
+    }
+}
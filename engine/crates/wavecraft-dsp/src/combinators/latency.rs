@@ -0,0 +1,148 @@
+//! Dry-signal delay used by combinators to stay latency-aligned.
+
+use super::MAX_CHANNELS;
+
+/// Fixed-capacity multichannel delay line for dry-signal compensation.
+///
+/// Storage is allocated up front by [`DryDelay::ensure_capacity`] (never on the
+/// audio thread). [`DryDelay::set_delay`] only changes the active length within
+/// that capacity, so it is real-time safe.
+#[derive(Debug, Default)]
+pub(crate) struct DryDelay {
+    channels: Vec<Vec<f32>>,
+    delay: usize,
+    write_pos: usize,
+}
+
+impl DryDelay {
+    /// Grow storage so delays up to `capacity` samples are possible.
+    ///
+    /// Allocates; call from `set_sample_rate` or construction only.
+    pub(crate) fn ensure_capacity(&mut self, capacity: usize) {
+        if self.capacity() >= capacity {
+            return;
+        }
+
+        self.channels = (0..MAX_CHANNELS).map(|_| vec![0.0; capacity]).collect();
+        self.write_pos = 0;
+    }
+
+    /// Allocated length per channel.
+    pub(crate) fn capacity(&self) -> usize {
+        self.channels.first().map_or(0, Vec::len)
+    }
+
+    /// Change the active delay, clamped to the allocated capacity.
+    ///
+    /// Clears the history when the delay changes.
+    pub(crate) fn set_delay(&mut self, delay: usize) {
+        let delay = delay.min(self.capacity());
+        if delay != self.delay {
+            self.delay = delay;
+            self.clear();
+        }
+    }
+
+    /// Zero the stored history.
+    pub(crate) fn clear(&mut self) {
+        for channel in &mut self.channels {
+            channel.fill(0.0);
+        }
+        self.write_pos = 0;
+    }
+
+    /// Push `buffer` into the delay line and replace it with the delayed signal.
+    pub(crate) fn process(&mut self, buffer: &mut [&mut [f32]]) {
+        self.run(buffer, true);
+    }
+
+    /// Push `buffer` into the delay line without modifying it.
+    ///
+    /// Keeps the history current while the delayed signal is not needed.
+    pub(crate) fn write(&mut self, buffer: &mut [&mut [f32]]) {
+        self.run(buffer, false);
+    }
+
+    fn run(&mut self, buffer: &mut [&mut [f32]], replace: bool) {
+        if self.delay == 0 {
+            return;
+        }
+
+        let samples = buffer
+            .iter()
+            .map(|channel| channel.len())
+            .min()
+            .unwrap_or(0);
+        let delay = self.delay;
+
+        for (channel, history) in buffer.iter_mut().zip(self.channels.iter_mut()) {
+            let mut pos = self.write_pos;
+            for sample in channel.iter_mut().take(samples) {
+                let delayed = history[pos];
+                history[pos] = *sample;
+                if replace {
+                    *sample = delayed;
+                }
+                pos += 1;
+                if pos == delay {
+                    pos = 0;
+                }
+            }
+        }
+
+        self.write_pos = (self.write_pos + samples) % delay;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_capacity(capacity: usize) -> DryDelay {
+        let mut delay = DryDelay::default();
+        delay.ensure_capacity(capacity);
+        delay
+    }
+
+    #[test]
+    fn test_process_delays_by_active_length() {
+        let mut delay = with_capacity(8);
+        delay.set_delay(2);
+
+        let mut left = [1.0, 2.0, 3.0, 4.0];
+        let mut right = [5.0, 6.0, 7.0, 8.0];
+        delay.process(&mut [&mut left[..], &mut right[..]]);
+
+        assert_eq!(left, [0.0, 0.0, 1.0, 2.0]);
+        assert_eq!(right, [0.0, 0.0, 5.0, 6.0]);
+
+        let mut next = [9.0, 10.0];
+        let mut right_next = [0.0, 0.0];
+        delay.process(&mut [&mut next[..], &mut right_next[..]]);
+        assert_eq!(next, [3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_write_keeps_history_without_modifying_buffer() {
+        let mut delay = with_capacity(4);
+        delay.set_delay(3);
+
+        let mut block = [1.0, 2.0, 3.0];
+        delay.write(&mut [&mut block[..]]);
+        assert_eq!(block, [1.0, 2.0, 3.0]);
+
+        let mut next = [0.0; 3];
+        delay.process(&mut [&mut next[..]]);
+        assert_eq!(next, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_set_delay_clamps_to_capacity() {
+        let mut delay = with_capacity(4);
+        delay.set_delay(100);
+
+        let mut impulse = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        delay.process(&mut [&mut impulse[..]]);
+        assert_eq!(impulse, [0.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+    }
+}
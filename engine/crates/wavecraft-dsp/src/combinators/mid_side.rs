@@ -0,0 +1,429 @@
+//! Mid/side combinator for processing the sum and difference of a stereo pair.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use wavecraft_protocol::{MeterFrame, OscilloscopeFrame};
+
+use super::latency::DryDelay;
+use super::scratch::ScratchBuffer;
+use crate::events::NoteEvent;
+use crate::layout::ChannelLayout;
+use crate::state::PersistentField;
+use crate::tail::TailLength;
+use crate::taps::MeteringTap;
+use crate::traits::{ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
+
+/// Which of the mid and side signals [`MidSide`] processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MidSideMode {
+    /// Process the mid (L + R) signal; the side passes through.
+    Mid,
+    /// Process the side (L - R) signal; the mid passes through.
+    Side,
+    /// Process both, each through its own processor instance.
+    #[default]
+    Both,
+}
+
+impl MidSideMode {
+    const NAMES: [&'static str; 3] = ["Mid", "Side", "Both"];
+
+    fn from_index(index: i32) -> Self {
+        match index {
+            0 => Self::Mid,
+            1 => Self::Side,
+            _ => Self::Both,
+        }
+    }
+
+    fn processes_mid(self) -> bool {
+        matches!(self, Self::Mid | Self::Both)
+    }
+
+    fn processes_side(self) -> bool {
+        matches!(self, Self::Side | Self::Both)
+    }
+}
+
+/// Runs a processor on the mid and/or side signal of a stereo pair.
+///
+/// The first two channels are encoded to mid `(L + R) / 2` and side
+/// `(L - R) / 2`, the selected signals are processed as mono buffers, and
+/// the result is decoded back to left and right. Mid and side have their own
+/// instances (`mid` and `side`), so stateful processors like filters don't
+/// share history between the two. An instance that is switched back on
+/// starts from [`Processor::reset`].
+///
+/// A signal that is not processed is delayed by the wrapped processor's
+/// [`Processor::latency_samples`] so both stay aligned. Mono buffers are all
+/// mid, and channels beyond the second pass through unchanged.
+///
+/// Metering taps of the `side` instance are published with `_side` appended
+/// to their ID (a `"gr"` tap is also served as `"gr_side"`), and each
+/// persistent field is saved once with the state of both instances.
+///
+/// ```rust,no_run
+/// use wavecraft_dsp::combinators::MidSide;
+/// use wavecraft_dsp::{Processor, SignalChain, Transport};
+///
+/// #[derive(Default)]
+/// struct Darken;
+///
+/// impl Processor for Darken {
+///     type Params = ();
+///
+///     fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+///         for sample in buffer.iter_mut().flat_map(|channel| channel.iter_mut()) {
+///             *sample *= 0.5;
+///         }
+///     }
+/// }
+///
+/// type Chain = SignalChain![MidSide<Darken>];
+/// ```
+pub struct MidSide<P> {
+    pub mid: P,
+    pub side: P,
+    scratch: ScratchBuffer,
+    align_mid: DryDelay,
+    align_side: DryDelay,
+    /// Mode of the previous block, to reset instances that come back on.
+    mode: Option<MidSideMode>,
+}
+
+impl<P> MidSide<P> {
+    /// Creates a mid/side wrapper from the mid and side instances.
+    pub fn new(mid: P, side: P) -> Self {
+        Self {
+            mid,
+            side,
+            scratch: ScratchBuffer::default(),
+            align_mid: DryDelay::default(),
+            align_side: DryDelay::default(),
+            mode: None,
+        }
+    }
+}
+
+impl<P> MidSide<P>
+where
+    P: Processor,
+{
+    /// Delay needed after the mid and side paths in `mode` so both line up.
+    fn alignment(&self, mode: MidSideMode) -> (usize, usize) {
+        let total = self.latency_samples();
+        let own = |active: bool, processor: &P| {
+            if active {
+                processor.latency_samples()
+            } else {
+                0
+            }
+        };
+        (
+            (total - own(mode.processes_mid(), &self.mid)) as usize,
+            (total - own(mode.processes_side(), &self.side)) as usize,
+        )
+    }
+}
+
+impl<P> Default for MidSide<P>
+where
+    P: Default,
+{
+    fn default() -> Self {
+        Self::new(P::default(), P::default())
+    }
+}
+
+/// Parameters for [`MidSide`].
+///
+/// The wrapped processor's parameters, shared by both instances, followed by
+/// the `ms_mode` switch.
+pub struct MidSideParams<PP> {
+    pub inner: PP,
+    pub mode: MidSideMode,
+}
+
+impl<PP> Default for MidSideParams<PP>
+where
+    PP: Default,
+{
+    fn default() -> Self {
+        Self {
+            inner: PP::default(),
+            mode: MidSideMode::default(),
+        }
+    }
+}
+
+impl<PP> ProcessorParams for MidSideParams<PP>
+where
+    PP: ProcessorParams,
+{
+    fn param_specs() -> &'static [ParamSpec] {
+        let inner_specs = PP::param_specs();
+        let mut merged = Vec::with_capacity(inner_specs.len() + 1);
+
+        merged.extend(inner_specs.iter().cloned());
+        merged.push(ParamSpec {
+            name: "M/S Mode",
+            id_suffix: "ms_mode",
+            range: ParamRange::Enum {
+                variants: &MidSideMode::NAMES,
+            },
+            default: 2.0,
+            unit: "",
+            group: None,
+        });
+
+        // See comment in ChainParams::param_specs for rationale.
+        Box::leak(merged.into_boxed_slice())
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            inner: PP::from_param_defaults(),
+            mode: MidSideMode::default(),
+        }
+    }
+
+    fn plain_value_count() -> usize {
+        PP::plain_value_count() + 1
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        let split_at = PP::plain_value_count().min(values.len());
+        let (inner_values, mode_values) = values.split_at(split_at);
+
+        self.inner.apply_plain_values(inner_values);
+
+        if let Some(mode) = mode_values.first() {
+            self.mode = MidSideMode::from_index(mode.round() as i32);
+        }
+    }
+}
+
+impl<P> Processor for MidSide<P>
+where
+    P: Processor,
+{
+    type Params = MidSideParams<P::Params>;
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.process_events(buffer, &[], transport, params);
+    }
+
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_with_sidechain(buffer, &[], events, transport, params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        let mode = params.mode;
+        if let Some(previous) = self.mode.replace(mode) {
+            if mode.processes_mid() && !previous.processes_mid() {
+                self.mid.reset();
+            }
+            if mode.processes_side() && !previous.processes_side() {
+                self.side.reset();
+            }
+        }
+        let (delay_mid, delay_side) = self.alignment(mode);
+        self.align_mid.set_delay(delay_mid);
+        self.align_side.set_delay(delay_side);
+
+        if buffer.len() < 2 {
+            if mode.processes_mid() {
+                self.mid.process_with_sidechain(
+                    buffer,
+                    sidechain,
+                    events,
+                    transport,
+                    &params.inner,
+                );
+            }
+            self.align_mid.process(buffer);
+            return;
+        }
+
+        let samples = buffer[0].len().min(buffer[1].len());
+        self.scratch.reserve(samples);
+        let mut copy = self.scratch.slices(2, samples);
+        let (mid, side) = copy.split_at_mut(1);
+        let side = &mut side[..1];
+
+        let (left, right) = buffer.split_at_mut(1);
+        let (left, right) = (&mut *left[0], &mut *right[0]);
+        let inputs = left.iter().zip(right.iter());
+        for ((l, r), (m, s)) in inputs.zip(mid[0].iter_mut().zip(side[0].iter_mut())) {
+            *m = 0.5 * (l + r);
+            *s = 0.5 * (l - r);
+        }
+
+        if mode.processes_mid() {
+            self.mid
+                .process_with_sidechain(mid, sidechain, events, transport, &params.inner);
+        }
+        self.align_mid.process(mid);
+        if mode.processes_side() {
+            self.side
+                .process_with_sidechain(side, sidechain, events, transport, &params.inner);
+        }
+        self.align_side.process(side);
+
+        let outputs = left.iter_mut().zip(right.iter_mut());
+        for ((l, r), (m, s)) in outputs.zip(mid[0].iter().zip(side[0].iter())) {
+            *l = m + s;
+            *r = m - s;
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.mid.set_sample_rate(sample_rate);
+        self.side.set_sample_rate(sample_rate);
+        self.scratch.preallocate();
+
+        let capacity = self.latency_samples() as usize;
+        self.align_mid.ensure_capacity(capacity);
+        self.align_side.ensure_capacity(capacity);
+    }
+
+    fn set_channel_layout(&mut self, _layout: ChannelLayout) {
+        // Mid and side are always processed as separate mono signals.
+        self.mid.set_channel_layout(ChannelLayout::MONO);
+        self.side.set_channel_layout(ChannelLayout::MONO);
+    }
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        let (mut mid, mut side) = (Vec::new(), Vec::new());
+        self.mid.persistent_fields(&mut mid);
+        self.side.persistent_fields(&mut side);
+
+        let count = mid.len().max(side.len());
+        let (mut mid, mut side) = (mid.into_iter(), side.into_iter());
+        fields.extend((0..count).map(|_| {
+            Arc::new(MidSideField {
+                mid: mid.next(),
+                side: side.next(),
+            }) as Arc<dyn PersistentField>
+        }));
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        self.mid.metering_taps(taps);
+
+        let mut side = Vec::new();
+        self.side.metering_taps(&mut side);
+        taps.extend(side.into_iter().map(|tap| {
+            Arc::new(SideTap {
+                id: format!("{}{SIDE_TAP_SUFFIX}", tap.id()),
+                tap,
+            }) as Arc<dyn MeteringTap>
+        }));
+    }
+
+    fn reset(&mut self) {
+        self.mid.reset();
+        self.side.reset();
+        self.align_mid.clear();
+        self.align_side.clear();
+    }
+
+    fn latency_samples(&self) -> u32 {
+        self.mid.latency_samples().max(self.side.latency_samples())
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        let mode = self.mode.unwrap_or_default();
+        let (delay_mid, delay_side) = self.alignment(mode);
+        let branch = |active: bool, processor: &P, delay: usize| {
+            let delay = TailLength::Samples(delay as u32);
+            if active {
+                processor.tail_samples().then(delay)
+            } else {
+                delay
+            }
+        };
+        branch(mode.processes_mid(), &self.mid, delay_mid).longest(branch(
+            mode.processes_side(),
+            &self.side,
+            delay_side,
+        ))
+    }
+}
+
+/// Suffix added to the IDs of the side instance's metering taps.
+const SIDE_TAP_SUFFIX: &str = "_side";
+
+/// Metering tap of the side instance, published under its ID plus
+/// [`SIDE_TAP_SUFFIX`] so it doesn't shadow the mid instance's tap.
+struct SideTap {
+    id: String,
+    tap: Arc<dyn MeteringTap>,
+}
+
+impl MeteringTap for SideTap {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn meter_frame(&self) -> Option<MeterFrame> {
+        self.tap.meter_frame()
+    }
+
+    fn oscilloscope_frame(&self) -> Option<OscilloscopeFrame> {
+        self.tap.oscilloscope_frame()
+    }
+}
+
+/// A persistent field of the mid instance and its counterpart in the side
+/// instance, saved as one entry with a `mid` and a `side` key.
+struct MidSideField {
+    mid: Option<Arc<dyn PersistentField>>,
+    side: Option<Arc<dyn PersistentField>>,
+}
+
+impl MidSideField {
+    fn instances(&self) -> impl Iterator<Item = (&'static str, &Arc<dyn PersistentField>)> {
+        [("mid", &self.mid), ("side", &self.side)]
+            .into_iter()
+            .filter_map(|(key, field)| Some((key, field.as_ref()?)))
+    }
+}
+
+impl PersistentField for MidSideField {
+    fn serialize(&self) -> Option<String> {
+        let instances: BTreeMap<_, _> = self
+            .instances()
+            .filter_map(|(key, field)| Some((key, field.serialize()?)))
+            .collect();
+        serde_json::to_string(&instances).ok()
+    }
+
+    fn deserialize(&self, data: &str) -> bool {
+        let Ok(instances) = serde_json::from_str::<BTreeMap<String, String>>(data) else {
+            return false;
+        };
+
+        // Restore every instance present even if another fails to decode.
+        self.instances().fold(true, |restored, (key, field)| {
+            instances
+                .get(key)
+                .is_none_or(|data| field.deserialize(data))
+                && restored
+        })
+    }
+}
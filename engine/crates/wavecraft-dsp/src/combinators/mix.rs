@@ -0,0 +1,277 @@
+//! Dry/wet mix combinator.
+
+use std::sync::Arc;
+
+use super::latency::DryDelay;
+use super::scratch::ScratchBuffer;
+use crate::events::NoteEvent;
+use crate::layout::ChannelLayout;
+use crate::smoothing::{Smoother, SmoothingStyle};
+use crate::state::PersistentField;
+use crate::tail::TailLength;
+use crate::taps::MeteringTap;
+use crate::traits::{ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
+
+/// Ramp applied to mix changes to avoid zipper noise.
+const MIX_SMOOTHING: SmoothingStyle = SmoothingStyle::Linear(10.0);
+
+/// Processor wrapper that adds a standard dry/wet `mix` parameter.
+///
+/// `mix = 0.0` outputs the dry input and `mix = 1.0` the fully processed
+/// signal. The dry signal is delayed by the wrapped processor's
+/// [`Processor::latency_samples`] before blending, so partial mixes don't
+/// comb-filter.
+pub struct Mix<P> {
+    pub processor: P,
+    scratch: ScratchBuffer,
+    dry_delay: DryDelay,
+    mix: Smoother,
+}
+
+impl<P> Mix<P> {
+    /// Creates a dry/wet wrapper around a processor instance.
+    pub fn new(processor: P) -> Self {
+        Self {
+            processor,
+            scratch: ScratchBuffer::default(),
+            dry_delay: DryDelay::default(),
+            mix: Smoother::new(MIX_SMOOTHING),
+        }
+    }
+}
+
+impl<P> Default for Mix<P>
+where
+    P: Default,
+{
+    fn default() -> Self {
+        Self::new(P::default())
+    }
+}
+
+impl<P> From<P> for Mix<P> {
+    fn from(processor: P) -> Self {
+        Self::new(processor)
+    }
+}
+
+/// Parameters for [`Mix`].
+///
+/// Includes wrapped processor parameters plus the dry/wet amount.
+pub struct MixParams<PP> {
+    pub inner: PP,
+    /// Wet amount in `0.0..=1.0`.
+    pub mix: f32,
+}
+
+impl<PP> Default for MixParams<PP>
+where
+    PP: Default,
+{
+    fn default() -> Self {
+        Self {
+            inner: PP::default(),
+            mix: 1.0,
+        }
+    }
+}
+
+impl<PP> ProcessorParams for MixParams<PP>
+where
+    PP: ProcessorParams,
+{
+    fn param_specs() -> &'static [ParamSpec] {
+        let inner_specs = PP::param_specs();
+        let mut merged = Vec::with_capacity(inner_specs.len() + 1);
+
+        merged.extend(inner_specs.iter().map(|spec| ParamSpec {
+            name: spec.name,
+            id_suffix: spec.id_suffix,
+            range: spec.range.clone(),
+            default: spec.default,
+            unit: spec.unit,
+            group: spec.group,
+        }));
+        merged.push(ParamSpec {
+            name: "Mix",
+            id_suffix: "mix",
+            range: ParamRange::Linear { min: 0.0, max: 1.0 },
+            default: 1.0,
+            unit: "",
+            group: None,
+        });
+
+        // See comment in ChainParams::param_specs for rationale.
+        Box::leak(merged.into_boxed_slice())
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            inner: PP::from_param_defaults(),
+            mix: 1.0,
+        }
+    }
+
+    fn plain_value_count() -> usize {
+        PP::plain_value_count() + 1
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        let split_at = PP::plain_value_count().min(values.len());
+        let (inner_values, mix_values) = values.split_at(split_at);
+
+        self.inner.apply_plain_values(inner_values);
+
+        if let Some(mix) = mix_values.first() {
+            self.mix = *mix;
+        }
+    }
+}
+
+impl<P> Processor for Mix<P>
+where
+    P: Processor,
+{
+    type Params = MixParams<P::Params>;
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.process_events(buffer, &[], transport, params);
+    }
+
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_with_sidechain(buffer, &[], events, transport, params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        let (channels, samples) = self.scratch.copy_from(buffer);
+
+        self.processor
+            .process_with_sidechain(buffer, sidechain, events, transport, &params.inner);
+
+        let mut copy = self.scratch.slices(channels, samples);
+        let dry = &mut copy[..channels];
+        self.dry_delay
+            .set_delay(self.processor.latency_samples() as usize);
+        self.dry_delay.process(dry);
+
+        self.mix.set_target(params.mix.clamp(0.0, 1.0));
+
+        for idx in 0..samples {
+            let wet_gain = self.mix.next();
+            let dry_gain = 1.0 - wet_gain;
+
+            for (channel, dry) in buffer.iter_mut().zip(dry.iter()) {
+                channel[idx] = channel[idx] * wet_gain + dry[idx] * dry_gain;
+            }
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.processor.set_sample_rate(sample_rate);
+        self.mix.set_sample_rate(sample_rate);
+        self.scratch.preallocate();
+
+        let latency = self.processor.latency_samples() as usize;
+        self.dry_delay.ensure_capacity(latency);
+        self.dry_delay.set_delay(latency);
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        self.processor.set_channel_layout(layout);
+    }
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        self.processor.persistent_fields(fields);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        self.processor.metering_taps(taps);
+    }
+
+    fn reset(&mut self) {
+        self.processor.reset();
+        self.dry_delay.clear();
+        self.mix.reset();
+    }
+
+    fn latency_samples(&self) -> u32 {
+        self.processor.latency_samples()
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        // The delayed dry signal keeps playing for `latency` samples.
+        self.processor
+            .tail_samples()
+            .longest(TailLength::Samples(self.processor.latency_samples()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Delays its input by a fixed number of samples (mono, test only).
+    struct TestLatencyDsp {
+        latency: usize,
+        history: Vec<f32>,
+    }
+
+    impl TestLatencyDsp {
+        fn new(latency: usize) -> Self {
+            Self {
+                latency,
+                history: vec![0.0; latency],
+            }
+        }
+    }
+
+    impl Processor for TestLatencyDsp {
+        type Params = ();
+
+        fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+            for sample in buffer[0].iter_mut() {
+                self.history.push(*sample);
+                *sample = self.history.remove(0);
+            }
+        }
+
+        fn latency_samples(&self) -> u32 {
+            self.latency as u32
+        }
+
+        fn tail_samples(&self) -> TailLength {
+            TailLength::Samples(self.latency as u32)
+        }
+    }
+
+    #[test]
+    fn test_mix_delays_dry_signal_to_match_wet() {
+        let mut mix = Mix::new(TestLatencyDsp::new(2));
+        mix.set_sample_rate(48_000.0);
+        assert_eq!(mix.latency_samples(), 2);
+        assert_eq!(mix.tail_samples(), TailLength::Samples(2));
+
+        let params = MixParams {
+            inner: (),
+            mix: 0.5,
+        };
+        let mut impulse = [1.0_f32, 0.0, 0.0, 0.0];
+        mix.process(&mut [&mut impulse[..]], &Transport::default(), &params);
+
+        // Half dry + half wet, both delayed: no comb filtering.
+        assert_eq!(impulse, [0.0, 0.0, 1.0, 0.0]);
+    }
+}
@@ -1,8 +1,24 @@
 //! Processor combinators for chaining DSP operations.
 
 mod chain;
+mod half_band;
+mod latency;
+mod mid_side;
+mod mix;
+mod oversampled;
+mod parallel;
+mod scratch;
 
 pub use chain::{Bypassed, Chain};
+pub use mid_side::{MidSide, MidSideMode};
+pub use mix::Mix;
+pub use oversampled::Oversampled;
+pub use parallel::Parallel;
+
+/// Maximum channel count combinators keep internal copies of (dry signal,
+/// latency compensation). Channels beyond this limit pass through the wrapped
+/// processors but are not delayed or mixed.
+pub(crate) const MAX_CHANNELS: usize = 8;
 
 /// Combines processors into a serial signal chain.
 ///
@@ -0,0 +1,339 @@
+//! Oversampling combinator for nonlinear processors.
+
+use std::sync::Arc;
+
+use super::MAX_CHANNELS;
+use super::half_band::{
+    Downsampler, HalfBandCoefficients, STAGE_PAIR_LATENCY, Upsampler, half_band_coefficients,
+};
+use super::latency::DryDelay;
+use super::scratch::{DEFAULT_SCRATCH_SAMPLES, ScratchBuffer};
+use crate::events::{MAX_EVENTS_PER_BLOCK, NoteEvent};
+use crate::layout::ChannelLayout;
+use crate::state::PersistentField;
+use crate::tail::TailLength;
+use crate::taps::MeteringTap;
+use crate::traits::{Processor, Transport};
+
+/// Number of 2x stages for the largest supported factor (8x).
+const MAX_STAGES: usize = 3;
+
+/// Per-channel resampling state.
+#[derive(Debug, Clone, Default)]
+struct ChannelFilters {
+    up: [Upsampler; MAX_STAGES],
+    down: [Downsampler; MAX_STAGES],
+}
+
+impl ChannelFilters {
+    fn reset(&mut self) {
+        self.up.iter_mut().for_each(Upsampler::reset);
+        self.down.iter_mut().for_each(Downsampler::reset);
+    }
+}
+
+/// Push one sample through a cascade of 2x upsamplers, writing the
+/// `2^stages.len()` results to `output` starting at `pos`.
+fn upsample_into(
+    stages: &mut [Upsampler],
+    coefficients: &HalfBandCoefficients,
+    sample: f32,
+    output: &mut [f32],
+    pos: &mut usize,
+) {
+    match stages.split_first_mut() {
+        None => {
+            output[*pos] = sample;
+            *pos += 1;
+        }
+        Some((stage, rest)) => {
+            let (first, second) = stage.push(coefficients, sample);
+            upsample_into(rest, coefficients, first, output, pos);
+            upsample_into(rest, coefficients, second, output, pos);
+        }
+    }
+}
+
+fn upsample(
+    stages: &mut [Upsampler],
+    coefficients: &HalfBandCoefficients,
+    input: &[f32],
+    output: &mut [f32],
+) {
+    let mut pos = 0;
+    for &sample in input {
+        upsample_into(stages, coefficients, sample, output, &mut pos);
+    }
+}
+
+fn downsample(
+    stages: &mut [Downsampler],
+    coefficients: &HalfBandCoefficients,
+    input: &[f32],
+    output: &mut [f32],
+) {
+    let mut outputs = output.iter_mut();
+    for &sample in input {
+        // The highest-rate stage comes last in `stages`.
+        let mut value = Some(sample);
+        for stage in stages.iter_mut().rev() {
+            value = value.and_then(|value| stage.push(coefficients, value));
+        }
+        if let Some(value) = value
+            && let Some(target) = outputs.next()
+        {
+            *target = value;
+        }
+    }
+}
+
+/// Runs a processor at `FACTOR` times the host sample rate.
+///
+/// The input is upsampled with a cascade of polyphase half-band filters
+/// (one 2x stage per doubling), processed, and filtered back down, which keeps
+/// harmonics generated by waveshapers and saturators from aliasing. `FACTOR`
+/// must be 2, 4 or 8; anything else fails to compile.
+///
+/// The wrapped processor sees the multiplied rate in `set_sample_rate`, blocks
+/// `FACTOR` times longer, and note event timings scaled to match. Parameters
+/// are passed through unchanged. The resampling filters add latency, which is
+/// reported through [`Processor::latency_samples`] (31, 47 and 55 samples for
+/// 2x, 4x and 8x, plus the inner processor's own latency).
+///
+/// ```rust,no_run
+/// use wavecraft_dsp::combinators::Oversampled;
+/// use wavecraft_dsp::{Processor, SignalChain, Transport};
+///
+/// #[derive(Default)]
+/// struct HardClip;
+///
+/// impl Processor for HardClip {
+///     type Params = ();
+///
+///     fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+///         for sample in buffer.iter_mut().flat_map(|channel| channel.iter_mut()) {
+///             *sample = sample.clamp(-0.5, 0.5);
+///         }
+///     }
+/// }
+///
+/// type Chain = SignalChain![Oversampled<HardClip, 4>];
+/// ```
+pub struct Oversampled<P, const FACTOR: usize> {
+    pub processor: P,
+    coefficients: HalfBandCoefficients,
+    filters: Vec<ChannelFilters>,
+    sidechain_filters: Vec<[Upsampler; MAX_STAGES]>,
+    upsampled: ScratchBuffer,
+    upsampled_sidechain: ScratchBuffer,
+    events: Vec<NoteEvent>,
+    /// Pads the oversampled signal so the total latency is a whole number of
+    /// host-rate samples.
+    alignment: DryDelay,
+}
+
+impl<P, const FACTOR: usize> Oversampled<P, FACTOR> {
+    /// Number of cascaded 2x stages.
+    const STAGES: usize = match FACTOR {
+        2 => 1,
+        4 => 2,
+        8 => 3,
+        _ => panic!("Oversampled FACTOR must be 2, 4 or 8"),
+    };
+
+    /// Creates an oversampling wrapper around a processor instance.
+    pub fn new(processor: P) -> Self {
+        let _ = Self::STAGES;
+
+        let mut alignment = DryDelay::default();
+        alignment.ensure_capacity(FACTOR);
+
+        Self {
+            processor,
+            coefficients: half_band_coefficients(),
+            filters: vec![ChannelFilters::default(); MAX_CHANNELS],
+            sidechain_filters: vec![Default::default(); MAX_CHANNELS],
+            upsampled: ScratchBuffer::default(),
+            upsampled_sidechain: ScratchBuffer::default(),
+            events: Vec::with_capacity(MAX_EVENTS_PER_BLOCK),
+            alignment,
+        }
+    }
+}
+
+impl<P, const FACTOR: usize> Oversampled<P, FACTOR>
+where
+    P: Processor,
+{
+    /// Resampling filter latency plus the inner latency, in oversampled
+    /// samples, and the padding that rounds it up to a multiple of `FACTOR`.
+    fn oversampled_latency(&self) -> (usize, usize) {
+        let filters = STAGE_PAIR_LATENCY * (FACTOR - 1);
+        let total = filters + self.processor.latency_samples() as usize;
+        let padding = (FACTOR - total % FACTOR) % FACTOR;
+        (total, padding)
+    }
+}
+
+impl<P, const FACTOR: usize> Default for Oversampled<P, FACTOR>
+where
+    P: Default,
+{
+    fn default() -> Self {
+        Self::new(P::default())
+    }
+}
+
+impl<P, const FACTOR: usize> From<P> for Oversampled<P, FACTOR> {
+    fn from(processor: P) -> Self {
+        Self::new(processor)
+    }
+}
+
+impl<P, const FACTOR: usize> Processor for Oversampled<P, FACTOR>
+where
+    P: Processor,
+{
+    type Params = P::Params;
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.process_events(buffer, &[], transport, params);
+    }
+
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_with_sidechain(buffer, &[], events, transport, params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        let samples = buffer
+            .iter()
+            .map(|channel| channel.len())
+            .min()
+            .unwrap_or(0);
+        let channels = buffer.len().min(MAX_CHANNELS);
+        let sidechain_channels = sidechain.len().min(MAX_CHANNELS);
+        let stages = Self::STAGES;
+        let oversampled_len = samples * FACTOR;
+        let (_, padding) = self.oversampled_latency();
+
+        self.upsampled.reserve(oversampled_len);
+        self.upsampled_sidechain.reserve(oversampled_len);
+
+        let mut upsampled = self.upsampled.slices(channels, oversampled_len);
+        for ((channel, filters), target) in buffer
+            .iter()
+            .zip(self.filters.iter_mut())
+            .zip(upsampled.iter_mut())
+        {
+            upsample(
+                &mut filters.up[..stages],
+                &self.coefficients,
+                &channel[..samples],
+                target,
+            );
+        }
+
+        let mut upsampled_sidechain = self
+            .upsampled_sidechain
+            .slices(sidechain_channels, oversampled_len);
+        for ((channel, filters), target) in sidechain
+            .iter()
+            .zip(self.sidechain_filters.iter_mut())
+            .zip(upsampled_sidechain.iter_mut())
+        {
+            let len = channel.len().min(samples);
+            upsample(
+                &mut filters[..stages],
+                &self.coefficients,
+                &channel[..len],
+                target,
+            );
+            target[len * FACTOR..].fill(0.0);
+        }
+
+        self.events.clear();
+        self.events.extend(events.iter().map(|event| NoteEvent {
+            timing: event.timing.saturating_mul(FACTOR as u32),
+            ..*event
+        }));
+
+        self.processor.process_with_sidechain(
+            &mut upsampled[..channels],
+            &upsampled_sidechain[..sidechain_channels],
+            &self.events,
+            transport,
+            params,
+        );
+
+        self.alignment.set_delay(padding);
+        self.alignment.process(&mut upsampled[..channels]);
+
+        for ((channel, filters), source) in buffer
+            .iter_mut()
+            .zip(self.filters.iter_mut())
+            .zip(upsampled.iter())
+        {
+            downsample(
+                &mut filters.down[..stages],
+                &self.coefficients,
+                source,
+                &mut channel[..samples],
+            );
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.processor.set_sample_rate(sample_rate * FACTOR as f32);
+        self.upsampled.reserve(DEFAULT_SCRATCH_SAMPLES * FACTOR);
+        self.upsampled_sidechain
+            .reserve(DEFAULT_SCRATCH_SAMPLES * FACTOR);
+        self.events.reserve(MAX_EVENTS_PER_BLOCK);
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        self.processor.set_channel_layout(layout);
+    }
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        self.processor.persistent_fields(fields);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        self.processor.metering_taps(taps);
+    }
+
+    fn reset(&mut self) {
+        self.processor.reset();
+        self.filters.iter_mut().for_each(ChannelFilters::reset);
+        for filters in &mut self.sidechain_filters {
+            filters.iter_mut().for_each(Upsampler::reset);
+        }
+        self.alignment.clear();
+    }
+
+    fn latency_samples(&self) -> u32 {
+        let (total, padding) = self.oversampled_latency();
+        ((total + padding) / FACTOR) as u32
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        let inner = match self.processor.tail_samples() {
+            TailLength::Samples(samples) => TailLength::Samples(samples.div_ceil(FACTOR as u32)),
+            TailLength::Infinite => TailLength::Infinite,
+        };
+        inner.then(TailLength::Samples(self.latency_samples()))
+    }
+}
@@ -0,0 +1,359 @@
+//! Parallel combinator for summing two processors fed the same input.
+
+use std::sync::Arc;
+
+use super::latency::DryDelay;
+use super::scratch::ScratchBuffer;
+use crate::events::NoteEvent;
+use crate::layout::ChannelLayout;
+use crate::smoothing::{Smoother, SmoothingStyle};
+use crate::state::PersistentField;
+use crate::tail::TailLength;
+use crate::taps::MeteringTap;
+use crate::traits::{ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
+
+/// Ramp applied to branch gain changes to avoid zipper noise.
+const BRANCH_GAIN_SMOOTHING: SmoothingStyle = SmoothingStyle::Linear(10.0);
+
+/// Runs two processors on copies of the same input and sums them: A + B.
+///
+/// Each branch is scaled by its own gain parameter before summing. When the
+/// branches report different [`Processor::latency_samples`], the faster one is
+/// delayed so both stay aligned (e.g. parallel compression with lookahead).
+///
+/// Branch parameter IDs are prefixed with `a_` and `b_` so two branches of the
+/// same processor type do not collide. Inside `SignalChain!`, the instance
+/// prefix is added on top, e.g. `parallel_a_level` and `parallel_gain_b`.
+pub struct Parallel<A, B> {
+    pub first: A,
+    pub second: B,
+    scratch: ScratchBuffer,
+    align_first: DryDelay,
+    align_second: DryDelay,
+    gain_a: Smoother,
+    gain_b: Smoother,
+}
+
+impl<A, B> Parallel<A, B> {
+    /// Creates a parallel combinator from two branch processors.
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            scratch: ScratchBuffer::default(),
+            align_first: DryDelay::default(),
+            align_second: DryDelay::default(),
+            gain_a: Smoother::new(BRANCH_GAIN_SMOOTHING),
+            gain_b: Smoother::new(BRANCH_GAIN_SMOOTHING),
+        }
+    }
+}
+
+impl<A, B> Parallel<A, B>
+where
+    A: Processor,
+    B: Processor,
+{
+    /// Delay needed on each branch so both outputs line up.
+    fn alignment(&self) -> (usize, usize) {
+        let first = self.first.latency_samples();
+        let second = self.second.latency_samples();
+        let total = first.max(second);
+        ((total - first) as usize, (total - second) as usize)
+    }
+}
+
+impl<A, B> Default for Parallel<A, B>
+where
+    A: Default,
+    B: Default,
+{
+    fn default() -> Self {
+        Self::new(A::default(), B::default())
+    }
+}
+
+/// Parameters for [`Parallel`].
+///
+/// Both branches' parameters, followed by the two branch gains.
+pub struct ParallelParams<PA, PB> {
+    pub first: PA,
+    pub second: PB,
+    /// Linear gain applied to branch A.
+    pub gain_a: f32,
+    /// Linear gain applied to branch B.
+    pub gain_b: f32,
+}
+
+impl<PA, PB> Default for ParallelParams<PA, PB>
+where
+    PA: Default,
+    PB: Default,
+{
+    fn default() -> Self {
+        Self {
+            first: PA::default(),
+            second: PB::default(),
+            gain_a: 1.0,
+            gain_b: 1.0,
+        }
+    }
+}
+
+fn branch_gain_spec(name: &'static str, id_suffix: &'static str) -> ParamSpec {
+    ParamSpec {
+        name,
+        id_suffix,
+        range: ParamRange::Skewed {
+            min: 0.0,
+            max: 2.0,
+            factor: 2.5,
+        },
+        default: 1.0,
+        unit: "x",
+        group: None,
+    }
+}
+
+impl<PA, PB> ProcessorParams for ParallelParams<PA, PB>
+where
+    PA: ProcessorParams,
+    PB: ProcessorParams,
+{
+    fn param_specs() -> &'static [ParamSpec] {
+        fn leak(value: String) -> &'static str {
+            Box::leak(value.into_boxed_str())
+        }
+
+        fn extend_prefixed(target: &mut Vec<ParamSpec>, source: &[ParamSpec], branch: &str) {
+            for spec in source {
+                target.push(ParamSpec {
+                    name: leak(format!("{} {}", branch.to_uppercase(), spec.name)),
+                    id_suffix: leak(format!("{}_{}", branch, spec.id_suffix)),
+                    range: spec.range.clone(),
+                    default: spec.default,
+                    unit: spec.unit,
+                    group: spec.group,
+                });
+            }
+        }
+
+        let first_specs = PA::param_specs();
+        let second_specs = PB::param_specs();
+        let mut merged = Vec::with_capacity(first_specs.len() + second_specs.len() + 2);
+
+        extend_prefixed(&mut merged, first_specs, "a");
+        extend_prefixed(&mut merged, second_specs, "b");
+        merged.push(branch_gain_spec("Gain A", "gain_a"));
+        merged.push(branch_gain_spec("Gain B", "gain_b"));
+
+        // Leaked like ChainParams::param_specs (no OnceLock, see rationale there).
+        Box::leak(merged.into_boxed_slice())
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            first: PA::from_param_defaults(),
+            second: PB::from_param_defaults(),
+            gain_a: 1.0,
+            gain_b: 1.0,
+        }
+    }
+
+    fn plain_value_count() -> usize {
+        PA::plain_value_count() + PB::plain_value_count() + 2
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        let (first_values, rest) = values.split_at(PA::plain_value_count().min(values.len()));
+        let (second_values, gain_values) = rest.split_at(PB::plain_value_count().min(rest.len()));
+
+        self.first.apply_plain_values(first_values);
+        self.second.apply_plain_values(second_values);
+
+        if let Some(gain_a) = gain_values.first() {
+            self.gain_a = *gain_a;
+        }
+        if let Some(gain_b) = gain_values.get(1) {
+            self.gain_b = *gain_b;
+        }
+    }
+}
+
+impl<A, B> Processor for Parallel<A, B>
+where
+    A: Processor,
+    B: Processor,
+{
+    type Params = ParallelParams<A::Params, B::Params>;
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.process_events(buffer, &[], transport, params);
+    }
+
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_with_sidechain(buffer, &[], events, transport, params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        let (delay_first, delay_second) = self.alignment();
+        let (channels, samples) = self.scratch.copy_from(buffer);
+
+        // Branch A runs in place, branch B on the copy of the input.
+        self.first
+            .process_with_sidechain(buffer, sidechain, events, transport, &params.first);
+        let mut copy = self.scratch.slices(channels, samples);
+        let second = &mut copy[..channels];
+        self.second
+            .process_with_sidechain(second, sidechain, events, transport, &params.second);
+
+        self.align_first.set_delay(delay_first);
+        self.align_first.process(buffer);
+        self.align_second.set_delay(delay_second);
+        self.align_second.process(second);
+
+        self.gain_a.set_target(params.gain_a);
+        self.gain_b.set_target(params.gain_b);
+
+        for idx in 0..samples {
+            let gain_a = self.gain_a.next();
+            let gain_b = self.gain_b.next();
+
+            for (channel, branch) in buffer.iter_mut().zip(second.iter()) {
+                channel[idx] = channel[idx] * gain_a + branch[idx] * gain_b;
+            }
+            // Channels without a copy only carry branch A.
+            for channel in buffer.iter_mut().skip(channels) {
+                channel[idx] *= gain_a;
+            }
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.first.set_sample_rate(sample_rate);
+        self.second.set_sample_rate(sample_rate);
+        self.gain_a.set_sample_rate(sample_rate);
+        self.gain_b.set_sample_rate(sample_rate);
+        self.scratch.preallocate();
+
+        // Either branch may end up the faster one after a latency change.
+        let capacity = self.latency_samples() as usize;
+        self.align_first.ensure_capacity(capacity);
+        self.align_second.ensure_capacity(capacity);
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        self.first.set_channel_layout(layout);
+        self.second.set_channel_layout(layout);
+    }
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        self.first.persistent_fields(fields);
+        self.second.persistent_fields(fields);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        self.first.metering_taps(taps);
+        self.second.metering_taps(taps);
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.align_first.clear();
+        self.align_second.clear();
+        self.gain_a.reset();
+        self.gain_b.reset();
+    }
+
+    fn latency_samples(&self) -> u32 {
+        self.first
+            .latency_samples()
+            .max(self.second.latency_samples())
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        let (delay_first, delay_second) = self.alignment();
+        let first = self
+            .first
+            .tail_samples()
+            .then(TailLength::Samples(delay_first as u32));
+        let second = self
+            .second
+            .tail_samples()
+            .then(TailLength::Samples(delay_second as u32));
+        first.longest(second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Delays its input by a fixed number of samples (mono, test only).
+    struct TestLatencyDsp {
+        latency: usize,
+        history: Vec<f32>,
+    }
+
+    impl TestLatencyDsp {
+        fn new(latency: usize) -> Self {
+            Self {
+                latency,
+                history: vec![0.0; latency],
+            }
+        }
+    }
+
+    impl Processor for TestLatencyDsp {
+        type Params = ();
+
+        fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+            for sample in buffer[0].iter_mut() {
+                self.history.push(*sample);
+                *sample = self.history.remove(0);
+            }
+        }
+
+        fn latency_samples(&self) -> u32 {
+            self.latency as u32
+        }
+
+        fn tail_samples(&self) -> TailLength {
+            TailLength::Samples(self.latency as u32)
+        }
+    }
+
+    #[test]
+    fn test_parallel_aligns_branch_latency() {
+        let mut parallel = Parallel::new(TestLatencyDsp::new(3), TestLatencyDsp::new(0));
+        parallel.set_sample_rate(48_000.0);
+        assert_eq!(parallel.latency_samples(), 3);
+        assert_eq!(parallel.tail_samples(), TailLength::Samples(3));
+
+        let params = ParallelParams {
+            first: (),
+            second: (),
+            gain_a: 1.0,
+            gain_b: 1.0,
+        };
+        let mut impulse = [1.0_f32, 0.0, 0.0, 0.0, 0.0];
+        parallel.process(&mut [&mut impulse[..]], &Transport::default(), &params);
+
+        // Both branches arrive together, 3 samples late.
+        assert_eq!(impulse, [0.0, 0.0, 0.0, 2.0, 0.0]);
+    }
+}
@@ -0,0 +1,105 @@
+//! Block-sized copy of the input for combinators that need it after the
+//! wrapped processor has overwritten the buffer in place.
+
+use super::MAX_CHANNELS;
+
+/// Samples per channel preallocated by [`ScratchBuffer::preallocate`].
+///
+/// Covers the block sizes hosts use in practice; larger blocks grow the
+/// storage once (the only case in which the audio thread allocates).
+pub(crate) const DEFAULT_SCRATCH_SAMPLES: usize = 4096;
+
+/// Multichannel scratch storage for one block of audio.
+#[derive(Debug, Default)]
+pub(crate) struct ScratchBuffer {
+    channels: Vec<Vec<f32>>,
+}
+
+impl ScratchBuffer {
+    /// Allocate storage for [`DEFAULT_SCRATCH_SAMPLES`] per channel.
+    ///
+    /// Call from `set_sample_rate` so the first block does not allocate.
+    pub(crate) fn preallocate(&mut self) {
+        self.reserve(DEFAULT_SCRATCH_SAMPLES);
+    }
+
+    /// Grow storage to at least `samples` per channel.
+    ///
+    /// Allocates only when `samples` exceeds the current length.
+    pub(crate) fn reserve(&mut self, samples: usize) {
+        if self.channels.first().map_or(0, Vec::len) >= samples {
+            return;
+        }
+
+        self.channels = (0..MAX_CHANNELS).map(|_| vec![0.0; samples]).collect();
+    }
+
+    /// Copy `buffer` into scratch storage.
+    ///
+    /// Returns the number of channels and samples copied; pass them to
+    /// [`ScratchBuffer::slices`] to get the copy back.
+    pub(crate) fn copy_from(&mut self, buffer: &[&mut [f32]]) -> (usize, usize) {
+        let samples = buffer
+            .iter()
+            .map(|channel| channel.len())
+            .min()
+            .unwrap_or(0);
+        let channels = buffer.len().min(MAX_CHANNELS);
+        self.reserve(samples);
+
+        for (source, target) in buffer.iter().zip(self.channels.iter_mut()) {
+            target[..samples].copy_from_slice(&source[..samples]);
+        }
+
+        (channels, samples)
+    }
+
+    /// Borrow the first `channels` channels, `samples` long, as a processor
+    /// buffer. Unused slots are empty; slice the result to `..channels`.
+    pub(crate) fn slices(&mut self, channels: usize, samples: usize) -> [&mut [f32]; MAX_CHANNELS] {
+        let mut slices: [&mut [f32]; MAX_CHANNELS] = Default::default();
+        for (slot, channel) in slices
+            .iter_mut()
+            .zip(self.channels.iter_mut())
+            .take(channels)
+        {
+            *slot = &mut channel[..samples];
+        }
+        slices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_round_trips_block() {
+        let mut scratch = ScratchBuffer::default();
+        scratch.preallocate();
+
+        let mut left = [1.0, 2.0, 3.0];
+        let mut right = [4.0, 5.0, 6.0];
+        let (channels, samples) = scratch.copy_from(&[&mut left[..], &mut right[..]]);
+        assert_eq!((channels, samples), (2, 3));
+
+        left.fill(0.0);
+        let slices = scratch.slices(channels, samples);
+        assert_eq!(&slices[0][..], &[1.0, 2.0, 3.0]);
+        assert_eq!(&slices[1][..], &[4.0, 5.0, 6.0]);
+        assert!(slices[2].is_empty());
+
+        slices[0][0] = 9.0;
+        assert_eq!(scratch.slices(1, 1)[0][0], 9.0);
+    }
+
+    #[test]
+    fn test_copy_grows_for_oversized_blocks() {
+        let mut scratch = ScratchBuffer::default();
+        let mut block = vec![0.5_f32; DEFAULT_SCRATCH_SAMPLES + 1];
+
+        let (_, samples) = scratch.copy_from(&[&mut block[..]]);
+        assert_eq!(samples, DEFAULT_SCRATCH_SAMPLES + 1);
+        assert_eq!(scratch.slices(1, samples)[0][samples - 1], 0.5);
+    }
+}
@@ -0,0 +1,132 @@
+//! Note and MIDI controller events delivered to processors.
+//!
+//! Events are passed to [`Processor::process_events`](crate::Processor::process_events)
+//! as a slice sorted by [`NoteEvent::timing`], which is the sample offset of
+//! the event within the current block.
+
+/// Most note events a generated plugin delivers to its processor per block.
+///
+/// The plugin preallocates its event buffer with this capacity and drops
+/// events beyond it, so processors that copy or rescale the events (such as
+/// [`Oversampled`](crate::combinators::Oversampled)) can size their own
+/// buffers to it and never reallocate on the audio thread.
+pub const MAX_EVENTS_PER_BLOCK: usize = 1024;
+
+/// A timed note or controller event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoteEvent {
+    /// Sample offset of the event within the current block.
+    ///
+    /// Always smaller than the block length.
+    pub timing: u32,
+
+    /// What happened.
+    pub kind: NoteEventKind,
+}
+
+/// Payload of a [`NoteEvent`].
+///
+/// Channels are zero-based MIDI channels (`0..=15`) and notes are MIDI note
+/// numbers (`0..=127`, 60 = middle C). Continuous values are normalized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoteEventKind {
+    /// A note was pressed. `velocity` is in `0.0..=1.0`.
+    NoteOn {
+        channel: u8,
+        note: u8,
+        velocity: f32,
+    },
+
+    /// A note was released. `velocity` is the release velocity in `0.0..=1.0`.
+    NoteOff {
+        channel: u8,
+        note: u8,
+        velocity: f32,
+    },
+
+    /// Polyphonic aftertouch for a single note, in `0.0..=1.0`.
+    PolyPressure {
+        channel: u8,
+        note: u8,
+        pressure: f32,
+    },
+
+    /// Channel aftertouch, in `0.0..=1.0`.
+    ChannelPressure { channel: u8, pressure: f32 },
+
+    /// Control change. `value` is in `0.0..=1.0`.
+    ControlChange { channel: u8, cc: u8, value: f32 },
+
+    /// Pitch bend in `-1.0..=1.0`, where `0.0` is centered.
+    PitchBend { channel: u8, value: f32 },
+}
+
+impl NoteEvent {
+    /// Create a note-on event.
+    pub fn note_on(timing: u32, channel: u8, note: u8, velocity: f32) -> Self {
+        Self {
+            timing,
+            kind: NoteEventKind::NoteOn {
+                channel,
+                note,
+                velocity,
+            },
+        }
+    }
+
+    /// Create a note-off event.
+    pub fn note_off(timing: u32, channel: u8, note: u8, velocity: f32) -> Self {
+        Self {
+            timing,
+            kind: NoteEventKind::NoteOff {
+                channel,
+                note,
+                velocity,
+            },
+        }
+    }
+
+    /// Returns the MIDI channel the event was sent on.
+    pub fn channel(&self) -> u8 {
+        match self.kind {
+            NoteEventKind::NoteOn { channel, .. }
+            | NoteEventKind::NoteOff { channel, .. }
+            | NoteEventKind::PolyPressure { channel, .. }
+            | NoteEventKind::ChannelPressure { channel, .. }
+            | NoteEventKind::ControlChange { channel, .. }
+            | NoteEventKind::PitchBend { channel, .. } => channel,
+        }
+    }
+}
+
+/// Convert a MIDI note number to a frequency in Hz (A4 = note 69 = 440 Hz).
+#[inline]
+pub fn note_to_frequency(note: u8) -> f32 {
+    440.0 * 2.0_f32.powf((f32::from(note) - 69.0) / 12.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_to_frequency() {
+        assert!((note_to_frequency(69) - 440.0).abs() < 1e-3);
+        assert!((note_to_frequency(81) - 880.0).abs() < 1e-3);
+        assert!((note_to_frequency(60) - 261.626).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_event_channel() {
+        let event = NoteEvent {
+            timing: 3,
+            kind: NoteEventKind::PitchBend {
+                channel: 9,
+                value: 0.0,
+            },
+        };
+
+        assert_eq!(event.channel(), 9);
+        assert_eq!(NoteEvent::note_on(0, 2, 60, 1.0).channel(), 2);
+    }
+}
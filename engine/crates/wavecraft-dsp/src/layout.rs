@@ -0,0 +1,48 @@
+//! Main-bus channel layouts.
+//!
+//! The plugin host picks one of the layouts declared in `wavecraft_plugin!`
+//! and the processor learns about it through
+//! [`Processor::set_channel_layout`](crate::Processor::set_channel_layout).
+
+/// Input and output channel counts of the main audio bus.
+///
+/// Instruments have no main input, so `inputs` is `0` for them. Surround
+/// layouts use the host's channel order; for 5.1 and 7.1 the first two
+/// channels are front left and front right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelLayout {
+    /// Main input channel count.
+    pub inputs: u32,
+    /// Main output channel count.
+    pub outputs: u32,
+}
+
+impl ChannelLayout {
+    /// One channel in, one channel out.
+    pub const MONO: Self = Self::new(1, 1);
+    /// Two channels in, two channels out.
+    pub const STEREO: Self = Self::new(2, 2);
+    /// One channel in, two channels out.
+    pub const MONO_TO_STEREO: Self = Self::new(1, 2);
+    /// Six channels in and out (L, R, C, LFE, Ls, Rs).
+    pub const SURROUND_5_1: Self = Self::new(6, 6);
+    /// Eight channels in and out (L, R, C, LFE, Ls, Rs, Lrs, Rrs).
+    pub const SURROUND_7_1: Self = Self::new(8, 8);
+
+    /// Creates a layout from channel counts.
+    pub const fn new(inputs: u32, outputs: u32) -> Self {
+        Self { inputs, outputs }
+    }
+
+    /// Returns true if the single input channel is spread over several
+    /// outputs (e.g. mono-to-stereo).
+    pub const fn is_upmix(&self) -> bool {
+        self.inputs == 1 && self.outputs > 1
+    }
+}
+
+impl Default for ChannelLayout {
+    fn default() -> Self {
+        Self::STEREO
+    }
+}
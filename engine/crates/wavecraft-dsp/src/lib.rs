@@ -4,14 +4,51 @@
 //! making it fully testable in isolation.
 
 pub mod combinators;
+pub mod events;
 pub mod gain;
+pub mod layout;
+pub mod modulation;
+pub mod safety;
+pub mod smoothing;
+pub mod state;
+pub mod tail;
+pub mod taps;
+pub mod testing;
 pub mod traits;
 
 // Core DSP contracts.
 pub use traits::{ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
 
+// Note/MIDI events.
+pub use events::{MAX_EVENTS_PER_BLOCK, NoteEvent, NoteEventKind, note_to_frequency};
+
+// Channel layouts.
+pub use layout::ChannelLayout;
+
+// Modulation sources and routing.
+pub use modulation::{
+    EnvelopeFollower, Lfo, ModulationHandle, ModulationMatrix, ModulationTarget, time_coefficient,
+};
+
+// Denormal and NaN/Inf protection.
+pub use safety::{
+    DEFAULT_OUTPUT_LIMIT, DenormalGuard, OutputSanitizer, SanitizerCounts, SanitizerStats,
+};
+
+// Parameter smoothing.
+pub use smoothing::{ParamSmoothing, Smoother, SmoothingStyle};
+
+// Persistent non-parameter state.
+pub use state::{PersistentField, PersistentState};
+
+// Tail length and silence tracking.
+pub use tail::{SILENCE_THRESHOLD, TailLength, TailState, TailTracker, is_silent};
+
+// Named metering taps.
+pub use taps::{MeteringTap, TapId};
+
 // Combinators and helpers.
-pub use combinators::{Bypassed, Chain};
+pub use combinators::{Bypassed, Chain, MidSide, MidSideMode, Mix, Oversampled, Parallel};
 
-// Note: SignalChain! and Chain! macros are automatically exported at crate root
+// Note: SignalChain!, Chain! and tap_id! macros are automatically exported at crate root
 // via #[macro_export] in combinators/mod.rs
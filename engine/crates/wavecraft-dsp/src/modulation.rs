@@ -0,0 +1,777 @@
+//! Modulation sources (LFOs, envelope followers) routed to parameters.
+//!
+//! The routings themselves are a [`ModulationConfig`] from
+//! `wavecraft-protocol`, edited from the UI and saved with the plugin. A
+//! [`ModulationMatrix`] owned by the plugin runs the configured sources once
+//! per block and offsets the parameter values before they are applied to the
+//! processor chain. Offsets are added in the normalized (knob position)
+//! domain, so a given depth sweeps the same share of the knob on linear and
+//! skewed parameters alike:
+//!
+//! ```rust
+//! use wavecraft_dsp::modulation::{ModulationMatrix, ModulationTarget};
+//! use wavecraft_dsp::Transport;
+//! use wavecraft_protocol::{LfoRate, LfoShape, ModulationConfig, ModulationRouting, ModulationSource};
+//!
+//! let mut matrix = ModulationMatrix::new(vec![ModulationTarget {
+//!     id: "filter_cutoff".to_string(),
+//!     min: 20.0,
+//!     max: 20_000.0,
+//!     skew: 0.3,
+//! }]);
+//!
+//! // Off the audio thread (IPC, state restore):
+//! matrix.handle().set_config(ModulationConfig {
+//!     sources: vec![ModulationSource::Lfo {
+//!         shape: LfoShape::Sine,
+//!         rate: LfoRate::Hz { hz: 2.0 },
+//!         phase: 0.25,
+//!     }],
+//!     routings: vec![ModulationRouting {
+//!         source: 0,
+//!         target: "filter_cutoff".to_string(),
+//!         depth: 0.1,
+//!         polarity: Default::default(),
+//!     }],
+//! });
+//!
+//! // On the audio thread, once per block:
+//! let mut left = [0.0_f32; 64];
+//! let mut right = [0.0_f32; 64];
+//! let mut values = [1_000.0_f32];
+//! matrix.advance(&[&mut left[..], &mut right[..]], &Transport::default());
+//! matrix.apply(&mut values);
+//! // A tenth of the knob travel up from 1 kHz on the skewed range.
+//! assert!((values[0] - 2_065.0).abs() < 2.0);
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use wavecraft_protocol::{
+    LfoRate, LfoShape, MAX_MODULATION_ROUTINGS, MAX_MODULATION_SOURCES, ModulatedValue,
+    ModulationConfig, ModulationFrame, ModulationPolarity, ModulationSource,
+};
+
+use crate::state::{PersistentField, PersistentState};
+use crate::traits::Transport;
+
+/// Sample rate assumed until `set_sample_rate` is called.
+const DEFAULT_SAMPLE_RATE: f32 = 44_100.0;
+
+/// Tempo used for beat-synced LFOs when the host doesn't report one.
+const FALLBACK_TEMPO: f64 = 120.0;
+
+/// Low-frequency oscillator evaluated once per block.
+#[derive(Debug, Clone)]
+pub struct Lfo {
+    shape: LfoShape,
+    rate: LfoRate,
+    start_phase: f64,
+    /// Position within the current cycle (`0.0..1.0`).
+    phase: f64,
+    /// Current sample-and-hold value.
+    held: f32,
+    rng: u32,
+}
+
+impl Lfo {
+    /// Creates an LFO starting at `phase` (a fraction of a cycle).
+    pub fn new(shape: LfoShape, rate: LfoRate, phase: f32) -> Self {
+        let start_phase = f64::from(phase).rem_euclid(1.0);
+        let mut lfo = Self {
+            shape,
+            rate,
+            start_phase,
+            phase: start_phase,
+            held: 0.0,
+            rng: 0x9E37_79B9,
+        };
+        lfo.held = lfo.next_random();
+        lfo
+    }
+
+    /// Returns the value (`-1.0..=1.0`) at the start of the block and
+    /// advances the phase by `samples`.
+    ///
+    /// Beat-synced LFOs lock their phase to the host's song position while
+    /// the transport is playing, and otherwise run free at the host tempo
+    /// (120 BPM if it doesn't report one).
+    pub fn advance(&mut self, samples: usize, sample_rate: f32, transport: &Transport) -> f32 {
+        let cycles_per_sample = match self.rate {
+            LfoRate::Hz { hz } => f64::from(hz) / f64::from(sample_rate),
+            LfoRate::Beats { beats } => {
+                if transport.playing
+                    && let Some(pos_beats) = transport.pos_beats
+                    && beats > 0.0
+                {
+                    self.set_phase(pos_beats / f64::from(beats) + self.start_phase);
+                }
+                let samples_per_beat = transport
+                    .samples_per_beat(sample_rate)
+                    .unwrap_or(f64::from(sample_rate) * 60.0 / FALLBACK_TEMPO);
+                1.0 / (samples_per_beat * f64::from(beats))
+            }
+        };
+
+        let value = self.value();
+        if cycles_per_sample.is_finite() && cycles_per_sample > 0.0 {
+            self.set_phase(self.phase + cycles_per_sample * samples as f64);
+        }
+        value
+    }
+
+    /// Current output in `-1.0..=1.0`.
+    pub fn value(&self) -> f32 {
+        let phase = self.phase as f32;
+        match self.shape {
+            LfoShape::Sine => (phase * std::f32::consts::TAU).sin(),
+            LfoShape::Triangle => {
+                if phase < 0.25 {
+                    4.0 * phase
+                } else if phase < 0.75 {
+                    2.0 - 4.0 * phase
+                } else {
+                    4.0 * phase - 4.0
+                }
+            }
+            LfoShape::Saw => 2.0 * phase - 1.0,
+            LfoShape::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            LfoShape::SampleAndHold => self.held,
+        }
+    }
+
+    /// Returns to the start phase.
+    pub fn reset(&mut self) {
+        self.phase = self.start_phase;
+    }
+
+    fn set_phase(&mut self, phase: f64) {
+        let phase = phase.rem_euclid(1.0);
+        // A new cycle (or a jump back, e.g. a transport loop) picks a new
+        // sample-and-hold value.
+        if phase < self.phase {
+            self.held = self.next_random();
+        }
+        self.phase = phase;
+    }
+
+    /// xorshift32, mapped to `-1.0..=1.0`.
+    fn next_random(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+}
+
+/// Peak envelope follower with separate attack and release times.
+#[derive(Debug, Clone)]
+pub struct EnvelopeFollower {
+    attack_ms: f32,
+    release_ms: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    level: f32,
+}
+
+impl EnvelopeFollower {
+    /// Creates a follower with the given attack and release times.
+    pub fn new(attack_ms: f32, release_ms: f32) -> Self {
+        let mut follower = Self {
+            attack_ms,
+            release_ms,
+            attack_coeff: 0.0,
+            release_coeff: 0.0,
+            level: 0.0,
+        };
+        follower.set_sample_rate(DEFAULT_SAMPLE_RATE);
+        follower
+    }
+
+    /// Recomputes the attack and release coefficients.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.attack_coeff = time_coefficient(self.attack_ms, sample_rate);
+        self.release_coeff = time_coefficient(self.release_ms, sample_rate);
+    }
+
+    /// Follows the loudest channel through a block and returns the level
+    /// (`0.0..=1.0`) at its end.
+    pub fn process(&mut self, input: &[&mut [f32]]) -> f32 {
+        let samples = input.iter().map(|channel| channel.len()).min().unwrap_or(0);
+        for index in 0..samples {
+            let peak = input
+                .iter()
+                .map(|channel| channel[index].abs())
+                .fold(0.0, f32::max)
+                .min(1.0);
+            let coeff = if peak > self.level {
+                self.attack_coeff
+            } else {
+                self.release_coeff
+            };
+            self.level = peak + coeff * (self.level - peak);
+        }
+        self.level
+    }
+
+    /// Current level in `0.0..=1.0`.
+    pub fn value(&self) -> f32 {
+        self.level
+    }
+
+    /// Drops the level to zero.
+    pub fn reset(&mut self) {
+        self.level = 0.0;
+    }
+}
+
+/// Per-sample coefficient of a one-pole smoother reaching ~63% of a step in
+/// `time_ms`.
+///
+/// Returns `0.0` (no smoothing) for zero, negative or non-finite times.
+pub fn time_coefficient(time_ms: f32, sample_rate: f32) -> f32 {
+    let samples = time_ms * 0.001 * sample_rate;
+    if samples > 0.0 && samples.is_finite() {
+        (-1.0 / samples).exp()
+    } else {
+        0.0
+    }
+}
+
+/// A parameter modulation can be routed to, in the order of the plain value
+/// slice passed to [`ModulationMatrix::apply`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModulationTarget {
+    /// Parameter ID (e.g. `"filter_cutoff"`).
+    pub id: String,
+    /// Lowest plain value.
+    pub min: f32,
+    /// Highest plain value.
+    pub max: f32,
+    /// Skew factor of the parameter's normalized mapping, as in
+    /// [`ParamRange::Skewed`](crate::ParamRange::Skewed); `1.0` for linear
+    /// ranges.
+    pub skew: f32,
+}
+
+impl ModulationTarget {
+    /// Maps a plain value to `0.0..=1.0`, the same way the host maps it to a
+    /// knob position.
+    fn normalize(&self, plain: f32) -> f32 {
+        if self.max == self.min {
+            return 0.0;
+        }
+        let linear = ((plain - self.min) / (self.max - self.min)).clamp(0.0, 1.0);
+        if self.skew == 1.0 || self.skew <= 0.0 {
+            linear
+        } else {
+            linear.powf(self.skew)
+        }
+    }
+
+    /// Inverse of [`Self::normalize`].
+    fn denormalize(&self, normalized: f32) -> f32 {
+        let linear = if self.skew == 1.0 || self.skew <= 0.0 {
+            normalized
+        } else {
+            normalized.powf(self.skew.recip())
+        };
+        self.min + linear * (self.max - self.min)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum SourceRuntime {
+    Lfo(Lfo),
+    Envelope(EnvelopeFollower),
+}
+
+#[derive(Debug, Clone)]
+struct ActiveSource {
+    config: ModulationSource,
+    runtime: SourceRuntime,
+    /// Output from the last `advance`, in the source's native range.
+    value: f32,
+}
+
+impl ActiveSource {
+    fn new(config: ModulationSource, sample_rate: f32) -> Self {
+        let runtime = match config {
+            ModulationSource::Lfo { shape, rate, phase } => {
+                SourceRuntime::Lfo(Lfo::new(shape, rate, phase))
+            }
+            ModulationSource::EnvelopeFollower {
+                attack_ms,
+                release_ms,
+            } => {
+                let mut follower = EnvelopeFollower::new(attack_ms, release_ms);
+                follower.set_sample_rate(sample_rate);
+                SourceRuntime::Envelope(follower)
+            }
+        };
+        Self {
+            config,
+            runtime,
+            value: 0.0,
+        }
+    }
+
+    /// Maps the output to `-1.0..=1.0` (bipolar) or `0.0..=1.0` (unipolar).
+    fn mapped(&self, polarity: ModulationPolarity) -> f32 {
+        match (&self.runtime, polarity) {
+            (SourceRuntime::Lfo(_), ModulationPolarity::Bipolar)
+            | (SourceRuntime::Envelope(_), ModulationPolarity::Unipolar) => self.value,
+            (SourceRuntime::Lfo(_), ModulationPolarity::Unipolar) => (self.value + 1.0) * 0.5,
+            (SourceRuntime::Envelope(_), ModulationPolarity::Bipolar) => self.value * 2.0 - 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ActiveRouting {
+    source: usize,
+    target: usize,
+    depth: f32,
+    polarity: ModulationPolarity,
+}
+
+#[derive(Debug, Default)]
+struct MonitorSlot {
+    routed: AtomicBool,
+    value: AtomicU32,
+    offset: AtomicU32,
+}
+
+/// Latest modulated values, written by the audio thread and read by the UI.
+#[derive(Debug, Default)]
+struct ModulationMonitor {
+    ids: Vec<String>,
+    slots: Vec<MonitorSlot>,
+    published: AtomicBool,
+}
+
+/// Shared access to a [`ModulationMatrix`] from outside the audio thread.
+///
+/// Clones share the same configuration and monitor. A default handle is
+/// detached from any matrix and has no targets.
+#[derive(Debug, Clone, Default)]
+pub struct ModulationHandle {
+    config: PersistentState<ModulationConfig>,
+    monitor: Arc<ModulationMonitor>,
+}
+
+impl ModulationHandle {
+    /// Returns a copy of the current configuration. Blocks on the lock.
+    pub fn config(&self) -> ModulationConfig {
+        self.config.get()
+    }
+
+    /// Replaces the configuration; the audio thread picks it up at the next
+    /// block. Blocks on the lock, so don't call this from the audio thread.
+    pub fn set_config(&self, config: ModulationConfig) {
+        self.config.set(config);
+    }
+
+    /// IDs of the parameters modulation can be routed to.
+    pub fn target_ids(&self) -> impl Iterator<Item = &str> {
+        self.monitor.ids.iter().map(String::as_str)
+    }
+
+    /// Latest values of the modulated parameters, or `None` if no block has
+    /// been processed yet.
+    pub fn frame(&self) -> Option<ModulationFrame> {
+        if !self.monitor.published.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let values = self
+            .monitor
+            .ids
+            .iter()
+            .zip(&self.monitor.slots)
+            .filter(|(_, slot)| slot.routed.load(Ordering::Relaxed))
+            .map(|(id, slot)| {
+                let value = ModulatedValue {
+                    value: f32::from_bits(slot.value.load(Ordering::Relaxed)),
+                    offset: f32::from_bits(slot.offset.load(Ordering::Relaxed)),
+                };
+                (id.clone(), value)
+            })
+            .collect();
+        Some(ModulationFrame { values })
+    }
+
+    /// The configuration as a field of the plugin state.
+    pub fn field(&self) -> Arc<dyn PersistentField> {
+        self.config.field()
+    }
+}
+
+/// Runs the modulation sources and applies their routings to plain parameter
+/// values on the audio thread.
+///
+/// Configuration changes made through a [`ModulationHandle`] are picked up
+/// without blocking or allocating. Sources keep running across changes that
+/// leave their own settings untouched. Routings to unknown parameters or
+/// sources are ignored.
+#[derive(Debug)]
+pub struct ModulationMatrix {
+    config: PersistentState<ModulationConfig>,
+    monitor: Arc<ModulationMonitor>,
+    targets: Vec<ModulationTarget>,
+    sources: Vec<ActiveSource>,
+    routings: Vec<ActiveRouting>,
+    /// Per-target offset scratch for `apply`.
+    offsets: Vec<f32>,
+    routed: Vec<bool>,
+    sample_rate: f32,
+}
+
+impl ModulationMatrix {
+    /// Creates a matrix for `targets`, with no sources or routings.
+    pub fn new(targets: Vec<ModulationTarget>) -> Self {
+        let monitor = ModulationMonitor {
+            ids: targets.iter().map(|target| target.id.clone()).collect(),
+            slots: targets.iter().map(|_| MonitorSlot::default()).collect(),
+            published: AtomicBool::new(false),
+        };
+
+        Self {
+            config: PersistentState::default(),
+            monitor: Arc::new(monitor),
+            offsets: vec![0.0; targets.len()],
+            routed: vec![false; targets.len()],
+            targets,
+            sources: Vec::with_capacity(MAX_MODULATION_SOURCES),
+            routings: Vec::with_capacity(MAX_MODULATION_ROUTINGS),
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        }
+    }
+
+    /// Returns a handle for editing the configuration and reading values.
+    pub fn handle(&self) -> ModulationHandle {
+        ModulationHandle {
+            config: self.config.clone(),
+            monitor: Arc::clone(&self.monitor),
+        }
+    }
+
+    /// Updates the sample rate used by the sources.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate <= 0.0 {
+            return;
+        }
+        self.sample_rate = sample_rate;
+        for source in &mut self.sources {
+            if let SourceRuntime::Envelope(follower) = &mut source.runtime {
+                follower.set_sample_rate(sample_rate);
+            }
+        }
+    }
+
+    /// Restarts LFOs and clears envelope levels.
+    pub fn reset(&mut self) {
+        for source in &mut self.sources {
+            match &mut source.runtime {
+                SourceRuntime::Lfo(lfo) => lfo.reset(),
+                SourceRuntime::Envelope(follower) => follower.reset(),
+            }
+            source.value = 0.0;
+        }
+    }
+
+    /// Picks up configuration changes and runs the sources for one block.
+    ///
+    /// `input` is the plugin's main input before processing; its length sets
+    /// the block size and envelope followers track its level.
+    pub fn advance(&mut self, input: &[&mut [f32]], transport: &Transport) {
+        self.sync_config();
+
+        let samples = input.iter().map(|channel| channel.len()).min().unwrap_or(0);
+        for source in &mut self.sources {
+            source.value = match &mut source.runtime {
+                SourceRuntime::Lfo(lfo) => lfo.advance(samples, self.sample_rate, transport),
+                SourceRuntime::Envelope(follower) => follower.process(input),
+            };
+        }
+    }
+
+    /// Adds the routed modulation to `values` (plain values in target order)
+    /// in the normalized domain, clamps each modulated value to its range and
+    /// publishes the result for [`ModulationHandle::frame`].
+    pub fn apply(&mut self, values: &mut [f32]) {
+        self.offsets.fill(0.0);
+        self.routed.fill(false);
+
+        // A depth of 1.0 sweeps the full normalized range.
+        for routing in &self.routings {
+            let amount = self.sources[routing.source].mapped(routing.polarity) * routing.depth;
+            self.offsets[routing.target] += amount;
+            self.routed[routing.target] = true;
+        }
+
+        for (index, (value, slot)) in values.iter_mut().zip(&self.monitor.slots).enumerate() {
+            let routed = self.routed[index];
+            slot.routed.store(routed, Ordering::Relaxed);
+            if !routed {
+                continue;
+            }
+
+            let target = &self.targets[index];
+            let base = *value;
+            let normalized = (target.normalize(base) + self.offsets[index]).clamp(0.0, 1.0);
+            *value = target.denormalize(normalized);
+            slot.value.store(value.to_bits(), Ordering::Relaxed);
+            slot.offset
+                .store((*value - base).to_bits(), Ordering::Relaxed);
+        }
+
+        self.monitor.published.store(true, Ordering::Release);
+    }
+
+    fn sync_config(&mut self) {
+        let Self {
+            config,
+            targets,
+            sources,
+            routings,
+            sample_rate,
+            ..
+        } = self;
+
+        config.try_update(|config| {
+            let source_count = config.sources.len().min(MAX_MODULATION_SOURCES);
+            sources.truncate(source_count);
+            for (index, &source) in config.sources[..source_count].iter().enumerate() {
+                match sources.get_mut(index) {
+                    Some(active) if active.config == source => {}
+                    Some(active) => *active = ActiveSource::new(source, *sample_rate),
+                    None => sources.push(ActiveSource::new(source, *sample_rate)),
+                }
+            }
+
+            routings.clear();
+            for routing in &config.routings {
+                if routings.len() == MAX_MODULATION_ROUTINGS {
+                    break;
+                }
+                let Some(target) = targets
+                    .iter()
+                    .position(|target| target.id == routing.target)
+                else {
+                    continue;
+                };
+                if routing.source >= sources.len() || !routing.depth.is_finite() {
+                    continue;
+                }
+                routings.push(ActiveRouting {
+                    source: routing.source,
+                    target,
+                    depth: routing.depth.clamp(-1.0, 1.0),
+                    polarity: routing.polarity,
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_protocol::ModulationRouting;
+
+    fn target(id: &str, min: f32, max: f32) -> ModulationTarget {
+        ModulationTarget {
+            id: id.to_string(),
+            min,
+            max,
+            skew: 1.0,
+        }
+    }
+
+    fn routing(source: usize, target: &str, depth: f32) -> ModulationRouting {
+        ModulationRouting {
+            source,
+            target: target.to_string(),
+            depth,
+            polarity: ModulationPolarity::Bipolar,
+        }
+    }
+
+    #[test]
+    fn lfo_shapes_start_at_expected_values() {
+        let transport = Transport::default();
+        let start = |shape| Lfo::new(shape, LfoRate::Hz { hz: 1.0 }, 0.25).value();
+
+        assert!((start(LfoShape::Sine) - 1.0).abs() < 1e-6);
+        assert!((start(LfoShape::Triangle) - 1.0).abs() < 1e-6);
+        assert!((start(LfoShape::Saw) + 0.5).abs() < 1e-6);
+        assert_eq!(start(LfoShape::Square), 1.0);
+
+        // Half a cycle later the sine is at its trough.
+        let mut lfo = Lfo::new(LfoShape::Sine, LfoRate::Hz { hz: 1.0 }, 0.25);
+        lfo.advance(500, 1000.0, &transport);
+        assert!((lfo.value() + 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn beat_synced_lfo_follows_tempo_and_song_position() {
+        // Free-running at 120 BPM: a one-beat cycle lasts 0.5 s.
+        let mut lfo = Lfo::new(LfoShape::Saw, LfoRate::Beats { beats: 1.0 }, 0.0);
+        let stopped = Transport {
+            tempo: Some(120.0),
+            ..Transport::default()
+        };
+        lfo.advance(250, 1000.0, &stopped);
+        assert!(lfo.value().abs() < 1e-5);
+
+        // While playing, the phase locks to the song position.
+        let playing = Transport {
+            tempo: Some(120.0),
+            playing: true,
+            pos_beats: Some(10.75),
+            ..Transport::default()
+        };
+        assert!((lfo.advance(0, 1000.0, &playing) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn envelope_follower_attacks_and_releases() {
+        let mut follower = EnvelopeFollower::new(0.0, 10.0);
+        follower.set_sample_rate(1000.0);
+
+        let mut loud = [0.8_f32; 4];
+        assert!((follower.process(&[&mut loud[..]]) - 0.8).abs() < 1e-6);
+
+        let mut silence = [0.0_f32; 10];
+        let level = follower.process(&[&mut silence[..]]);
+        assert!((level - 0.8 * (-1.0_f32).exp()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn matrix_applies_depth_and_polarity_and_clamps() {
+        let mut matrix =
+            ModulationMatrix::new(vec![target("gain", -60.0, 0.0), target("mix", 0.0, 1.0)]);
+        let handle = matrix.handle();
+        handle.set_config(ModulationConfig {
+            sources: vec![ModulationSource::Lfo {
+                shape: LfoShape::Square,
+                rate: LfoRate::Hz { hz: 1.0 },
+                phase: 0.0,
+            }],
+            routings: vec![
+                routing(0, "gain", 0.5),
+                ModulationRouting {
+                    polarity: ModulationPolarity::Unipolar,
+                    ..routing(0, "mix", -0.25)
+                },
+                routing(0, "unknown", 1.0),
+            ],
+        });
+
+        matrix.advance(&[], &Transport::default());
+        let mut values = [-10.0, 0.5];
+        matrix.apply(&mut values);
+
+        // +1 * 0.5 * 60 dB = +30 dB, clamped to the 0 dB maximum.
+        assert_eq!(values, [0.0, 0.25]);
+
+        let frame = handle.frame().expect("frame after apply");
+        assert_eq!(
+            frame.values["gain"],
+            ModulatedValue {
+                value: 0.0,
+                offset: 10.0
+            }
+        );
+        assert_eq!(frame.values["mix"].offset, -0.25);
+    }
+
+    #[test]
+    fn matrix_modulates_skewed_parameters_in_the_normalized_domain() {
+        // A frequency range skewed towards the low end, as a host knob maps it.
+        let cutoff = |id: &str| ModulationTarget {
+            skew: 0.25,
+            ..target(id, 20.0, 20_000.0)
+        };
+        let mut matrix = ModulationMatrix::new(vec![cutoff("low"), cutoff("high")]);
+        matrix.handle().set_config(ModulationConfig {
+            sources: vec![ModulationSource::Lfo {
+                shape: LfoShape::Square,
+                rate: LfoRate::Hz { hz: 1.0 },
+                phase: 0.0,
+            }],
+            routings: vec![routing(0, "low", 0.1), routing(0, "high", 0.1)],
+        });
+
+        matrix.advance(&[], &Transport::default());
+        let mut values = [100.0, 10_000.0];
+        matrix.apply(&mut values);
+
+        // Both move a tenth of the knob travel rather than a tenth of the
+        // plain range (+1998 Hz), which would swamp the low setting.
+        assert!((values[0] - 325.17).abs() < 0.1, "low: {}", values[0]);
+        assert!((values[1] - 15_664.94).abs() < 1.0, "high: {}", values[1]);
+        for (value, base) in values.into_iter().zip([100.0, 10_000.0]) {
+            let target = cutoff("any");
+            let moved = target.normalize(value) - target.normalize(base);
+            assert!((moved - 0.1).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn matrix_without_routings_leaves_values_untouched() {
+        let mut matrix = ModulationMatrix::new(vec![target("gain", 0.0, 1.0)]);
+        assert!(matrix.handle().frame().is_none());
+
+        matrix.advance(&[], &Transport::default());
+        let mut values = [0.3];
+        matrix.apply(&mut values);
+
+        assert_eq!(values, [0.3]);
+        assert!(
+            matrix
+                .handle()
+                .frame()
+                .expect("published")
+                .values
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unchanged_sources_keep_running_across_config_changes() {
+        let lfo = ModulationSource::Lfo {
+            shape: LfoShape::Saw,
+            rate: LfoRate::Hz { hz: 1.0 },
+            phase: 0.0,
+        };
+        let mut matrix = ModulationMatrix::new(vec![target("mix", 0.0, 1.0)]);
+        matrix.set_sample_rate(1000.0);
+        let handle = matrix.handle();
+        handle.set_config(ModulationConfig {
+            sources: vec![lfo],
+            routings: vec![routing(0, "mix", 0.5)],
+        });
+
+        let mut block = [0.0_f32; 250];
+        matrix.advance(&[&mut block[..]], &Transport::default());
+        handle.set_config(ModulationConfig {
+            sources: vec![lfo],
+            routings: vec![routing(0, "mix", 1.0)],
+        });
+        matrix.advance(&[&mut block[..]], &Transport::default());
+
+        // Second block starts a quarter cycle in: saw = -0.5.
+        let mut values = [0.5];
+        matrix.apply(&mut values);
+        assert!(values[0].abs() < 1e-5);
+    }
+}
@@ -0,0 +1,345 @@
+//! Denormal and NaN/Inf protection for the audio path.
+//!
+//! Plugin wrappers hold a [`DenormalGuard`] for the duration of each process
+//! call, so decaying filter and reverb states flush to zero instead of
+//! running into slow subnormal arithmetic. The processed block then passes
+//! through an [`OutputSanitizer`], which keeps NaN, infinities and runaway
+//! levels from reaching the host. Its [`SanitizerStats`] are read off the
+//! audio thread and surfaced to the UI as an audio diagnostic.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use wavecraft_protocol::{AudioDiagnostic, AudioDiagnosticCode};
+
+/// Default sample magnitude the [`OutputSanitizer`] clamps to (+12 dBFS).
+pub const DEFAULT_OUTPUT_LIMIT: f32 = 4.0;
+
+/// Enables flush-to-zero and denormals-are-zero on the current thread until
+/// dropped, then restores the previous floating-point mode.
+///
+/// Uses MXCSR on x86/x86_64 and FPCR on aarch64; a no-op elsewhere. The
+/// guard is `!Send` because the mode belongs to the thread that set it.
+#[must_use = "flush-to-zero is only enabled while the guard is alive"]
+pub struct DenormalGuard {
+    previous: arch::State,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl DenormalGuard {
+    /// Enable flush-to-zero for the current thread.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            previous: arch::enable_flush_to_zero(),
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Default for DenormalGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DenormalGuard {
+    #[inline]
+    fn drop(&mut self) {
+        arch::restore(self.previous);
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse"
+))]
+mod arch {
+    use std::arch::asm;
+
+    /// MXCSR flush-to-zero (bit 15) and denormals-are-zero (bit 6).
+    const FTZ_DAZ: u32 = (1 << 15) | (1 << 6);
+
+    pub(super) type State = u32;
+
+    #[inline]
+    pub(super) fn enable_flush_to_zero() -> State {
+        let mut previous: u32 = 0;
+        // SAFETY: `stmxcsr`/`ldmxcsr` only read and write the SSE control
+        // register through valid pointers to local variables. Setting FTZ and
+        // DAZ changes rounding of subnormals only, which Rust code tolerates.
+        unsafe {
+            asm!("stmxcsr [{}]", in(reg) &mut previous, options(nostack, preserves_flags));
+            let flushed = previous | FTZ_DAZ;
+            asm!("ldmxcsr [{}]", in(reg) &flushed, options(nostack, readonly, preserves_flags));
+        }
+        previous
+    }
+
+    #[inline]
+    pub(super) fn restore(previous: State) {
+        // SAFETY: restores the value previously read from MXCSR.
+        unsafe {
+            asm!("ldmxcsr [{}]", in(reg) &previous, options(nostack, readonly, preserves_flags));
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+    use std::arch::asm;
+
+    /// FPCR flush-to-zero (bit 24); applies to both inputs and outputs.
+    const FZ: u64 = 1 << 24;
+
+    pub(super) type State = u64;
+
+    #[inline]
+    pub(super) fn enable_flush_to_zero() -> State {
+        let previous: u64;
+        // SAFETY: reading and writing FPCR only changes the floating-point
+        // mode of the current thread.
+        unsafe {
+            asm!("mrs {}, fpcr", out(reg) previous, options(nomem, nostack, preserves_flags));
+            asm!("msr fpcr, {}", in(reg) previous | FZ, options(nomem, nostack, preserves_flags));
+        }
+        previous
+    }
+
+    #[inline]
+    pub(super) fn restore(previous: State) {
+        // SAFETY: restores the value previously read from FPCR.
+        unsafe {
+            asm!("msr fpcr, {}", in(reg) previous, options(nomem, nostack, preserves_flags));
+        }
+    }
+}
+
+#[cfg(not(any(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse"
+    ),
+    target_arch = "aarch64"
+)))]
+mod arch {
+    pub(super) type State = ();
+
+    #[inline]
+    pub(super) fn enable_flush_to_zero() -> State {}
+
+    #[inline]
+    pub(super) fn restore(_previous: State) {}
+}
+
+/// Replaces non-finite samples with silence and clamps runaway levels.
+///
+/// Call [`process`](Self::process) on every output block. It is
+/// allocation-free and only touches atomics when something was fixed.
+#[derive(Debug)]
+pub struct OutputSanitizer {
+    limit: f32,
+    reset_on_non_finite: bool,
+    stats: Arc<SanitizerStats>,
+}
+
+impl OutputSanitizer {
+    /// Sanitizer clamping to [`DEFAULT_OUTPUT_LIMIT`] that never asks for a
+    /// processor reset.
+    pub fn new() -> Self {
+        Self {
+            limit: DEFAULT_OUTPUT_LIMIT,
+            reset_on_non_finite: false,
+            stats: Arc::new(SanitizerStats::default()),
+        }
+    }
+
+    /// Clamp samples to `±limit` instead of [`DEFAULT_OUTPUT_LIMIT`].
+    pub fn with_limit(mut self, limit: f32) -> Self {
+        assert!(limit > 0.0, "output limit must be positive");
+        self.limit = limit;
+        self
+    }
+
+    /// Ask for a processor reset whenever a block contained NaN or Inf.
+    ///
+    /// Non-finite values usually come from filter or feedback state that
+    /// has blown up and will keep producing garbage until cleared.
+    pub fn with_reset_on_non_finite(mut self, reset: bool) -> Self {
+        self.reset_on_non_finite = reset;
+        self
+    }
+
+    /// Shared counters for reading from a non-audio thread.
+    pub fn stats(&self) -> Arc<SanitizerStats> {
+        Arc::clone(&self.stats)
+    }
+
+    /// Sanitize `buffer` in place.
+    ///
+    /// Returns `true` when the caller should reset its processor: the block
+    /// contained NaN or Inf and resets are enabled.
+    #[inline]
+    pub fn process(&mut self, buffer: &mut [&mut [f32]]) -> bool {
+        let mut non_finite = 0_u64;
+        let mut clamped = 0_u64;
+        for channel in buffer.iter_mut() {
+            for sample in channel.iter_mut() {
+                if !sample.is_finite() {
+                    *sample = 0.0;
+                    non_finite += 1;
+                } else if sample.abs() > self.limit {
+                    *sample = sample.clamp(-self.limit, self.limit);
+                    clamped += 1;
+                }
+            }
+        }
+
+        if clamped > 0 {
+            self.stats.clamped.fetch_add(clamped, Ordering::Relaxed);
+        }
+        if non_finite == 0 {
+            return false;
+        }
+        self.stats
+            .non_finite
+            .fetch_add(non_finite, Ordering::Relaxed);
+        if self.reset_on_non_finite {
+            self.stats.resets.fetch_add(1, Ordering::Relaxed);
+        }
+        self.reset_on_non_finite
+    }
+}
+
+impl Default for OutputSanitizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Running totals of an [`OutputSanitizer`], shared across threads.
+#[derive(Debug, Default)]
+pub struct SanitizerStats {
+    non_finite: AtomicU64,
+    clamped: AtomicU64,
+    resets: AtomicU64,
+}
+
+impl SanitizerStats {
+    /// Snapshot of the counters.
+    pub fn counts(&self) -> SanitizerCounts {
+        SanitizerCounts {
+            non_finite: self.non_finite.load(Ordering::Relaxed),
+            clamped: self.clamped.load(Ordering::Relaxed),
+            resets: self.resets.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Snapshot of [`SanitizerStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SanitizerCounts {
+    /// NaN or infinite samples replaced with silence.
+    pub non_finite: u64,
+    /// Samples clamped to the output limit.
+    pub clamped: u64,
+    /// Processor resets requested after non-finite output.
+    pub resets: u64,
+}
+
+impl SanitizerCounts {
+    /// Total number of sanitized samples.
+    pub fn total(&self) -> u64 {
+        self.non_finite + self.clamped
+    }
+
+    /// `outputSanitized` diagnostic describing these counts, or `None` if
+    /// nothing has been sanitized.
+    pub fn diagnostic(&self) -> Option<AudioDiagnostic> {
+        if self.total() == 0 {
+            return None;
+        }
+
+        let mut message = format!(
+            "Output sanitizer replaced {} NaN/Inf sample(s) and clamped {} runaway sample(s)",
+            self.non_finite, self.clamped
+        );
+        if self.resets > 0 {
+            message.push_str(&format!("; processor reset {} time(s)", self.resets));
+        }
+
+        Some(AudioDiagnostic {
+            code: AudioDiagnosticCode::OutputSanitized,
+            message,
+            hint: Some("Check filter coefficients and feedback paths for instability.".to_string()),
+            count: Some(self.total()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitizer_replaces_non_finite_and_clamps() {
+        let mut sanitizer = OutputSanitizer::new().with_limit(1.0);
+        let mut left = [0.5, f32::NAN, 2.0, -3.0];
+        let mut right = [f32::INFINITY, f32::NEG_INFINITY, -0.25, 1.0];
+        let mut buffer = [&mut left[..], &mut right[..]];
+
+        assert!(!sanitizer.process(&mut buffer));
+        assert_eq!(left, [0.5, 0.0, 1.0, -1.0]);
+        assert_eq!(right, [0.0, 0.0, -0.25, 1.0]);
+        assert_eq!(
+            sanitizer.stats().counts(),
+            SanitizerCounts {
+                non_finite: 3,
+                clamped: 2,
+                resets: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sanitizer_requests_reset_only_for_non_finite_blocks() {
+        let mut sanitizer = OutputSanitizer::new().with_reset_on_non_finite(true);
+        let mut clean = [0.1, 100.0];
+        assert!(!sanitizer.process(&mut [&mut clean[..]]));
+
+        let mut broken = [f32::NAN, 0.1];
+        assert!(sanitizer.process(&mut [&mut broken[..]]));
+        assert_eq!(sanitizer.stats().counts().resets, 1);
+    }
+
+    #[test]
+    fn test_counts_diagnostic() {
+        assert!(SanitizerCounts::default().diagnostic().is_none());
+
+        let diagnostic = SanitizerCounts {
+            non_finite: 2,
+            clamped: 3,
+            resets: 1,
+        }
+        .diagnostic()
+        .expect("sanitized samples produce a diagnostic");
+        assert_eq!(diagnostic.code, AudioDiagnosticCode::OutputSanitized);
+        assert_eq!(diagnostic.count, Some(5));
+        assert!(diagnostic.message.contains("processor reset 1 time(s)"));
+    }
+
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    #[test]
+    fn test_denormal_guard_flushes_subnormals_and_restores() {
+        let half_min = || std::hint::black_box(f32::MIN_POSITIVE) * std::hint::black_box(0.5);
+        assert!(half_min().is_subnormal());
+
+        {
+            let _guard = DenormalGuard::new();
+            assert_eq!(half_min(), 0.0);
+        }
+
+        assert!(half_min().is_subnormal());
+    }
+}
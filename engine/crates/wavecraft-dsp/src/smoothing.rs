@@ -0,0 +1,496 @@
+//! Parameter smoothing to avoid zipper noise on automation.
+//!
+//! Parameter values reach processors once per block via
+//! [`ProcessorParams::apply_plain_values`](crate::ProcessorParams::apply_plain_values).
+//! Applying those values directly produces audible steps ("zipper noise") when
+//! a parameter is automated. A [`Smoother`] owned by the processor turns the
+//! per-block target into a per-sample ramp.
+//!
+//! Smoothing is declared next to the parameter, either with
+//! `#[param(smoothing = "exp", smoothing_ms = 20)]` on a derived params struct
+//! or by overriding [`ProcessorParams::param_smoothing`](crate::ProcessorParams::param_smoothing)
+//! by hand. Processors then build matching smoothers with [`Smoother::for_param`]:
+//!
+//! ```rust
+//! use wavecraft_dsp::{
+//!     ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams, Smoother,
+//!     SmoothingStyle, Transport,
+//! };
+//!
+//! #[derive(Default)]
+//! struct LevelParams {
+//!     level: f32,
+//! }
+//!
+//! impl ProcessorParams for LevelParams {
+//!     fn param_specs() -> &'static [ParamSpec] {
+//!         static SPECS: [ParamSpec; 1] = [ParamSpec {
+//!             name: "Level",
+//!             id_suffix: "level",
+//!             range: ParamRange::Linear { min: 0.0, max: 1.0 },
+//!             default: 1.0,
+//!             unit: "",
+//!             group: None,
+//!         }];
+//!         &SPECS
+//!     }
+//!
+//!     fn param_smoothing() -> &'static [ParamSmoothing] {
+//!         &[ParamSmoothing {
+//!             id_suffix: "level",
+//!             style: SmoothingStyle::Linear(20.0),
+//!         }]
+//!     }
+//! }
+//!
+//! struct Level {
+//!     level: Smoother,
+//! }
+//!
+//! impl Default for Level {
+//!     fn default() -> Self {
+//!         Self {
+//!             level: Smoother::for_param::<LevelParams>("level"),
+//!         }
+//!     }
+//! }
+//!
+//! impl Processor for Level {
+//!     type Params = LevelParams;
+//!
+//!     fn process(&mut self, buffer: &mut [&mut [f32]], _: &Transport, params: &LevelParams) {
+//!         self.level.set_target(params.level);
+//!         let num_samples = buffer.first().map_or(0, |channel| channel.len());
+//!         for index in 0..num_samples {
+//!             let gain = self.level.next();
+//!             for channel in buffer.iter_mut() {
+//!                 channel[index] *= gain;
+//!             }
+//!         }
+//!     }
+//!
+//!     fn set_sample_rate(&mut self, sample_rate: f32) {
+//!         self.level.set_sample_rate(sample_rate);
+//!     }
+//!
+//!     fn reset(&mut self) {
+//!         self.level.reset();
+//!     }
+//! }
+//! ```
+//!
+//! Because the smoother lives inside the processor and only depends on the
+//! sample rate and the per-block targets, it behaves identically in the
+//! nih-plug wrapper and in the dev FFI audio path.
+
+use crate::traits::ProcessorParams;
+
+/// Sample rate assumed until [`Smoother::set_sample_rate`] is called.
+const DEFAULT_SAMPLE_RATE: f32 = 44_100.0;
+
+/// Residual distance to the target after an exponential ramp completes.
+const EXPONENTIAL_RESIDUAL: f32 = 1.0e-4;
+
+/// Smoothing curve and ramp duration for a parameter.
+///
+/// The `f32` payload is the ramp time in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SmoothingStyle {
+    /// No smoothing: the target value is applied immediately.
+    #[default]
+    None,
+
+    /// Constant-rate ramp that reaches the target after the given time.
+    Linear(f32),
+
+    /// One-pole style approach that is within 0.01% of the target after the
+    /// given time. Fast at first, then easing in.
+    Exponential(f32),
+
+    /// Constant ratio per sample (linear in the log domain). Well suited to
+    /// frequencies and linear gain. Falls back to a linear ramp when the
+    /// start and target values are not both strictly positive.
+    Logarithmic(f32),
+}
+
+impl SmoothingStyle {
+    /// Returns the ramp time in milliseconds (`0.0` for [`SmoothingStyle::None`]).
+    pub fn time_ms(&self) -> f32 {
+        match *self {
+            Self::None => 0.0,
+            Self::Linear(ms) | Self::Exponential(ms) | Self::Logarithmic(ms) => ms,
+        }
+    }
+}
+
+/// Smoothing declaration for a single parameter.
+///
+/// Returned by [`ProcessorParams::param_smoothing`](crate::ProcessorParams::param_smoothing)
+/// and matched against [`ParamSpec::id_suffix`](crate::ParamSpec::id_suffix).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamSmoothing {
+    /// ID suffix of the smoothed parameter (e.g., "level").
+    pub id_suffix: &'static str,
+
+    /// Smoothing curve and ramp time.
+    pub style: SmoothingStyle,
+}
+
+/// Per-sample parameter smoother.
+///
+/// Call [`Smoother::set_target`] once per block with the current parameter
+/// value, then [`Smoother::next`] once per sample. All methods are real-time
+/// safe.
+///
+/// The first target after construction or [`Smoother::reset`] is applied
+/// immediately so processors do not ramp from stale values when playback
+/// starts.
+#[derive(Debug, Clone)]
+pub struct Smoother {
+    style: SmoothingStyle,
+    sample_rate: f32,
+    current: f32,
+    target: f32,
+    /// Per-sample increment (linear) or multiplier (exponential/logarithmic).
+    step: f32,
+    steps_left: u32,
+    /// Whether the current ramp for a logarithmic style runs in the log domain.
+    multiplicative: bool,
+    primed: bool,
+}
+
+impl Default for Smoother {
+    fn default() -> Self {
+        Self::new(SmoothingStyle::None)
+    }
+}
+
+impl Smoother {
+    /// Create a smoother with the given style.
+    pub fn new(style: SmoothingStyle) -> Self {
+        Self {
+            style,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            current: 0.0,
+            target: 0.0,
+            step: 0.0,
+            steps_left: 0,
+            multiplicative: false,
+            primed: false,
+        }
+    }
+
+    /// Create a smoother configured from `P`'s smoothing declaration for the
+    /// parameter with the given ID suffix.
+    ///
+    /// [`Smoother::current`] reports the parameter's declared default until
+    /// the first [`Smoother::set_target`], which snaps straight to the target
+    /// without ramping from that default. Parameters without a smoothing
+    /// declaration get [`SmoothingStyle::None`].
+    pub fn for_param<P: ProcessorParams>(id_suffix: &str) -> Self {
+        let style = P::param_smoothing()
+            .iter()
+            .find(|smoothing| smoothing.id_suffix == id_suffix)
+            .map_or(SmoothingStyle::None, |smoothing| smoothing.style);
+        let default = P::param_specs()
+            .iter()
+            .find(|spec| spec.id_suffix == id_suffix)
+            .map_or(0.0, |spec| spec.default as f32);
+
+        let mut smoother = Self::new(style);
+        smoother.current = default;
+        smoother.target = default;
+        smoother
+    }
+
+    /// Returns the configured smoothing style.
+    pub fn style(&self) -> SmoothingStyle {
+        self.style
+    }
+
+    /// Update the sample rate used to convert ramp times into sample counts.
+    ///
+    /// Any ramp in progress is completed immediately.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate > 0.0 {
+            self.sample_rate = sample_rate;
+        }
+        self.current = self.target;
+        self.steps_left = 0;
+    }
+
+    /// Forget the ramp state; the next [`Smoother::set_target`] snaps.
+    pub fn reset(&mut self) {
+        self.current = self.target;
+        self.steps_left = 0;
+        self.primed = false;
+    }
+
+    /// Jump straight to `value` without ramping.
+    pub fn set_immediate(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+        self.steps_left = 0;
+        self.primed = true;
+    }
+
+    /// Set a new target value, starting a ramp from the current value.
+    ///
+    /// Setting the same target again does not restart the ramp, so this can be
+    /// called once per block with the latest parameter value.
+    pub fn set_target(&mut self, target: f32) {
+        if !self.primed {
+            self.set_immediate(target);
+            return;
+        }
+
+        if target == self.target {
+            return;
+        }
+
+        self.target = target;
+        let steps = (self.style.time_ms() * 0.001 * self.sample_rate).round() as u32;
+        if steps == 0 || self.current == target {
+            self.current = target;
+            self.steps_left = 0;
+            return;
+        }
+
+        self.steps_left = steps;
+        self.multiplicative = false;
+        let inverse_steps = 1.0 / steps as f32;
+        self.step = match self.style {
+            SmoothingStyle::None => 0.0,
+            SmoothingStyle::Linear(_) => (target - self.current) * inverse_steps,
+            SmoothingStyle::Exponential(_) => EXPONENTIAL_RESIDUAL.powf(inverse_steps),
+            SmoothingStyle::Logarithmic(_) => {
+                if self.current > 0.0 && target > 0.0 {
+                    self.multiplicative = true;
+                    (target / self.current).powf(inverse_steps)
+                } else {
+                    (target - self.current) * inverse_steps
+                }
+            }
+        };
+    }
+
+    /// Returns the current target value.
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Returns the most recently produced value without advancing.
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    /// Returns `true` while a ramp is in progress.
+    pub fn is_smoothing(&self) -> bool {
+        self.steps_left > 0
+    }
+
+    /// Advance by one sample and return the smoothed value.
+    #[inline]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> f32 {
+        if self.steps_left == 0 {
+            return self.target;
+        }
+
+        self.steps_left -= 1;
+        if self.steps_left == 0 {
+            self.current = self.target;
+            return self.current;
+        }
+
+        self.current = match self.style {
+            SmoothingStyle::None => self.target,
+            SmoothingStyle::Linear(_) => self.current + self.step,
+            SmoothingStyle::Exponential(_) => {
+                self.target + (self.current - self.target) * self.step
+            }
+            SmoothingStyle::Logarithmic(_) if self.multiplicative => self.current * self.step,
+            SmoothingStyle::Logarithmic(_) => self.current + self.step,
+        };
+        self.current
+    }
+
+    /// Fill `output` with consecutive smoothed values.
+    pub fn next_block(&mut self, output: &mut [f32]) {
+        if self.steps_left == 0 {
+            output.fill(self.target);
+            return;
+        }
+
+        for value in output.iter_mut() {
+            *value = self.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{ParamRange, ParamSpec};
+
+    fn primed(style: SmoothingStyle, sample_rate: f32, start: f32) -> Smoother {
+        let mut smoother = Smoother::new(style);
+        smoother.set_sample_rate(sample_rate);
+        smoother.set_target(start);
+        smoother
+    }
+
+    #[test]
+    fn test_first_target_snaps() {
+        let mut smoother = Smoother::new(SmoothingStyle::Linear(10.0));
+        smoother.set_target(0.75);
+
+        assert!(!smoother.is_smoothing());
+        assert_eq!(smoother.next(), 0.75);
+    }
+
+    #[test]
+    fn test_none_applies_target_immediately() {
+        let mut smoother = primed(SmoothingStyle::None, 1000.0, 0.0);
+        smoother.set_target(1.0);
+
+        assert_eq!(smoother.next(), 1.0);
+    }
+
+    #[test]
+    fn test_linear_ramp_reaches_target_after_ramp_time() {
+        // 10 ms at 1 kHz = 10 steps
+        let mut smoother = primed(SmoothingStyle::Linear(10.0), 1000.0, 0.0);
+        smoother.set_target(1.0);
+
+        let first = smoother.next();
+        assert!((first - 0.1).abs() < 1e-6);
+
+        for _ in 0..8 {
+            smoother.next();
+        }
+        assert!(smoother.is_smoothing());
+        assert_eq!(smoother.next(), 1.0);
+        assert!(!smoother.is_smoothing());
+    }
+
+    #[test]
+    fn test_exponential_ramp_is_front_loaded_and_converges() {
+        let mut smoother = primed(SmoothingStyle::Exponential(10.0), 1000.0, 0.0);
+        smoother.set_target(1.0);
+
+        let first = smoother.next();
+        assert!(
+            first > 0.1,
+            "exponential ramp should move faster than linear"
+        );
+
+        let mut previous = first;
+        for _ in 0..8 {
+            let value = smoother.next();
+            assert!(value > previous && value < 1.0);
+            previous = value;
+        }
+        assert_eq!(smoother.next(), 1.0);
+    }
+
+    #[test]
+    fn test_logarithmic_ramp_uses_constant_ratio() {
+        let mut smoother = primed(SmoothingStyle::Logarithmic(2.0), 1000.0, 100.0);
+        smoother.set_target(10_000.0);
+
+        // 2 steps from 100 to 10k: 1k halfway
+        assert!((smoother.next() - 1000.0).abs() < 1e-2);
+        assert_eq!(smoother.next(), 10_000.0);
+    }
+
+    #[test]
+    fn test_logarithmic_falls_back_to_linear_through_zero() {
+        let mut smoother = primed(SmoothingStyle::Logarithmic(2.0), 1000.0, 0.0);
+        smoother.set_target(1.0);
+
+        assert!((smoother.next() - 0.5).abs() < 1e-6);
+        assert_eq!(smoother.next(), 1.0);
+    }
+
+    #[test]
+    fn test_repeated_target_does_not_restart_ramp() {
+        let mut smoother = primed(SmoothingStyle::Linear(4.0), 1000.0, 0.0);
+        smoother.set_target(1.0);
+        smoother.next();
+        smoother.set_target(1.0);
+
+        assert!((smoother.next() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reset_makes_next_target_snap() {
+        let mut smoother = primed(SmoothingStyle::Linear(10.0), 1000.0, 0.0);
+        smoother.set_target(1.0);
+        smoother.next();
+        smoother.reset();
+        smoother.set_target(0.25);
+
+        assert_eq!(smoother.next(), 0.25);
+    }
+
+    #[test]
+    fn test_next_block_fills_ramp_then_target() {
+        let mut smoother = primed(SmoothingStyle::Linear(2.0), 1000.0, 0.0);
+        smoother.set_target(1.0);
+        let mut output = [0.0; 4];
+        smoother.next_block(&mut output);
+
+        assert_eq!(output, [0.5, 1.0, 1.0, 1.0]);
+    }
+
+    #[derive(Default)]
+    struct SmoothedParams;
+
+    impl ProcessorParams for SmoothedParams {
+        fn param_specs() -> &'static [ParamSpec] {
+            static SPECS: [ParamSpec; 2] = [
+                ParamSpec {
+                    name: "Level",
+                    id_suffix: "level",
+                    range: ParamRange::Linear { min: 0.0, max: 1.0 },
+                    default: 0.5,
+                    unit: "",
+                    group: None,
+                },
+                ParamSpec {
+                    name: "Mode",
+                    id_suffix: "mode",
+                    range: ParamRange::Stepped { min: 0, max: 1 },
+                    default: 1.0,
+                    unit: "",
+                    group: None,
+                },
+            ];
+            &SPECS
+        }
+
+        fn param_smoothing() -> &'static [ParamSmoothing] {
+            &[ParamSmoothing {
+                id_suffix: "level",
+                style: SmoothingStyle::Exponential(20.0),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_for_param_uses_declared_style_and_default() {
+        let smoother = Smoother::for_param::<SmoothedParams>("level");
+
+        assert_eq!(smoother.style(), SmoothingStyle::Exponential(20.0));
+        assert_eq!(smoother.current(), 0.5);
+    }
+
+    #[test]
+    fn test_for_param_without_declaration_is_unsmoothed() {
+        let smoother = Smoother::for_param::<SmoothedParams>("mode");
+
+        assert_eq!(smoother.style(), SmoothingStyle::None);
+        assert_eq!(smoother.current(), 1.0);
+    }
+}
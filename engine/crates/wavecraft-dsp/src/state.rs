@@ -0,0 +1,235 @@
+//! Non-parameter processor state saved with the plugin.
+//!
+//! Parameters are persisted by the host automatically. Anything else a
+//! processor needs to restore (a loaded impulse response path, a learned EQ
+//! curve, UI-only settings) goes into a [`PersistentState`] cell that the
+//! processor exposes through
+//! [`Processor::persistent_fields`](crate::Processor::persistent_fields).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Type-erased view of a [`PersistentState`] used by the plugin's state
+/// save/restore.
+///
+/// Both methods lock the cell and allocate, so they are only called off the
+/// audio thread.
+pub trait PersistentField: Send + Sync {
+    /// Serializes the current value, or `None` if it can't be serialized.
+    fn serialize(&self) -> Option<String>;
+
+    /// Replaces the value with one deserialized from `data`.
+    ///
+    /// Returns `false` (keeping the current value) if `data` doesn't decode.
+    fn deserialize(&self, data: &str) -> bool;
+}
+
+struct Shared<T> {
+    value: Mutex<T>,
+    /// Bumped on every change so handles can tell when to pick it up.
+    version: AtomicU64,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, T> {
+        self.value.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn replace(&self, value: T) {
+        let previous = std::mem::replace(&mut *self.lock(), value);
+        self.version.fetch_add(1, Ordering::Release);
+        // Dropped here, after the lock is released.
+        drop(previous);
+    }
+}
+
+impl<T> PersistentField for Shared<T>
+where
+    T: Serialize + DeserializeOwned + Send,
+{
+    fn serialize(&self) -> Option<String> {
+        serde_json::to_string(&*self.lock()).ok()
+    }
+
+    fn deserialize(&self, data: &str) -> bool {
+        match serde_json::from_str(data) {
+            Ok(value) => {
+                self.replace(value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// A value stored in the plugin state alongside the parameters.
+///
+/// Clones share the same value. Restoring a saved session decodes the value
+/// on the host's main thread and swaps it in there, so the audio thread only
+/// ever reads it through the non-blocking [`try_update`](Self::try_update)
+/// and [`try_modify`](Self::try_modify).
+///
+/// ```rust
+/// use wavecraft_dsp::PersistentState;
+///
+/// let mut curve = PersistentState::new(vec![0.0_f32; 4]);
+/// let handle = curve.clone();
+///
+/// // Off the audio thread (e.g. after loading a session):
+/// handle.set(vec![1.0; 4]);
+///
+/// // On the audio thread:
+/// let mut gains = [0.0_f32; 4];
+/// curve.try_update(|curve| gains.copy_from_slice(curve));
+/// assert_eq!(gains, [1.0; 4]);
+/// ```
+pub struct PersistentState<T> {
+    shared: Arc<Shared<T>>,
+    /// Version this handle last observed.
+    seen: u64,
+}
+
+impl<T> PersistentState<T> {
+    /// Creates a cell holding `value`.
+    ///
+    /// A fresh cell counts as changed, so the first
+    /// [`try_update`](Self::try_update) sees the initial value.
+    pub fn new(value: T) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                value: Mutex::new(value),
+                version: AtomicU64::new(1),
+            }),
+            seen: 0,
+        }
+    }
+
+    /// Replaces the value. Blocks on the lock, so don't call this from the
+    /// audio thread.
+    pub fn set(&self, value: T) {
+        self.shared.replace(value);
+    }
+
+    /// Returns a copy of the current value. Blocks on the lock.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.shared.lock().clone()
+    }
+
+    /// Calls `f` with the value if it changed since this handle last saw it.
+    ///
+    /// Never blocks: if another thread holds the lock, returns `None` and
+    /// the change is picked up on a later call. Real-time safe as long as
+    /// `f` is.
+    pub fn try_update<R>(&mut self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let version = self.shared.version.load(Ordering::Acquire);
+        if version == self.seen {
+            return None;
+        }
+
+        let value = self.shared.value.try_lock().ok()?;
+        self.seen = version;
+        Some(f(&value))
+    }
+
+    /// Modifies the value in place if the lock is free, e.g. to store a
+    /// curve learned on the audio thread. Returns `false` if it was busy.
+    ///
+    /// The change is saved with the plugin but not reported back to this
+    /// handle's [`try_update`](Self::try_update).
+    pub fn try_modify(&mut self, f: impl FnOnce(&mut T)) -> bool {
+        let Ok(mut value) = self.shared.value.try_lock() else {
+            return false;
+        };
+        f(&mut value);
+        let version = self.shared.version.fetch_add(1, Ordering::Release) + 1;
+        if self.seen == version - 1 {
+            self.seen = version;
+        }
+        true
+    }
+
+    /// Returns the type-erased field registered with the plugin state.
+    pub fn field(&self) -> Arc<dyn PersistentField>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+    {
+        self.shared.clone()
+    }
+}
+
+impl<T> Clone for PersistentState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            seen: self.seen,
+        }
+    }
+}
+
+impl<T: Default> Default for PersistentState<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> std::fmt::Debug for PersistentState<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistentState")
+            .field("version", &self.shared.version.load(Ordering::Relaxed))
+            .field("seen", &self.seen)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_update_sees_each_change_once() {
+        let mut state = PersistentState::new(1_u32);
+        assert_eq!(state.try_update(|value| *value), Some(1));
+        assert_eq!(state.try_update(|value| *value), None);
+
+        state.clone().set(2);
+        assert_eq!(state.try_update(|value| *value), Some(2));
+        assert_eq!(state.try_update(|value| *value), None);
+    }
+
+    #[test]
+    fn try_update_skips_while_locked() {
+        let mut state = PersistentState::new(1_u32);
+        let shared = Arc::clone(&state.shared);
+
+        let guard = shared.lock();
+        assert_eq!(state.try_update(|value| *value), None);
+        drop(guard);
+
+        // The change is still pending after the lock is released.
+        assert_eq!(state.try_update(|value| *value), Some(1));
+    }
+
+    #[test]
+    fn field_round_trips_through_serialization() {
+        let mut source = PersistentState::new(vec![0.5_f32, 0.25]);
+        assert!(source.try_modify(|curve| curve.push(0.125)));
+        let saved = source.field().serialize().expect("serializable");
+
+        let mut restored = PersistentState::<Vec<f32>>::default();
+        restored.try_update(|_| ());
+        assert!(restored.field().deserialize(&saved));
+        assert_eq!(
+            restored.try_update(|curve| curve.clone()),
+            Some(vec![0.5, 0.25, 0.125])
+        );
+
+        assert!(!restored.field().deserialize("not json"));
+        assert_eq!(restored.get(), [0.5, 0.25, 0.125]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Tail length reporting and silence tracking.
+//!
+//! Processors describe how long they ring after their input stops with
+//! [`TailLength`]. Plugin wrappers feed each block's silence state into a
+//! [`TailTracker`] to find out when the output has died away, so the host can
+//! suspend processing without cutting reverb or delay tails off.
+
+/// Peak level below which a block counts as silent (about -120 dBFS).
+pub const SILENCE_THRESHOLD: f32 = 1.0e-6;
+
+/// Returns `true` when every sample in `buffer` is below [`SILENCE_THRESHOLD`].
+///
+/// Stops at the first audible sample, so busy blocks are cheap to check.
+#[inline]
+pub fn is_silent(buffer: &[&mut [f32]]) -> bool {
+    buffer.iter().all(|channel| {
+        channel
+            .iter()
+            .all(|sample| sample.abs() < SILENCE_THRESHOLD)
+    })
+}
+
+/// Length of the output a processor produces after its input goes silent.
+///
+/// See [`Processor::tail_samples`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TailLength {
+    /// Output decays to silence within this many samples.
+    Samples(u32),
+
+    /// Output may never decay (self-oscillating filters, freeze, generators).
+    Infinite,
+}
+
+impl TailLength {
+    /// No tail: output is silent as soon as the input is.
+    pub const NONE: Self = Self::Samples(0);
+
+    /// Tail of two processors in series.
+    ///
+    /// The second stage rings on after the first stage's tail ends, so finite
+    /// lengths add up (saturating).
+    pub fn then(self, next: Self) -> Self {
+        match (self, next) {
+            (Self::Samples(a), Self::Samples(b)) => Self::Samples(a.saturating_add(b)),
+            _ => Self::Infinite,
+        }
+    }
+
+    /// Tail of two processors running side by side: the longer of the two.
+    pub fn longest(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Samples(a), Self::Samples(b)) => Self::Samples(a.max(b)),
+            _ => Self::Infinite,
+        }
+    }
+
+    /// Finite tail length in samples, or `None` for [`TailLength::Infinite`].
+    pub fn samples(self) -> Option<u32> {
+        match self {
+            Self::Samples(samples) => Some(samples),
+            Self::Infinite => None,
+        }
+    }
+}
+
+impl Default for TailLength {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Where a processor is in its tail, as reported by [`TailTracker::advance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TailState {
+    /// The input is audible. `tail` is the full tail the output will ring for
+    /// once it goes silent.
+    Active { tail: u32 },
+
+    /// The input is silent and the output is still ringing for `remaining`
+    /// more samples.
+    Ringing { remaining: u32 },
+
+    /// The input has been silent for longer than the tail; output is silent.
+    Finished,
+
+    /// The processor has an infinite tail and must keep running.
+    Infinite,
+}
+
+/// Counts how long the input has been silent and compares it to the tail.
+///
+/// Holds no buffers, so [`TailTracker::advance`] is real-time safe.
+#[derive(Debug, Clone, Default)]
+pub struct TailTracker {
+    silent_samples: u64,
+}
+
+impl TailTracker {
+    /// Create a tracker that treats the input as audible.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget any silence counted so far.
+    pub fn reset(&mut self) {
+        self.silent_samples = 0;
+    }
+
+    /// Samples of consecutive silent input seen so far.
+    pub fn silent_samples(&self) -> u64 {
+        self.silent_samples
+    }
+
+    /// Record one block of `block_len` samples and return the tail state
+    /// after it.
+    pub fn advance(&mut self, input_silent: bool, block_len: usize, tail: TailLength) -> TailState {
+        if !input_silent {
+            self.silent_samples = 0;
+        } else {
+            self.silent_samples = self.silent_samples.saturating_add(block_len as u64);
+        }
+
+        let TailLength::Samples(tail) = tail else {
+            return TailState::Infinite;
+        };
+
+        if !input_silent {
+            return TailState::Active { tail };
+        }
+
+        match u64::from(tail).checked_sub(self.silent_samples) {
+            Some(remaining) if remaining > 0 => TailState::Ringing {
+                remaining: remaining as u32,
+            },
+            _ => TailState::Finished,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_length_combines_serial_and_parallel_tails() {
+        assert_eq!(
+            TailLength::Samples(10).then(TailLength::Samples(5)),
+            TailLength::Samples(15)
+        );
+        assert_eq!(
+            TailLength::Samples(u32::MAX).then(TailLength::Samples(1)),
+            TailLength::Samples(u32::MAX)
+        );
+        assert_eq!(
+            TailLength::NONE.then(TailLength::Infinite),
+            TailLength::Infinite
+        );
+        assert_eq!(TailLength::Infinite.samples(), None);
+        assert_eq!(
+            TailLength::Samples(10).longest(TailLength::Samples(5)),
+            TailLength::Samples(10)
+        );
+        assert_eq!(
+            TailLength::Samples(10).longest(TailLength::Infinite),
+            TailLength::Infinite
+        );
+    }
+
+    #[test]
+    fn test_is_silent_threshold() {
+        let mut quiet = [0.0, 1.0e-7, -1.0e-7];
+        let mut loud = [0.0, 0.0, 1.0e-3];
+        assert!(is_silent(&[&mut quiet[..]]));
+        assert!(!is_silent(&[&mut quiet[..], &mut loud[..]]));
+        assert!(is_silent(&[]));
+    }
+
+    #[test]
+    fn test_tracker_counts_down_finite_tail() {
+        let mut tracker = TailTracker::new();
+        let tail = TailLength::Samples(100);
+
+        assert_eq!(
+            tracker.advance(false, 64, tail),
+            TailState::Active { tail: 100 }
+        );
+        assert_eq!(
+            tracker.advance(true, 64, tail),
+            TailState::Ringing { remaining: 36 }
+        );
+        assert_eq!(tracker.advance(true, 64, tail), TailState::Finished);
+
+        // Audible input restarts the countdown.
+        assert_eq!(
+            tracker.advance(false, 64, tail),
+            TailState::Active { tail: 100 }
+        );
+        assert_eq!(tracker.silent_samples(), 0);
+    }
+
+    #[test]
+    fn test_tracker_without_tail_finishes_on_first_silent_block() {
+        let mut tracker = TailTracker::new();
+        assert_eq!(
+            tracker.advance(true, 32, TailLength::NONE),
+            TailState::Finished
+        );
+    }
+
+    #[test]
+    fn test_tracker_infinite_tail_never_finishes() {
+        let mut tracker = TailTracker::new();
+        for _ in 0..4 {
+            assert_eq!(
+                tracker.advance(true, 1024, TailLength::Infinite),
+                TailState::Infinite
+            );
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Named metering taps placed inside the signal chain.
+//!
+//! The output meters of a plugin only see the end of the chain. Tap
+//! processors can sit anywhere in a `SignalChain!` and publish what they
+//! measure under a stable ID, so a UI can show input and output meters side
+//! by side or derive a gain-reduction view. The plugin collects the taps
+//! through [`Processor::metering_taps`](crate::Processor::metering_taps)
+//! when it is created, and the IPC layer looks them up by [`TapId::ID`].
+
+use wavecraft_protocol::{MeterFrame, OscilloscopeFrame, SpectrumFrame};
+
+/// Compile-time ID of a metering tap.
+///
+/// Tap processors are built with `Default` inside a signal chain, so the ID
+/// is carried by a marker type. Declare one with [`tap_id!`](crate::tap_id).
+pub trait TapId: Send + Sync + 'static {
+    /// ID the UI uses to select this tap's stream.
+    const ID: &'static str;
+}
+
+/// Read side of a metering tap, shared between the tap processor and the
+/// editor.
+///
+/// Reads drain the tap's lock-free channel and may lock, so they are only
+/// called off the audio thread. A tap implements the methods for the data
+/// it produces; the others return `None`.
+pub trait MeteringTap: Send + Sync {
+    /// The tap's ID (see [`TapId`]).
+    fn id(&self) -> &str;
+
+    /// Latest meter frame published since the previous call, if any.
+    fn meter_frame(&self) -> Option<MeterFrame> {
+        None
+    }
+
+    /// Latest oscilloscope frame published since the previous call, if any.
+    fn oscilloscope_frame(&self) -> Option<OscilloscopeFrame> {
+        None
+    }
+
+    /// Latest spectrum frame published since the previous call, if any.
+    fn spectrum_frame(&self) -> Option<SpectrumFrame> {
+        None
+    }
+}
+
+/// Declares a [`TapId`] marker type.
+///
+/// ```rust
+/// use wavecraft_dsp::{TapId, tap_id};
+///
+/// tap_id!(pub Input = "input");
+///
+/// assert_eq!(Input::ID, "input");
+/// ```
+#[macro_export]
+macro_rules! tap_id {
+    ($(#[$meta:meta])* $vis:vis $name:ident = $id:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, Copy)]
+        $vis struct $name;
+
+        impl $crate::TapId for $name {
+            const ID: &'static str = $id;
+        }
+    };
+}
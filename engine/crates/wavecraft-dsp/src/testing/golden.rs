@@ -0,0 +1,134 @@
+//! Golden-file comparisons.
+
+use std::path::Path;
+
+/// Set this environment variable to `1` to rewrite golden files with the
+/// current output instead of comparing against them.
+pub const GOLDEN_UPDATE_ENV: &str = "WAVECRAFT_UPDATE_GOLDEN";
+
+/// Compare rendered `output` against the golden file at `path`.
+///
+/// Golden files are JSON arrays of channels. Run the test once with
+/// [`GOLDEN_UPDATE_ENV`] set to record the reference, commit it alongside
+/// the test, and set the variable again to rewrite it after an intentional
+/// change. Otherwise panics if the file is missing, if the channel layout or
+/// length differs, or if any sample is further than `tolerance` from the
+/// reference.
+///
+/// Relative paths resolve against the working directory, which for
+/// `cargo test` is the crate root, so
+/// `concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/...")` is the most
+/// robust way to name a file.
+pub fn assert_golden(path: impl AsRef<Path>, output: &[Vec<f32>], tolerance: f32) {
+    let update = std::env::var(GOLDEN_UPDATE_ENV).is_ok_and(|value| value == "1");
+    check_golden(path.as_ref(), output, tolerance, update);
+}
+
+/// [`assert_golden`] with the update switch passed in, so tests don't
+/// depend on the environment.
+fn check_golden(path: &Path, output: &[Vec<f32>], tolerance: f32, update: bool) {
+    if let Some((channel, index)) = output.iter().enumerate().find_map(|(channel, samples)| {
+        samples
+            .iter()
+            .position(|sample| !sample.is_finite())
+            .map(|index| (channel, index))
+    }) {
+        panic!("output is not finite at channel {channel}, sample {index}");
+    }
+
+    if update {
+        write_golden(path, output);
+        return;
+    }
+    assert!(
+        path.exists(),
+        "golden file {} is missing; run with {GOLDEN_UPDATE_ENV}=1 to record it",
+        path.display()
+    );
+
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|error| panic!("failed to read golden file {}: {error}", path.display()));
+    let expected: Vec<Vec<f32>> = serde_json::from_str(&contents)
+        .unwrap_or_else(|error| panic!("invalid golden file {}: {error}", path.display()));
+
+    assert_eq!(
+        expected.len(),
+        output.len(),
+        "channel count differs from golden file {} (set {GOLDEN_UPDATE_ENV}=1 to update)",
+        path.display()
+    );
+    for (channel, (expected, actual)) in expected.iter().zip(output).enumerate() {
+        assert_eq!(
+            expected.len(),
+            actual.len(),
+            "length of channel {channel} differs from golden file {} (set {GOLDEN_UPDATE_ENV}=1 to update)",
+            path.display()
+        );
+        if let Some((index, (e, a))) = expected
+            .iter()
+            .zip(actual)
+            .enumerate()
+            .find(|(_, (e, a))| (*e - *a).abs() > tolerance)
+        {
+            panic!(
+                "output differs from golden file {} at channel {channel}, sample {index}: \
+                 {a} vs {e} (tolerance {tolerance}; set {GOLDEN_UPDATE_ENV}=1 to update)",
+                path.display()
+            );
+        }
+    }
+}
+
+fn write_golden(path: &Path, output: &[Vec<f32>]) {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .unwrap_or_else(|error| panic!("failed to create {}: {error}", parent.display()));
+    }
+    let json = serde_json::to_string(output).expect("finite samples serialize");
+    std::fs::write(path, json)
+        .unwrap_or_else(|error| panic!("failed to write golden file {}: {error}", path.display()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wavecraft-golden-{}", std::process::id()));
+        dir.join(name)
+    }
+
+    #[test]
+    #[should_panic(expected = "WAVECRAFT_UPDATE_GOLDEN=1 to record it")]
+    fn test_missing_golden_panics() {
+        let path = golden_path("missing.json");
+        assert_golden(&path, &[vec![0.5, -0.25, 0.125]], 0.0);
+    }
+
+    #[test]
+    fn test_golden_is_recorded_on_update_then_compared() {
+        let path = golden_path("recorded.json");
+        let output = vec![vec![0.5, -0.25, 0.125]];
+
+        check_golden(&path, &output, 0.0, true);
+        assert!(path.exists());
+        check_golden(&path, &[vec![0.5, -0.25, 0.126]], 0.01, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "channel 0, sample 2")]
+    fn test_golden_mismatch_panics() {
+        let path = golden_path("mismatch.json");
+        check_golden(&path, &[vec![0.5, -0.25, 0.125]], 0.0, true);
+        check_golden(&path, &[vec![0.5, -0.25, 0.2]], 0.01, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "not finite")]
+    fn test_golden_rejects_nan() {
+        assert_golden(golden_path("nan.json"), &[vec![f32::NAN]], 0.0);
+    }
+}
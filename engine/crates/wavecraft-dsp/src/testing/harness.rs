@@ -0,0 +1,454 @@
+//! Block-wise rendering, parameter automation and sweeps.
+
+use crate::traits::{Processor, ProcessorParams, Transport};
+
+/// Block sizes worth checking for block-size dependent bugs: single
+/// samples, a size that doesn't divide common buffer lengths, and typical
+/// host buffers.
+pub const BLOCK_SIZES: &[usize] = &[1, 7, 64, 512, 4096];
+
+/// Common host sample rates.
+pub const SAMPLE_RATES: &[f32] = &[44_100.0, 48_000.0, 88_200.0, 96_000.0, 192_000.0];
+
+/// Renders signals through a processor the way a host would: block by
+/// block, with an advancing, playing [`Transport`].
+///
+/// The processor's sample rate is set before rendering; its state is not
+/// reset, so renders can be chained.
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    sample_rate: f32,
+    block_size: usize,
+    channels: usize,
+}
+
+impl Renderer {
+    /// Renderer at `sample_rate` with 512-sample blocks and two channels.
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            block_size: 512,
+            channels: 2,
+        }
+    }
+
+    /// Set the number of samples per `process` call.
+    pub fn block_size(mut self, block_size: usize) -> Self {
+        assert!(block_size > 0, "block_size must be non-zero");
+        self.block_size = block_size;
+        self
+    }
+
+    /// Set how many channels [`render`](Self::render) copies its input to.
+    pub fn channels(mut self, channels: usize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Render a mono `input`, copied to every channel, with fixed `params`.
+    pub fn render<P: Processor>(
+        &self,
+        processor: &mut P,
+        params: &P::Params,
+        input: &[f32],
+    ) -> Vec<Vec<f32>> {
+        let input = vec![input.to_vec(); self.channels];
+        self.render_channels(processor, params, &input)
+    }
+
+    /// Render multichannel `input` with fixed `params`.
+    pub fn render_channels<P: Processor>(
+        &self,
+        processor: &mut P,
+        params: &P::Params,
+        input: &[Vec<f32>],
+    ) -> Vec<Vec<f32>> {
+        let mut output = input.to_vec();
+        processor.set_sample_rate(self.sample_rate);
+
+        let len = frames(&output);
+        for start in (0..len).step_by(self.block_size) {
+            let end = (start + self.block_size).min(len);
+            process_block(processor, params, &mut output, start, end);
+        }
+        output
+    }
+
+    /// Render a mono `input`, copied to every channel, while `automation`
+    /// changes parameters.
+    ///
+    /// Parameters start at their [`ParamSpec`](crate::ParamSpec) defaults and
+    /// are applied with [`ProcessorParams::apply_plain_values`]. Blocks are
+    /// split where a step or ramp begins or ends, so steps land on the exact
+    /// sample; ramp values are updated once per block.
+    pub fn render_automated<P: Processor>(
+        &self,
+        processor: &mut P,
+        automation: &Automation,
+        input: &[f32],
+    ) -> Vec<Vec<f32>> {
+        let specs = P::Params::param_specs();
+        let lanes: Vec<(usize, &Lane)> = automation
+            .lanes
+            .iter()
+            .map(|lane| {
+                let index = specs
+                    .iter()
+                    .position(|spec| spec.id_suffix == lane.id)
+                    .unwrap_or_else(|| {
+                        let known: Vec<_> = specs.iter().map(|spec| spec.id_suffix).collect();
+                        panic!("unknown parameter '{}'; expected one of {known:?}", lane.id)
+                    });
+                (index, lane)
+            })
+            .collect();
+
+        let defaults: Vec<f32> = specs.iter().map(|spec| spec.default as f32).collect();
+        let mut values = defaults.clone();
+        let mut params = P::Params::from_param_defaults();
+        let mut output = vec![input.to_vec(); self.channels];
+        processor.set_sample_rate(self.sample_rate);
+
+        let len = input.len();
+        let mut start = 0;
+        while start < len {
+            let end = lanes
+                .iter()
+                .filter_map(|(_, lane)| lane.next_boundary_after(start))
+                .fold((start + self.block_size).min(len), usize::min);
+
+            for &(index, lane) in &lanes {
+                values[index] = lane.value_at(start, defaults[index]);
+            }
+            params.apply_plain_values(&values);
+
+            process_block(processor, &params, &mut output, start, end);
+            start = end;
+        }
+        output
+    }
+}
+
+fn frames(channels: &[Vec<f32>]) -> usize {
+    channels.first().map_or(0, Vec::len)
+}
+
+fn process_block<P: Processor>(
+    processor: &mut P,
+    params: &P::Params,
+    output: &mut [Vec<f32>],
+    start: usize,
+    end: usize,
+) {
+    let transport = Transport {
+        pos_samples: start as i64,
+        playing: true,
+        offline: true,
+        ..Transport::default()
+    };
+    let mut block: Vec<&mut [f32]> = output
+        .iter_mut()
+        .map(|channel| &mut channel[start..end])
+        .collect();
+    processor.process(&mut block, &transport, params);
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    start: usize,
+    end: usize,
+    target: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Lane {
+    id: String,
+    segments: Vec<Segment>,
+}
+
+impl Lane {
+    fn value_at(&self, sample: usize, default: f32) -> f32 {
+        let mut value = default;
+        for segment in &self.segments {
+            if sample >= segment.end {
+                value = segment.target;
+            } else if sample >= segment.start {
+                let position =
+                    (sample - segment.start) as f32 / (segment.end - segment.start) as f32;
+                return value + (segment.target - value) * position;
+            } else {
+                break;
+            }
+        }
+        value
+    }
+
+    fn next_boundary_after(&self, sample: usize) -> Option<usize> {
+        self.segments
+            .iter()
+            .flat_map(|segment| [segment.start, segment.end])
+            .filter(|&boundary| boundary > sample)
+            .min()
+    }
+}
+
+/// A script of parameter changes for [`Renderer::render_automated`].
+///
+/// Parameters are addressed by [`ParamSpec::id_suffix`](crate::ParamSpec)
+/// and take plain values. Times are in samples from the start of the render.
+///
+/// ```
+/// use wavecraft_dsp::testing::Automation;
+///
+/// let automation = Automation::new()
+///     .set("level", 0, 0.5)
+///     .ramp("level", 4_800, 9_600, 1.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Automation {
+    lanes: Vec<Lane>,
+}
+
+impl Automation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Jump to `value` at sample `at`.
+    pub fn set(self, id: &str, at: usize, value: f32) -> Self {
+        self.push(
+            id,
+            Segment {
+                start: at,
+                end: at,
+                target: value,
+            },
+        )
+    }
+
+    /// Move linearly from the current value to `target` between samples
+    /// `start` and `end`.
+    pub fn ramp(self, id: &str, start: usize, end: usize, target: f32) -> Self {
+        assert!(start <= end, "ramp must end after it starts");
+        self.push(id, Segment { start, end, target })
+    }
+
+    fn push(mut self, id: &str, segment: Segment) -> Self {
+        let lane = match self.lanes.iter_mut().position(|lane| lane.id == id) {
+            Some(index) => &mut self.lanes[index],
+            None => {
+                self.lanes.push(Lane {
+                    id: id.to_string(),
+                    segments: Vec::new(),
+                });
+                self.lanes.last_mut().expect("lane was just pushed")
+            }
+        };
+
+        if let Some(last) = lane.segments.last() {
+            assert!(
+                segment.start >= last.end,
+                "automation for '{id}' must be added in time order without overlaps"
+            );
+        }
+        lane.segments.push(segment);
+        self
+    }
+}
+
+/// Assert that `processor` produces the same output for every block size.
+///
+/// Renders `input` once per entry in `block_sizes` with a fresh processor
+/// from `make` and panics if any sample differs from the first render by
+/// more than `tolerance`. Block-size dependence usually means state that is
+/// reset or sampled per block instead of per sample.
+pub fn assert_block_size_invariant<P, F>(
+    make: F,
+    params: &P::Params,
+    sample_rate: f32,
+    input: &[f32],
+    block_sizes: &[usize],
+    tolerance: f32,
+) where
+    P: Processor,
+    F: Fn() -> P,
+{
+    let Some((&reference_size, rest)) = block_sizes.split_first() else {
+        return;
+    };
+    let render = |block_size| {
+        Renderer::new(sample_rate)
+            .block_size(block_size)
+            .render(&mut make(), params, input)
+    };
+
+    let reference = render(reference_size);
+    for &block_size in rest {
+        let output = render(block_size);
+        for (channel, (expected, actual)) in reference.iter().zip(&output).enumerate() {
+            if let Some((index, (e, a))) = expected
+                .iter()
+                .zip(actual)
+                .enumerate()
+                .find(|(_, (e, a))| (*e - *a).is_nan() || (*e - *a).abs() > tolerance)
+            {
+                panic!(
+                    "block size {block_size} differs from block size {reference_size} \
+                     at channel {channel}, sample {index}: {a} vs {e}"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{ParamRange, ParamSpec};
+
+    #[derive(Default)]
+    struct LevelParams {
+        level: f32,
+    }
+
+    impl ProcessorParams for LevelParams {
+        fn param_specs() -> &'static [ParamSpec] {
+            static SPECS: [ParamSpec; 1] = [ParamSpec {
+                name: "Level",
+                id_suffix: "level",
+                range: ParamRange::Linear { min: 0.0, max: 2.0 },
+                default: 1.0,
+                unit: "",
+                group: None,
+            }];
+            &SPECS
+        }
+
+        fn from_param_defaults() -> Self {
+            Self { level: 1.0 }
+        }
+
+        fn apply_plain_values(&mut self, values: &[f32]) {
+            if let Some(level) = values.first() {
+                self.level = *level;
+            }
+        }
+    }
+
+    /// Scales by the level and records where each block started.
+    #[derive(Default)]
+    struct Level {
+        block_starts: Vec<i64>,
+        sample_rate: f32,
+    }
+
+    impl Processor for Level {
+        type Params = LevelParams;
+
+        fn process(
+            &mut self,
+            buffer: &mut [&mut [f32]],
+            transport: &Transport,
+            params: &LevelParams,
+        ) {
+            self.block_starts.push(transport.pos_samples);
+            for channel in buffer.iter_mut() {
+                channel
+                    .iter_mut()
+                    .for_each(|sample| *sample *= params.level);
+            }
+        }
+
+        fn set_sample_rate(&mut self, sample_rate: f32) {
+            self.sample_rate = sample_rate;
+        }
+    }
+
+    /// Resets its running sum every block, so output depends on block size.
+    #[derive(Default)]
+    struct BlockSum;
+
+    impl Processor for BlockSum {
+        type Params = ();
+
+        fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+            for channel in buffer.iter_mut() {
+                let mut sum = 0.0;
+                for sample in channel.iter_mut() {
+                    sum += *sample;
+                    *sample = sum;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_processes_in_blocks() {
+        let mut processor = Level::default();
+        let output = Renderer::new(44_100.0).block_size(4).channels(3).render(
+            &mut processor,
+            &LevelParams { level: 0.5 },
+            &[1.0; 10],
+        );
+
+        assert_eq!(output, vec![vec![0.5; 10]; 3]);
+        assert_eq!(processor.block_starts, [0, 4, 8]);
+        assert_eq!(processor.sample_rate, 44_100.0);
+    }
+
+    #[test]
+    fn test_automation_steps_are_sample_accurate() {
+        let automation = Automation::new()
+            .set("level", 3, 0.0)
+            .ramp("level", 4, 8, 2.0);
+        let mut processor = Level::default();
+        let output = Renderer::new(48_000.0)
+            .block_size(2)
+            .channels(1)
+            .render_automated(&mut processor, &automation, &[1.0; 10]);
+
+        // Blocks are split at the step (3) and at the ramp's start (4) and
+        // end (8); the ramp advances once per block.
+        assert_eq!(processor.block_starts, [0, 2, 3, 4, 6, 8]);
+        assert_eq!(
+            output[0],
+            [1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown parameter 'gain'")]
+    fn test_automation_rejects_unknown_parameters() {
+        let automation = Automation::new().set("gain", 0, 1.0);
+        Renderer::new(48_000.0).render_automated(&mut Level::default(), &automation, &[0.0; 4]);
+    }
+
+    #[test]
+    fn test_block_size_invariant_accepts_stateless_processor() {
+        assert_block_size_invariant(
+            Level::default,
+            &LevelParams { level: 0.5 },
+            48_000.0,
+            &[0.25; 100],
+            BLOCK_SIZES,
+            0.0,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "block size 7 differs from block size 1")]
+    fn test_block_size_invariant_catches_per_block_state() {
+        assert_block_size_invariant(
+            BlockSum::default,
+            &(),
+            48_000.0,
+            &[0.25; 100],
+            BLOCK_SIZES,
+            0.0,
+        );
+    }
+}
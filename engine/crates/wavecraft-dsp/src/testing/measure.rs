@@ -0,0 +1,190 @@
+//! Measurements on rendered signals.
+//!
+//! Functions take plain sample slices so they work on any render. Skip the
+//! start of a render when a processor needs time to settle (filters, level
+//! smoothing) before measuring steady-state behaviour.
+
+use std::f64::consts::{PI, TAU};
+
+/// Largest absolute sample value.
+pub fn peak(signal: &[f32]) -> f32 {
+    signal
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()))
+}
+
+/// Root mean square level (`0.0` for an empty signal).
+pub fn rms(signal: &[f32]) -> f32 {
+    if signal.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = signal.iter().map(|&s| f64::from(s) * f64::from(s)).sum();
+    (sum / signal.len() as f64).sqrt() as f32
+}
+
+/// Convert a linear gain to decibels.
+pub fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.max(f32::MIN_POSITIVE).log10()
+}
+
+/// Largest absolute sample-by-sample difference between two signals.
+///
+/// Panics if the lengths differ.
+pub fn max_difference(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "signals must have the same length");
+    a.iter()
+        .zip(b)
+        .fold(0.0_f32, |max, (a, b)| max.max((a - b).abs()))
+}
+
+/// Gain and phase of a processor at one frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Response {
+    /// Output level relative to input, in dB.
+    pub gain_db: f32,
+    /// Output phase relative to input in radians, wrapped to `(-π, π]`.
+    /// Includes any latency.
+    pub phase: f32,
+}
+
+/// Response of a processor at `frequency` from a sine `input` and the
+/// processor's `output`.
+///
+/// Both signals are analysed over the largest whole number of periods that
+/// fits, so pass the settled part of a render of
+/// [`signals::sine`](super::signals::sine).
+pub fn frequency_response(
+    input: &[f32],
+    output: &[f32],
+    frequency: f32,
+    sample_rate: f32,
+) -> Response {
+    let (input_magnitude, input_phase) = tone(input, frequency, sample_rate);
+    let (output_magnitude, output_phase) = tone(output, frequency, sample_rate);
+
+    let mut phase = output_phase - input_phase;
+    while phase > PI {
+        phase -= TAU;
+    }
+    while phase <= -PI {
+        phase += TAU;
+    }
+
+    Response {
+        gain_db: gain_to_db((output_magnitude / input_magnitude) as f32),
+        phase: phase as f32,
+    }
+}
+
+/// Total harmonic distortion of `signal` with fundamental `frequency`.
+///
+/// Returns the ratio of the combined level of harmonics 2 to `harmonics`
+/// (those below Nyquist) to the fundamental; multiply by 100 for percent.
+pub fn thd(signal: &[f32], frequency: f32, sample_rate: f32, harmonics: usize) -> f32 {
+    let (fundamental, _) = tone(signal, frequency, sample_rate);
+    let distortion: f64 = (2..=harmonics)
+        .map(|harmonic| frequency * harmonic as f32)
+        .take_while(|&harmonic| harmonic < sample_rate / 2.0)
+        .map(|harmonic| tone(signal, harmonic, sample_rate).0.powi(2))
+        .sum();
+    (distortion.sqrt() / fundamental) as f32
+}
+
+/// Latency in samples between `input` and `output`, up to `max_latency`.
+///
+/// Finds the delay with the strongest cross-correlation, so it works for
+/// impulses and broadband signals such as [`signals::noise`](super::signals::noise)
+/// even through filters. Returns `None` if the output is silent.
+pub fn detect_latency(input: &[f32], output: &[f32], max_latency: usize) -> Option<usize> {
+    let mut best = None;
+    let mut best_correlation = 0.0;
+    for lag in 0..=max_latency.min(output.len()) {
+        let correlation: f64 = input
+            .iter()
+            .zip(&output[lag..])
+            .map(|(&x, &y)| f64::from(x) * f64::from(y))
+            .sum::<f64>()
+            .abs();
+        if correlation > best_correlation {
+            best_correlation = correlation;
+            best = Some(lag);
+        }
+    }
+    best
+}
+
+/// Magnitude and phase (relative to a sine) of the `frequency` component of
+/// `signal`, from a single-bin DFT over whole periods.
+fn tone(signal: &[f32], frequency: f32, sample_rate: f32) -> (f64, f64) {
+    let period = f64::from(sample_rate) / f64::from(frequency);
+    let periods = (signal.len() as f64 / period).floor();
+    let len = if periods >= 1.0 {
+        (periods * period).round() as usize
+    } else {
+        signal.len()
+    };
+
+    let step = TAU / period;
+    let (mut re, mut im) = (0.0, 0.0);
+    for (n, &sample) in signal[..len].iter().enumerate() {
+        let angle = step * n as f64;
+        re += f64::from(sample) * angle.sin();
+        im += f64::from(sample) * angle.cos();
+    }
+
+    let scale = 2.0 / len.max(1) as f64;
+    ((re * scale).hypot(im * scale), im.atan2(re))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::signals;
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    #[test]
+    fn test_levels() {
+        let sine = signals::sine(1_000.0, SAMPLE_RATE, 4_800);
+        assert!((peak(&sine) - 1.0).abs() < 1e-6);
+        assert!((rms(&sine) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-4);
+        assert_eq!(rms(&[]), 0.0);
+        assert!((gain_to_db(0.5) + 6.0206).abs() < 1e-3);
+        assert_eq!(max_difference(&[1.0, 0.5], &[0.75, 0.5]), 0.25);
+    }
+
+    #[test]
+    fn test_frequency_response_of_gain_and_delay() {
+        let input = signals::sine(1_000.0, SAMPLE_RATE, 4_800);
+        // Half gain, delayed by 12 samples (a quarter period).
+        let mut output = vec![0.0; 12];
+        output.extend(input[..input.len() - 12].iter().map(|s| s * 0.5));
+
+        let response = frequency_response(&input[48..], &output[48..], 1_000.0, SAMPLE_RATE);
+        assert!((response.gain_db + 6.0206).abs() < 0.01, "{response:?}");
+        assert!(
+            (response.phase + std::f32::consts::FRAC_PI_2).abs() < 0.01,
+            "{response:?}"
+        );
+    }
+
+    #[test]
+    fn test_thd_of_clipped_sine() {
+        let clean = signals::sine(1_000.0, SAMPLE_RATE, 4_800);
+        assert!(thd(&clean, 1_000.0, SAMPLE_RATE, 10) < 1e-4);
+
+        let clipped: Vec<f32> = clean.iter().map(|s| s.clamp(-0.5, 0.5)).collect();
+        let distortion = thd(&clipped, 1_000.0, SAMPLE_RATE, 10);
+        assert!(distortion > 0.1, "thd {distortion}");
+    }
+
+    #[test]
+    fn test_detect_latency() {
+        let input = signals::noise(1_024, 1);
+        let mut output = vec![0.0; 37];
+        output.extend_from_slice(&input[..input.len() - 37]);
+
+        assert_eq!(detect_latency(&input, &output, 100), Some(37));
+        assert_eq!(detect_latency(&input, &[0.0; 1_024], 100), None);
+    }
+}
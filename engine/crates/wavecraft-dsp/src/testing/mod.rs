@@ -0,0 +1,44 @@
+//! Helpers for testing [`Processor`](crate::Processor) implementations.
+//!
+//! These run processors outside a host with heap-allocated buffers, so they
+//! are meant for tests only:
+//!
+//! - [`signals`]: deterministic test signals (sine, sweep, impulse, noise)
+//! - [`Renderer`]: block-wise rendering with parameter [`Automation`], plus
+//!   block-size and sample-rate sweeps
+//! - [`measure`]: peak/RMS, frequency and phase response, THD and latency
+//! - [`assert_golden`]: comparison against stored reference output
+//! - [`assert_tail_ends`]: checks a processor's declared tail length
+//!
+//! ```
+//! use wavecraft_dsp::testing::{Renderer, measure, signals};
+//! use wavecraft_dsp::{Processor, Transport};
+//!
+//! #[derive(Default)]
+//! struct Halve;
+//!
+//! impl Processor for Halve {
+//!     type Params = ();
+//!     fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+//!         for channel in buffer.iter_mut() {
+//!             channel.iter_mut().for_each(|sample| *sample *= 0.5);
+//!         }
+//!     }
+//! }
+//!
+//! let input = signals::sine(1_000.0, 48_000.0, 4_800);
+//! let output = Renderer::new(48_000.0).render(&mut Halve, &(), &input);
+//!
+//! let response = measure::frequency_response(&input, &output[0], 1_000.0, 48_000.0);
+//! assert!((response.gain_db + 6.02).abs() < 0.01);
+//! ```
+
+mod golden;
+mod harness;
+pub mod measure;
+pub mod signals;
+mod tail;
+
+pub use golden::{GOLDEN_UPDATE_ENV, assert_golden};
+pub use harness::{Automation, BLOCK_SIZES, Renderer, SAMPLE_RATES, assert_block_size_invariant};
+pub use tail::{assert_tail_ends, measure_tail};
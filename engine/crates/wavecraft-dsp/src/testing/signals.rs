@@ -0,0 +1,109 @@
+//! Deterministic mono test signals.
+//!
+//! Signals are full scale; multiply by a gain for other levels.
+
+use std::f64::consts::TAU;
+
+/// Sine at `frequency` Hz, starting at phase zero.
+pub fn sine(frequency: f32, sample_rate: f32, len: usize) -> Vec<f32> {
+    let step = TAU * f64::from(frequency) / f64::from(sample_rate);
+    (0..len).map(|n| (step * n as f64).sin() as f32).collect()
+}
+
+/// Exponential sine sweep from `start` to `end` Hz over `len` samples.
+///
+/// Spends equal time per octave, which suits frequency response and
+/// distortion measurements across the audio band.
+pub fn sweep(start: f32, end: f32, sample_rate: f32, len: usize) -> Vec<f32> {
+    assert!(
+        start > 0.0 && end > 0.0,
+        "sweep frequencies must be positive"
+    );
+
+    let (start, end) = (f64::from(start), f64::from(end));
+    let duration = len as f64 / f64::from(sample_rate);
+    let rate = (end / start).ln();
+    (0..len)
+        .map(|n| {
+            let t = n as f64 / f64::from(sample_rate);
+            let phase = if rate.abs() < f64::EPSILON {
+                TAU * start * t
+            } else {
+                TAU * start * duration / rate * ((t / duration * rate).exp() - 1.0)
+            };
+            phase.sin() as f32
+        })
+        .collect()
+}
+
+/// Unit impulse: `1.0` followed by `len - 1` zeros.
+pub fn impulse(len: usize) -> Vec<f32> {
+    let mut signal = vec![0.0; len];
+    if let Some(first) = signal.first_mut() {
+        *first = 1.0;
+    }
+    signal
+}
+
+/// Uniform white noise in `[-1, 1)`.
+///
+/// The same `seed` always produces the same samples, so renders of noise
+/// can be compared against golden files.
+pub fn noise(len: usize, seed: u64) -> Vec<f32> {
+    // xorshift64*; the state must never be zero.
+    let mut state = seed ^ 0x9E37_79B9_7F4A_7C15;
+    if state == 0 {
+        state = 1;
+    }
+
+    (0..len)
+        .map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            let bits = state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 40;
+            (bits as f32 / (1_u32 << 23) as f32) - 1.0
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sine_period_and_level() {
+        let signal = sine(1_000.0, 48_000.0, 96);
+        assert_eq!(signal[0], 0.0);
+        assert!((signal[12] - 1.0).abs() < 1e-6);
+        assert!((signal[48] - signal[0]).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_sweep_starts_at_start_frequency() {
+        let sample_rate = 48_000.0;
+        let sweep = sweep(100.0, 10_000.0, sample_rate, 48_000);
+        let sine = sine(100.0, sample_rate, 48);
+        // The instantaneous frequency is still ~100 Hz in the first millisecond.
+        for (a, b) in sweep.iter().zip(&sine) {
+            assert!((a - b).abs() < 0.01);
+        }
+        assert!(sweep.iter().all(|sample| sample.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_impulse() {
+        assert_eq!(impulse(3), [1.0, 0.0, 0.0]);
+        assert!(impulse(0).is_empty());
+    }
+
+    #[test]
+    fn test_noise_is_deterministic_and_in_range() {
+        let a = noise(1_000, 7);
+        assert_eq!(a, noise(1_000, 7));
+        assert_ne!(a, noise(1_000, 8));
+        assert!(a.iter().all(|sample| (-1.0..1.0).contains(sample)));
+        let mean = a.iter().sum::<f32>() / a.len() as f32;
+        assert!(mean.abs() < 0.1);
+    }
+}
@@ -0,0 +1,193 @@
+//! Tail length measurement.
+
+use crate::tail::{SILENCE_THRESHOLD, TailLength};
+use crate::traits::{Processor, Transport};
+
+/// Feed `max_samples` of silence through `processor` and measure its tail.
+///
+/// Returns how many samples passed until the output was last audible (`0` if
+/// it was silent straight away), or `None` if it was still audible in the
+/// final block. Excite the processor (e.g. with an impulse) before calling.
+pub fn measure_tail<P: Processor>(
+    processor: &mut P,
+    params: &P::Params,
+    channels: usize,
+    block_size: usize,
+    max_samples: usize,
+) -> Option<usize> {
+    assert!(block_size > 0, "block_size must be non-zero");
+
+    let transport = Transport::default();
+    let mut storage = vec![vec![0.0_f32; block_size]; channels];
+    let mut rendered = 0;
+    let mut tail_end = 0;
+    let mut last_block_start = 0;
+
+    while rendered < max_samples {
+        let len = block_size.min(max_samples - rendered);
+        for channel in &mut storage {
+            channel[..len].fill(0.0);
+        }
+
+        let mut buffer: Vec<&mut [f32]> = storage
+            .iter_mut()
+            .map(|channel| &mut channel[..len])
+            .collect();
+        processor.process(&mut buffer, &transport, params);
+
+        let last_audible = buffer
+            .iter()
+            .filter_map(|channel| {
+                channel
+                    .iter()
+                    .rposition(|sample| sample.is_nan() || sample.abs() >= SILENCE_THRESHOLD)
+            })
+            .max();
+        if let Some(idx) = last_audible {
+            tail_end = rendered + idx + 1;
+        }
+
+        last_block_start = rendered;
+        rendered += len;
+    }
+
+    (tail_end <= last_block_start).then_some(tail_end)
+}
+
+/// Assert that `processor`'s output dies away within its declared
+/// [`Processor::tail_samples`].
+///
+/// Renders silence for the declared tail plus a few blocks of margin and
+/// panics if the output is still audible after the declared length (NaN
+/// counts as audible). Panics for processors declaring
+/// [`TailLength::Infinite`]. Returns the measured tail length.
+///
+/// ```
+/// use wavecraft_dsp::testing::assert_tail_ends;
+/// use wavecraft_dsp::{Processor, Transport};
+///
+/// #[derive(Default)]
+/// struct Passthrough;
+///
+/// impl Processor for Passthrough {
+///     type Params = ();
+///     fn process(&mut self, _buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {}
+/// }
+///
+/// let mut processor = Passthrough;
+/// let mut impulse = [1.0_f32, 0.0, 0.0, 0.0];
+/// processor.process(&mut [&mut impulse[..]], &Transport::default(), &());
+///
+/// assert_eq!(assert_tail_ends(&mut processor, &(), 1, 64), 0);
+/// ```
+pub fn assert_tail_ends<P: Processor>(
+    processor: &mut P,
+    params: &P::Params,
+    channels: usize,
+    block_size: usize,
+) -> usize {
+    let TailLength::Samples(declared) = processor.tail_samples() else {
+        panic!("processor declares an infinite tail, which never ends");
+    };
+
+    let declared = declared as usize;
+    let window = declared + block_size.saturating_mul(4);
+    let measured = measure_tail(processor, params, channels, block_size, window);
+
+    match measured {
+        Some(measured) if measured <= declared => measured,
+        Some(measured) => {
+            panic!("tail rang for {measured} samples but tail_samples() declares {declared}")
+        }
+        None => panic!(
+            "output still audible after {window} samples but tail_samples() declares {declared}"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Single-tap feedback-free delay: echoes the input `delay` samples later.
+    struct EchoDsp {
+        line: Vec<f32>,
+        pos: usize,
+        declared: TailLength,
+    }
+
+    impl EchoDsp {
+        fn new(delay: usize, declared: TailLength) -> Self {
+            Self {
+                line: vec![0.0; delay],
+                pos: 0,
+                declared,
+            }
+        }
+    }
+
+    impl Processor for EchoDsp {
+        type Params = ();
+
+        fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+            if let Some(channel) = buffer.first_mut() {
+                for sample in channel.iter_mut() {
+                    std::mem::swap(&mut self.line[self.pos], sample);
+                    self.pos = (self.pos + 1) % self.line.len();
+                }
+            }
+        }
+
+        fn tail_samples(&self) -> TailLength {
+            self.declared
+        }
+    }
+
+    fn excite(processor: &mut EchoDsp) {
+        let mut impulse = [0.0_f32; 4];
+        impulse[3] = 1.0;
+        processor.process(&mut [&mut impulse[..]], &Transport::default(), &());
+    }
+
+    #[test]
+    fn test_measure_tail_finds_last_audible_sample() {
+        let mut echo = EchoDsp::new(10, TailLength::Samples(10));
+        excite(&mut echo);
+
+        // Impulse entered at index 3 of a 4-sample block, so the echo lands
+        // 10 - 1 = 9 samples into the silence.
+        assert_eq!(measure_tail(&mut echo, &(), 1, 4, 64), Some(10));
+    }
+
+    #[test]
+    fn test_measure_tail_reports_unfinished_tail() {
+        let mut echo = EchoDsp::new(10, TailLength::Samples(10));
+        excite(&mut echo);
+
+        assert_eq!(measure_tail(&mut echo, &(), 1, 4, 12), None);
+    }
+
+    #[test]
+    fn test_assert_tail_ends_accepts_declared_tail() {
+        let mut echo = EchoDsp::new(10, TailLength::Samples(10));
+        excite(&mut echo);
+
+        assert_eq!(assert_tail_ends(&mut echo, &(), 1, 4), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "tail_samples() declares 2")]
+    fn test_assert_tail_ends_rejects_understated_tail() {
+        let mut echo = EchoDsp::new(10, TailLength::Samples(2));
+        excite(&mut echo);
+
+        assert_tail_ends(&mut echo, &(), 1, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "infinite tail")]
+    fn test_assert_tail_ends_rejects_infinite_tail() {
+        let mut echo = EchoDsp::new(10, TailLength::Infinite);
+        assert_tail_ends(&mut echo, &(), 1, 4);
+    }
+}
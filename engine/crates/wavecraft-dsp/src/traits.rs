@@ -3,10 +3,32 @@
 //! This module defines the primary extension points for users building plugins
 //! with Wavecraft. The `Processor` trait is the main interface for custom DSP code.
 
+use std::sync::Arc;
+
+use crate::events::NoteEvent;
+use crate::layout::ChannelLayout;
+use crate::smoothing::ParamSmoothing;
+use crate::state::PersistentField;
+use crate::tail::TailLength;
+use crate::taps::MeteringTap;
+
 /// Transport information for timing-aware DSP.
 ///
-/// Provides context about playback state, tempo, and position.
-#[derive(Debug, Clone, Copy, Default)]
+/// Provides context about playback state, tempo, and position. Fields the host
+/// does not report are left as `None` (or `false`/`0` for the plain fields).
+///
+/// The struct is `#[non_exhaustive]` so hosts can report more fields later
+/// without breaking callers. Outside this crate, build one from
+/// [`Transport::default()`] with the `with_*` methods:
+///
+/// ```rust
+/// use wavecraft_dsp::Transport;
+///
+/// let transport = Transport::default().with_tempo(120.0).with_playing(true);
+/// assert_eq!(transport.samples_per_beat(48_000.0), Some(24_000.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
 pub struct Transport {
     /// Current tempo in BPM (beats per minute).
     pub tempo: Option<f64>,
@@ -16,6 +38,118 @@ pub struct Transport {
 
     /// True if the host is playing.
     pub playing: bool,
+
+    /// True if the host is recording.
+    pub recording: bool,
+
+    /// True if the host is rendering offline (faster or slower than real time).
+    pub offline: bool,
+
+    /// Time signature numerator (e.g. the `6` in 6/8).
+    pub time_sig_numerator: Option<u32>,
+
+    /// Time signature denominator (e.g. the `8` in 6/8).
+    pub time_sig_denominator: Option<u32>,
+
+    /// Current playback position in quarter notes (PPQ position).
+    pub pos_beats: Option<f64>,
+
+    /// Position of the start of the current bar in quarter notes.
+    pub bar_start_pos_beats: Option<f64>,
+
+    /// Zero-based index of the current bar.
+    pub bar_number: Option<i32>,
+
+    /// Active loop range as `(start, end)` in samples, if looping is enabled.
+    pub loop_range_samples: Option<(i64, i64)>,
+
+    /// Active loop range as `(start, end)` in quarter notes, if looping is enabled.
+    pub loop_range_beats: Option<(f64, f64)>,
+}
+
+impl Transport {
+    /// Sets the tempo in BPM.
+    pub const fn with_tempo(mut self, tempo: f64) -> Self {
+        self.tempo = Some(tempo);
+        self
+    }
+
+    /// Sets the playback position in samples.
+    pub const fn with_pos_samples(mut self, pos_samples: i64) -> Self {
+        self.pos_samples = pos_samples;
+        self
+    }
+
+    /// Sets whether the host is playing.
+    pub const fn with_playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+
+    /// Sets whether the host is recording.
+    pub const fn with_recording(mut self, recording: bool) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Sets whether the host is rendering offline.
+    pub const fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Sets the time signature, e.g. `(6, 8)` for 6/8.
+    pub const fn with_time_signature(mut self, numerator: u32, denominator: u32) -> Self {
+        self.time_sig_numerator = Some(numerator);
+        self.time_sig_denominator = Some(denominator);
+        self
+    }
+
+    /// Sets the playback position in quarter notes.
+    pub const fn with_pos_beats(mut self, pos_beats: f64) -> Self {
+        self.pos_beats = Some(pos_beats);
+        self
+    }
+
+    /// Sets the current bar's start position (in quarter notes) and index.
+    pub const fn with_bar(mut self, bar_start_pos_beats: f64, bar_number: i32) -> Self {
+        self.bar_start_pos_beats = Some(bar_start_pos_beats);
+        self.bar_number = Some(bar_number);
+        self
+    }
+
+    /// Sets the active loop range in samples.
+    pub const fn with_loop_range_samples(mut self, start: i64, end: i64) -> Self {
+        self.loop_range_samples = Some((start, end));
+        self
+    }
+
+    /// Sets the active loop range in quarter notes.
+    pub const fn with_loop_range_beats(mut self, start: f64, end: f64) -> Self {
+        self.loop_range_beats = Some((start, end));
+        self
+    }
+
+    /// Returns the length of one quarter note in samples at `sample_rate`.
+    ///
+    /// Returns `None` when the host does not report a (positive) tempo.
+    pub fn samples_per_beat(&self, sample_rate: f32) -> Option<f64> {
+        self.tempo
+            .filter(|tempo| *tempo > 0.0)
+            .map(|tempo| f64::from(sample_rate) * 60.0 / tempo)
+    }
+
+    /// Returns the length of one bar in quarter notes.
+    ///
+    /// Returns `None` when the host does not report a time signature.
+    pub fn beats_per_bar(&self) -> Option<f64> {
+        match (self.time_sig_numerator, self.time_sig_denominator) {
+            (Some(numerator), Some(denominator)) if denominator > 0 => {
+                Some(f64::from(numerator) * 4.0 / f64::from(denominator))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Trait for defining processor parameters.
@@ -54,6 +188,19 @@ pub trait ProcessorParams: Default + Send + Sync + 'static {
     /// from external automation/UI state. Implementations may ignore unknown or
     /// missing values.
     fn apply_plain_values(&mut self, _values: &[f32]) {}
+
+    /// Returns smoothing declarations for parameters that should be smoothed.
+    ///
+    /// Entries are matched to [`ParamSpec::id_suffix`]; parameters without an
+    /// entry are not smoothed. Processors use this via
+    /// [`Smoother::for_param`](crate::Smoother::for_param) to build smoothers that
+    /// match the declaration (e.g. `#[param(smoothing = "exp", smoothing_ms = 20)]`).
+    ///
+    /// # Default
+    /// No parameters are smoothed by default.
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[]
+    }
 }
 
 /// Specification for a single processor parameter.
@@ -179,6 +326,52 @@ pub trait Processor: Send + 'static {
     /// - No panics (use `debug_assert!` only)
     fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params);
 
+    /// Process a buffer of audio samples together with note/MIDI events.
+    ///
+    /// `events` is sorted by [`NoteEvent::timing`], the sample offset of each
+    /// event within `buffer`. Instruments override this to render voices
+    /// sample-accurately; their `process` implementation typically forwards to
+    /// this method with an empty event slice.
+    ///
+    /// # Real-Time Safety
+    /// Same requirements as [`Processor::process`].
+    ///
+    /// # Default
+    /// Ignores the events and calls [`Processor::process`].
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process(buffer, transport, params);
+    }
+
+    /// Process a buffer of audio samples with access to the sidechain input.
+    ///
+    /// `sidechain` holds the auxiliary input channels for this block, each
+    /// the same length as the `buffer` channels. It is read-only and empty
+    /// unless the plugin declares a `sidechain` bus and the host feeds it.
+    /// Ducking compressors and keyed gates override this to drive their
+    /// detector from `sidechain` while processing `buffer`.
+    ///
+    /// # Real-Time Safety
+    /// Same requirements as [`Processor::process`].
+    ///
+    /// # Default
+    /// Ignores the sidechain and calls [`Processor::process_events`].
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _sidechain: &[&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.process_events(buffer, events, transport, params);
+    }
+
     /// Called when the sample rate changes.
     ///
     /// Use this to update internal state that depends on sample rate
@@ -191,6 +384,40 @@ pub trait Processor: Send + 'static {
     /// No-op by default. Override if your processor needs sample rate.
     fn set_sample_rate(&mut self, _sample_rate: f32) {}
 
+    /// Called when the plugin is initialized with the main-bus layout the
+    /// host selected, before [`Processor::set_sample_rate`].
+    ///
+    /// Buffers passed to `process` then have `layout.outputs` channels. For
+    /// effects with a mono input and more outputs (mono-to-stereo), the input
+    /// is copied to every output channel before processing. Allocate
+    /// per-channel state here rather than on the audio thread.
+    ///
+    /// # Default
+    /// No-op by default. Override if your processor depends on the layout.
+    fn set_channel_layout(&mut self, _layout: ChannelLayout) {}
+
+    /// Registers the [`PersistentState`](crate::PersistentState) cells this
+    /// processor saves with the plugin, in addition to its parameters.
+    ///
+    /// Called once when the plugin instance is created. Push
+    /// [`PersistentState::field`](crate::PersistentState::field) for every
+    /// cell, always in the same order: fields are matched to saved state by
+    /// position in the signal chain.
+    ///
+    /// # Default
+    /// Registers nothing.
+    fn persistent_fields(&self, _fields: &mut Vec<Arc<dyn PersistentField>>) {}
+
+    /// Registers the named [`MeteringTap`]s placed in this processor.
+    ///
+    /// Called once when the plugin instance is created; the editor serves
+    /// each tap's frames to the UI by its ID. Tap processors push
+    /// themselves, and combinators forward to the processors they wrap.
+    ///
+    /// # Default
+    /// Registers nothing.
+    fn metering_taps(&self, _taps: &mut Vec<Arc<dyn MeteringTap>>) {}
+
     /// Reset processor state.
     ///
     /// Called when the host stops playback or when the user resets the plugin.
@@ -199,4 +426,60 @@ pub trait Processor: Send + 'static {
     /// # Default
     /// No-op by default. Override if your processor maintains state.
     fn reset(&mut self) {}
+
+    /// Processing latency introduced by this processor, in samples.
+    ///
+    /// Lookahead, FFT and oversampling processors delay their output; reporting
+    /// it here lets the host compensate so the plugin stays aligned with other
+    /// tracks. The value may depend on the sample rate and is queried after
+    /// [`Processor::set_sample_rate`] and once per block, so changes are
+    /// reported to the host.
+    ///
+    /// # Default
+    /// `0` (no latency).
+    fn latency_samples(&self) -> u32 {
+        0
+    }
+
+    /// How long the processor keeps producing output after its input goes
+    /// silent.
+    ///
+    /// Reverbs and delays ring out after the input stops. Hosts use this to
+    /// decide when a plugin can be put to sleep without cutting the tail off.
+    /// Like [`Processor::latency_samples`], this is queried after
+    /// [`Processor::set_sample_rate`] and once per block.
+    ///
+    /// # Default
+    /// [`TailLength::NONE`] (output stops with the input).
+    fn tail_samples(&self) -> TailLength {
+        TailLength::NONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_per_beat_uses_tempo_and_sample_rate() {
+        let transport = Transport::default().with_tempo(120.0);
+
+        assert_eq!(transport.samples_per_beat(48_000.0), Some(24_000.0));
+    }
+
+    #[test]
+    fn test_samples_per_beat_requires_positive_tempo() {
+        assert_eq!(Transport::default().samples_per_beat(48_000.0), None);
+
+        let transport = Transport::default().with_tempo(0.0);
+        assert_eq!(transport.samples_per_beat(48_000.0), None);
+    }
+
+    #[test]
+    fn test_beats_per_bar_from_time_signature() {
+        let transport = Transport::default().with_time_signature(6, 8);
+
+        assert_eq!(transport.beats_per_bar(), Some(3.0));
+        assert_eq!(Transport::default().beats_per_bar(), None);
+    }
 }
@@ -0,0 +1,307 @@
+//! Tests for the `MidSide` combinator.
+
+use std::sync::Arc;
+use wavecraft_dsp::combinators::{MidSide, MidSideMode};
+
+use wavecraft_dsp::{
+    MeteringTap, ParamRange, ParamSpec, PersistentField, PersistentState, Processor,
+    ProcessorParams, SignalChain, TailLength, Transport,
+};
+
+#[derive(Default)]
+struct TestGainDsp;
+
+struct TestGainParams {
+    level: f32,
+}
+
+impl Default for TestGainParams {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+impl ProcessorParams for TestGainParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static SPECS: [ParamSpec; 1] = [ParamSpec {
+            name: "Level",
+            id_suffix: "level",
+            range: ParamRange::Linear { min: 0.0, max: 2.0 },
+            default: 1.0,
+            unit: "x",
+            group: None,
+        }];
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self { level: 1.0 }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(level) = values.first() {
+            self.level = *level;
+        }
+    }
+}
+
+impl Processor for TestGainDsp {
+    type Params = TestGainParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        for sample in buffer.iter_mut().flat_map(|channel| channel.iter_mut()) {
+            *sample *= params.level;
+        }
+    }
+}
+
+/// Two-sample delay that only handles mono buffers, so any state shared
+/// between mid and side would show up in the output.
+#[derive(Default)]
+struct TestDelayDsp {
+    history: [f32; 2],
+}
+
+impl Processor for TestDelayDsp {
+    type Params = ();
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+        assert_eq!(buffer.len(), 1, "mid and side are processed as mono");
+        for sample in buffer[0].iter_mut() {
+            let input = *sample;
+            *sample = self.history[0];
+            self.history = [self.history[1], input];
+        }
+    }
+
+    fn reset(&mut self) {
+        self.history = [0.0; 2];
+    }
+
+    fn latency_samples(&self) -> u32 {
+        2
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        TailLength::Samples(2)
+    }
+}
+
+/// Pass-through processor with a metering tap and a persistent value.
+#[derive(Default)]
+struct TestTappedDsp {
+    state: PersistentState<u32>,
+}
+
+struct TestTap;
+
+impl MeteringTap for TestTap {
+    fn id(&self) -> &str {
+        "gr"
+    }
+}
+
+impl Processor for TestTappedDsp {
+    type Params = ();
+
+    fn process(&mut self, _buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {}
+
+    fn persistent_fields(&self, fields: &mut Vec<Arc<dyn PersistentField>>) {
+        fields.push(self.state.field());
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        taps.push(Arc::new(TestTap));
+    }
+}
+
+fn gain_params(level: f32, mode: MidSideMode) -> <MidSide<TestGainDsp> as Processor>::Params {
+    let mut params = <MidSide<TestGainDsp> as Processor>::Params::default();
+    params.inner.level = level;
+    params.mode = mode;
+    params
+}
+
+fn delay_params(mode: MidSideMode) -> <MidSide<TestDelayDsp> as Processor>::Params {
+    let mut params = <MidSide<TestDelayDsp> as Processor>::Params::from_param_defaults();
+    params.mode = mode;
+    params
+}
+
+fn process_stereo<P: Processor>(
+    processor: &mut P,
+    params: &P::Params,
+    left: &[f32],
+    right: &[f32],
+) -> (Vec<f32>, Vec<f32>) {
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    processor.process(
+        &mut [&mut left[..], &mut right[..]],
+        &Transport::default(),
+        params,
+    );
+    (left, right)
+}
+
+#[test]
+fn test_mid_side_appends_mode_param() {
+    type Params = <SignalChain![MidSide<TestGainDsp>] as Processor>::Params;
+
+    let suffixes: Vec<_> = Params::param_specs()
+        .iter()
+        .map(|spec| spec.id_suffix)
+        .collect();
+    assert_eq!(suffixes, ["level", "ms_mode", "bypass"]);
+    assert_eq!(Params::plain_value_count(), 3);
+
+    let mut params = <MidSide<TestGainDsp> as Processor>::Params::default();
+    assert_eq!(params.mode, MidSideMode::Both);
+    params.apply_plain_values(&[0.5, 1.0]);
+    assert_eq!(params.inner.level, 0.5);
+    assert_eq!(params.mode, MidSideMode::Side);
+    params.apply_plain_values(&[0.5, 0.0]);
+    assert_eq!(params.mode, MidSideMode::Mid);
+}
+
+#[test]
+fn test_mid_side_processes_selected_signal() {
+    let mut mid_side = MidSide::<TestGainDsp>::default();
+    mid_side.set_sample_rate(48_000.0);
+    let (left, right) = ([1.0_f32, 0.5], [0.0_f32, 0.5]);
+
+    // Removing the mid leaves only the side: (L - R) / 2 on left, inverted on right.
+    let output = process_stereo(
+        &mut mid_side,
+        &gain_params(0.0, MidSideMode::Mid),
+        &left,
+        &right,
+    );
+    assert_eq!(output, (vec![0.5, 0.0], vec![-0.5, 0.0]));
+
+    // Removing the side collapses to mono.
+    let output = process_stereo(
+        &mut mid_side,
+        &gain_params(0.0, MidSideMode::Side),
+        &left,
+        &right,
+    );
+    assert_eq!(output, (vec![0.5, 0.5], vec![0.5, 0.5]));
+
+    // Processing both is the same as processing left and right.
+    let output = process_stereo(
+        &mut mid_side,
+        &gain_params(2.0, MidSideMode::Both),
+        &left,
+        &right,
+    );
+    assert_eq!(output, (vec![2.0, 1.0], vec![0.0, 1.0]));
+}
+
+#[test]
+fn test_mid_side_instances_are_independent_and_aligned() {
+    let left = [1.0_f32, 0.0, 0.25, 0.0, 0.0, 0.0];
+    let right = [0.0_f32, 0.5, 0.0, 0.0, 0.0, 0.0];
+    let delayed = |signal: &[f32]| {
+        let mut delayed = vec![0.0; 2];
+        delayed.extend_from_slice(&signal[..signal.len() - 2]);
+        delayed
+    };
+
+    for mode in [MidSideMode::Mid, MidSideMode::Side, MidSideMode::Both] {
+        let mut mid_side = MidSide::<TestDelayDsp>::default();
+        mid_side.set_sample_rate(48_000.0);
+        assert_eq!(mid_side.latency_samples(), 2);
+
+        let output = process_stereo(&mut mid_side, &delay_params(mode), &left, &right);
+        assert_eq!(output, (delayed(&left), delayed(&right)), "{mode:?}");
+        assert_eq!(mid_side.tail_samples(), TailLength::Samples(2));
+    }
+}
+
+#[test]
+fn test_mid_side_resets_instances_switched_back_on() {
+    let mut mid_side = MidSide::<TestDelayDsp>::default();
+    mid_side.set_sample_rate(48_000.0);
+
+    process_stereo(
+        &mut mid_side,
+        &delay_params(MidSideMode::Both),
+        &[1.0; 4],
+        &[0.0; 4],
+    );
+    assert_eq!(mid_side.side.history, [0.5, 0.5]);
+
+    process_stereo(
+        &mut mid_side,
+        &delay_params(MidSideMode::Mid),
+        &[0.0; 4],
+        &[0.0; 4],
+    );
+    assert_eq!(
+        mid_side.side.history,
+        [0.5, 0.5],
+        "inactive side is untouched"
+    );
+
+    let mut silence = [0.0_f32; 1];
+    let mut other = [0.0_f32; 1];
+    mid_side.process(
+        &mut [&mut silence[..], &mut other[..]],
+        &Transport::default(),
+        &delay_params(MidSideMode::Both),
+    );
+    assert_eq!(mid_side.side.history, [0.0, 0.0]);
+}
+
+#[test]
+fn test_mid_side_treats_mono_as_mid() {
+    let mut mid_side = MidSide::<TestGainDsp>::default();
+    mid_side.set_sample_rate(48_000.0);
+
+    let mut mono = [0.5_f32, -1.0];
+    mid_side.process(
+        &mut [&mut mono[..]],
+        &Transport::default(),
+        &gain_params(2.0, MidSideMode::Mid),
+    );
+    assert_eq!(mono, [1.0, -2.0]);
+
+    mid_side.process(
+        &mut [&mut mono[..]],
+        &Transport::default(),
+        &gain_params(2.0, MidSideMode::Side),
+    );
+    assert_eq!(mono, [1.0, -2.0]);
+}
+
+#[test]
+fn test_mid_side_namespaces_side_taps_and_state() {
+    let mid_side = MidSide::<TestTappedDsp>::default();
+
+    let mut taps = Vec::new();
+    mid_side.metering_taps(&mut taps);
+    let ids: Vec<_> = taps.iter().map(|tap| tap.id()).collect();
+    assert_eq!(ids, ["gr", "gr_side"]);
+
+    mid_side.mid.state.set(1);
+    mid_side.side.state.set(2);
+    let mut fields = Vec::new();
+    mid_side.persistent_fields(&mut fields);
+    assert_eq!(fields.len(), 1, "one entry holds both instances");
+    let saved = fields[0].serialize().expect("state serializes");
+
+    let restored = MidSide::<TestTappedDsp>::default();
+    let mut fields = Vec::new();
+    restored.persistent_fields(&mut fields);
+    assert!(fields[0].deserialize(&saved));
+    assert_eq!(
+        (restored.mid.state.get(), restored.side.state.get()),
+        (1, 2)
+    );
+    assert!(!fields[0].deserialize("3"), "not a mid/side entry");
+}
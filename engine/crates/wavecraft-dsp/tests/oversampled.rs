@@ -0,0 +1,227 @@
+//! Tests for the `Oversampled` combinator.
+
+use std::f32::consts::PI;
+
+use wavecraft_dsp::combinators::Oversampled;
+use wavecraft_dsp::{
+    MAX_EVENTS_PER_BLOCK, NoteEvent, NoteEventKind, ParamRange, ParamSpec, Processor,
+    ProcessorParams, SignalChain, TailLength, Transport,
+};
+
+const SAMPLE_RATE: f32 = 44_100.0;
+
+#[derive(Default)]
+struct TestIdentityDsp {
+    sample_rate: f32,
+    block_lens: Vec<usize>,
+    event_timings: Vec<u32>,
+}
+
+impl Processor for TestIdentityDsp {
+    type Params = ();
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], _transport: &Transport, _params: &()) {
+        self.block_lens.push(buffer[0].len());
+    }
+
+    fn process_events(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        events: &[NoteEvent],
+        transport: &Transport,
+        params: &(),
+    ) {
+        self.event_timings
+            .extend(events.iter().map(|event| event.timing));
+        self.process(buffer, transport, params);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        TailLength::Samples(8)
+    }
+}
+
+#[derive(Default)]
+struct TestHardClipDsp;
+
+#[derive(Default)]
+struct TestHardClipParams;
+
+impl ProcessorParams for TestHardClipParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static SPECS: [ParamSpec; 1] = [ParamSpec {
+            name: "Ceiling",
+            id_suffix: "ceiling",
+            range: ParamRange::Linear { min: 0.0, max: 1.0 },
+            default: 0.5,
+            unit: "",
+            group: None,
+        }];
+        &SPECS
+    }
+}
+
+impl Processor for TestHardClipDsp {
+    type Params = TestHardClipParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        _params: &Self::Params,
+    ) {
+        for sample in buffer.iter_mut().flat_map(|channel| channel.iter_mut()) {
+            *sample = sample.clamp(-0.5, 0.5);
+        }
+    }
+}
+
+fn sine(frequency: f32, len: usize) -> Vec<f32> {
+    (0..len)
+        .map(|n| (2.0 * PI * frequency * n as f32 / SAMPLE_RATE).sin())
+        .collect()
+}
+
+/// Magnitude of one frequency component (Goertzel).
+fn magnitude_at(signal: &[f32], frequency: f32) -> f32 {
+    let omega = 2.0 * PI * frequency / SAMPLE_RATE;
+    let coeff = 2.0 * omega.cos();
+    let (mut s1, mut s2) = (0.0_f32, 0.0_f32);
+    for &sample in signal {
+        let s0 = sample + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+    (s1 * s1 + s2 * s2 - coeff * s1 * s2).sqrt() / signal.len() as f32
+}
+
+fn render<P: Processor>(processor: &mut P, params: &P::Params, input: &[f32]) -> Vec<f32> {
+    let mut output = input.to_vec();
+    for block in output.chunks_mut(256) {
+        processor.process(&mut [block], &Transport::default(), params);
+    }
+    output
+}
+
+#[test]
+fn test_oversampled_runs_inner_at_multiplied_rate() {
+    let mut oversampled = Oversampled::<TestIdentityDsp, 4>::default();
+    oversampled.set_sample_rate(SAMPLE_RATE);
+    assert_eq!(oversampled.processor.sample_rate, SAMPLE_RATE * 4.0);
+
+    let mut left = [0.0_f32; 64];
+    let events = [NoteEvent {
+        timing: 10,
+        kind: NoteEventKind::NoteOff {
+            channel: 0,
+            note: 60,
+            velocity: 0.0,
+        },
+    }];
+    oversampled.process_events(&mut [&mut left[..]], &events, &Transport::default(), &());
+
+    assert_eq!(oversampled.processor.block_lens, [256]);
+    assert_eq!(oversampled.processor.event_timings, [40]);
+}
+
+#[test]
+fn test_oversampled_forwards_a_full_event_block() {
+    let mut oversampled = Oversampled::<TestIdentityDsp, 2>::default();
+    oversampled.set_sample_rate(SAMPLE_RATE);
+
+    let mut left = [0.0_f32; MAX_EVENTS_PER_BLOCK];
+    let events: Vec<_> = (0..MAX_EVENTS_PER_BLOCK as u32)
+        .map(|timing| NoteEvent::note_on(timing, 0, 60, 1.0))
+        .collect();
+    oversampled.process_events(&mut [&mut left[..]], &events, &Transport::default(), &());
+
+    let timings = &oversampled.processor.event_timings;
+    assert_eq!(timings.len(), MAX_EVENTS_PER_BLOCK);
+    assert_eq!(
+        timings.last(),
+        Some(&(2 * (MAX_EVENTS_PER_BLOCK as u32 - 1)))
+    );
+}
+
+#[test]
+fn test_oversampled_reports_integral_filter_latency() {
+    assert_eq!(
+        Oversampled::<TestIdentityDsp, 2>::default().latency_samples(),
+        31
+    );
+    assert_eq!(
+        Oversampled::<TestIdentityDsp, 4>::default().latency_samples(),
+        47
+    );
+    assert_eq!(
+        Oversampled::<TestIdentityDsp, 8>::default().latency_samples(),
+        55
+    );
+
+    // Inner tail is converted to host-rate samples, then the filters ring on.
+    let oversampled = Oversampled::<TestIdentityDsp, 4>::default();
+    assert_eq!(oversampled.tail_samples(), TailLength::Samples(2 + 47));
+}
+
+fn assert_delays_by_latency<const FACTOR: usize>() {
+    let mut oversampled = Oversampled::<TestIdentityDsp, FACTOR>::default();
+    oversampled.set_sample_rate(SAMPLE_RATE);
+    let latency = oversampled.latency_samples() as usize;
+
+    let input = sine(1_000.0, 2048);
+    let output = render(&mut oversampled, &(), &input);
+
+    for n in (latency + 256)..output.len() {
+        assert!(
+            (output[n] - input[n - latency]).abs() < 2e-3,
+            "{FACTOR}x: sample {n} differs from input delayed by {latency}"
+        );
+    }
+}
+
+#[test]
+fn test_oversampled_output_matches_input_delayed_by_latency() {
+    assert_delays_by_latency::<2>();
+    assert_delays_by_latency::<4>();
+    assert_delays_by_latency::<8>();
+}
+
+#[test]
+fn test_oversampled_reduces_clipping_aliases() {
+    // The 3rd harmonic of 15 kHz (45 kHz) folds back to 900 Hz at 44.1 kHz.
+    let input = sine(15_000.0, 8820);
+    let params = TestHardClipParams;
+
+    let naive = render(&mut TestHardClipDsp, &params, &input);
+    let mut oversampled = Oversampled::<TestHardClipDsp, 4>::default();
+    oversampled.set_sample_rate(SAMPLE_RATE);
+    let clean = render(&mut oversampled, &params, &input);
+
+    // Still clipped: only the band-limited fundamental (~0.61) remains.
+    let peak = clean[4410..]
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    assert!(peak < 0.75, "oversampled output peak {peak}");
+
+    let naive_alias = magnitude_at(&naive[4410..], 900.0);
+    let clean_alias = magnitude_at(&clean[4410..], 900.0);
+    assert!(
+        clean_alias < naive_alias * 0.05,
+        "alias at 900 Hz: naive {naive_alias}, oversampled {clean_alias}"
+    );
+}
+
+#[test]
+fn test_oversampled_in_signal_chain_keeps_inner_params() {
+    type Params = <SignalChain![Oversampled<TestHardClipDsp, 4>] as Processor>::Params;
+
+    let suffixes: Vec<_> = Params::param_specs()
+        .iter()
+        .map(|spec| spec.id_suffix)
+        .collect();
+    assert_eq!(suffixes, ["ceiling", "bypass"]);
+}
@@ -0,0 +1,123 @@
+//! Tests for `Parallel` and `Mix` composed inside `SignalChain!`.
+
+use wavecraft_dsp::combinators::{Mix, Parallel};
+use wavecraft_dsp::{ParamRange, ParamSpec, Processor, ProcessorParams, SignalChain, Transport};
+
+#[derive(Default)]
+struct TestGainDsp;
+
+#[derive(Clone)]
+struct TestGainParams {
+    level: f32,
+}
+
+impl Default for TestGainParams {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+impl ProcessorParams for TestGainParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static SPECS: [ParamSpec; 1] = [ParamSpec {
+            name: "Level",
+            id_suffix: "level",
+            range: ParamRange::Linear { min: 0.0, max: 2.0 },
+            default: 1.0,
+            unit: "x",
+            group: None,
+        }];
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self { level: 1.0 }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(level) = values.first() {
+            self.level = *level;
+        }
+    }
+}
+
+impl Processor for TestGainDsp {
+    type Params = TestGainParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        for channel in buffer.iter_mut() {
+            for sample in channel.iter_mut() {
+                *sample *= params.level;
+            }
+        }
+    }
+}
+
+fn suffixes<P: ProcessorParams>() -> Vec<&'static str> {
+    P::param_specs().iter().map(|spec| spec.id_suffix).collect()
+}
+
+#[test]
+fn test_parallel_prefixes_branch_params() {
+    type Params = <SignalChain![Parallel<TestGainDsp, TestGainDsp>] as Processor>::Params;
+
+    assert_eq!(
+        suffixes::<Params>(),
+        ["a_level", "b_level", "gain_a", "gain_b", "bypass"]
+    );
+    assert_eq!(Params::plain_value_count(), 5);
+    assert_eq!(Params::param_specs()[1].name, "B Level");
+}
+
+#[test]
+fn test_mix_appends_mix_param() {
+    type Params = <SignalChain![TestGainDsp, Mix<TestGainDsp>] as Processor>::Params;
+
+    assert_eq!(
+        suffixes::<Params>(),
+        ["level", "bypass", "level", "mix", "bypass"]
+    );
+}
+
+#[test]
+fn test_parallel_sums_branches_with_gains() {
+    let mut parallel = Parallel::<TestGainDsp, TestGainDsp>::default();
+    parallel.set_sample_rate(48_000.0);
+
+    let mut params = <Parallel<TestGainDsp, TestGainDsp> as Processor>::Params::default();
+    // a_level, b_level, gain_a, gain_b
+    params.apply_plain_values(&[2.0, 0.5, 1.0, 0.5]);
+
+    let mut left = [1.0_f32, -1.0];
+    let mut right = [0.5_f32, 0.0];
+    parallel.process(
+        &mut [&mut left[..], &mut right[..]],
+        &Transport::default(),
+        &params,
+    );
+
+    // 2.0 * x + 0.5 * (0.5 * x) = 2.25 * x
+    assert_eq!(left, [2.25, -2.25]);
+    assert_eq!(right, [1.125, 0.0]);
+}
+
+#[test]
+fn test_mix_blends_dry_and_wet() {
+    let mut mix = Mix::<TestGainDsp>::default();
+    mix.set_sample_rate(48_000.0);
+
+    let mut params = <Mix<TestGainDsp> as Processor>::Params::default();
+    // level, mix
+    params.apply_plain_values(&[0.0, 0.25]);
+
+    let mut samples = [1.0_f32; 4];
+    mix.process(&mut [&mut samples[..]], &Transport::default(), &params);
+
+    // Wet is silent, so only 75% of the dry signal remains.
+    assert_eq!(samples, [0.75; 4]);
+}
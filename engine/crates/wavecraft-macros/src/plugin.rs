@@ -76,6 +76,11 @@ fn expand_wavecraft_plugin(plugin_def: PluginDef) -> Result<proc_macro2::TokenSt
         url,
         vst3_id: &vst3_id,
         clap_id: &clap_id,
+        kind: plugin_def.kind,
+        sidechain: plugin_def.sidechain,
+        layouts: &plugin_def.layouts,
+        presets: &plugin_def.presets,
+        on_non_finite: plugin_def.on_non_finite,
     });
 
     Ok(expanded)
@@ -213,4 +218,464 @@ mod tests {
             "processor metadata IDs should be unique for repeated processor types"
         );
     }
+
+    #[test]
+    fn generated_param_map_prefixes_combinator_instances_by_outer_type() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Parallel<Compressor, Gain>, Mix<Reverb>],
+        });
+
+        assert!(normalized.contains("format!(\"{}_{}\",\"parallel\",spec.id_suffix)"));
+        assert!(normalized.contains("format!(\"{}_{}\",\"mix\",spec.id_suffix)"));
+        assert!(normalized.contains("Bypassed<Parallel<Compressor,Gain>>"));
+    }
+
+    #[test]
+    fn generated_process_hands_whole_block_to_processor_without_allocating() {
+        let input_tokens = quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        };
+
+        let plugin_def: super::parse::PluginDef =
+            syn::parse2(input_tokens).expect("plugin definition should parse");
+        let output = expand_wavecraft_plugin(plugin_def).expect("plugin should expand");
+        let normalized = output
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        assert!(
+            normalized.contains("letchannels=buffer.as_slice();"),
+            "generated process() should pass nih-plug's channel slices through directly"
+        );
+        assert!(
+            normalized.contains("&self.processor_params,"),
+            "generated process() should reuse the preallocated processor params"
+        );
+        assert!(
+            !normalized.contains("sample_idx") && !normalized.contains(".to_vec()"),
+            "generated process() should not copy audio per sample or snapshot buffers"
+        );
+        assert!(
+            normalized.contains("self.plain_values.iter_mut()"),
+            "processor param refresh should write into preallocated plain-value storage"
+        );
+    }
+
+    fn expand_normalized(input_tokens: proc_macro2::TokenStream) -> String {
+        let plugin_def: super::parse::PluginDef =
+            syn::parse2(input_tokens).expect("plugin definition should parse");
+        let output = expand_wavecraft_plugin(plugin_def).expect("plugin should expand");
+        output
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+    }
+
+    #[test]
+    fn generated_effect_keeps_stereo_io_without_midi() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains(
+            "constMIDI_INPUT:::wavecraft::__nih::MidiConfig=::wavecraft::__nih::MidiConfig::None;"
+        ));
+        assert!(normalized.contains("ClapFeature::AudioEffect"));
+        assert!(normalized.contains("Vst3SubCategory::Fx"));
+        assert!(
+            normalized
+                .contains("Processor::process_with_sidechain(&mutself.processor,channels,sidechain,&self.events,"),
+            "generated process() should deliver the drained event slice"
+        );
+        assert!(
+            normalized.contains("letinput_silent=::wavecraft::__internal::is_silent(channels);"),
+            "effects should detect silence on the input"
+        );
+        assert!(normalized.contains("process_status_for_tail(tail_state,false)"));
+        assert!(!normalized.contains("ProcessStatus::Normal"));
+    }
+
+    #[test]
+    fn generated_plugin_reports_processor_latency() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(
+            normalized.contains("context.set_latency_samples(self.reported_latency);"),
+            "initialize() should report the processor latency"
+        );
+        assert!(
+            normalized.contains("iflatency!=self.reported_latency{"),
+            "process() should report latency changes"
+        );
+        assert!(normalized.contains("latency_samples:"));
+    }
+
+    #[test]
+    fn generated_instrument_switches_layout_midi_and_categories() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Synth",
+            signal: SignalChain![Oscillator],
+            kind: instrument,
+        });
+
+        assert!(normalized.contains("main_input_channels:::std::option::Option::None"));
+        assert!(normalized.contains("constMIDI_INPUT:::wavecraft::__nih::MidiConfig=::wavecraft::__nih::MidiConfig::MidiCCs;"));
+        assert!(
+            normalized.contains("ClapFeature::Instrument")
+                && normalized.contains("ClapFeature::Synthesizer")
+                && !normalized.contains("ClapFeature::AudioEffect")
+        );
+        assert!(
+            normalized.contains("Vst3SubCategory::Instrument")
+                && normalized.contains("Vst3SubCategory::Synth")
+        );
+        assert!(normalized.contains("channel.fill(0.0);"));
+        assert!(normalized.contains("letinput_silent=self.events.is_empty()&&"));
+        assert!(normalized.contains("process_status_for_tail(tail_state,true)"));
+    }
+
+    #[test]
+    fn generated_plugin_without_sidechain_declares_no_aux_input() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains("letsidechain:&[&mut[f32]]=&[];"));
+        assert!(!normalized.contains("aux_input_ports"));
+    }
+
+    #[test]
+    fn generated_sidechain_adds_aux_input_bus() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Ducker",
+            signal: SignalChain![Gain],
+            sidechain: stereo,
+        });
+
+        assert!(
+            normalized.contains("aux_input_ports:&[::wavecraft::__nih::new_nonzero_u32(2u32)],")
+        );
+        assert!(normalized.contains("aux_inputs:&[\"Sidechain\"],"));
+        assert!(
+            normalized.contains("match_aux.inputs.first(){"),
+            "process() should read the first auxiliary input"
+        );
+    }
+
+    #[test]
+    fn generated_params_persist_processor_state() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains(
+            "::wavecraft::Processor::persistent_fields(&processor,&mutparams.persistent_fields);"
+        ));
+        assert!(normalized.contains(
+            "::wavecraft::__internal::serialize_persistent_fields(&self.persistent_fields)"
+        ));
+        assert!(normalized.contains(
+            "::wavecraft::__internal::deserialize_persistent_fields(&self.persistent_fields,serialized);"
+        ));
+    }
+
+    #[test]
+    fn generated_plugin_applies_and_persists_modulation() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains(
+            "letmodulation=::wavecraft::ModulationMatrix::new(params.modulation_targets());params.modulation=modulation.handle();"
+        ));
+        // Sources run on the unprocessed input before the params refresh,
+        // which stacks them on top of host modulation.
+        assert!(normalized.contains(
+            "self.modulation.advance(channels,&transport);self.update_processor_params();"
+        ));
+        assert!(normalized.contains(
+            "*slot=param.modulated_plain_value();}self.modulation.apply(&mutself.plain_values);"
+        ));
+        assert!(normalized.contains(
+            "::wavecraft::__internal::serialize_modulation(&self.modulation,&mutserialized);"
+        ));
+        assert!(normalized.contains(
+            "::wavecraft::__internal::deserialize_modulation(&self.modulation,serialized);"
+        ));
+        assert!(normalized.contains("self.params.modulation.clone(),"));
+    }
+
+    #[test]
+    fn generated_plugin_meters_processed_output() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains(
+            "self.meters=::wavecraft::MeterEngine::new(_buffer_config.sample_rate,self.channel_layout.outputsasusize,);"
+        ));
+        assert!(normalized.contains("self.meters.process(channels);"));
+        assert!(normalized.contains("self.meter_producer.push(self.meters.frame());"));
+        assert!(normalized.contains("self.meters.reset();"));
+        assert!(!normalized.contains("*0.707"));
+    }
+
+    #[test]
+    fn generated_plugin_publishes_spectrum_frames() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains(
+            "spectrum_analyzer:::wavecraft::SpectrumAnalyzer::with_output(spectrum_producer),"
+        ));
+        assert!(normalized.contains("self.spectrum_analyzer.capture_stereo(left,right);"));
+        assert!(normalized.contains("spectrum_consumer:self.spectrum_consumer"));
+        assert!(normalized.contains(
+            "::wavecraft::editor::create_webview_editor(self.params.clone(),channels,800,600)"
+        ));
+    }
+
+    #[test]
+    fn generated_plugin_analyzes_output_only_while_editor_is_open() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains("editor_presence:::wavecraft::__internal::EditorPresence,"));
+        assert!(normalized.contains(
+            "letanalysis_active=self.editor_presence.is_open();ifanalysis_active{if!self.analysis_active{self.meters.reset();"
+        ));
+        assert!(normalized.contains("self.analysis_active=analysis_active;"));
+        assert!(normalized.contains("presence:self.editor_presence.clone(),"));
+    }
+
+    #[test]
+    fn generated_plugin_shares_sample_rate_with_editor() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains("sample_rate:::wavecraft::__internal::SharedSampleRate,"));
+        assert!(normalized.contains("self.sample_rate.set(_buffer_config.sample_rate);"));
+        assert!(normalized.contains("sample_rate:self.sample_rate.clone(),"));
+    }
+
+    #[test]
+    fn generated_plugin_guards_output() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(
+            normalized.contains("let_denormals=::wavecraft::__internal::DenormalGuard::new();")
+        );
+        assert!(normalized.contains(".with_reset_on_non_finite(false),"));
+        assert!(normalized.contains(
+            "ifself.output_sanitizer.process(channels){::wavecraft::Processor::reset(&mutself.processor);}"
+        ));
+        assert!(normalized.contains("self.output_sanitizer.stats(),"));
+    }
+
+    #[test]
+    fn generated_plugin_can_reset_on_non_finite_output() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            on_non_finite: reset,
+        });
+
+        assert!(normalized.contains(".with_reset_on_non_finite(true),"));
+    }
+
+    #[test]
+    fn rejects_unknown_non_finite_handling() {
+        let result = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            on_non_finite: panic,
+        });
+
+        let err = result.err().expect("unknown non-finite handling must fail");
+        assert!(
+            err.to_string()
+                .contains("unknown non-finite output handling")
+        );
+    }
+
+    #[test]
+    fn generated_plugin_collects_metering_taps() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert!(normalized.contains("::wavecraft::Processor::metering_taps(&processor,&muttaps);"));
+        assert!(normalized.contains("metering_taps:self.metering_taps.clone(),"));
+    }
+
+    #[test]
+    fn generated_plugin_declares_each_listed_layout() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            layouts: [stereo, mono, mono_to_stereo, surround_5_1],
+        });
+
+        assert_eq!(normalized.matches("main_input_channels:").count(), 4);
+        assert!(normalized.contains(
+            "main_input_channels:::std::num::NonZeroU32::new(1u32),main_output_channels:::std::num::NonZeroU32::new(2u32)"
+        ));
+        assert!(normalized.contains("NonZeroU32::new(6u32)"));
+        assert!(normalized.contains("ClapFeature::Stereo,::wavecraft::__nih::ClapFeature::Mono,::wavecraft::__nih::ClapFeature::Surround,"));
+        assert!(
+            normalized
+                .contains("Processor::set_channel_layout(&mutself.processor,self.channel_layout);"),
+            "initialize() should pass the host layout to the processor"
+        );
+        assert!(normalized.contains("ifself.channel_layout.is_upmix(){"));
+    }
+
+    #[test]
+    fn generated_dev_ffi_reports_and_selects_layout() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            layouts: [mono_to_stereo, stereo],
+        });
+
+        assert!(
+            normalized
+                .contains("default_num_inputs:1u32,default_num_outputs:2u32,set_channel_layout,"),
+            "dev FFI vtable should report the first declared layout"
+        );
+        assert!(
+            normalized.contains("ChannelLayout::new(num_inputs,num_outputs)"),
+            "dev FFI set_channel_layout should forward the requested layout"
+        );
+        assert!(
+            !normalized.contains("ChannelLayout::STEREO"),
+            "dev FFI should not hard-code a stereo layout"
+        );
+    }
+
+    #[test]
+    fn generated_plugin_defaults_to_stereo_layout() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+        });
+
+        assert_eq!(normalized.matches("main_input_channels:").count(), 1);
+        assert!(normalized.contains("main_input_channels:::std::num::NonZeroU32::new(2u32)"));
+        assert!(!normalized.contains("ClapFeature::Mono"));
+    }
+
+    #[test]
+    fn rejects_unknown_or_empty_layouts() {
+        let unknown = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            layouts: [stereo, quad],
+        });
+        let err = unknown.err().expect("unknown layout must fail");
+        assert!(err.to_string().contains("unknown channel layout"));
+
+        let empty = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            layouts: [],
+        });
+        let err = empty.err().expect("empty layouts must fail");
+        assert!(err.to_string().contains("at least one channel layout"));
+    }
+
+    #[test]
+    fn rejects_mono_to_stereo_for_instruments() {
+        let result = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Synth",
+            signal: SignalChain![Oscillator],
+            layouts: [mono_to_stereo],
+            kind: instrument,
+        });
+
+        let err = result.err().expect("instrument upmix layout must fail");
+        assert!(err.to_string().contains("instruments don't have"));
+    }
+
+    #[test]
+    fn rejects_unknown_sidechain_layout() {
+        let result = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            sidechain: surround,
+        });
+
+        let err = result.err().expect("unknown sidechain layout must fail");
+        assert!(err.to_string().contains("unknown sidechain layout"));
+    }
+
+    #[test]
+    fn rejects_unknown_plugin_kind() {
+        let result = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            kind: sampler,
+        });
+
+        let err = result.err().expect("unknown kind must fail");
+        assert!(err.to_string().contains("unknown plugin kind"));
+    }
+
+    #[test]
+    fn generated_plugin_embeds_factory_presets() {
+        let normalized = expand_normalized(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            presets: ["presets/init.json", "presets/warm.json"],
+        });
+
+        assert!(normalized.contains(
+            r#"("presets/init.json",include_str!(concat!(env!("CARGO_MANIFEST_DIR"),"/","presets/init.json")))"#
+        ));
+        assert!(normalized.contains(r#""presets/warm.json""#));
+        assert!(normalized.contains("fnwavecraft_get_presets_json()"));
+        assert!(normalized.contains("PresetLibrary::from_catalog(__wavecraft_preset_catalog())"));
+    }
+
+    #[test]
+    fn rejects_absolute_or_duplicate_preset_paths() {
+        let absolute = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            presets: ["/tmp/init.json"],
+        });
+        let err = absolute.err().expect("absolute preset path must fail");
+        assert!(err.to_string().contains("relative to the plugin crate"));
+
+        let duplicate = syn::parse2::<super::parse::PluginDef>(quote! {
+            name: "Test Plugin",
+            signal: SignalChain![Gain],
+            presets: ["presets/init.json", "presets/init.json"],
+        });
+        let err = duplicate.err().expect("duplicate preset path must fail");
+        assert!(err.to_string().contains("duplicate entry in `presets`"));
+    }
 }
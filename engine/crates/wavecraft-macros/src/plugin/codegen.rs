@@ -1,5 +1,7 @@
 use quote::quote;
 
+use super::parse::{Layout, OnNonFinite, PluginKind, Sidechain};
+
 pub(super) struct CodegenInput<'a> {
     pub(super) name: &'a syn::LitStr,
     pub(super) signal_type: &'a syn::Expr,
@@ -11,6 +13,174 @@ pub(super) struct CodegenInput<'a> {
     pub(super) url: &'a str,
     pub(super) vst3_id: &'a proc_macro2::TokenStream,
     pub(super) clap_id: &'a str,
+    pub(super) kind: PluginKind,
+    pub(super) sidechain: Sidechain,
+    pub(super) layouts: &'a [Layout],
+    pub(super) presets: &'a [syn::LitStr],
+    pub(super) on_non_finite: OnNonFinite,
+}
+
+/// Kind-dependent pieces of the generated `Plugin`/`ClapPlugin`/`Vst3Plugin` impls.
+struct KindTokens {
+    audio_io_layouts: proc_macro2::TokenStream,
+    midi_input: proc_macro2::TokenStream,
+    clap_features: proc_macro2::TokenStream,
+    vst3_subcategories: proc_macro2::TokenStream,
+    clear_output: proc_macro2::TokenStream,
+    silence_before: proc_macro2::TokenStream,
+    silence_after: proc_macro2::TokenStream,
+    /// Spreads the single input channel over all outputs for upmix layouts.
+    upmix: proc_macro2::TokenStream,
+    is_instrument: bool,
+}
+
+/// Sidechain-dependent pieces of the generated `Plugin` impl.
+struct SidechainTokens {
+    /// Extra `AudioIOLayout` fields declaring the auxiliary input bus.
+    layout_fields: proc_macro2::TokenStream,
+    /// Expression yielding the sidechain channels for the current block.
+    channels: proc_macro2::TokenStream,
+}
+
+fn sidechain_tokens(sidechain: Sidechain, krate: &syn::Path) -> SidechainTokens {
+    match sidechain.channels() {
+        None => SidechainTokens {
+            layout_fields: quote! {},
+            channels: quote! { &[] },
+        },
+        Some(channels) => SidechainTokens {
+            layout_fields: quote! {
+                aux_input_ports: &[#krate::__nih::new_nonzero_u32(#channels)],
+                names: #krate::__nih::PortNames {
+                    aux_inputs: &["Sidechain"],
+                    ..#krate::__nih::PortNames::const_default()
+                },
+            },
+            channels: quote! {
+                match _aux.inputs.first() {
+                    ::std::option::Option::Some(input) => input.as_slice_immutable(),
+                    ::std::option::Option::None => &[],
+                }
+            },
+        },
+    }
+}
+
+/// One `AudioIOLayout` per declared layout, in declaration order.
+fn audio_io_layouts(
+    kind: PluginKind,
+    layouts: &[Layout],
+    sidechain_layout: &proc_macro2::TokenStream,
+    krate: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let entries = layouts.iter().map(|layout| {
+        let (inputs, outputs) = layout.channels();
+        let main_input = match kind {
+            PluginKind::Effect => quote! { ::std::num::NonZeroU32::new(#inputs) },
+            PluginKind::Instrument => quote! { ::std::option::Option::None },
+        };
+
+        quote! {
+            #krate::__nih::AudioIOLayout {
+                main_input_channels: #main_input,
+                main_output_channels: ::std::num::NonZeroU32::new(#outputs),
+                #sidechain_layout
+                ..#krate::__nih::AudioIOLayout::const_default()
+            },
+        }
+    });
+
+    quote! { #(#entries)* }
+}
+
+/// CLAP channel features covering the declared output layouts.
+fn layout_clap_features(layouts: &[Layout], krate: &syn::Path) -> proc_macro2::TokenStream {
+    let mut features = Vec::new();
+    for layout in layouts {
+        let feature = match layout {
+            Layout::Mono => "Mono",
+            Layout::Stereo | Layout::MonoToStereo => "Stereo",
+            Layout::Surround51 | Layout::Surround71 => "Surround",
+        };
+        if !features.contains(&feature) {
+            features.push(feature);
+        }
+    }
+
+    let features = features
+        .into_iter()
+        .map(|feature| syn::Ident::new(feature, proc_macro2::Span::call_site()));
+    quote! { #(#krate::__nih::ClapFeature::#features,)* }
+}
+
+fn kind_tokens(
+    kind: PluginKind,
+    layouts: &[Layout],
+    sidechain_layout: &proc_macro2::TokenStream,
+    krate: &syn::Path,
+) -> KindTokens {
+    let audio_io_layouts = audio_io_layouts(kind, layouts, sidechain_layout, krate);
+    let layout_features = layout_clap_features(layouts, krate);
+
+    match kind {
+        PluginKind::Effect => KindTokens {
+            audio_io_layouts,
+            midi_input: quote! { #krate::__nih::MidiConfig::None },
+            clap_features: quote! {
+                #krate::__nih::ClapFeature::AudioEffect,
+                #layout_features
+            },
+            vst3_subcategories: quote! {
+                #krate::__nih::Vst3SubCategory::Fx,
+            },
+            clear_output: quote! {},
+            // Effects go quiet when their input does; check before the
+            // in-place processing overwrites it.
+            silence_before: quote! {
+                let input_silent = #krate::__internal::is_silent(channels);
+            },
+            silence_after: quote! {},
+            // nih-plug only fills the outputs backed by an input channel.
+            upmix: quote! {
+                if self.channel_layout.is_upmix() {
+                    if let ::std::option::Option::Some((first, rest)) = channels.split_first_mut() {
+                        for channel in rest.iter_mut() {
+                            channel.copy_from_slice(first);
+                        }
+                    }
+                }
+            },
+            is_instrument: false,
+        },
+        PluginKind::Instrument => KindTokens {
+            audio_io_layouts,
+            midi_input: quote! { #krate::__nih::MidiConfig::MidiCCs },
+            clap_features: quote! {
+                #krate::__nih::ClapFeature::Instrument,
+                #krate::__nih::ClapFeature::Synthesizer,
+                #layout_features
+            },
+            vst3_subcategories: quote! {
+                #krate::__nih::Vst3SubCategory::Instrument,
+                #krate::__nih::Vst3SubCategory::Synth,
+            },
+            // Instruments have no main input, so start every block from silence.
+            clear_output: quote! {
+                for channel in channels.iter_mut() {
+                    channel.fill(0.0);
+                }
+            },
+            // Without an audio input, a block counts as silent when no events
+            // arrived and the voices have stopped sounding.
+            silence_before: quote! {},
+            silence_after: quote! {
+                let input_silent =
+                    self.events.is_empty() && #krate::__internal::is_silent(channels);
+            },
+            upmix: quote! {},
+            is_instrument: true,
+        },
+    }
 }
 
 pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::TokenStream {
@@ -25,8 +195,44 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
         url,
         vst3_id,
         clap_id,
+        kind,
+        sidechain,
+        layouts,
+        presets,
+        on_non_finite,
     } = input;
 
+    let SidechainTokens {
+        layout_fields: sidechain_layout,
+        channels: sidechain_channels,
+    } = sidechain_tokens(sidechain, krate);
+    let KindTokens {
+        audio_io_layouts,
+        midi_input,
+        clap_features,
+        vst3_subcategories,
+        clear_output,
+        silence_before,
+        silence_after,
+        upmix,
+        is_instrument,
+    } = kind_tokens(kind, layouts, &sidechain_layout, krate);
+
+    let reset_on_non_finite = on_non_finite == OnNonFinite::Reset;
+
+    // Dev FFI instances start in the first declared layout, like a host that
+    // accepts the plugin's preferred layout.
+    let (default_num_inputs, default_num_outputs) = match kind {
+        PluginKind::Effect => layouts[0].channels(),
+        PluginKind::Instrument => (0, layouts[0].channels().1),
+    };
+
+    let factory_presets = presets.iter().map(|path| {
+        quote! {
+            (#path, include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path)))
+        }
+    });
+
     quote! {
         // Use the signal expression as the processor type
         type __ProcessorType = #signal_type;
@@ -49,12 +255,49 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
         pub struct __WavecraftPlugin {
             params: ::std::sync::Arc<__WavecraftParams>,
             processor: __ProcessorType,
+            // Preallocated processor params and plain-value scratch space so
+            // `process()` never allocates on the audio thread.
+            processor_params: <__ProcessorType as #krate::Processor>::Params,
+            plain_values: ::std::vec::Vec<f32>,
+            // Captured from `BufferConfig::process_mode` in `initialize()`.
+            offline_render: bool,
+            // Main-bus layout selected by the host in `initialize()`.
+            channel_layout: #krate::ChannelLayout,
+            // Latency last reported to the host.
+            reported_latency: u32,
+            // Counts silent input to report the remaining tail to the host.
+            tail_tracker: #krate::__internal::TailTracker,
+            // Keeps NaN/Inf and runaway levels from reaching the host.
+            output_sanitizer: #krate::__internal::OutputSanitizer,
+            // Note events for the current block, preallocated to
+            // `MAX_EVENTS_PER_BLOCK`; events beyond it are dropped instead of
+            // reallocating on the audio thread.
+            events: ::std::vec::Vec<#krate::NoteEvent>,
+            // LFOs and envelope followers routed to parameters; edited
+            // through `params.modulation`.
+            modulation: #krate::ModulationMatrix,
+            // Output analysis below only feeds the editor and runs while
+            // `editor_presence` reports an open window.
+            editor_presence: #krate::__internal::EditorPresence,
+            // Set in `initialize`; the editor evaluates EQ curves at this rate.
+            sample_rate: #krate::__internal::SharedSampleRate,
+            // Whether analysis ran in the previous block; stale state is
+            // cleared when it resumes.
+            analysis_active: bool,
             oscilloscope_tap: #krate::OscilloscopeTap,
+            spectrum_analyzer: #krate::SpectrumAnalyzer,
+            // Peak, RMS, true-peak and loudness measurement of the output.
+            meters: #krate::MeterEngine,
             meter_producer: #krate::MeterProducer,
             #[cfg(any(target_os = "macos", target_os = "windows"))]
             meter_consumer: ::std::sync::Mutex<::std::option::Option<#krate::MeterConsumer>>,
             #[cfg(any(target_os = "macos", target_os = "windows"))]
             oscilloscope_consumer: ::std::sync::Mutex<::std::option::Option<#krate::OscilloscopeFrameConsumer>>,
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            spectrum_consumer: ::std::sync::Mutex<::std::option::Option<#krate::SpectrumFrameConsumer>>,
+            // Named meter/oscilloscope taps placed in the signal chain.
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            metering_taps: ::std::vec::Vec<::std::sync::Arc<dyn #krate::MeteringTap>>,
         }
 
         /// Generated params struct.
@@ -81,6 +324,18 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                 // SAFETY: ParamPtr originates from `self` and remains valid for this call.
                 unsafe { ptr.modulated_plain_value() }
             }
+
+            fn plain_range(&self) -> (f32, f32) {
+                use #krate::__nih::Param;
+
+                match self {
+                    Self::Float(param) => (param.preview_plain(0.0), param.preview_plain(1.0)),
+                    Self::Int(param) => (
+                        param.preview_plain(0.0) as f32,
+                        param.preview_plain(1.0) as f32,
+                    ),
+                }
+            }
         }
 
         pub struct __WavecraftParams {
@@ -90,6 +345,12 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
             ids: ::std::vec::Vec<::std::string::String>,
             // Optional parameter group names (empty string when none)
             groups: ::std::vec::Vec<::std::string::String>,
+            // Skew factor of each parameter's normalized mapping (1.0 when linear)
+            skews: ::std::vec::Vec<f32>,
+            // Non-parameter processor state, in signal chain order
+            persistent_fields: ::std::vec::Vec<::std::sync::Arc<dyn #krate::PersistentField>>,
+            // Modulation routings, shared with the plugin's modulation matrix
+            modulation: #krate::ModulationHandle,
         }
 
         impl __WavecraftParams {
@@ -102,10 +363,31 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                 let mut params = ::std::vec::Vec::new();
                 let mut ids = ::std::vec::Vec::new();
                 let mut groups = ::std::vec::Vec::new();
+                let mut skews = ::std::vec::Vec::new();
 
                 #(#runtime_param_blocks)*
 
-                Self { params, ids, groups }
+                Self {
+                    params,
+                    ids,
+                    groups,
+                    skews,
+                    persistent_fields: ::std::vec::Vec::new(),
+                    modulation: ::std::default::Default::default(),
+                }
+            }
+
+            /// Every parameter as a modulation target, in plain-value order.
+            fn modulation_targets(&self) -> ::std::vec::Vec<#krate::ModulationTarget> {
+                self.params
+                    .iter()
+                    .zip(self.ids.iter())
+                    .zip(self.skews.iter())
+                    .map(|((param, id), &skew)| {
+                        let (min, max) = param.plain_range();
+                        #krate::ModulationTarget { id: id.clone(), min, max, skew }
+                    })
+                    .collect()
             }
         }
 
@@ -131,6 +413,25 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                     })
                     .collect()
             }
+
+            fn serialize_fields(
+                &self,
+            ) -> ::std::collections::BTreeMap<::std::string::String, ::std::string::String> {
+                let mut serialized =
+                    #krate::__internal::serialize_persistent_fields(&self.persistent_fields);
+                #krate::__internal::serialize_modulation(&self.modulation, &mut serialized);
+                serialized
+            }
+
+            // Called off the audio thread; the processor picks up the
+            // restored values through its `PersistentState` handles.
+            fn deserialize_fields(
+                &self,
+                serialized: &::std::collections::BTreeMap<::std::string::String, ::std::string::String>,
+            ) {
+                #krate::__internal::deserialize_persistent_fields(&self.persistent_fields, serialized);
+                #krate::__internal::deserialize_modulation(&self.modulation, serialized);
+            }
         }
 
         impl ::std::default::Default for __WavecraftPlugin {
@@ -139,15 +440,49 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                     #krate::create_meter_channel(64);
                 let (oscilloscope_producer, _oscilloscope_consumer) =
                     #krate::create_oscilloscope_channel(8);
+                let (spectrum_producer, _spectrum_consumer) =
+                    #krate::create_spectrum_channel(4);
+                let processor = <__ProcessorType as ::std::default::Default>::default();
+                let mut params = __WavecraftParams::default();
+                #krate::Processor::persistent_fields(&processor, &mut params.persistent_fields);
+                #[cfg(any(target_os = "macos", target_os = "windows"))]
+                let metering_taps = {
+                    let mut taps = ::std::vec::Vec::new();
+                    #krate::Processor::metering_taps(&processor, &mut taps);
+                    taps
+                };
+                let modulation = #krate::ModulationMatrix::new(params.modulation_targets());
+                params.modulation = modulation.handle();
+                let plain_values = ::std::vec![0.0_f32; params.params.len()];
                 Self {
-                    params: ::std::sync::Arc::new(__WavecraftParams::default()),
-                    processor: <__ProcessorType as ::std::default::Default>::default(),
+                    params: ::std::sync::Arc::new(params),
+                    processor,
+                    processor_params:
+                        <<__ProcessorType as #krate::Processor>::Params as #krate::ProcessorParams>::from_param_defaults(),
+                    plain_values,
+                    offline_render: false,
+                    channel_layout: ::std::default::Default::default(),
+                    reported_latency: 0,
+                    tail_tracker: #krate::__internal::TailTracker::new(),
+                    output_sanitizer: #krate::__internal::OutputSanitizer::new()
+                        .with_reset_on_non_finite(#reset_on_non_finite),
+                    events: ::std::vec::Vec::with_capacity(#krate::__internal::MAX_EVENTS_PER_BLOCK),
+                    modulation,
+                    editor_presence: ::std::default::Default::default(),
+                    sample_rate: ::std::default::Default::default(),
+                    analysis_active: false,
                     oscilloscope_tap: #krate::OscilloscopeTap::with_output(oscilloscope_producer),
+                    spectrum_analyzer: #krate::SpectrumAnalyzer::with_output(spectrum_producer),
+                    meters: #krate::MeterEngine::new(44_100.0, 2),
                     meter_producer,
                     #[cfg(any(target_os = "macos", target_os = "windows"))]
                     meter_consumer: ::std::sync::Mutex::new(::std::option::Option::Some(_meter_consumer)),
                     #[cfg(any(target_os = "macos", target_os = "windows"))]
                     oscilloscope_consumer: ::std::sync::Mutex::new(::std::option::Option::Some(_oscilloscope_consumer)),
+                    #[cfg(any(target_os = "macos", target_os = "windows"))]
+                    spectrum_consumer: ::std::sync::Mutex::new(::std::option::Option::Some(_spectrum_consumer)),
+                    #[cfg(any(target_os = "macos", target_os = "windows"))]
+                    metering_taps,
                 }
             }
         }
@@ -160,15 +495,10 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
             const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
             const AUDIO_IO_LAYOUTS: &'static [#krate::__nih::AudioIOLayout] = &[
-                #krate::__nih::AudioIOLayout {
-                    main_input_channels: ::std::num::NonZeroU32::new(2),
-                    main_output_channels: ::std::num::NonZeroU32::new(2),
-                    ..#krate::__nih::AudioIOLayout::const_default()
-                }
+                #audio_io_layouts
             ];
 
-            const MIDI_INPUT: #krate::__nih::MidiConfig =
-                #krate::__nih::MidiConfig::None;
+            const MIDI_INPUT: #krate::__nih::MidiConfig = #midi_input;
             const MIDI_OUTPUT: #krate::__nih::MidiConfig =
                 #krate::__nih::MidiConfig::None;
 
@@ -185,23 +515,32 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
             ) -> ::std::option::Option<::std::boxed::Box<dyn #krate::__nih::Editor>> {
                 #[cfg(any(target_os = "macos", target_os = "windows"))]
                 {
-                    let meter_consumer = self
-                        .meter_consumer
-                        .lock()
-                        .expect("meter_consumer mutex poisoned - previous panic in editor thread")
-                        .take();
-                    let oscilloscope_consumer = self
-                        .oscilloscope_consumer
-                        .lock()
-                        .expect("oscilloscope_consumer mutex poisoned - previous panic in editor thread")
-                        .take();
-                    #krate::editor::create_webview_editor(
-                        self.params.clone(),
-                        meter_consumer,
-                        oscilloscope_consumer,
-                        800,
-                        600,
-                    )
+                    let channels = #krate::editor::EditorChannels {
+                        meter_consumer: self
+                            .meter_consumer
+                            .lock()
+                            .expect("meter_consumer mutex poisoned - previous panic in editor thread")
+                            .take(),
+                        oscilloscope_consumer: self
+                            .oscilloscope_consumer
+                            .lock()
+                            .expect("oscilloscope_consumer mutex poisoned - previous panic in editor thread")
+                            .take(),
+                        spectrum_consumer: self
+                            .spectrum_consumer
+                            .lock()
+                            .expect("spectrum_consumer mutex poisoned - previous panic in editor thread")
+                            .take(),
+                        metering_taps: self.metering_taps.clone(),
+                        presets: ::std::sync::Arc::new(
+                            #krate::__internal::PresetLibrary::from_catalog(__wavecraft_preset_catalog()),
+                        ),
+                        modulation: self.params.modulation.clone(),
+                        output_stats: self.output_sanitizer.stats(),
+                        presence: self.editor_presence.clone(),
+                        sample_rate: self.sample_rate.clone(),
+                    };
+                    #krate::editor::create_webview_editor(self.params.clone(), channels, 800, 600)
                 }
 
                 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
@@ -212,159 +551,160 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
 
             fn initialize(
                 &mut self,
-                _audio_io_layout: &#krate::__nih::AudioIOLayout,
+                audio_io_layout: &#krate::__nih::AudioIOLayout,
                 _buffer_config: &#krate::__nih::BufferConfig,
-                _context: &mut impl #krate::__nih::InitContext<Self>,
+                context: &mut impl #krate::__nih::InitContext<Self>,
             ) -> bool {
+                self.channel_layout =
+                    #krate::__internal::channel_layout_from_host(audio_io_layout);
+                #krate::Processor::set_channel_layout(&mut self.processor, self.channel_layout);
                 #krate::Processor::set_sample_rate(
                     &mut self.processor,
                     _buffer_config.sample_rate,
                 );
+                self.reported_latency = #krate::Processor::latency_samples(&self.processor);
+                context.set_latency_samples(self.reported_latency);
+                self.modulation.set_sample_rate(_buffer_config.sample_rate);
+                self.sample_rate.set(_buffer_config.sample_rate);
                 self.oscilloscope_tap
                     .set_sample_rate_hz(_buffer_config.sample_rate);
+                self.spectrum_analyzer
+                    .set_sample_rate_hz(_buffer_config.sample_rate);
+                self.meters = #krate::MeterEngine::new(
+                    _buffer_config.sample_rate,
+                    self.channel_layout.outputs as usize,
+                );
+                self.offline_render = _buffer_config.process_mode
+                    == #krate::__nih::ProcessMode::Offline;
                 true
             }
 
             fn reset(&mut self) {
                 #krate::Processor::reset(&mut self.processor);
                 #krate::Processor::reset(&mut self.oscilloscope_tap);
+                #krate::Processor::reset(&mut self.spectrum_analyzer);
+                self.modulation.reset();
+                self.meters.reset();
+                self.tail_tracker.reset();
             }
 
             fn process(
                 &mut self,
                 buffer: &mut #krate::__nih::Buffer,
                 _aux: &mut #krate::__nih::AuxiliaryBuffers,
-                _context: &mut impl #krate::__nih::ProcessContext<Self>,
+                context: &mut impl #krate::__nih::ProcessContext<Self>,
             ) -> #krate::__nih::ProcessStatus {
-                let num_samples = buffer.samples();
-                let channels = buffer.channels();
-
-                // Build processor params from current parameter values
-                let processor_params = self.build_processor_params();
-
-                // Convert nih-plug buffer to wavecraft-dsp format
-                // We process sample-by-sample to properly handle the buffer format
-                for sample_idx in 0..num_samples {
-                    // Create a temporary buffer for this sample
-                    let mut sample_buffers: ::std::vec::Vec<::std::vec::Vec<f32>> =
-                        (0..channels).map(|ch| {
-                            vec![buffer.as_slice()[ch][sample_idx]]
-                        }).collect();
-
-                    let mut sample_ptrs: ::std::vec::Vec<&mut [f32]> =
-                        sample_buffers.iter_mut().map(|v| &mut v[..]).collect();
+                // Flush subnormals to zero for the rest of this call.
+                let _denormals = #krate::__internal::DenormalGuard::new();
 
-                    let transport = #krate::Transport::default();
+                let transport = #krate::__internal::transport_from_host(
+                    context.transport(),
+                    self.offline_render,
+                );
 
-                    // Import Processor trait for process() method
-                    use #krate::Processor as _;
-                    self.processor.process(&mut sample_ptrs, &transport, &processor_params);
-
-                    // Write processed samples back
-                    for (ch, sample_buf) in sample_buffers.iter().enumerate() {
-                        if let Some(channel) = buffer.as_slice().get(ch) {
-                            if sample_idx < channel.len() {
-                                // SAFETY JUSTIFICATION:
-                                //
-                                // 1. Exclusive Access: nih-plug's process() callback guarantees exclusive
-                                //    buffer access (no concurrent reads/writes from other threads).
-                                //
-                                // 2. Bounds Check: The `if` guards above ensure:
-                                //    - `ch` is a valid channel index (within buffer.channels())
-                                //    - `sample_idx < channel.len()` (within channel sample count)
-                                //
-                                // 3. Pointer Validity:
-                                //    - `channel.as_ptr()` is from nih-plug's Buffer allocation (valid)
-                                //    - `.add(sample_idx)` offset is within bounds (checked above)
-                                //    - Pointer is properly aligned (f32 alignment guaranteed by host)
-                                //
-                                // 4. Write Safety:
-                                //    - f32 is Copy (atomic write, no drop required)
-                                //    - No aliasing: Buffer<'a> lifetime ensures no other refs exist
-                                //    - Host expects in-place modification (plugin contract)
-                                //
-                                // 5. Why unsafe is necessary:
-                                //    nih-plug's Buffer API only provides immutable refs (as_slice()).
-                                //    However, the plugin contract allows (and expects) in-place writes.
-                                //    Casting *const → *mut is sound because we have exclusive access
-                                //    during process() callback (guaranteed by DAW host).
-                                unsafe {
-                                    let channel_ptr = channel.as_ptr() as *mut f32;
-                                    *channel_ptr.add(sample_idx) = sample_buf[0];
-                                }
-                            }
-                        }
+                // Drain host note events into the preallocated buffer. Events
+                // arrive sorted by sample offset.
+                self.events.clear();
+                while let Some(event) = context.next_event() {
+                    if self.events.len() == self.events.capacity() {
+                        continue;
+                    }
+                    if let Some(event) = #krate::__internal::note_event_from_host(&event) {
+                        self.events.push(event);
                     }
                 }
 
-                // Update meters (simplified - just measure output peaks)
-                let mut peak_left = 0.0_f32;
-                let mut peak_right = 0.0_f32;
-
-                if channels >= 1 {
-                    peak_left = buffer.as_slice()[0].iter().map(|&s| s.abs()).fold(0.0, f32::max);
-                }
-                if channels >= 2 {
-                    peak_right = buffer.as_slice()[1].iter().map(|&s| s.abs()).fold(0.0, f32::max);
+                // Hand the whole block to the processor chain in one call.
+                // `Buffer::as_slice()` already exposes nih-plug's storage as
+                // `&mut [&mut [f32]]`, so no copies are required.
+                let block_len = buffer.samples();
+                let sidechain: &[&mut [f32]] = #sidechain_channels;
+                let channels = buffer.as_slice();
+                #clear_output
+                #upmix
+
+                // Run modulation sources on the unprocessed input, then
+                // refresh processor params in place from current parameter
+                // values plus modulation.
+                self.modulation.advance(channels, &transport);
+                self.update_processor_params();
+
+                #silence_before
+                #krate::Processor::process_with_sidechain(
+                    &mut self.processor,
+                    channels,
+                    sidechain,
+                    &self.events,
+                    &transport,
+                    &self.processor_params,
+                );
+                if self.output_sanitizer.process(channels) {
+                    #krate::Processor::reset(&mut self.processor);
                 }
+                #silence_after
+
+                // Meters, oscilloscope and spectrum only feed the editor, so
+                // headless hosts and offline renders skip them entirely.
+                let analysis_active = self.editor_presence.is_open();
+                if analysis_active {
+                    if !self.analysis_active {
+                        self.meters.reset();
+                        #krate::Processor::reset(&mut self.oscilloscope_tap);
+                        #krate::Processor::reset(&mut self.spectrum_analyzer);
+                    }
 
-                if channels >= 1 {
-                    let left_snapshot: ::std::vec::Vec<f32> = buffer.as_slice()[0].to_vec();
-                    let right_snapshot: ::std::vec::Vec<f32> = if channels >= 2 {
-                        buffer.as_slice()[1].to_vec()
-                    } else {
-                        left_snapshot.clone()
-                    };
+                    // Measure the processed output. Mono layouts show the
+                    // same level on both meters.
+                    self.meters.process(channels);
 
-                    self.oscilloscope_tap
-                        .capture_stereo(&left_snapshot, &right_snapshot);
-                }
+                    // Capture directly from the processed buffer (mono is
+                    // mirrored to both oscilloscope and analyzer channels).
+                    if let Some(left) = channels.first() {
+                        let right = channels.get(1).unwrap_or(left);
+                        self.oscilloscope_tap.capture_stereo(left, right);
+                        self.spectrum_analyzer.capture_stereo(left, right);
+                    }
 
-                let frame = #krate::MeterFrame {
-                    peak_l: peak_left,
-                    peak_r: peak_right,
-                    // Simplified RMS estimation: peak * 1/√2 (0.707)
-                    // This is exact for sine waves but approximate for other signals.
-                    // Acceptable for basic metering; for accurate RMS, use sliding window average.
-                    rms_l: peak_left * 0.707,
-                    rms_r: peak_right * 0.707,
-                    // Note: Timestamp not implemented for DSL plugins.
-                    // Basic metering doesn't require sample-accurate timing.
-                    // For advanced metering with sample position tracking,
-                    // implement Plugin trait directly and use context.transport().
-                    timestamp: 0,
-                };
+                    self.meter_producer.push(self.meters.frame());
+                }
+                self.analysis_active = analysis_active;
 
-                let _ = self.meter_producer.push(frame);
+                // Report latency changes (e.g. a lookahead time parameter).
+                let latency = #krate::Processor::latency_samples(&self.processor);
+                if latency != self.reported_latency {
+                    self.reported_latency = latency;
+                    context.set_latency_samples(latency);
+                }
 
-                #krate::__nih::ProcessStatus::Normal
+                // Let the host suspend the plugin once the tail has rung out.
+                let tail_state = self.tail_tracker.advance(
+                    input_silent,
+                    block_len,
+                    #krate::Processor::tail_samples(&self.processor),
+                );
+                #krate::__internal::process_status_for_tail(tail_state, #is_instrument)
             }
         }
 
         impl __WavecraftPlugin {
-            /// Build processor parameters from current nih-plug parameter values.
-            ///
-            /// # Known Limitation
+            /// Refresh the cached processor parameters from current nih-plug
+            /// parameter values.
             ///
-            /// Full bidirectional parameter sync between nih-plug and processor
-            /// params is implemented by applying plain host values in the same
-            /// order as `ProcessorParams::param_specs()`.
-            fn build_processor_params(&self) -> <__ProcessorType as #krate::Processor>::Params {
-                let mut params =
-                    <<__ProcessorType as #krate::Processor>::Params as #krate::ProcessorParams>::from_param_defaults();
-                let values: ::std::vec::Vec<f32> = self
-                    .params
-                    .params
-                    .iter()
-                    .map(|param| param.modulated_plain_value())
-                    .collect();
+            /// Plain host values (including host modulation) are copied into
+            /// preallocated scratch storage, offset by the plugin's own
+            /// modulation routings and applied in the same order as
+            /// `ProcessorParams::param_specs()`, so this is allocation-free and
+            /// safe to call from the audio thread.
+            fn update_processor_params(&mut self) {
+                for (slot, param) in self.plain_values.iter_mut().zip(self.params.params.iter()) {
+                    *slot = param.modulated_plain_value();
+                }
+                self.modulation.apply(&mut self.plain_values);
 
                 <<__ProcessorType as #krate::Processor>::Params as #krate::ProcessorParams>::apply_plain_values(
-                    &mut params,
-                    &values,
+                    &mut self.processor_params,
+                    &self.plain_values,
                 );
-
-                params
             }
         }
 
@@ -374,15 +714,14 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
             const CLAP_MANUAL_URL: Option<&'static str> = None;
             const CLAP_SUPPORT_URL: Option<&'static str> = None;
             const CLAP_FEATURES: &'static [#krate::__nih::ClapFeature] = &[
-                #krate::__nih::ClapFeature::AudioEffect,
-                #krate::__nih::ClapFeature::Stereo,
+                #clap_features
             ];
         }
 
         impl #krate::__nih::Vst3Plugin for __WavecraftPlugin {
             const VST3_CLASS_ID: [u8; 16] = #vst3_id;
             const VST3_SUBCATEGORIES: &'static [#krate::__nih::Vst3SubCategory] = &[
-                #krate::__nih::Vst3SubCategory::Fx,
+                #vst3_subcategories
             ];
         }
 
@@ -394,6 +733,24 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
         #[cfg(not(feature = "_param-discovery"))]
         #krate::__nih::nih_export_vst3!(__WavecraftPlugin);
 
+        // ================================================================
+        // Factory presets
+        // ================================================================
+
+        /// Factory preset files as `(path, json)`, embedded at compile time.
+        const __WAVECRAFT_FACTORY_PRESETS: &[(&str, &str)] = &[
+            #(#factory_presets),*
+        ];
+
+        fn __wavecraft_preset_catalog() -> #krate::__internal::PresetCatalog {
+            #krate::__internal::preset_catalog(
+                __WAVECRAFT_FACTORY_PRESETS,
+                #vendor,
+                #name,
+                env!("CARGO_PKG_VERSION"),
+            )
+        }
+
         // ================================================================
         // FFI Exports for Parameter Discovery (used by `wavecraft start`)
         // ================================================================
@@ -444,6 +801,24 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                 .unwrap_or(::std::ptr::null_mut())
         }
 
+        /// Returns the JSON-serialized preset catalog (factory presets, user
+        /// preset directory and plugin version).
+        ///
+        /// This function is called by `wavecraft start` so the dev server can
+        /// serve the same presets as the plugin editor.
+        ///
+        /// # Safety
+        /// The returned pointer must be freed with `wavecraft_free_string`.
+        #[unsafe(no_mangle)]
+        pub extern "C" fn wavecraft_get_presets_json() -> *mut ::std::ffi::c_char {
+            let json = #krate::__internal::serde_json::to_string(&__wavecraft_preset_catalog())
+                .unwrap_or_else(|_| "{}".to_string());
+
+            ::std::ffi::CString::new(json)
+                .map(|s| s.into_raw())
+                .unwrap_or(::std::ptr::null_mut())
+        }
+
         /// Frees a string returned by `wavecraft_get_params_json`.
         ///
         /// # Safety
@@ -470,6 +845,7 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
 
             type __P = __ProcessorType;
             type __Params = <__P as #krate::Processor>::Params;
+            const __MAX_CHANNELS: usize = #krate::__internal::DEV_PROCESSOR_MAX_CHANNELS as usize;
             struct __DevProcessorInstance {
                 processor: __P,
                 params: __Params,
@@ -477,10 +853,14 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
 
             extern "C" fn create() -> *mut c_void {
                 let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
-                    let instance = ::std::boxed::Box::new(__DevProcessorInstance {
+                    let mut instance = ::std::boxed::Box::new(__DevProcessorInstance {
                         processor: <__P as ::std::default::Default>::default(),
                         params: <__Params as #krate::ProcessorParams>::from_param_defaults(),
                     });
+                    #krate::Processor::set_channel_layout(
+                        &mut instance.processor,
+                        #krate::ChannelLayout::new(#default_num_inputs, #default_num_outputs),
+                    );
                     ::std::boxed::Box::into_raw(instance) as *mut c_void
                 }));
                 match result {
@@ -489,61 +869,85 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                 }
             }
 
+            /// Borrow raw dev-FFI channel pointers as slices.
+            ///
+            /// Buses wider than `DEV_PROCESSOR_MAX_CHANNELS` (or with a null
+            /// channel pointer) are rejected.
+            unsafe fn channel_slices<'a>(
+                channels: *mut *mut f32,
+                num_channels: u32,
+                num_samples: usize,
+            ) -> ::std::option::Option<([&'a mut [f32]; __MAX_CHANNELS], usize)> {
+                let count = num_channels as usize;
+                if count > __MAX_CHANNELS || (count > 0 && channels.is_null()) {
+                    return ::std::option::Option::None;
+                }
+
+                let mut slices: [&'a mut [f32]; __MAX_CHANNELS] =
+                    ::std::default::Default::default();
+                for (index, slot) in slices.iter_mut().enumerate().take(count) {
+                    let ptr = unsafe { *channels.add(index) };
+                    if ptr.is_null() {
+                        return ::std::option::Option::None;
+                    }
+                    *slot = unsafe { ::std::slice::from_raw_parts_mut(ptr, num_samples) };
+                }
+                ::std::option::Option::Some((slices, count))
+            }
+
             extern "C" fn process(
                 instance: *mut c_void,
                 channels: *mut *mut f32,
                 num_channels: u32,
                 num_samples: u32,
+            ) {
+                process_with_sidechain(
+                    instance,
+                    channels,
+                    num_channels,
+                    ::std::ptr::null_mut(),
+                    0,
+                    num_samples,
+                );
+            }
+
+            extern "C" fn process_with_sidechain(
+                instance: *mut c_void,
+                channels: *mut *mut f32,
+                num_channels: u32,
+                sidechain: *mut *mut f32,
+                num_sidechain_channels: u32,
+                num_samples: u32,
             ) {
                 let _ = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
                     if instance.is_null() || channels.is_null() || num_channels == 0 || num_samples == 0 {
                         return;
                     }
                     let instance = unsafe { &mut *(instance as *mut __DevProcessorInstance) };
-                    let num_ch = num_channels as usize;
                     let num_samp = num_samples as usize;
 
                     let transport = #krate::Transport::default();
 
                     // Build stack-local channel slices for the current block.
-                    // Wavecraft dev audio currently targets mono/stereo.
-                    match num_ch {
-                        1 => {
-                            let ch0_ptr = unsafe { *channels.add(0) };
-                            if ch0_ptr.is_null() {
-                                return;
-                            }
-
-                            let ch0 = unsafe { ::std::slice::from_raw_parts_mut(ch0_ptr, num_samp) };
-                            let mut channel_slices: [&mut [f32]; 1] = [ch0];
-                            #krate::Processor::process(
-                                &mut instance.processor,
-                                &mut channel_slices,
-                                &transport,
-                                &instance.params,
-                            );
-                        }
-                        2 => {
-                            let ch0_ptr = unsafe { *channels.add(0) };
-                            let ch1_ptr = unsafe { *channels.add(1) };
-                            if ch0_ptr.is_null() || ch1_ptr.is_null() {
-                                return;
-                            }
-
-                            let ch0 = unsafe { ::std::slice::from_raw_parts_mut(ch0_ptr, num_samp) };
-                            let ch1 = unsafe { ::std::slice::from_raw_parts_mut(ch1_ptr, num_samp) };
-                            let mut channel_slices: [&mut [f32]; 2] = [ch0, ch1];
-                            #krate::Processor::process(
-                                &mut instance.processor,
-                                &mut channel_slices,
-                                &transport,
-                                &instance.params,
-                            );
-                        }
-                        _ => {
-                            // Unsupported channel topology in dev-FFI path.
-                        }
-                    }
+                    let ::std::option::Option::Some((mut main, main_count)) =
+                        (unsafe { channel_slices(channels, num_channels, num_samp) })
+                    else {
+                        // Unsupported channel topology in dev-FFI path.
+                        return;
+                    };
+                    // An unusable sidechain is dropped rather than skipping the block.
+                    let (side, side_count) =
+                        unsafe { channel_slices(sidechain, num_sidechain_channels, num_samp) }
+                            .unwrap_or((::std::default::Default::default(), 0));
+
+                    #krate::Processor::process_with_sidechain(
+                        &mut instance.processor,
+                        &mut main[..main_count],
+                        &side[..side_count],
+                        &[],
+                        &transport,
+                        &instance.params,
+                    );
                 }));
                 // If panic occurred, audio buffer is left unmodified (pass-through)
             }
@@ -586,6 +990,23 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                 }));
             }
 
+            extern "C" fn set_channel_layout(
+                instance: *mut c_void,
+                num_inputs: u32,
+                num_outputs: u32,
+            ) {
+                let _ = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                    if instance.is_null() {
+                        return;
+                    }
+                    let instance = unsafe { &mut *(instance as *mut __DevProcessorInstance) };
+                    #krate::Processor::set_channel_layout(
+                        &mut instance.processor,
+                        #krate::ChannelLayout::new(num_inputs, num_outputs),
+                    );
+                }));
+            }
+
             extern "C" fn reset(instance: *mut c_void) {
                 let _ = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
                     if instance.is_null() {
@@ -614,6 +1035,10 @@ pub(super) fn generate_plugin_code(input: CodegenInput<'_>) -> proc_macro2::Toke
                 set_sample_rate,
                 reset,
                 drop: drop_fn,
+                process_with_sidechain,
+                default_num_inputs: #default_num_inputs,
+                default_num_outputs: #default_num_outputs,
+                set_channel_layout,
             }
         }
     }
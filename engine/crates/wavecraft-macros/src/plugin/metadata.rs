@@ -92,12 +92,16 @@ pub(super) fn processor_info_entries(
     signal_processors: &[Type],
     krate: &Path,
 ) -> Vec<proc_macro2::TokenStream> {
-    naming::instance_id_prefixes(signal_processors)
-        .into_iter()
-        .map(|processor_id| {
+    signal_processors
+        .iter()
+        .zip(naming::instance_id_prefixes(signal_processors))
+        .map(|(processor_type, processor_id)| {
             quote! {
                 #krate::__internal::ProcessorInfo {
                     id: #processor_id.to_string(),
+                    latency_samples: #krate::Processor::latency_samples(
+                        &<#processor_type as ::std::default::Default>::default(),
+                    ),
                 }
             }
         })
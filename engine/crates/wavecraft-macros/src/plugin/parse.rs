@@ -6,10 +6,234 @@ use syn::{
     spanned::Spanned,
 };
 
+/// Plugin category selected with the optional `kind` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum PluginKind {
+    /// Audio effect: main input and output, no MIDI input.
+    #[default]
+    Effect,
+    /// Instrument: main output only, receives note/MIDI CC events.
+    Instrument,
+}
+
+impl Parse for PluginKind {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "effect" => Ok(Self::Effect),
+            "instrument" => Ok(Self::Instrument),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "unknown plugin kind: `{}`\n\
+                     \n\
+                     Expected one of:\n\
+                     - kind: effect (default)\n\
+                     - kind: instrument",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Auxiliary sidechain input selected with the optional `sidechain` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum Sidechain {
+    /// No sidechain bus.
+    #[default]
+    None,
+    /// One-channel sidechain bus.
+    Mono,
+    /// Two-channel sidechain bus.
+    Stereo,
+}
+
+impl Sidechain {
+    /// Channel count of the auxiliary input bus, if any.
+    pub(super) fn channels(self) -> Option<u32> {
+        match self {
+            Self::None => None,
+            Self::Mono => Some(1),
+            Self::Stereo => Some(2),
+        }
+    }
+}
+
+impl Parse for Sidechain {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "mono" => Ok(Self::Mono),
+            "stereo" => Ok(Self::Stereo),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "unknown sidechain layout: `{}`\n\
+                     \n\
+                     Expected one of:\n\
+                     - sidechain: mono\n\
+                     - sidechain: stereo",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Handling of NaN/Inf output selected with the optional `on_non_finite` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum OnNonFinite {
+    /// Replace non-finite samples with silence.
+    #[default]
+    Replace,
+    /// Replace non-finite samples and reset the processor chain.
+    Reset,
+}
+
+impl Parse for OnNonFinite {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "replace" => Ok(Self::Replace),
+            "reset" => Ok(Self::Reset),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "unknown non-finite output handling: `{}`\n\
+                     \n\
+                     Expected one of:\n\
+                     - on_non_finite: replace (default)\n\
+                     - on_non_finite: reset",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Main-bus channel layout listed in the optional `layouts` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Layout {
+    Mono,
+    Stereo,
+    MonoToStereo,
+    Surround51,
+    Surround71,
+}
+
+impl Layout {
+    /// Main input and output channel counts.
+    pub(super) fn channels(self) -> (u32, u32) {
+        match self {
+            Self::Mono => (1, 1),
+            Self::Stereo => (2, 2),
+            Self::MonoToStereo => (1, 2),
+            Self::Surround51 => (6, 6),
+            Self::Surround71 => (8, 8),
+        }
+    }
+}
+
+impl Parse for Layout {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "mono" => Ok(Self::Mono),
+            "stereo" => Ok(Self::Stereo),
+            "mono_to_stereo" => Ok(Self::MonoToStereo),
+            "surround_5_1" => Ok(Self::Surround51),
+            "surround_7_1" => Ok(Self::Surround71),
+            other => Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "unknown channel layout: `{}`\n\
+                     \n\
+                     Expected a list of:\n\
+                     - mono\n\
+                     - stereo\n\
+                     - mono_to_stereo\n\
+                     - surround_5_1\n\
+                     - surround_7_1",
+                    other
+                ),
+            )),
+        }
+    }
+}
+
+/// Parse the bracketed `layouts: [stereo, mono, ...]` list.
+fn parse_layouts(input: ParseStream) -> Result<(Vec<Layout>, proc_macro2::Span)> {
+    let content;
+    let bracket = syn::bracketed!(content in input);
+    let span = bracket.span.join();
+    let parsed = Punctuated::<Layout, Token![,]>::parse_terminated(&content)?;
+
+    let mut layouts = Vec::new();
+    for layout in parsed {
+        if layouts.contains(&layout) {
+            return Err(syn::Error::new(span, "duplicate entry in `layouts`"));
+        }
+        layouts.push(layout);
+    }
+
+    if layouts.is_empty() {
+        return Err(syn::Error::new(
+            span,
+            "`layouts` must list at least one channel layout, e.g. `layouts: [stereo]`",
+        ));
+    }
+
+    Ok((layouts, span))
+}
+
+/// Parse the bracketed `presets: ["presets/init.json", ...]` list of factory
+/// preset files, relative to the plugin crate's `Cargo.toml`.
+fn parse_presets(input: ParseStream) -> Result<Vec<LitStr>> {
+    let content;
+    syn::bracketed!(content in input);
+    let parsed = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+
+    let mut presets: Vec<LitStr> = Vec::new();
+    for path in parsed {
+        let value = path.value();
+        if value.trim().is_empty() {
+            return Err(syn::Error::new(
+                path.span(),
+                "preset path must not be empty",
+            ));
+        }
+        if value.starts_with(['/', '\\']) || std::path::Path::new(&value).is_absolute() {
+            return Err(syn::Error::new(
+                path.span(),
+                "preset paths are relative to the plugin crate's Cargo.toml, \
+                 e.g. \"presets/init.json\"",
+            ));
+        }
+        if presets.iter().any(|existing| existing.value() == value) {
+            return Err(syn::Error::new(path.span(), "duplicate entry in `presets`"));
+        }
+        presets.push(path);
+    }
+
+    Ok(presets)
+}
+
 /// Input structure for `wavecraft_plugin!` macro.
 pub(super) struct PluginDef {
     pub(super) name: LitStr,
     pub(super) signal: Expr,
+    /// Plugin category (default: effect).
+    pub(super) kind: PluginKind,
+    /// Auxiliary sidechain input (default: none).
+    pub(super) sidechain: Sidechain,
+    /// Supported main-bus layouts; the first is the host default
+    /// (default: `[stereo]`).
+    pub(super) layouts: Vec<Layout>,
+    /// Factory preset JSON files embedded into the plugin (default: none).
+    pub(super) presets: Vec<LitStr>,
+    /// What to do when the processor outputs NaN/Inf (default: replace).
+    pub(super) on_non_finite: OnNonFinite,
     /// Optional crate path for nih-plug integration crate (default: `::wavecraft`).
     /// Use `crate: my_name` only if you've renamed the wavecraft dependency in Cargo.toml.
     pub(super) krate: Option<Path>,
@@ -19,6 +243,11 @@ impl Parse for PluginDef {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut name = None;
         let mut signal = None;
+        let mut kind = None;
+        let mut sidechain = None;
+        let mut layouts = None;
+        let mut presets = None;
+        let mut on_non_finite = None;
         let mut krate = None;
 
         // Parse key-value pairs
@@ -41,6 +270,11 @@ impl Parse for PluginDef {
             match key.to_string().as_str() {
                 "name" => name = Some(input.parse()?),
                 "signal" => signal = Some(input.parse()?),
+                "kind" => kind = Some(input.parse()?),
+                "sidechain" => sidechain = Some(input.parse()?),
+                "layouts" => layouts = Some(parse_layouts(input)?),
+                "presets" => presets = Some(parse_presets(input)?),
+                "on_non_finite" => on_non_finite = Some(input.parse()?),
                 _ => {
                     return Err(syn::Error::new(
                         key.span(),
@@ -50,6 +284,11 @@ impl Parse for PluginDef {
                              The wavecraft_plugin! macro only accepts:\n\
                              - name: \"Plugin Name\" (required)\n\
                              - signal: SignalChain![...] (required)\n\
+                             - kind: effect | instrument (optional, default: effect)\n\
+                             - sidechain: mono | stereo (optional, default: none)\n\
+                             - layouts: [stereo, mono, ...] (optional, default: [stereo])\n\
+                             - presets: [\"presets/init.json\", ...] (optional, default: none)\n\
+                             - on_non_finite: replace | reset (optional, default: replace)\n\
                              - crate: custom_name (optional, for Cargo renames)",
                             key
                         ),
@@ -98,6 +337,22 @@ impl Parse for PluginDef {
             ));
         }
 
+        let kind: PluginKind = kind.unwrap_or_default();
+        let layouts = match layouts {
+            Some((layouts, span)) => {
+                if kind == PluginKind::Instrument && layouts.contains(&Layout::MonoToStereo) {
+                    return Err(syn::Error::new(
+                        span,
+                        "`mono_to_stereo` needs a main input, which instruments don't have.\n\
+                         \n\
+                         Use `mono` or `stereo` to choose the instrument's output channels.",
+                    ));
+                }
+                layouts
+            }
+            None => vec![Layout::Stereo],
+        };
+
         Ok(PluginDef {
             name: name.ok_or_else(|| {
                 input.error(
@@ -111,6 +366,11 @@ impl Parse for PluginDef {
                 )
             })?,
             signal,
+            kind,
+            sidechain: sidechain.unwrap_or_default(),
+            layouts,
+            presets: presets.unwrap_or_default(),
+            on_non_finite: on_non_finite.unwrap_or_default(),
             // Default krate to ::wavecraft if not specified
             krate: krate.or_else(|| Some(syn::parse_quote!(::wavecraft))),
         })
@@ -112,6 +112,10 @@ pub(super) fn runtime_param_blocks(
 
                         ids.push(format!("{}_{}", #id_prefix, spec.id_suffix));
                         groups.push(spec.group.unwrap_or_default().to_string());
+                        skews.push(match spec.range {
+                            ParamRange::Skewed { factor, .. } => factor as f32,
+                            _ => 1.0,
+                        });
                     }
                 }
             }
@@ -8,6 +8,9 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DeriveInput, Expr, ExprLit, Fields, Lit, LitStr, parse_macro_input};
 
+/// Ramp time used when `smoothing` is set without `smoothing_ms`.
+const DEFAULT_SMOOTHING_MS: f64 = 10.0;
+
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -104,6 +107,21 @@ fn expand_derive(input: &DeriveInput) -> syn::Result<TokenStream> {
         }
     });
 
+    let smoothing_items = param_specs
+        .iter()
+        .filter_map(|spec| {
+            let style = spec.smoothing.as_ref()?;
+            let id_suffix = &spec.id_suffix;
+            Some(quote! {
+                ::wavecraft::ParamSmoothing {
+                    id_suffix: #id_suffix,
+                    style: #style,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let smoothing_count = smoothing_items.len();
+
     Ok(quote! {
         impl ::wavecraft::ProcessorParams for #struct_name {
             fn param_specs() -> &'static [::wavecraft::ParamSpec] {
@@ -122,6 +140,13 @@ fn expand_derive(input: &DeriveInput) -> syn::Result<TokenStream> {
             fn apply_plain_values(&mut self, values: &[f32]) {
                 #(#runtime_value_updates)*
             }
+
+            fn param_smoothing() -> &'static [::wavecraft::ParamSmoothing] {
+                static SMOOTHING: [::wavecraft::ParamSmoothing; #smoothing_count] = [
+                    #(#smoothing_items),*
+                ];
+                &SMOOTHING
+            }
         }
     })
 }
@@ -133,6 +158,7 @@ struct ParamSpecData {
     default: f64,
     unit: String,
     group: Option<String>,
+    smoothing: Option<TokenStream>,
 }
 
 fn parse_param_attr(field_name: &str, attr: &syn::Attribute) -> syn::Result<ParamSpecData> {
@@ -143,6 +169,8 @@ fn parse_param_attr(field_name: &str, attr: &syn::Attribute) -> syn::Result<Para
     let mut default: Option<f64> = None;
     let mut unit: Option<String> = None;
     let mut group: Option<String> = None;
+    let mut smoothing: Option<String> = None;
+    let mut smoothing_ms: Option<f64> = None;
 
     // Parse nested meta items using parse_nested_meta
     attr.parse_nested_meta(|meta| {
@@ -266,11 +294,44 @@ fn parse_param_attr(field_name: &str, attr: &syn::Attribute) -> syn::Result<Para
                     return Err(meta.error("Expected string literal for group"));
                 }
             }
+            "smoothing" => {
+                let value: Expr = meta.value()?.parse()?;
+                if let Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) = value
+                {
+                    smoothing = Some(lit_str.value());
+                } else {
+                    return Err(meta.error("Expected string literal for smoothing"));
+                }
+            }
+            "smoothing_ms" => {
+                let value: Expr = meta.value()?.parse()?;
+                let ms = match value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Float(lit_float),
+                        ..
+                    }) => lit_float.base10_parse()?,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(lit_int),
+                        ..
+                    }) => lit_int.base10_parse::<i64>()? as f64,
+                    _ => return Err(meta.error("Expected number for smoothing_ms")),
+                };
+                if !ms.is_finite() || ms < 0.0 {
+                    return Err(meta.error("smoothing_ms must be a non-negative number"));
+                }
+                smoothing_ms = Some(ms);
+            }
             _ => return Err(meta.error("Unknown param attribute")),
         }
         Ok(())
     })?;
 
+    let is_enum = variants.is_some();
+    let smoothing_tokens = parse_smoothing(attr, smoothing, smoothing_ms, is_enum)?;
+
     let (range_tokens, default_val) = if let Some(variant_list) = variants {
         if range_min.is_some() || range_max.is_some() {
             return Err(syn::Error::new_spanned(
@@ -365,9 +426,52 @@ fn parse_param_attr(field_name: &str, attr: &syn::Attribute) -> syn::Result<Para
         default: default_val,
         unit: unit_str,
         group,
+        smoothing: smoothing_tokens,
     })
 }
 
+fn parse_smoothing(
+    attr: &syn::Attribute,
+    style: Option<String>,
+    time_ms: Option<f64>,
+    is_enum: bool,
+) -> syn::Result<Option<TokenStream>> {
+    let Some(style) = style else {
+        if time_ms.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "'smoothing_ms' requires 'smoothing' (\"linear\", \"exp\" or \"log\")",
+            ));
+        }
+        return Ok(None);
+    };
+
+    if is_enum {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "'smoothing' cannot be used with 'variants'",
+        ));
+    }
+
+    let ms = time_ms.unwrap_or(DEFAULT_SMOOTHING_MS) as f32;
+    let tokens = match style.as_str() {
+        "none" => quote! { ::wavecraft::SmoothingStyle::None },
+        "linear" => quote! { ::wavecraft::SmoothingStyle::Linear(#ms) },
+        "exp" | "exponential" => quote! { ::wavecraft::SmoothingStyle::Exponential(#ms) },
+        "log" | "logarithmic" => quote! { ::wavecraft::SmoothingStyle::Logarithmic(#ms) },
+        other => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "Unknown smoothing style '{other}': expected \"none\", \"linear\", \"exp\" or \"log\""
+                ),
+            ));
+        }
+    };
+
+    Ok(Some(tokens))
+}
+
 #[cfg(test)]
 mod tests {
     use super::parse_param_attr;
@@ -403,4 +507,56 @@ mod tests {
 
         assert!(err.to_string().contains("out of range"));
     }
+
+    #[test]
+    fn smoothing_defaults_to_ten_milliseconds() {
+        let attr: syn::Attribute =
+            parse_quote!(#[param(range = "0.0..=1.0", smoothing = "linear")]);
+        let spec = parse_param_attr("level", &attr).expect("smoothing should parse");
+        let smoothing = spec.smoothing.expect("smoothing tokens").to_string();
+
+        assert!(smoothing.contains("Linear (10f32)"), "{smoothing}");
+    }
+
+    #[test]
+    fn smoothing_accepts_style_aliases_and_explicit_time() {
+        let attr: syn::Attribute = parse_quote!(
+            #[param(range = "20.0..=20000.0", smoothing = "exp", smoothing_ms = 20)]
+        );
+        let spec = parse_param_attr("frequency", &attr).expect("smoothing should parse");
+        let smoothing = spec.smoothing.expect("smoothing tokens").to_string();
+
+        assert!(smoothing.contains("Exponential (20f32)"), "{smoothing}");
+    }
+
+    #[test]
+    fn smoothing_rejects_unknown_style() {
+        let attr: syn::Attribute = parse_quote!(#[param(range = "0.0..=1.0", smoothing = "cubic")]);
+        let err = parse_param_attr("level", &attr)
+            .err()
+            .expect("unknown smoothing style must fail");
+
+        assert!(err.to_string().contains("Unknown smoothing style"));
+    }
+
+    #[test]
+    fn smoothing_ms_requires_smoothing_style() {
+        let attr: syn::Attribute = parse_quote!(#[param(range = "0.0..=1.0", smoothing_ms = 5)]);
+        let err = parse_param_attr("level", &attr)
+            .err()
+            .expect("smoothing_ms alone must fail");
+
+        assert!(err.to_string().contains("requires 'smoothing'"));
+    }
+
+    #[test]
+    fn smoothing_rejects_enum_params() {
+        let attr: syn::Attribute =
+            parse_quote!(#[param(variants = "Sine,Square", smoothing = "linear")]);
+        let err = parse_param_attr("waveform", &attr)
+            .err()
+            .expect("smoothing on enum must fail");
+
+        assert!(err.to_string().contains("cannot be used with 'variants'"));
+    }
 }
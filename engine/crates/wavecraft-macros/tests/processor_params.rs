@@ -98,3 +98,50 @@ fn test_from_param_defaults_uses_range_midpoint_when_default_missing() {
     let defaults = NoDefaultParam::from_param_defaults();
     assert!((defaults.level - 5.0).abs() < f32::EPSILON);
 }
+
+#[derive(ProcessorParams, Default)]
+struct SmoothedParams {
+    #[param(
+        range = "0.0..=2.0",
+        default = 1.0,
+        smoothing = "exp",
+        smoothing_ms = 20
+    )]
+    #[allow(dead_code)]
+    level: f32,
+
+    #[param(range = "20.0..=20000.0", default = 1000.0, smoothing = "log")]
+    #[allow(dead_code)]
+    cutoff: f32,
+
+    #[param(range = "0.0..=1.0")]
+    #[allow(dead_code)]
+    mix: f32,
+}
+
+#[test]
+fn test_smoothing_declarations() {
+    use wavecraft_dsp::{ParamSmoothing, Smoother, SmoothingStyle};
+
+    assert_eq!(
+        SmoothedParams::param_smoothing(),
+        &[
+            ParamSmoothing {
+                id_suffix: "level",
+                style: SmoothingStyle::Exponential(20.0),
+            },
+            ParamSmoothing {
+                id_suffix: "cutoff",
+                style: SmoothingStyle::Logarithmic(10.0),
+            },
+        ]
+    );
+
+    let mix = Smoother::for_param::<SmoothedParams>("mix");
+    assert_eq!(mix.style(), SmoothingStyle::None);
+}
+
+#[test]
+fn test_no_smoothing_declared_by_default() {
+    assert!(SimpleParams::param_smoothing().is_empty());
+}
@@ -1,9 +1,17 @@
 //! Real-time safe metering for audio→UI communication.
 //!
-//! Provides lock-free SPSC ring buffers for transferring peak/RMS meter data
-//! from the audio thread to the UI thread without allocations or blocking.
+//! Provides lock-free SPSC ring buffers for transferring meter data from the
+//! audio thread to the UI thread without allocations or blocking, and
+//! [`MeterEngine`] to measure peak, RMS, true peak and ITU-R BS.1770 loudness.
 
-pub use wavecraft_protocol::MeterFrame;
+mod loudness;
+mod meter;
+mod true_peak;
+
+pub use loudness::LoudnessMeter;
+pub use meter::MeterEngine;
+pub use true_peak::TruePeakDetector;
+pub use wavecraft_protocol::{LoudnessFrame, MeterFrame};
 
 /// Producer side of meter channel (audio thread).
 ///
@@ -79,13 +87,7 @@ mod tests {
     use super::*;
 
     fn empty_frame() -> MeterFrame {
-        MeterFrame {
-            peak_l: 0.0,
-            peak_r: 0.0,
-            rms_l: 0.0,
-            rms_r: 0.0,
-            timestamp: 0,
-        }
+        MeterFrame::default()
     }
 
     #[test]
@@ -98,6 +100,7 @@ mod tests {
             rms_l: 0.3,
             rms_r: 0.4,
             timestamp: 1000,
+            ..empty_frame()
         };
 
         producer.push(frame);
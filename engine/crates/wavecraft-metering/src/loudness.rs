@@ -0,0 +1,422 @@
+//! ITU-R BS.1770-4 / EBU R128 loudness measurement.
+//!
+//! Audio is K-weighted, squared and summed in 100 ms sub-blocks. Momentary
+//! (400 ms) and short-term (3 s) loudness are sliding sums over the most
+//! recent sub-blocks. Integrated loudness and loudness range gate every
+//! measurement block through fixed-size histograms, so a long session never
+//! allocates.
+
+use wavecraft_protocol::LoudnessFrame;
+
+/// Sub-block length in seconds; momentary and short-term loudness update at
+/// this rate.
+const SUB_BLOCK_SECONDS: f64 = 0.1;
+
+/// Sub-blocks in the 400 ms momentary window.
+const MOMENTARY_SUB_BLOCKS: usize = 4;
+
+/// Sub-blocks in the 3 s short-term window.
+const SHORT_TERM_SUB_BLOCKS: usize = 30;
+
+/// Absolute gate for integrated loudness and loudness range.
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Relative gate below the ungated mean for integrated loudness.
+const INTEGRATED_RELATIVE_GATE_LU: f64 = -10.0;
+
+/// Relative gate below the ungated mean for loudness range.
+const RANGE_RELATIVE_GATE_LU: f64 = -20.0;
+
+/// Histogram bounds and resolution for gated measurements.
+const HISTOGRAM_MAX_LUFS: f64 = 10.0;
+const HISTOGRAM_STEP_LU: f64 = 0.1;
+const HISTOGRAM_BINS: usize =
+    ((HISTOGRAM_MAX_LUFS - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP_LU) as usize;
+
+/// Mean square to LUFS (BS.1770: `-0.691 + 10 log10(z)`).
+fn energy_to_lufs(energy: f64) -> f64 {
+    -0.691 + 10.0 * energy.log10()
+}
+
+fn lufs_to_energy(lufs: f64) -> f64 {
+    10.0_f64.powf((lufs + 0.691) / 10.0)
+}
+
+/// Second-order IIR section (direct form I, `a0` normalized to 1).
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Self {
+            b,
+            a,
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, input: f64) -> f64 {
+        let output = self.b[0] * input + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+
+    fn reset(&mut self) {
+        self.x = [0.0; 2];
+        self.y = [0.0; 2];
+    }
+}
+
+/// BS.1770 K-weighting: a high-shelf "head" pre-filter followed by the RLB
+/// high-pass, with coefficients derived for any sample rate.
+#[derive(Debug, Clone, Copy)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: f64) -> Self {
+        // High shelf, +4 dB above ~1.7 kHz.
+        let f0 = 1_681.974_450_955_533;
+        let gain_db = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+        let vh = 10.0_f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad::new(
+            [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        // High pass at ~38 Hz.
+        let f0 = 38.135_470_876_024_44;
+        let q = 0.500_327_037_323_877_3;
+        let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad::new(
+            [1.0, -2.0, 1.0],
+            [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        );
+
+        Self { shelf, high_pass }
+    }
+
+    #[inline]
+    fn process(&mut self, input: f32) -> f64 {
+        self.high_pass.process(self.shelf.process(f64::from(input)))
+    }
+
+    fn reset(&mut self) {
+        self.shelf.reset();
+        self.high_pass.reset();
+    }
+}
+
+/// Counts of gated block loudness values in 0.1 LU bins above the absolute
+/// gate.
+#[derive(Debug, Clone)]
+struct LoudnessHistogram {
+    bins: Vec<u64>,
+}
+
+impl LoudnessHistogram {
+    fn new() -> Self {
+        Self {
+            bins: vec![0; HISTOGRAM_BINS],
+        }
+    }
+
+    fn bin_lufs(bin: usize) -> f64 {
+        ABSOLUTE_GATE_LUFS + (bin as f64 + 0.5) * HISTOGRAM_STEP_LU
+    }
+
+    /// Adds a block's loudness; blocks at or below the absolute gate are
+    /// dropped.
+    fn add(&mut self, lufs: f64) {
+        if lufs.is_nan() || lufs <= ABSOLUTE_GATE_LUFS {
+            return;
+        }
+        let bin = ((lufs - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP_LU) as usize;
+        self.bins[bin.min(HISTOGRAM_BINS - 1)] += 1;
+    }
+
+    /// Mean energy of the blocks at or above `gate_lufs`.
+    fn mean_energy_above(&self, gate_lufs: f64) -> Option<f64> {
+        let mut energy = 0.0;
+        let mut count = 0;
+        for (bin, &bin_count) in self.bins.iter().enumerate() {
+            let lufs = Self::bin_lufs(bin);
+            if bin_count > 0 && lufs >= gate_lufs {
+                energy += lufs_to_energy(lufs) * bin_count as f64;
+                count += bin_count;
+            }
+        }
+        (count > 0).then(|| energy / count as f64)
+    }
+
+    /// Relative gate: `offset_lu` below the loudness of all gated blocks.
+    fn relative_gate(&self, offset_lu: f64) -> Option<f64> {
+        self.mean_energy_above(ABSOLUTE_GATE_LUFS)
+            .map(|energy| energy_to_lufs(energy) + offset_lu)
+    }
+
+    /// Loudness at `fraction` (`0.0..=1.0`) of the blocks at or above
+    /// `gate_lufs`, in ascending order.
+    fn percentile_above(&self, gate_lufs: f64, fraction: f64) -> Option<f64> {
+        let first = self
+            .bins
+            .iter()
+            .enumerate()
+            .position(|(bin, _)| Self::bin_lufs(bin) >= gate_lufs)?;
+        let total: u64 = self.bins[first..].iter().sum();
+        if total == 0 {
+            return None;
+        }
+
+        let target = ((total - 1) as f64 * fraction).round() as u64;
+        let mut seen = 0;
+        for (bin, &bin_count) in self.bins.iter().enumerate().skip(first) {
+            seen += bin_count;
+            if seen > target {
+                return Some(Self::bin_lufs(bin));
+            }
+        }
+        None
+    }
+
+    fn clear(&mut self) {
+        self.bins.fill(0);
+    }
+}
+
+/// K-weighted loudness meter.
+///
+/// All channels are weighted equally (the BS.1770 weighting for left, right
+/// and centre), which is correct for mono and stereo buses.
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    filters: Vec<KWeighting>,
+    sub_block_len: usize,
+    /// Samples and summed energy of the sub-block being filled.
+    pending_samples: usize,
+    pending_energy: f64,
+    /// Energy of the most recent complete sub-blocks (ring).
+    sub_blocks: [f64; SHORT_TERM_SUB_BLOCKS],
+    sub_block_pos: usize,
+    sub_block_count: usize,
+    integrated: LoudnessHistogram,
+    range: LoudnessHistogram,
+    frame: LoudnessFrame,
+}
+
+impl LoudnessMeter {
+    /// Creates a meter for `channels` channels at `sample_rate`.
+    pub fn new(sample_rate: f32, channels: usize) -> Self {
+        let sample_rate = f64::from(sample_rate.max(1.0));
+        Self {
+            filters: vec![KWeighting::new(sample_rate); channels],
+            sub_block_len: ((sample_rate * SUB_BLOCK_SECONDS).round() as usize).max(1),
+            pending_samples: 0,
+            pending_energy: 0.0,
+            sub_blocks: [0.0; SHORT_TERM_SUB_BLOCKS],
+            sub_block_pos: 0,
+            sub_block_count: 0,
+            integrated: LoudnessHistogram::new(),
+            range: LoudnessHistogram::new(),
+            frame: LoudnessFrame::default(),
+        }
+    }
+
+    /// Feeds one block. Channels beyond the count given to [`Self::new`]
+    /// are ignored.
+    pub fn process<C: AsRef<[f32]>>(&mut self, channels: &[C]) {
+        let samples = channels
+            .iter()
+            .map(|channel| channel.as_ref().len())
+            .min()
+            .unwrap_or(0);
+
+        let mut offset = 0;
+        while offset < samples {
+            let len = (self.sub_block_len - self.pending_samples).min(samples - offset);
+            for (channel, filter) in channels.iter().zip(self.filters.iter_mut()) {
+                for &sample in &channel.as_ref()[offset..offset + len] {
+                    let weighted = filter.process(sample);
+                    self.pending_energy += weighted * weighted;
+                }
+            }
+            self.pending_samples += len;
+            offset += len;
+
+            if self.pending_samples == self.sub_block_len {
+                self.finish_sub_block();
+            }
+        }
+    }
+
+    /// Latest loudness values.
+    pub fn frame(&self) -> LoudnessFrame {
+        self.frame
+    }
+
+    /// Clears all measurements, including integrated loudness and loudness
+    /// range.
+    pub fn reset(&mut self) {
+        self.filters.iter_mut().for_each(KWeighting::reset);
+        self.pending_samples = 0;
+        self.pending_energy = 0.0;
+        self.sub_blocks = [0.0; SHORT_TERM_SUB_BLOCKS];
+        self.sub_block_pos = 0;
+        self.sub_block_count = 0;
+        self.integrated.clear();
+        self.range.clear();
+        self.frame = LoudnessFrame::default();
+    }
+
+    fn finish_sub_block(&mut self) {
+        self.sub_blocks[self.sub_block_pos] = self.pending_energy;
+        self.sub_block_pos = (self.sub_block_pos + 1) % SHORT_TERM_SUB_BLOCKS;
+        self.sub_block_count += 1;
+        self.pending_samples = 0;
+        self.pending_energy = 0.0;
+
+        if let Some(momentary) = self.window_lufs(MOMENTARY_SUB_BLOCKS) {
+            // 400 ms gating blocks with 75% overlap.
+            self.integrated.add(momentary);
+            self.frame.momentary_lufs = Some(momentary as f32);
+        }
+        if let Some(short_term) = self.window_lufs(SHORT_TERM_SUB_BLOCKS) {
+            self.range.add(short_term);
+            self.frame.short_term_lufs = Some(short_term as f32);
+        }
+
+        self.frame.integrated_lufs = self
+            .integrated
+            .relative_gate(INTEGRATED_RELATIVE_GATE_LU)
+            .and_then(|gate| self.integrated.mean_energy_above(gate))
+            .map(|energy| energy_to_lufs(energy) as f32);
+        self.frame.loudness_range_lu =
+            self.range
+                .relative_gate(RANGE_RELATIVE_GATE_LU)
+                .and_then(|gate| {
+                    let low = self.range.percentile_above(gate, 0.10)?;
+                    let high = self.range.percentile_above(gate, 0.95)?;
+                    Some((high - low) as f32)
+                });
+    }
+
+    /// Loudness of the last `sub_blocks` sub-blocks, once that many exist.
+    /// Silence is clamped to a finite (very low) value so it serializes.
+    fn window_lufs(&self, sub_blocks: usize) -> Option<f64> {
+        if self.sub_block_count < sub_blocks {
+            return None;
+        }
+        let energy: f64 = (1..=sub_blocks)
+            .map(|back| {
+                self.sub_blocks
+                    [(self.sub_block_pos + SHORT_TERM_SUB_BLOCKS - back) % SHORT_TERM_SUB_BLOCKS]
+            })
+            .sum();
+        let mean_square = energy / (sub_blocks * self.sub_block_len) as f64;
+        Some(energy_to_lufs(mean_square).max(f64::from(f32::MIN)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn sine(frequency: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        let len = (SAMPLE_RATE * seconds) as usize;
+        (0..len)
+            .map(|n| amplitude * (std::f32::consts::TAU * frequency * n as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    fn measure(meter: &mut LoudnessMeter, left: &[f32], right: &[f32]) {
+        for (left, right) in left.chunks(512).zip(right.chunks(512)) {
+            meter.process(&[left, right]);
+        }
+    }
+
+    #[test]
+    fn reference_stereo_sine_reads_minus_23_lufs() {
+        // EBU Tech 3341 case 1: a 1 kHz sine at -23 dBFS in both channels
+        // measures -23 LUFS.
+        let tone = sine(1000.0, 0.070_794_58, 4.0);
+        let mut meter = LoudnessMeter::new(SAMPLE_RATE, 2);
+        measure(&mut meter, &tone, &tone);
+
+        let frame = meter.frame();
+        for value in [
+            frame.momentary_lufs,
+            frame.short_term_lufs,
+            frame.integrated_lufs,
+        ] {
+            let value = value.expect("measured");
+            assert!((value + 23.0).abs() < 0.1, "{value} LUFS");
+        }
+    }
+
+    #[test]
+    fn windows_report_once_filled() {
+        let tone = sine(1000.0, 0.5, 0.35);
+        let mut meter = LoudnessMeter::new(SAMPLE_RATE, 1);
+        meter.process(&[&tone[..]]);
+        assert_eq!(meter.frame(), LoudnessFrame::default());
+
+        let tone = sine(1000.0, 0.5, 0.1);
+        meter.process(&[&tone[..]]);
+        assert!(meter.frame().momentary_lufs.is_some());
+        assert!(meter.frame().short_term_lufs.is_none());
+    }
+
+    #[test]
+    fn integrated_loudness_gates_out_silence() {
+        let tone = sine(1000.0, 0.1, 5.0);
+        let silence = vec![0.0; tone.len()];
+        let mut meter = LoudnessMeter::new(SAMPLE_RATE, 2);
+        measure(&mut meter, &tone, &tone);
+        let before = meter.frame().integrated_lufs.expect("integrated");
+
+        measure(&mut meter, &silence, &silence);
+        let after = meter.frame().integrated_lufs.expect("integrated");
+
+        assert!((before - after).abs() < 0.2, "{before} vs {after}");
+        assert!(meter.frame().momentary_lufs.unwrap() < -100.0);
+
+        meter.reset();
+        assert_eq!(meter.frame(), LoudnessFrame::default());
+    }
+
+    #[test]
+    fn loudness_range_spans_loud_and_quiet_passages() {
+        // EBU Tech 3342 case 1: 20 s at -20 dBFS then 20 s at -30 dBFS
+        // gives an LRA of 10 LU.
+        let loud = sine(1000.0, 0.1, 20.0);
+        let quiet = sine(1000.0, 0.031_622_776, 20.0);
+        let mut meter = LoudnessMeter::new(SAMPLE_RATE, 2);
+        measure(&mut meter, &loud, &loud);
+        measure(&mut meter, &quiet, &quiet);
+
+        let range = meter.frame().loudness_range_lu.expect("range");
+        assert!((range - 10.0).abs() < 0.2, "{range} LU");
+    }
+}
@@ -0,0 +1,263 @@
+//! Complete stereo meter: peak, windowed RMS, true peak and loudness.
+
+use wavecraft_protocol::MeterFrame;
+
+use crate::loudness::LoudnessMeter;
+use crate::true_peak::TruePeakDetector;
+
+/// RMS integration window.
+const RMS_WINDOW_SECONDS: f32 = 0.3;
+
+/// RMS sub-block length; the window slides in steps of this size.
+const RMS_SUB_BLOCK_SECONDS: f32 = 0.01;
+
+/// Sliding-window RMS built from fixed sub-block sums.
+#[derive(Debug, Clone)]
+struct WindowedRms {
+    sub_block_len: usize,
+    pending_samples: usize,
+    pending_sum: f64,
+    sub_blocks: Vec<f64>,
+    pos: usize,
+    filled: usize,
+    window_sum: f64,
+}
+
+impl WindowedRms {
+    fn new(sample_rate: f32) -> Self {
+        let sub_block_len = ((sample_rate * RMS_SUB_BLOCK_SECONDS).round() as usize).max(1);
+        let sub_blocks = ((RMS_WINDOW_SECONDS / RMS_SUB_BLOCK_SECONDS).round() as usize).max(1);
+        Self {
+            sub_block_len,
+            pending_samples: 0,
+            pending_sum: 0.0,
+            sub_blocks: vec![0.0; sub_blocks],
+            pos: 0,
+            filled: 0,
+            window_sum: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            self.pending_sum += f64::from(sample) * f64::from(sample);
+            self.pending_samples += 1;
+            if self.pending_samples == self.sub_block_len {
+                self.window_sum += self.pending_sum - self.sub_blocks[self.pos];
+                self.sub_blocks[self.pos] = self.pending_sum;
+                self.pos = (self.pos + 1) % self.sub_blocks.len();
+                self.filled = (self.filled + 1).min(self.sub_blocks.len());
+                self.pending_samples = 0;
+                self.pending_sum = 0.0;
+            }
+        }
+    }
+
+    /// RMS over the filled part of the window (the whole window once warm).
+    fn value(&self) -> f32 {
+        if self.filled == 0 {
+            return 0.0;
+        }
+        let samples = (self.filled * self.sub_block_len) as f64;
+        // Running add/subtract can leave a tiny negative residue.
+        (self.window_sum.max(0.0) / samples).sqrt() as f32
+    }
+
+    fn reset(&mut self) {
+        self.pending_samples = 0;
+        self.pending_sum = 0.0;
+        self.sub_blocks.fill(0.0);
+        self.pos = 0;
+        self.filled = 0;
+        self.window_sum = 0.0;
+    }
+}
+
+/// Per-channel peak, RMS and true-peak state.
+#[derive(Debug, Clone)]
+struct ChannelMeter {
+    peak: f32,
+    rms: WindowedRms,
+    true_peak: TruePeakDetector,
+}
+
+impl ChannelMeter {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            peak: 0.0,
+            rms: WindowedRms::new(sample_rate),
+            true_peak: TruePeakDetector::default(),
+        }
+    }
+
+    fn process(&mut self, samples: &[f32]) {
+        self.peak = samples
+            .iter()
+            .fold(self.peak, |peak, sample| peak.max(sample.abs()));
+        self.rms.process(samples);
+        self.true_peak.process(samples);
+    }
+
+    fn reset(&mut self) {
+        self.peak = 0.0;
+        self.rms.reset();
+        self.true_peak.reset();
+    }
+}
+
+/// Measures a stereo (or mono) bus and produces [`MeterFrame`]s.
+///
+/// Call [`process`](Self::process) with every audio block and
+/// [`frame`](Self::frame) whenever a frame should be published. Peaks and
+/// true peaks are held between `frame` calls, so none are missed when frames
+/// are published less often than blocks arrive; RMS is taken over the last
+/// 300 ms and loudness follows ITU-R BS.1770-4.
+///
+/// Only the first two channels are metered. A mono input is reported on
+/// both sides. All buffers are allocated in [`new`](Self::new) and
+/// [`set_sample_rate`](Self::set_sample_rate), so processing is real-time
+/// safe.
+///
+/// ```
+/// use wavecraft_metering::MeterEngine;
+///
+/// let mut meters = MeterEngine::new(48_000.0, 2);
+/// let left = [0.5_f32; 512];
+/// let right = [0.25_f32; 512];
+/// meters.process(&[&left[..], &right[..]]);
+///
+/// let frame = meters.frame();
+/// assert_eq!(frame.peak_l, 0.5);
+/// assert_eq!(frame.peak_r, 0.25);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeterEngine {
+    channels: Vec<ChannelMeter>,
+    loudness: LoudnessMeter,
+    samples_processed: u64,
+}
+
+impl MeterEngine {
+    /// Creates meters for `channels` channels (only the first two are
+    /// metered) at `sample_rate`.
+    pub fn new(sample_rate: f32, channels: usize) -> Self {
+        let channels = channels.clamp(1, 2);
+        Self {
+            channels: (0..channels)
+                .map(|_| ChannelMeter::new(sample_rate))
+                .collect(),
+            loudness: LoudnessMeter::new(sample_rate, channels),
+            samples_processed: 0,
+        }
+    }
+
+    /// Rebuilds the meters for a new sample rate, clearing all measurements.
+    ///
+    /// Allocates; call from `initialize`, not from the audio callback.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        *self = Self::new(sample_rate, self.channels.len());
+    }
+
+    /// Measures one block of audio.
+    pub fn process<C: AsRef<[f32]>>(&mut self, channels: &[C]) {
+        for (meter, channel) in self.channels.iter_mut().zip(channels) {
+            meter.process(channel.as_ref());
+        }
+        let metered = self.channels.len().min(channels.len());
+        self.loudness.process(&channels[..metered]);
+        self.samples_processed += channels
+            .first()
+            .map_or(0, |channel| channel.as_ref().len() as u64);
+    }
+
+    /// Returns the current measurements and starts a new peak hold period.
+    ///
+    /// `timestamp` is the number of samples processed since creation or the
+    /// last reset.
+    pub fn frame(&mut self) -> MeterFrame {
+        let right = self.channels.len() - 1;
+        let peak_l = std::mem::take(&mut self.channels[0].peak);
+        let peak_r = if right == 0 {
+            peak_l
+        } else {
+            std::mem::take(&mut self.channels[right].peak)
+        };
+        let true_peak_l = self.channels[0].true_peak.take_peak();
+        let true_peak_r = if right == 0 {
+            true_peak_l
+        } else {
+            self.channels[right].true_peak.take_peak()
+        };
+
+        MeterFrame {
+            peak_l,
+            peak_r,
+            rms_l: self.channels[0].rms.value(),
+            rms_r: self.channels[right].rms.value(),
+            true_peak_l,
+            true_peak_r,
+            loudness: Some(self.loudness.frame()),
+            timestamp: self.samples_processed,
+        }
+    }
+
+    /// Clears all measurements, including integrated loudness.
+    pub fn reset(&mut self) {
+        self.channels.iter_mut().for_each(ChannelMeter::reset);
+        self.loudness.reset();
+        self.samples_processed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rms_covers_the_recent_window() {
+        let mut meters = MeterEngine::new(48_000.0, 2);
+        let loud = vec![0.5_f32; 48_000];
+        meters.process(&[&loud[..], &loud[..]]);
+        let frame = meters.frame();
+        assert!((frame.rms_l - 0.5).abs() < 1e-4);
+
+        // 300 ms of silence flushes the window completely.
+        let silence = vec![0.0_f32; 14_400];
+        meters.process(&[&silence[..], &silence[..]]);
+        let frame = meters.frame();
+        assert!(frame.rms_l < 1e-4);
+        assert_eq!(frame.peak_l, 0.0);
+        assert_eq!(frame.timestamp, 62_400);
+    }
+
+    #[test]
+    fn mono_input_is_reported_on_both_sides() {
+        let mut meters = MeterEngine::new(44_100.0, 1);
+        let samples = [0.0_f32, -0.8, 0.3, 0.0];
+        meters.process(&[&samples[..]]);
+
+        let frame = meters.frame();
+        assert_eq!(frame.peak_l, 0.8);
+        assert_eq!(frame.peak_r, 0.8);
+        assert_eq!(frame.true_peak_l, frame.true_peak_r);
+        assert!(frame.true_peak_l >= 0.8);
+    }
+
+    #[test]
+    fn loudness_is_reported_and_reset() {
+        let mut meters = MeterEngine::new(48_000.0, 2);
+        let tone: Vec<f32> = (0..24_000)
+            .map(|n| 0.5 * (std::f32::consts::TAU * 1000.0 * n as f32 / 48_000.0).sin())
+            .collect();
+        meters.process(&[&tone[..], &tone[..]]);
+
+        let loudness = meters.frame().loudness.expect("loudness");
+        assert!(loudness.momentary_lufs.is_some());
+
+        meters.reset();
+        let frame = meters.frame();
+        assert_eq!(frame.loudness, Some(Default::default()));
+        assert_eq!(frame.timestamp, 0);
+    }
+}
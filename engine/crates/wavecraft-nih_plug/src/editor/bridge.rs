@@ -9,13 +9,23 @@ use std::sync::{Arc, Mutex};
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use nih_plug::prelude::*;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
-use wavecraft_bridge::{BridgeError, ParameterHost};
+use wavecraft_bridge::{BridgeError, ParameterHost, PresetLibrary};
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use wavecraft_dsp::{MeteringTap, ModulationHandle, SanitizerStats};
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use wavecraft_metering::MeterConsumer;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
-use wavecraft_processors::OscilloscopeFrameConsumer;
+use wavecraft_processors::{
+    OscilloscopeFrameConsumer, SpectrumFrameConsumer, eq_response_from_parameters,
+};
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use wavecraft_protocol::{
+    AudioRuntimePhase, AudioRuntimeStatus, ModulationConfig, ModulationFrame, ParameterInfo,
+    ParameterType, SpectrumFrame,
+};
+
 #[cfg(any(target_os = "macos", target_os = "windows"))]
-use wavecraft_protocol::{AudioRuntimeStatus, ParameterInfo, ParameterType};
+use crate::util::SharedSampleRate;
 
 /// Bridge between nih-plug and the IPC handler.
 ///
@@ -33,8 +43,20 @@ pub struct PluginEditorBridge<P: Params> {
     meter_consumer: Option<Arc<Mutex<MeterConsumer>>>,
     /// Optional oscilloscope consumer - may be None if oscilloscope is disabled
     oscilloscope_consumer: Option<Arc<Mutex<OscilloscopeFrameConsumer>>>,
+    /// Optional spectrum consumer - may be None if the analyzer is disabled
+    spectrum_consumer: Option<Arc<Mutex<SpectrumFrameConsumer>>>,
+    /// Named meter, oscilloscope and spectrum taps in the signal chain
+    metering_taps: Vec<Arc<dyn MeteringTap>>,
     /// Shared editor size - updated when resize is requested
     editor_size: Arc<Mutex<(u32, u32)>>,
+    /// Factory and user presets - shared by every editor instance
+    presets: Option<Arc<PresetLibrary>>,
+    /// Modulation routings and live values of the plugin instance
+    modulation: Option<ModulationHandle>,
+    /// Output sanitizer counters of the plugin instance
+    output_stats: Option<Arc<SanitizerStats>>,
+    /// Host sample rate of the plugin instance
+    sample_rate: Option<SharedSampleRate>,
 }
 
 #[cfg(any(target_os = "macos", target_os = "windows"))]
@@ -52,10 +74,66 @@ impl<P: Params> PluginEditorBridge<P> {
             context,
             meter_consumer: meter_consumer.map(|c| Arc::new(Mutex::new(c))),
             oscilloscope_consumer: oscilloscope_consumer.map(|c| Arc::new(Mutex::new(c))),
+            spectrum_consumer: None,
+            metering_taps: Vec::new(),
             editor_size,
+            presets: None,
+            modulation: None,
+            output_stats: None,
+            sample_rate: None,
         }
     }
 
+    /// Serve the preset IPC methods from `presets`.
+    pub fn with_presets(mut self, presets: Arc<PresetLibrary>) -> Self {
+        self.presets = Some(presets);
+        self
+    }
+
+    /// Serve `getSpectrumFrame` from `spectrum_consumer`.
+    pub fn with_spectrum_consumer(mut self, spectrum_consumer: SpectrumFrameConsumer) -> Self {
+        self.spectrum_consumer = Some(Arc::new(Mutex::new(spectrum_consumer)));
+        self
+    }
+
+    /// Serve tap-selected `getMeterFrame`/`getOscilloscopeFrame`/
+    /// `getSpectrumFrame` requests from `metering_taps`.
+    pub fn with_metering_taps(mut self, metering_taps: Vec<Arc<dyn MeteringTap>>) -> Self {
+        self.metering_taps = metering_taps;
+        self
+    }
+
+    /// Taps registered under `tap_id`. Meter, oscilloscope and spectrum taps
+    /// may share an ID; each request reads whichever of them provides its
+    /// data.
+    fn metering_taps_with_id<'a>(
+        &'a self,
+        tap_id: &'a str,
+    ) -> impl Iterator<Item = &'a Arc<dyn MeteringTap>> + 'a {
+        self.metering_taps
+            .iter()
+            .filter(move |tap| tap.id() == tap_id)
+    }
+
+    /// Serve the modulation IPC methods from `modulation`.
+    pub fn with_modulation(mut self, modulation: ModulationHandle) -> Self {
+        self.modulation = Some(modulation);
+        self
+    }
+
+    /// Report output sanitizer events from `output_stats` through
+    /// `getAudioStatus`.
+    pub fn with_output_stats(mut self, output_stats: Arc<SanitizerStats>) -> Self {
+        self.output_stats = Some(output_stats);
+        self
+    }
+
+    /// Evaluate `getEqResponse` at the host sample rate in `sample_rate`.
+    pub fn with_sample_rate(mut self, sample_rate: SharedSampleRate) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
     fn parameter_info_from_ptr(param_id: &str, param_ptr: ParamPtr, group: &str) -> ParameterInfo {
         // SAFETY: ParamPtr values come from `self.params.param_map()`, and `self.params` is
         // kept alive by `Arc<P>` on this struct for the full bridge lifetime.
@@ -179,6 +257,42 @@ impl<P: Params> ParameterHost for PluginEditorBridge<P> {
             .map(|frame| frame.to_protocol_frame())
     }
 
+    fn get_tap_meter_frame(&self, tap_id: &str) -> Option<wavecraft_protocol::MeterFrame> {
+        self.metering_taps_with_id(tap_id)
+            .find_map(|tap| tap.meter_frame())
+    }
+
+    fn get_tap_oscilloscope_frame(
+        &self,
+        tap_id: &str,
+    ) -> Option<wavecraft_protocol::OscilloscopeFrame> {
+        self.metering_taps_with_id(tap_id)
+            .find_map(|tap| tap.oscilloscope_frame())
+    }
+
+    fn get_spectrum_frame(&self) -> Option<SpectrumFrame> {
+        let consumer = self.spectrum_consumer.as_ref()?;
+        let mut consumer = consumer.lock().unwrap();
+        consumer
+            .read_latest()
+            .map(|frame| frame.to_protocol_frame())
+    }
+
+    fn get_tap_spectrum_frame(&self, tap_id: &str) -> Option<SpectrumFrame> {
+        self.metering_taps_with_id(tap_id)
+            .find_map(|tap| tap.spectrum_frame())
+    }
+
+    fn get_eq_response(&self, eq_id: Option<&str>, frequencies_hz: &[f32]) -> Option<Vec<f32>> {
+        let sample_rate = self.sample_rate.as_ref().and_then(SharedSampleRate::get);
+        eq_response_from_parameters(
+            &self.get_all_parameters(),
+            eq_id,
+            sample_rate,
+            frequencies_hz,
+        )
+    }
+
     fn request_resize(&self, width: u32, height: u32) -> bool {
         // Update the editor's size field
         *self.editor_size.lock().unwrap() = (width, height);
@@ -201,7 +315,39 @@ impl<P: Params> ParameterHost for PluginEditorBridge<P> {
     }
 
     fn get_audio_status(&self) -> Option<AudioRuntimeStatus> {
-        None
+        // The host owns the audio runtime; the only status the plugin has to
+        // report is output that had to be sanitized.
+        let diagnostic = self.output_stats.as_ref()?.counts().diagnostic()?;
+        let updated_at_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        Some(AudioRuntimeStatus {
+            phase: AudioRuntimePhase::Degraded,
+            diagnostic: Some(diagnostic),
+            sample_rate: None,
+            buffer_size: None,
+            updated_at_ms,
+        })
+    }
+
+    fn presets(&self) -> Option<&PresetLibrary> {
+        self.presets.as_deref()
+    }
+
+    fn get_modulation(&self) -> Option<ModulationConfig> {
+        self.modulation.as_ref().map(ModulationHandle::config)
+    }
+
+    fn set_modulation(&self, config: ModulationConfig) -> Result<(), BridgeError> {
+        let modulation = self.modulation.as_ref().ok_or_else(|| {
+            BridgeError::Internal("plugin does not support modulation".to_string())
+        })?;
+        modulation.set_config(config);
+        Ok(())
+    }
+
+    fn get_modulation_frame(&self) -> Option<ModulationFrame> {
+        self.modulation.as_ref()?.frame()
     }
 }
 
@@ -0,0 +1,55 @@
+//! Audio-thread streams and shared plugin state served to the editor UI.
+
+use std::sync::Arc;
+
+use wavecraft_bridge::PresetLibrary;
+use wavecraft_dsp::{MeteringTap, ModulationHandle, SanitizerStats};
+use wavecraft_metering::MeterConsumer;
+use wavecraft_processors::{OscilloscopeFrameConsumer, SpectrumFrameConsumer};
+
+use crate::util::{EditorPresence, SharedSampleRate};
+
+/// Everything the editor reads from a plugin instance besides its params.
+///
+/// The consumers are single-reader streams and move to the first spawned
+/// WebView; the remaining handles are shared by every WebView.
+pub struct EditorChannels {
+    /// Output meter frames
+    pub meter_consumer: Option<MeterConsumer>,
+    /// Output waveform snapshots
+    pub oscilloscope_consumer: Option<OscilloscopeFrameConsumer>,
+    /// Output analyzer frames
+    pub spectrum_consumer: Option<SpectrumFrameConsumer>,
+    /// Named meter, oscilloscope and spectrum taps in the signal chain, served by ID
+    pub metering_taps: Vec<Arc<dyn MeteringTap>>,
+    /// Factory and user presets served over IPC
+    pub presets: Arc<PresetLibrary>,
+    /// Modulation routings and live values served over IPC
+    pub modulation: ModulationHandle,
+    /// Output sanitizer counters reported through `getAudioStatus`
+    pub output_stats: Arc<SanitizerStats>,
+    /// Marked open while a WebView is spawned so the plugin runs its analysis
+    pub presence: EditorPresence,
+    /// Host sample rate, used to evaluate `getEqResponse`
+    pub sample_rate: SharedSampleRate,
+}
+
+impl EditorChannels {
+    /// Channels for a newly spawned WebView.
+    ///
+    /// Moves the consumers out of `self` (so only the first WebView receives
+    /// them) and clones the shared handles.
+    pub fn take_for_spawn(&mut self) -> Self {
+        Self {
+            meter_consumer: self.meter_consumer.take(),
+            oscilloscope_consumer: self.oscilloscope_consumer.take(),
+            spectrum_consumer: self.spectrum_consumer.take(),
+            metering_taps: self.metering_taps.clone(),
+            presets: self.presets.clone(),
+            modulation: self.modulation.clone(),
+            output_stats: self.output_stats.clone(),
+            presence: self.presence.clone(),
+            sample_rate: self.sample_rate.clone(),
+        }
+    }
+}
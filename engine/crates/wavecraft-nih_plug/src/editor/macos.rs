@@ -107,9 +107,8 @@ pub fn create_macos_webview<P: Params + 'static>(
     let handler = Arc::new(Mutex::new(super::webview::create_ipc_handler(
         config.params,
         config.context,
-        config.meter_consumer,
-        config.oscilloscope_consumer,
         config.editor_size,
+        config.channels,
     )));
 
     // Create WKWebView first (without configuration yet)
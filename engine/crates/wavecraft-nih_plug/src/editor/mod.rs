@@ -19,16 +19,6 @@ use std::sync::{Arc, Mutex};
     not(feature = "_param-discovery")
 ))]
 use nih_plug::prelude::*;
-#[cfg(all(
-    any(target_os = "macos", target_os = "windows"),
-    not(feature = "_param-discovery")
-))]
-use wavecraft_metering::MeterConsumer;
-#[cfg(all(
-    any(target_os = "macos", target_os = "windows"),
-    not(feature = "_param-discovery")
-))]
-use wavecraft_processors::OscilloscopeFrameConsumer;
 
 #[cfg(all(
     any(target_os = "macos", target_os = "windows"),
@@ -40,6 +30,8 @@ mod assets;
     not(feature = "_param-discovery")
 ))]
 mod bridge;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod channels;
 #[cfg(all(
     any(target_os = "macos", target_os = "windows"),
     not(feature = "_param-discovery")
@@ -55,6 +47,8 @@ mod macos;
 #[path = "windows/mod.rs"]
 mod windows;
 
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub use channels::EditorChannels;
 #[cfg(all(
     any(target_os = "macos", target_os = "windows"),
     not(feature = "_param-discovery")
@@ -73,10 +67,8 @@ pub use webview::{WebViewConfig, WebViewHandle, create_webview};
 ))]
 pub struct WavecraftEditor<P: Params> {
     params: Arc<P>,
-    /// Meter consumer for audio metering - taken on first editor spawn
-    meter_consumer: Mutex<Option<MeterConsumer>>,
-    /// Oscilloscope consumer for waveform snapshots - taken on first editor spawn
-    oscilloscope_consumer: Mutex<Option<OscilloscopeFrameConsumer>>,
+    /// Streams and shared state handed to each spawned WebView
+    channels: Mutex<EditorChannels>,
     size: Arc<Mutex<(u32, u32)>>,
     /// Handle to the WebView for resize operations
     webview_handle: Arc<Mutex<Option<Box<dyn WebViewHandle>>>>,
@@ -92,20 +84,13 @@ impl<P: Params> WavecraftEditor<P> {
     /// # Arguments
     ///
     /// * `params` - Shared parameter state
-    /// * `meter_consumer` - Optional meter consumer for audio metering
+    /// * `channels` - Metering streams, presets, modulation and diagnostics served over IPC
     /// * `width` - Initial editor width in pixels
     /// * `height` - Initial editor height in pixels
-    pub fn new(
-        params: Arc<P>,
-        meter_consumer: Option<MeterConsumer>,
-        oscilloscope_consumer: Option<OscilloscopeFrameConsumer>,
-        width: u32,
-        height: u32,
-    ) -> Self {
+    pub fn new(params: Arc<P>, channels: EditorChannels, width: u32, height: u32) -> Self {
         Self {
             params,
-            meter_consumer: Mutex::new(meter_consumer),
-            oscilloscope_consumer: Mutex::new(oscilloscope_consumer),
+            channels: Mutex::new(channels),
             size: Arc::new(Mutex::new((width, height))),
             webview_handle: Arc::new(Mutex::new(None)),
         }
@@ -122,9 +107,9 @@ impl<P: Params> Editor for WavecraftEditor<P> {
         parent: ParentWindowHandle,
         context: Arc<dyn GuiContext>,
     ) -> Box<dyn Any + Send> {
-        // Take the meter consumer (only works for first editor instance)
-        let meter_consumer = self.meter_consumer.lock().unwrap().take();
-        let oscilloscope_consumer = self.oscilloscope_consumer.lock().unwrap().take();
+        // Take the consumers (only works for first editor instance)
+        let channels = self.channels.lock().unwrap().take_for_spawn();
+        let presence = channels.presence.clone();
 
         let size = *self.size.lock().unwrap();
 
@@ -134,9 +119,8 @@ impl<P: Params> Editor for WavecraftEditor<P> {
             parent,
             width: size.0,
             height: size.1,
-            meter_consumer,
-            oscilloscope_consumer,
             editor_size: self.size.clone(),
+            channels,
         };
 
         match create_webview(config) {
@@ -144,8 +128,9 @@ impl<P: Params> Editor for WavecraftEditor<P> {
                 // Store the webview handle for resize operations
                 *self.webview_handle.lock().unwrap() = Some(webview);
 
-                // Return a dummy value that the host will hold
-                Box::new(())
+                // The host holds the guard until it closes the window, so
+                // output analysis runs exactly while the editor is open
+                Box::new(presence.open())
             }
             Err(e) => {
                 nih_error!("Failed to create WebView editor: {}", e);
@@ -248,17 +233,12 @@ impl<P: Params> Editor for WavecraftEditor<P> {
 ))]
 pub fn create_webview_editor<P: Params + 'static>(
     params: Arc<P>,
-    meter_consumer: Option<MeterConsumer>,
-    oscilloscope_consumer: Option<OscilloscopeFrameConsumer>,
+    channels: EditorChannels,
     width: u32,
     height: u32,
 ) -> Option<Box<dyn Editor>> {
     Some(Box::new(WavecraftEditor::new(
-        params,
-        meter_consumer,
-        oscilloscope_consumer,
-        width,
-        height,
+        params, channels, width, height,
     )))
 }
 
@@ -272,8 +252,7 @@ pub fn create_webview_editor<P: Params + 'static>(
 ))]
 pub fn create_webview_editor<P>(
     _params: std::sync::Arc<P>,
-    _meter_consumer: Option<wavecraft_metering::MeterConsumer>,
-    _oscilloscope_consumer: Option<wavecraft_processors::OscilloscopeFrameConsumer>,
+    _channels: EditorChannels,
     _width: u32,
     _height: u32,
 ) -> Option<Box<dyn nih_plug::prelude::Editor>> {
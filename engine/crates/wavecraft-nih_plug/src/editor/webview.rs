@@ -12,11 +12,9 @@ use std::sync::{Arc, Mutex};
 use nih_plug::prelude::*;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use wavecraft_bridge::IpcHandler;
-#[cfg(any(target_os = "macos", target_os = "windows"))]
-use wavecraft_metering::MeterConsumer;
-#[cfg(any(target_os = "macos", target_os = "windows"))]
-use wavecraft_processors::OscilloscopeFrameConsumer;
 
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use super::EditorChannels;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use super::bridge::PluginEditorBridge;
 
@@ -56,12 +54,10 @@ pub struct WebViewConfig<P: Params> {
     pub parent: ParentWindowHandle,
     pub width: u32,
     pub height: u32,
-    /// Optional meter consumer for audio metering
-    pub meter_consumer: Option<MeterConsumer>,
-    /// Optional oscilloscope consumer for waveform snapshots
-    pub oscilloscope_consumer: Option<OscilloscopeFrameConsumer>,
     /// Shared editor size - updated on resize requests
     pub editor_size: Arc<Mutex<(u32, u32)>>,
+    /// Metering streams, presets, modulation and diagnostics served over IPC
+    pub channels: EditorChannels,
 }
 
 /// Create a platform-specific WebView.
@@ -100,17 +96,24 @@ pub fn create_webview<P: Params>(
 pub fn create_ipc_handler<P: Params>(
     params: Arc<P>,
     context: Arc<dyn GuiContext>,
-    meter_consumer: Option<MeterConsumer>,
-    oscilloscope_consumer: Option<OscilloscopeFrameConsumer>,
     editor_size: Arc<Mutex<(u32, u32)>>,
+    channels: EditorChannels,
 ) -> IpcHandler<PluginEditorBridge<P>> {
-    let bridge = PluginEditorBridge::new(
+    let mut bridge = PluginEditorBridge::new(
         params,
         context,
-        meter_consumer,
-        oscilloscope_consumer,
+        channels.meter_consumer,
+        channels.oscilloscope_consumer,
         editor_size,
-    );
+    )
+    .with_presets(channels.presets)
+    .with_modulation(channels.modulation)
+    .with_metering_taps(channels.metering_taps)
+    .with_output_stats(channels.output_stats)
+    .with_sample_rate(channels.sample_rate);
+    if let Some(spectrum_consumer) = channels.spectrum_consumer {
+        bridge = bridge.with_spectrum_consumer(spectrum_consumer);
+    }
     IpcHandler::new(bridge)
 }
 
@@ -110,9 +110,8 @@ pub fn create_windows_webview<P: Params + 'static>(
         Arc::new(Mutex::new(super::webview::create_ipc_handler(
             config.params,
             config.context,
-            config.meter_consumer,
-            config.oscilloscope_consumer,
             config.editor_size,
+            config.channels,
         )));
 
     // Convert to trait object for type erasure
@@ -16,13 +16,27 @@ pub mod util;
 
 // Re-export key types for convenience
 pub use wavecraft_core::prelude as core_prelude;
-pub use wavecraft_dsp::{Bypassed, ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
-pub use wavecraft_metering::{MeterConsumer, MeterFrame, MeterProducer, create_meter_channel};
+/// Test harness for processors: signals, renders, measurements and golden files.
+pub use wavecraft_dsp::testing;
+pub use wavecraft_dsp::{
+    Bypassed, ChannelLayout, MeteringTap, MidSide, MidSideMode, Mix, ModulationHandle,
+    ModulationMatrix, ModulationTarget, NoteEvent, NoteEventKind, Oversampled, Parallel,
+    ParamRange, ParamSmoothing, ParamSpec, PersistentField, PersistentState, Processor,
+    ProcessorParams, Smoother, SmoothingStyle, TailLength, TapId, Transport, tap_id,
+};
+pub use wavecraft_metering::{
+    LoudnessFrame, MeterConsumer, MeterEngine, MeterFrame, MeterProducer, create_meter_channel,
+};
 pub use wavecraft_processors::{
-    OSCILLOSCOPE_FRAME_POINTS, Oscillator, OscillatorParams, OscilloscopeFrameConsumer,
-    OscilloscopeFrameProducer, OscilloscopeTap, create_oscilloscope_channel,
+    MeterTap, OSCILLOSCOPE_FRAME_POINTS, Oscillator, OscillatorParams, OscilloscopeFrameConsumer,
+    OscilloscopeFrameProducer, OscilloscopeTap, ScopeTap, SpectrumAnalyzer, SpectrumConfig,
+    SpectrumFrameConsumer, SpectrumFrameProducer, SpectrumTap, SpectrumWindow,
+    create_oscilloscope_channel, create_spectrum_channel,
+};
+pub use wavecraft_protocol::{
+    LfoRate, LfoShape, ModulationConfig, ModulationPolarity, ModulationRouting, ModulationSource,
+    ParameterInfo, ProcessorInfo,
 };
-pub use wavecraft_protocol::{ParameterInfo, ProcessorInfo};
 
 // Re-export the wavecraft_processor! macro from wavecraft_core
 pub use wavecraft_core::wavecraft_processor;
@@ -95,9 +109,42 @@ pub mod __internal {
     pub use wavecraft_protocol::ProcessorInfo;
 
     // Dev audio FFI types (used by macro-generated vtable export)
+    pub use wavecraft_protocol::DEV_PROCESSOR_MAX_CHANNELS;
     pub use wavecraft_protocol::DEV_PROCESSOR_VTABLE_VERSION;
     pub use wavecraft_protocol::DevProcessorVTable;
 
+    // Host transport and layout conversion (used by macro-generated plugin code)
+    pub use crate::util::{
+        channel_layout_from_host, note_event_from_host, process_status_for_tail,
+        transport_from_host,
+    };
+
+    // Persistent processor state (used by macro-generated Params impl)
+    pub use crate::util::{deserialize_persistent_fields, serialize_persistent_fields};
+
+    // Modulation routings (used by macro-generated Params impl)
+    pub use crate::util::{deserialize_modulation, serialize_modulation};
+
+    // Factory/user presets (used by macro-generated editor and FFI export)
+    pub use crate::util::preset_catalog;
+    pub use wavecraft_bridge::PresetLibrary;
+    pub use wavecraft_protocol::PresetCatalog;
+
+    // Note event buffer size shared with the Oversampled combinator
+    pub use wavecraft_dsp::MAX_EVENTS_PER_BLOCK;
+
+    // Editor-gated output analysis (used by macro-generated plugin code)
+    pub use crate::util::EditorPresence;
+
+    // Host sample rate shared with the editor (used by macro-generated plugin code)
+    pub use crate::util::SharedSampleRate;
+
+    // Silence tracking (used by macro-generated process())
+    pub use wavecraft_dsp::{TailTracker, is_silent};
+
+    // Denormal and NaN/Inf protection (used by macro-generated process())
+    pub use wavecraft_dsp::{DenormalGuard, OutputSanitizer};
+
     use wavecraft_dsp::ParamRange;
     use wavecraft_dsp::ParamSpec;
 
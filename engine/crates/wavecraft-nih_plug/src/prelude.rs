@@ -16,10 +16,16 @@ pub use nih_plug::prelude::*;
 pub use wavecraft_core::prelude::*;
 
 // Re-export wavecraft-dsp traits/types and wavecraft-processors implementations
-pub use wavecraft_dsp::{Processor, ProcessorParams, Transport};
+// (explicit imports take precedence over nih-plug's identically named
+// `Transport`, `NoteEvent`, `Smoother` and `SmoothingStyle`)
+pub use wavecraft_dsp::{
+    NoteEvent, NoteEventKind, ParamSmoothing, Processor, ProcessorParams, Smoother, SmoothingStyle,
+    TapId, Transport, tap_id,
+};
 pub use wavecraft_processors::{
-    GainDsp, Oscillator, OscillatorParams, PassthroughDsp, SaturatorDsp, UnifiedFilterDsp,
-    UnifiedFilterMode,
+    CompressorDsp, DelayDsp, DelayMode, GainDsp, LimiterDsp, MeterTap, NoteDivision, Oscillator,
+    OscillatorParams, ParametricEqDsp, PassthroughDsp, ReverbDsp, SaturatorDsp, ScopeTap,
+    SpectrumTap, StereoUtilityDsp, UnifiedFilterDsp, UnifiedFilterMode,
 };
 
 // Re-export wavecraft-protocol types
@@ -2,8 +2,317 @@
 //!
 //! This module provides helper functions used by the plugin framework.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
 use nih_plug::prelude::*;
+use wavecraft_bridge::default_user_preset_dir;
+use wavecraft_dsp::{
+    ChannelLayout, ModulationHandle, NoteEvent as DspNoteEvent, NoteEventKind, PersistentField,
+    TailState, Transport as DspTransport,
+};
 use wavecraft_metering::MeterFrame;
+use wavecraft_protocol::{Preset, PresetCatalog};
+
+/// Convert nih-plug's host transport into a Wavecraft [`DspTransport`].
+///
+/// nih-plug does not expose the processing mode on the transport, so the
+/// caller passes `offline` (captured from `BufferConfig::process_mode` during
+/// initialization). Negative time signature values reported by misbehaving
+/// hosts are treated as unknown.
+#[inline]
+pub fn transport_from_host(transport: &Transport, offline: bool) -> DspTransport {
+    // `DspTransport` is `#[non_exhaustive]`, so fill it field by field.
+    let mut dsp = DspTransport::default();
+    dsp.tempo = transport.tempo;
+    dsp.pos_samples = transport.pos_samples().unwrap_or(0);
+    dsp.playing = transport.playing;
+    dsp.recording = transport.recording;
+    dsp.offline = offline;
+    dsp.time_sig_numerator = transport
+        .time_sig_numerator
+        .and_then(|value| u32::try_from(value).ok());
+    dsp.time_sig_denominator = transport
+        .time_sig_denominator
+        .and_then(|value| u32::try_from(value).ok());
+    dsp.pos_beats = transport.pos_beats();
+    dsp.bar_start_pos_beats = transport.bar_start_pos_beats();
+    dsp.bar_number = transport.bar_number();
+    dsp.loop_range_samples = transport.loop_range_samples();
+    dsp.loop_range_beats = transport.loop_range_beats();
+    dsp
+}
+
+/// Convert the host-selected nih-plug [`AudioIOLayout`] into a Wavecraft
+/// [`ChannelLayout`].
+///
+/// A missing main input (instruments) is reported as zero input channels.
+#[inline]
+pub fn channel_layout_from_host(layout: &AudioIOLayout) -> ChannelLayout {
+    ChannelLayout::new(
+        layout
+            .main_input_channels
+            .map_or(0, |channels| channels.get()),
+        layout
+            .main_output_channels
+            .map_or(0, |channels| channels.get()),
+    )
+}
+
+/// Convert a nih-plug note event into a Wavecraft [`DspNoteEvent`].
+///
+/// Returns `None` for events Wavecraft processors do not consume (voice
+/// terminations, polyphonic modulation, program changes, SysEx, ...).
+/// Pitch bend is re-centered from nih-plug's `0.0..=1.0` to `-1.0..=1.0`.
+#[inline]
+pub fn note_event_from_host<S>(event: &NoteEvent<S>) -> Option<DspNoteEvent> {
+    let (timing, kind) = match *event {
+        NoteEvent::NoteOn {
+            timing,
+            channel,
+            note,
+            velocity,
+            ..
+        } => (
+            timing,
+            NoteEventKind::NoteOn {
+                channel,
+                note,
+                velocity,
+            },
+        ),
+        NoteEvent::NoteOff {
+            timing,
+            channel,
+            note,
+            velocity,
+            ..
+        } => (
+            timing,
+            NoteEventKind::NoteOff {
+                channel,
+                note,
+                velocity,
+            },
+        ),
+        NoteEvent::PolyPressure {
+            timing,
+            channel,
+            note,
+            pressure,
+            ..
+        } => (
+            timing,
+            NoteEventKind::PolyPressure {
+                channel,
+                note,
+                pressure,
+            },
+        ),
+        NoteEvent::MidiChannelPressure {
+            timing,
+            channel,
+            pressure,
+        } => (timing, NoteEventKind::ChannelPressure { channel, pressure }),
+        NoteEvent::MidiCC {
+            timing,
+            channel,
+            cc,
+            value,
+        } => (timing, NoteEventKind::ControlChange { channel, cc, value }),
+        NoteEvent::MidiPitchBend {
+            timing,
+            channel,
+            value,
+        } => (
+            timing,
+            NoteEventKind::PitchBend {
+                channel,
+                value: value * 2.0 - 1.0,
+            },
+        ),
+        _ => return None,
+    };
+
+    Some(DspNoteEvent { timing, kind })
+}
+
+/// Map the generated plugin's [`TailState`] to the status returned from
+/// `process()`.
+///
+/// Effects report their full tail while the input is audible and count it
+/// down once the input goes silent, so the host can suspend them afterwards.
+/// Instruments have no input for the host to watch, so they stay awake while
+/// notes are sounding.
+#[inline]
+pub fn process_status_for_tail(state: TailState, instrument: bool) -> ProcessStatus {
+    match state {
+        TailState::Infinite => ProcessStatus::KeepAlive,
+        TailState::Active { .. } if instrument => ProcessStatus::KeepAlive,
+        TailState::Active { tail: 0 } | TailState::Finished => ProcessStatus::Normal,
+        TailState::Active { tail } => ProcessStatus::Tail(tail),
+        TailState::Ringing { remaining } => ProcessStatus::Tail(remaining),
+    }
+}
+
+/// Tracks whether an editor window of the plugin instance is open.
+///
+/// The generated plugin only runs its output meters, oscilloscope and
+/// spectrum analyzer while this reports open, so headless hosts and offline
+/// renders don't pay for analysis nobody looks at. Clones share the same
+/// state.
+#[derive(Debug, Clone, Default)]
+pub struct EditorPresence {
+    open_editors: Arc<AtomicUsize>,
+}
+
+impl EditorPresence {
+    /// Mark an editor as open until the returned guard is dropped.
+    pub fn open(&self) -> EditorOpenGuard {
+        self.open_editors.fetch_add(1, Ordering::Relaxed);
+        EditorOpenGuard {
+            open_editors: self.open_editors.clone(),
+        }
+    }
+
+    /// Whether at least one editor is open. Real-time safe.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.open_editors.load(Ordering::Relaxed) > 0
+    }
+}
+
+/// Keeps an [`EditorPresence`] open; returned from `Editor::spawn()` so the
+/// host drops it when it closes the window.
+#[derive(Debug)]
+pub struct EditorOpenGuard {
+    open_editors: Arc<AtomicUsize>,
+}
+
+impl Drop for EditorOpenGuard {
+    fn drop(&mut self) {
+        self.open_editors.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Sample rate the host initialized the plugin with, shared with the
+/// editor. Clones share the same value.
+#[derive(Debug, Clone, Default)]
+pub struct SharedSampleRate {
+    bits: Arc<AtomicU32>,
+}
+
+impl SharedSampleRate {
+    /// Record the host sample rate.
+    pub fn set(&self, sample_rate: f32) {
+        self.bits.store(sample_rate.to_bits(), Ordering::Relaxed);
+    }
+
+    /// The host sample rate, or `None` before the plugin is initialized.
+    pub fn get(&self) -> Option<f32> {
+        let sample_rate = f32::from_bits(self.bits.load(Ordering::Relaxed));
+        (sample_rate > 0.0).then_some(sample_rate)
+    }
+}
+
+/// Key prefix of persistent processor state in nih-plug's serialized fields.
+const PERSISTENT_STATE_KEY_PREFIX: &str = "wavecraft_state_";
+
+/// Serialize the processor chain's persistent fields for the plugin state.
+///
+/// Each field is stored under its position in the chain, so saved state
+/// restores into the same chain layout.
+pub fn serialize_persistent_fields(
+    fields: &[Arc<dyn PersistentField>],
+) -> BTreeMap<String, String> {
+    fields
+        .iter()
+        .enumerate()
+        .filter_map(|(index, field)| {
+            let data = field.serialize()?;
+            Some((format!("{PERSISTENT_STATE_KEY_PREFIX}{index}"), data))
+        })
+        .collect()
+}
+
+/// Restore persistent fields from the plugin state.
+///
+/// nih-plug calls this off the audio thread; fields missing from the state or
+/// failing to decode keep their current value.
+pub fn deserialize_persistent_fields(
+    fields: &[Arc<dyn PersistentField>],
+    serialized: &BTreeMap<String, String>,
+) {
+    for (index, field) in fields.iter().enumerate() {
+        if let Some(data) = serialized.get(&format!("{PERSISTENT_STATE_KEY_PREFIX}{index}"))
+            && !field.deserialize(data)
+        {
+            nih_warn!("Ignoring unreadable state for persistent field {index}");
+        }
+    }
+}
+
+/// Key of the modulation routings in nih-plug's serialized fields.
+const MODULATION_STATE_KEY: &str = "wavecraft_modulation";
+
+/// Add the modulation routings to the serialized plugin state.
+///
+/// Stored under their own key so they survive changes to the processor
+/// chain's persistent fields.
+pub fn serialize_modulation(
+    modulation: &ModulationHandle,
+    serialized: &mut BTreeMap<String, String>,
+) {
+    if let Some(data) = modulation.field().serialize() {
+        serialized.insert(MODULATION_STATE_KEY.to_string(), data);
+    }
+}
+
+/// Restore the modulation routings from the plugin state.
+///
+/// Routings to parameters the plugin no longer has are kept but ignored by
+/// the audio thread.
+pub fn deserialize_modulation(
+    modulation: &ModulationHandle,
+    serialized: &BTreeMap<String, String>,
+) {
+    if let Some(data) = serialized.get(MODULATION_STATE_KEY)
+        && !modulation.field().deserialize(data)
+    {
+        nih_warn!("Ignoring unreadable modulation state");
+    }
+}
+
+/// Build the plugin's preset catalog from the factory preset files embedded
+/// by `wavecraft_plugin!`, given as `(path, json)` pairs.
+///
+/// Files that don't parse are left out with a warning rather than failing
+/// plugin initialization.
+pub fn preset_catalog(
+    factory: &[(&str, &str)],
+    vendor: &str,
+    plugin_name: &str,
+    plugin_version: &str,
+) -> PresetCatalog {
+    let factory = factory
+        .iter()
+        .filter_map(|(path, json)| match serde_json::from_str::<Preset>(json) {
+            Ok(preset) => Some(preset),
+            Err(err) => {
+                nih_warn!("Skipping invalid factory preset {path}: {err}");
+                None
+            }
+        })
+        .collect();
+
+    PresetCatalog {
+        plugin_version: plugin_version.to_string(),
+        user_dir: default_user_preset_dir(vendor, plugin_name)
+            .map(|dir| dir.to_string_lossy().into_owned()),
+        factory,
+    }
+}
 
 /// Calculate stereo peak and RMS meters from a nih-plug buffer.
 ///
@@ -16,18 +325,13 @@ use wavecraft_metering::MeterFrame;
 ///
 /// # Returns
 ///
-/// A `MeterFrame` containing the peak and RMS values in linear scale.
+/// A `MeterFrame` containing the peak and RMS values in linear scale. True
+/// peak and loudness are left empty; use `MeterEngine` to measure those.
 #[inline]
 pub fn calculate_stereo_meters(buffer: &Buffer) -> MeterFrame {
     let num_samples = buffer.samples();
     if num_samples == 0 {
-        return MeterFrame {
-            peak_l: 0.0,
-            peak_r: 0.0,
-            rms_l: 0.0,
-            rms_r: 0.0,
-            timestamp: 0,
-        };
+        return MeterFrame::default();
     }
 
     let mut peak_l = 0.0f32;
@@ -62,6 +366,6 @@ pub fn calculate_stereo_meters(buffer: &Buffer) -> MeterFrame {
         peak_r,
         rms_l,
         rms_r,
-        timestamp: 0,
+        ..MeterFrame::default()
     }
 }
@@ -0,0 +1,75 @@
+//! Second-order filter sections shared by the filter processors.
+
+use core::f64::consts::TAU;
+
+/// Normalized biquad coefficients (`a0 == 1`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BiquadCoefficients {
+    pub(crate) b0: f32,
+    pub(crate) b1: f32,
+    pub(crate) b2: f32,
+    pub(crate) a1: f32,
+    pub(crate) a2: f32,
+}
+
+impl BiquadCoefficients {
+    /// Section that passes its input through unchanged.
+    pub(crate) const IDENTITY: Self = Self {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+
+    /// Normalize raw coefficients by `a0`.
+    pub(crate) fn from_raw(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Self {
+            b0: (b0 / a0) as f32,
+            b1: (b1 / a0) as f32,
+            b2: (b2 / a0) as f32,
+            a1: (a1 / a0) as f32,
+            a2: (a2 / a0) as f32,
+        }
+    }
+
+    /// Linear magnitude response at `frequency_hz`.
+    pub(crate) fn magnitude(&self, frequency_hz: f32, sample_rate_hz: f32) -> f64 {
+        let omega = TAU * f64::from(frequency_hz) / f64::from(sample_rate_hz);
+        let (cos1, sin1) = (omega.cos(), omega.sin());
+        let (cos2, sin2) = ((2.0 * omega).cos(), (2.0 * omega).sin());
+
+        let (b0, b1, b2) = (f64::from(self.b0), f64::from(self.b1), f64::from(self.b2));
+        let (a1, a2) = (f64::from(self.a1), f64::from(self.a2));
+
+        // Evaluate numerator and denominator at z = e^(jω).
+        let numerator = (b0 + b1 * cos1 + b2 * cos2).hypot(b1 * sin1 + b2 * sin2);
+        let denominator = (1.0 + a1 * cos1 + a2 * cos2).hypot(a1 * sin1 + a2 * sin2);
+        numerator / denominator
+    }
+}
+
+/// Direct form I state for one biquad section on one channel.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BiquadState {
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl BiquadState {
+    #[inline]
+    pub(crate) fn process_sample(&mut self, input: f32, coeffs: BiquadCoefficients) -> f32 {
+        let output = coeffs.b0 * input + coeffs.b1 * self.x1 + coeffs.b2 * self.x2
+            - coeffs.a1 * self.y1
+            - coeffs.a2 * self.y2;
+
+        self.x2 = self.x1;
+        self.x1 = input;
+        self.y2 = self.y1;
+        self.y1 = output;
+
+        output
+    }
+}
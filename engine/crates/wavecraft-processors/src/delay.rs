@@ -0,0 +1,818 @@
+//! Stereo / ping-pong delay with filtered feedback and tempo sync.
+
+use core::f32::consts::TAU;
+use wavecraft_dsp::{
+    ChannelLayout, ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams,
+    SILENCE_THRESHOLD, Smoother, SmoothingStyle, TailLength, Transport,
+};
+
+const MIN_TIME_MS: f32 = 1.0;
+const MAX_TIME_MS: f32 = 2_000.0;
+const DEFAULT_TIME_MS: f32 = 375.0;
+const MAX_FEEDBACK: f32 = 0.95;
+const MAX_MOD_DEPTH_MS: f32 = 10.0;
+const MIN_CUT_HZ: f32 = 20.0;
+const MAX_CUT_HZ: f32 = 20_000.0;
+/// Longest delay the lines hold. Synced note values can exceed
+/// [`MAX_TIME_MS`] at slow tempos and are clamped to this.
+const MAX_DELAY_SECONDS: f32 = 4.0;
+/// Glide time when the delay time changes, giving a tape-style pitch bend
+/// instead of clicks.
+const DELAY_TIME_SMOOTHING_MS: f32 = 100.0;
+/// Channels with delay lines before the host reports a layout.
+const DEFAULT_DELAY_CHANNELS: usize = 2;
+const DEFAULT_SAMPLE_RATE_HZ: f32 = 44_100.0;
+
+/// How repeats are routed between channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DelayMode {
+    /// Each channel repeats into itself.
+    #[default]
+    Stereo,
+    /// The first two channels are summed into the left line and repeats
+    /// bounce between left and right. Further channels use `Stereo`.
+    PingPong,
+}
+
+/// Note value a synced delay time follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteDivision {
+    Whole,
+    HalfDotted,
+    Half,
+    HalfTriplet,
+    QuarterDotted,
+    Quarter,
+    QuarterTriplet,
+    EighthDotted,
+    Eighth,
+    EighthTriplet,
+    SixteenthDotted,
+    Sixteenth,
+    SixteenthTriplet,
+    ThirtySecond,
+}
+
+impl NoteDivision {
+    /// Divisions in the order of the `sync` parameter, after "Off".
+    const ALL: [Self; 14] = [
+        Self::Whole,
+        Self::HalfDotted,
+        Self::Half,
+        Self::HalfTriplet,
+        Self::QuarterDotted,
+        Self::Quarter,
+        Self::QuarterTriplet,
+        Self::EighthDotted,
+        Self::Eighth,
+        Self::EighthTriplet,
+        Self::SixteenthDotted,
+        Self::Sixteenth,
+        Self::SixteenthTriplet,
+        Self::ThirtySecond,
+    ];
+
+    /// Length in quarter notes.
+    pub fn beats(self) -> f64 {
+        match self {
+            Self::Whole => 4.0,
+            Self::HalfDotted => 3.0,
+            Self::Half => 2.0,
+            Self::HalfTriplet => 4.0 / 3.0,
+            Self::QuarterDotted => 1.5,
+            Self::Quarter => 1.0,
+            Self::QuarterTriplet => 2.0 / 3.0,
+            Self::EighthDotted => 0.75,
+            Self::Eighth => 0.5,
+            Self::EighthTriplet => 1.0 / 3.0,
+            Self::SixteenthDotted => 0.375,
+            Self::Sixteenth => 0.25,
+            Self::SixteenthTriplet => 1.0 / 6.0,
+            Self::ThirtySecond => 0.125,
+        }
+    }
+
+    fn from_sync_index(index: i32) -> Option<Self> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| Self::ALL.get(index).copied())
+    }
+}
+
+/// Parameters for the delay processor.
+#[derive(Debug, Clone)]
+pub struct DelayParams {
+    /// Repeat routing.
+    pub mode: DelayMode,
+    /// Delay time in milliseconds, used when `sync` is off or the host
+    /// reports no tempo.
+    pub time_ms: f32,
+    /// Note value the delay time follows, from [`Transport::tempo`].
+    pub sync: Option<NoteDivision>,
+    /// Amount of each repeat fed back into the line (`0.0..=0.95`).
+    pub feedback: f32,
+    /// Low-pass cutoff applied to repeats, in Hz.
+    pub high_cut_hz: f32,
+    /// High-pass cutoff applied to repeats, in Hz.
+    pub low_cut_hz: f32,
+    /// Read-head modulation rate in Hz.
+    pub mod_rate_hz: f32,
+    /// Read-head modulation depth in milliseconds.
+    pub mod_depth_ms: f32,
+    /// Dry/wet balance (`0.0` = dry, `1.0` = wet).
+    pub mix: f32,
+}
+
+impl Default for DelayParams {
+    fn default() -> Self {
+        Self::from_param_defaults()
+    }
+}
+
+impl ProcessorParams for DelayParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static MODES: [&str; 2] = ["Stereo", "Ping-pong"];
+        static SYNC: [&str; 15] = [
+            "Off", "1/1", "1/2 D", "1/2", "1/2 T", "1/4 D", "1/4", "1/4 T", "1/8 D", "1/8",
+            "1/8 T", "1/16 D", "1/16", "1/16 T", "1/32",
+        ];
+        static SPECS: [ParamSpec; 9] = [
+            ParamSpec {
+                name: "Mode",
+                id_suffix: "mode",
+                range: ParamRange::Enum { variants: &MODES },
+                default: 0.0,
+                unit: "",
+                group: Some("Delay"),
+            },
+            ParamSpec {
+                name: "Time",
+                id_suffix: "time_ms",
+                range: ParamRange::Skewed {
+                    min: MIN_TIME_MS as f64,
+                    max: MAX_TIME_MS as f64,
+                    factor: 2.5,
+                },
+                default: DEFAULT_TIME_MS as f64,
+                unit: "ms",
+                group: Some("Delay"),
+            },
+            ParamSpec {
+                name: "Sync",
+                id_suffix: "sync",
+                range: ParamRange::Enum { variants: &SYNC },
+                default: 0.0,
+                unit: "",
+                group: Some("Delay"),
+            },
+            ParamSpec {
+                name: "Feedback",
+                id_suffix: "feedback",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: MAX_FEEDBACK as f64,
+                },
+                default: 0.4,
+                unit: "",
+                group: Some("Delay"),
+            },
+            ParamSpec {
+                name: "High Cut",
+                id_suffix: "high_cut_hz",
+                range: ParamRange::Skewed {
+                    min: MIN_CUT_HZ as f64,
+                    max: MAX_CUT_HZ as f64,
+                    factor: 2.5,
+                },
+                default: 8_000.0,
+                unit: "Hz",
+                group: Some("Feedback"),
+            },
+            ParamSpec {
+                name: "Low Cut",
+                id_suffix: "low_cut_hz",
+                range: ParamRange::Skewed {
+                    min: MIN_CUT_HZ as f64,
+                    max: MAX_CUT_HZ as f64,
+                    factor: 2.5,
+                },
+                default: 80.0,
+                unit: "Hz",
+                group: Some("Feedback"),
+            },
+            ParamSpec {
+                name: "Mod Rate",
+                id_suffix: "mod_rate_hz",
+                range: ParamRange::Skewed {
+                    min: 0.05,
+                    max: 10.0,
+                    factor: 2.0,
+                },
+                default: 0.5,
+                unit: "Hz",
+                group: Some("Modulation"),
+            },
+            ParamSpec {
+                name: "Mod Depth",
+                id_suffix: "mod_depth_ms",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: MAX_MOD_DEPTH_MS as f64,
+                },
+                default: 0.0,
+                unit: "ms",
+                group: Some("Modulation"),
+            },
+            ParamSpec {
+                name: "Mix",
+                id_suffix: "mix",
+                range: ParamRange::Linear { min: 0.0, max: 1.0 },
+                default: 0.35,
+                unit: "",
+                group: Some("Delay"),
+            },
+        ];
+
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            mode: DelayMode::Stereo,
+            time_ms: DEFAULT_TIME_MS,
+            sync: None,
+            feedback: 0.4,
+            high_cut_hz: 8_000.0,
+            low_cut_hz: 80.0,
+            mod_rate_hz: 0.5,
+            mod_depth_ms: 0.0,
+            mix: 0.35,
+        }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(mode) = values.first() {
+            self.mode = if mode.round() as i32 == 1 {
+                DelayMode::PingPong
+            } else {
+                DelayMode::Stereo
+            };
+        }
+        if let Some(time_ms) = values.get(1) {
+            self.time_ms = *time_ms;
+        }
+        if let Some(sync) = values.get(2) {
+            self.sync = NoteDivision::from_sync_index(sync.round() as i32);
+        }
+        if let Some(feedback) = values.get(3) {
+            self.feedback = *feedback;
+        }
+        if let Some(high_cut_hz) = values.get(4) {
+            self.high_cut_hz = *high_cut_hz;
+        }
+        if let Some(low_cut_hz) = values.get(5) {
+            self.low_cut_hz = *low_cut_hz;
+        }
+        if let Some(mod_rate_hz) = values.get(6) {
+            self.mod_rate_hz = *mod_rate_hz;
+        }
+        if let Some(mod_depth_ms) = values.get(7) {
+            self.mod_depth_ms = *mod_depth_ms;
+        }
+        if let Some(mix) = values.get(8) {
+            self.mix = *mix;
+        }
+    }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[
+            ParamSmoothing {
+                id_suffix: "feedback",
+                style: SmoothingStyle::Linear(20.0),
+            },
+            ParamSmoothing {
+                id_suffix: "mix",
+                style: SmoothingStyle::Linear(20.0),
+            },
+        ]
+    }
+}
+
+/// One-pole coefficient for a cutoff at `sample_rate_hz`.
+#[inline]
+fn one_pole_coefficient(cutoff_hz: f32, sample_rate_hz: f32) -> f32 {
+    let cutoff_hz = cutoff_hz.clamp(MIN_CUT_HZ, sample_rate_hz * 0.49);
+    1.0 - (-TAU * cutoff_hz / sample_rate_hz).exp()
+}
+
+/// Delay line and feedback filter state for one channel.
+#[derive(Debug, Clone, Default)]
+struct DelayChannel {
+    line: Vec<f32>,
+    write_pos: usize,
+    high_cut_state: f32,
+    low_cut_state: f32,
+}
+
+impl DelayChannel {
+    fn allocate(&mut self, len: usize) {
+        if self.line.len() != len {
+            self.line = vec![0.0; len];
+        }
+        self.clear();
+    }
+
+    fn clear(&mut self) {
+        self.line.fill(0.0);
+        self.write_pos = 0;
+        self.high_cut_state = 0.0;
+        self.low_cut_state = 0.0;
+    }
+
+    /// Linearly interpolated sample written `delay` samples ago.
+    #[inline]
+    fn read(&self, delay: f32) -> f32 {
+        let len = self.line.len();
+        let delay = delay.clamp(1.0, (len - 2) as f32);
+        let position = self.write_pos as f32 - delay;
+        let position = if position < 0.0 {
+            position + len as f32
+        } else {
+            position
+        };
+        let index = position as usize;
+        let fraction = position - index as f32;
+        let current = self.line[index % len];
+        let next = self.line[(index + 1) % len];
+        current + (next - current) * fraction
+    }
+
+    #[inline]
+    fn write(&mut self, sample: f32) {
+        self.line[self.write_pos] = sample;
+        self.write_pos += 1;
+        if self.write_pos == self.line.len() {
+            self.write_pos = 0;
+        }
+    }
+
+    /// Band-limit a repeat before it is fed back.
+    #[inline]
+    fn filter(&mut self, sample: f32, high_cut: f32, low_cut: f32) -> f32 {
+        self.high_cut_state += high_cut * (sample - self.high_cut_state);
+        self.low_cut_state += low_cut * (self.high_cut_state - self.low_cut_state);
+        self.high_cut_state - self.low_cut_state
+    }
+}
+
+/// Tempo-syncable stereo / ping-pong delay.
+///
+/// Delay lines are allocated in `set_sample_rate` (and `set_channel_layout`)
+/// and hold up to four seconds; until then the processor passes audio
+/// through. Repeats go through a high-cut and low-cut filter before being
+/// fed back, and the read head can be modulated by a sine LFO for chorus
+/// and tape-wobble effects. Changes to the delay time glide rather than
+/// jump.
+#[derive(Debug)]
+pub struct DelayDsp {
+    sample_rate_hz: f32,
+    channels: Vec<DelayChannel>,
+    delay_samples: Smoother,
+    feedback: Smoother,
+    mix: Smoother,
+    lfo_phase: f32,
+    tail: TailLength,
+}
+
+impl Default for DelayDsp {
+    fn default() -> Self {
+        let params = DelayParams::from_param_defaults();
+        Self {
+            sample_rate_hz: DEFAULT_SAMPLE_RATE_HZ,
+            channels: vec![DelayChannel::default(); DEFAULT_DELAY_CHANNELS],
+            delay_samples: Smoother::new(SmoothingStyle::Exponential(DELAY_TIME_SMOOTHING_MS)),
+            feedback: Smoother::for_param::<DelayParams>("feedback"),
+            mix: Smoother::for_param::<DelayParams>("mix"),
+            lfo_phase: 0.0,
+            tail: tail_length(
+                params.time_ms * DEFAULT_SAMPLE_RATE_HZ / 1000.0,
+                0.0,
+                params.feedback,
+            ),
+        }
+    }
+}
+
+impl DelayDsp {
+    fn line_len(&self) -> usize {
+        (self.sample_rate_hz * MAX_DELAY_SECONDS) as usize + 2
+    }
+
+    fn allocate_lines(&mut self) {
+        let len = self.line_len();
+        for channel in &mut self.channels {
+            channel.allocate(len);
+        }
+    }
+
+    /// Target delay in samples: the synced note value when the host reports
+    /// a tempo, `time_ms` otherwise.
+    fn target_delay_samples(&self, params: &DelayParams, transport: &Transport) -> f32 {
+        let synced = params
+            .sync
+            .zip(transport.samples_per_beat(self.sample_rate_hz));
+        let samples = match synced {
+            Some((division, samples_per_beat)) => (division.beats() * samples_per_beat) as f32,
+            None => params.time_ms.clamp(MIN_TIME_MS, MAX_TIME_MS) * self.sample_rate_hz / 1000.0,
+        };
+        samples.min(self.sample_rate_hz * MAX_DELAY_SECONDS)
+    }
+}
+
+impl Processor for DelayDsp {
+    type Params = DelayParams;
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        if self
+            .channels
+            .first()
+            .is_none_or(|channel| channel.line.is_empty())
+        {
+            return;
+        }
+
+        let sample_rate = self.sample_rate_hz;
+        let feedback_target = params.feedback.clamp(0.0, MAX_FEEDBACK);
+        let mod_depth = params.mod_depth_ms.clamp(0.0, MAX_MOD_DEPTH_MS) * sample_rate / 1000.0;
+        let target_delay = self.target_delay_samples(params, transport);
+        self.delay_samples.set_target(target_delay);
+        self.feedback.set_target(feedback_target);
+        self.mix.set_target(params.mix.clamp(0.0, 1.0));
+        self.tail = tail_length(target_delay, mod_depth, feedback_target);
+
+        let high_cut = one_pole_coefficient(params.high_cut_hz, sample_rate);
+        let low_cut = one_pole_coefficient(params.low_cut_hz, sample_rate);
+        let lfo_step = params.mod_rate_hz.max(0.0) / sample_rate;
+
+        // Channels beyond the configured layout have no line and pass through.
+        let channels = buffer.len().min(self.channels.len());
+        let ping_pong = params.mode == DelayMode::PingPong && channels >= 2;
+        let num_samples = buffer.first().map_or(0, |channel| channel.len());
+
+        for frame in 0..num_samples {
+            let delay = self.delay_samples.next();
+            let feedback = self.feedback.next();
+            let mix = self.mix.next();
+            // Each channel's read head is a quarter cycle ahead of the previous
+            // one, so modulation widens the stereo image.
+            let modulation = |channel: usize| {
+                mod_depth * (0.5 + 0.5 * (TAU * (self.lfo_phase + 0.25 * channel as f32)).sin())
+            };
+
+            let first_independent = if ping_pong {
+                let (left_dry, right_dry) = (buffer[0][frame], buffer[1][frame]);
+                let left_wet = self.channels[0].read(delay + modulation(0));
+                let right_wet = self.channels[1].read(delay + modulation(1));
+                let left_repeat = self.channels[0].filter(left_wet, high_cut, low_cut);
+                let right_repeat = self.channels[1].filter(right_wet, high_cut, low_cut);
+                self.channels[0].write(0.5 * (left_dry + right_dry) + feedback * right_repeat);
+                self.channels[1].write(feedback * left_repeat);
+                buffer[0][frame] = left_dry + (left_wet - left_dry) * mix;
+                buffer[1][frame] = right_dry + (right_wet - right_dry) * mix;
+                2
+            } else {
+                0
+            };
+
+            let independent = buffer
+                .iter_mut()
+                .zip(self.channels.iter_mut())
+                .enumerate()
+                .skip(first_independent);
+            for (index, (samples, channel)) in independent {
+                let dry = samples[frame];
+                let wet = channel.read(delay + modulation(index));
+                let repeat = channel.filter(wet, high_cut, low_cut);
+                channel.write(dry + feedback * repeat);
+                samples[frame] = dry + (wet - dry) * mix;
+            }
+
+            self.lfo_phase += lfo_step;
+            if self.lfo_phase >= 1.0 {
+                self.lfo_phase -= 1.0;
+            }
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate_hz = sample_rate.max(1.0);
+        self.delay_samples.set_sample_rate(self.sample_rate_hz);
+        self.feedback.set_sample_rate(self.sample_rate_hz);
+        self.mix.set_sample_rate(self.sample_rate_hz);
+        self.allocate_lines();
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        let channels = layout.inputs.max(layout.outputs) as usize;
+        let allocated = self
+            .channels
+            .first()
+            .is_some_and(|channel| !channel.line.is_empty());
+        self.channels
+            .resize(channels.max(1), DelayChannel::default());
+        if allocated {
+            self.allocate_lines();
+        }
+    }
+
+    fn reset(&mut self) {
+        for channel in &mut self.channels {
+            channel.clear();
+        }
+        self.delay_samples.reset();
+        self.feedback.reset();
+        self.mix.reset();
+        self.lfo_phase = 0.0;
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        self.tail
+    }
+}
+
+/// Time for repeats of `delay` samples to decay below [`SILENCE_THRESHOLD`].
+///
+/// Ignores the feedback filters, which only shorten the tail.
+fn tail_length(delay: f32, mod_depth: f32, feedback: f32) -> TailLength {
+    let repeats = if feedback > 0.0 {
+        (SILENCE_THRESHOLD.ln() / feedback.ln()).ceil() + 1.0
+    } else {
+        1.0
+    };
+    TailLength::Samples((repeats * (delay + mod_depth)).ceil().min(u32::MAX as f32) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::testing::{Renderer, assert_block_size_invariant, signals};
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn dry_delay(time_ms: f32, feedback: f32) -> DelayParams {
+        DelayParams {
+            time_ms,
+            feedback,
+            high_cut_hz: MAX_CUT_HZ,
+            low_cut_hz: MIN_CUT_HZ,
+            mix: 1.0,
+            ..DelayParams::from_param_defaults()
+        }
+    }
+
+    /// Level of a repeat starting at `index`. Repeats that went through the
+    /// feedback filters are smeared over a few samples, so sum them.
+    fn repeat_at(signal: &[f32], index: usize) -> f32 {
+        signal[index..index + 16].iter().sum()
+    }
+
+    fn peak_index(signal: &[f32]) -> usize {
+        signal
+            .iter()
+            .enumerate()
+            .fold((0, 0.0_f32), |(best, peak), (index, sample)| {
+                if sample.abs() > peak {
+                    (index, sample.abs())
+                } else {
+                    (best, peak)
+                }
+            })
+            .0
+    }
+
+    #[test]
+    fn param_specs_use_expected_suffixes_and_groups() {
+        let specs = DelayParams::param_specs();
+        let suffixes: Vec<_> = specs.iter().map(|spec| spec.id_suffix).collect();
+        assert_eq!(
+            suffixes,
+            [
+                "mode",
+                "time_ms",
+                "sync",
+                "feedback",
+                "high_cut_hz",
+                "low_cut_hz",
+                "mod_rate_hz",
+                "mod_depth_ms",
+                "mix"
+            ]
+        );
+        assert_eq!(specs[4].group, Some("Feedback"));
+        assert_eq!(specs[7].unit, "ms");
+    }
+
+    #[test]
+    fn apply_plain_values_maps_mode_and_sync() {
+        let mut params = DelayParams::from_param_defaults();
+        params.apply_plain_values(&[1.0, 250.0, 6.0, 0.5]);
+
+        assert_eq!(params.mode, DelayMode::PingPong);
+        assert_eq!(params.sync, Some(NoteDivision::Quarter));
+        assert!((params.time_ms - 250.0).abs() < f32::EPSILON);
+        assert!((params.feedback - 0.5).abs() < f32::EPSILON);
+
+        params.apply_plain_values(&[0.0, 250.0, 0.0]);
+        assert_eq!(params.mode, DelayMode::Stereo);
+        assert_eq!(params.sync, None);
+    }
+
+    #[test]
+    fn passes_audio_through_before_allocation() {
+        let mut delay = DelayDsp::default();
+        let mut mono = [0.5_f32, -0.25];
+        delay.process(
+            &mut [&mut mono[..]],
+            &Transport::default(),
+            &dry_delay(10.0, 0.0),
+        );
+        assert_eq!(mono, [0.5, -0.25]);
+    }
+
+    #[test]
+    fn impulse_repeats_after_delay_time_with_decaying_feedback() {
+        let mut delay = DelayDsp::default();
+        let input = signals::impulse(SAMPLE_RATE as usize / 2);
+        let output = Renderer::new(SAMPLE_RATE).channels(1).render(
+            &mut delay,
+            &dry_delay(100.0, 0.5),
+            &input,
+        );
+
+        let step = 4_800;
+        assert!(output[0][..step].iter().all(|sample| sample.abs() < 1e-6));
+        assert!((output[0][step] - 1.0).abs() < 1e-3);
+        assert!((repeat_at(&output[0], 2 * step) - 0.5).abs() < 0.025);
+        assert!((repeat_at(&output[0], 3 * step) - 0.25).abs() < 0.025);
+    }
+
+    #[test]
+    fn sync_follows_host_tempo() {
+        let mut delay = DelayDsp::default();
+        delay.set_sample_rate(SAMPLE_RATE);
+        let params = DelayParams {
+            sync: Some(NoteDivision::EighthDotted),
+            ..dry_delay(10.0, 0.0)
+        };
+        let transport = Transport::default().with_tempo(120.0);
+
+        let mut mono = signals::impulse(SAMPLE_RATE as usize);
+        delay.process(&mut [&mut mono[..]], &transport, &params);
+        // A dotted eighth at 120 BPM is 375 ms.
+        assert_eq!(peak_index(&mono), 18_000);
+
+        // Without a tempo the free-running time is used.
+        let mut delay = DelayDsp::default();
+        delay.set_sample_rate(SAMPLE_RATE);
+        let mut mono = signals::impulse(SAMPLE_RATE as usize);
+        delay.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        assert_eq!(peak_index(&mono), 480);
+    }
+
+    #[test]
+    fn ping_pong_alternates_channels() {
+        let mut delay = DelayDsp::default();
+        let params = DelayParams {
+            mode: DelayMode::PingPong,
+            ..dry_delay(50.0, 0.5)
+        };
+        let left = signals::impulse(SAMPLE_RATE as usize / 4);
+        let right = vec![0.0; left.len()];
+        let output =
+            Renderer::new(SAMPLE_RATE).render_channels(&mut delay, &params, &[left, right]);
+
+        let step = 2_400;
+        assert!(
+            (output[0][step] - 0.5).abs() < 1e-3,
+            "mono sum hits left first"
+        );
+        assert!(output[1][step].abs() < 1e-6);
+        assert!((repeat_at(&output[1], 2 * step) - 0.25).abs() < 0.0125);
+        assert!(repeat_at(&output[0], 2 * step).abs() < 1e-3);
+        assert!((repeat_at(&output[0], 3 * step) - 0.125).abs() < 0.0125);
+    }
+
+    #[test]
+    fn feedback_filter_darkens_repeats() {
+        let input = signals::noise(SAMPLE_RATE as usize / 4, 7);
+        let render = |high_cut_hz| {
+            let params = DelayParams {
+                high_cut_hz,
+                ..dry_delay(20.0, 0.9)
+            };
+            Renderer::new(SAMPLE_RATE)
+                .channels(1)
+                .render(&mut DelayDsp::default(), &params, &input)
+        };
+        let roughness = |signal: &[f32]| -> f32 {
+            signal
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).abs())
+                .sum()
+        };
+
+        let bright = render(MAX_CUT_HZ);
+        let dark = render(1_000.0);
+        let tail = input.len() / 2..;
+        assert!(roughness(&dark[0][tail.clone()]) < roughness(&bright[0][tail]) * 0.8);
+    }
+
+    #[test]
+    fn modulation_moves_the_read_head() {
+        let params = DelayParams {
+            mod_depth_ms: 5.0,
+            mod_rate_hz: 2.0,
+            ..dry_delay(50.0, 0.0)
+        };
+        let input = signals::sine(1_000.0, SAMPLE_RATE, SAMPLE_RATE as usize / 2);
+        let modulated = Renderer::new(SAMPLE_RATE).channels(1).render(
+            &mut DelayDsp::default(),
+            &params,
+            &input,
+        );
+        let steady = Renderer::new(SAMPLE_RATE).channels(1).render(
+            &mut DelayDsp::default(),
+            &dry_delay(50.0, 0.0),
+            &input,
+        );
+
+        let difference = modulated[0]
+            .iter()
+            .zip(&steady[0])
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(difference > 0.5, "difference {difference}");
+        assert!(modulated[0].iter().all(|sample| sample.abs() <= 1.0 + 1e-3));
+    }
+
+    #[test]
+    fn output_is_block_size_invariant() {
+        let params = DelayParams {
+            mode: DelayMode::PingPong,
+            mod_depth_ms: 2.0,
+            ..DelayParams::from_param_defaults()
+        };
+        assert_block_size_invariant(
+            DelayDsp::default,
+            &params,
+            SAMPLE_RATE,
+            &signals::noise(24_000, 3),
+            &[1, 7, 512],
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn tail_grows_with_feedback_and_reset_clears_lines() {
+        let mut delay = DelayDsp::default();
+        delay.set_sample_rate(SAMPLE_RATE);
+        let mut mono = signals::impulse(64);
+        delay.process(
+            &mut [&mut mono[..]],
+            &Transport::default(),
+            &dry_delay(100.0, 0.0),
+        );
+        assert_eq!(delay.tail_samples(), TailLength::Samples(4_800));
+
+        delay.process(
+            &mut [&mut mono[..]],
+            &Transport::default(),
+            &dry_delay(100.0, 0.5),
+        );
+        // 0.5^20 is below -120 dB.
+        assert_eq!(delay.tail_samples(), TailLength::Samples(21 * 4_800));
+
+        delay.reset();
+        assert!(
+            delay
+                .channels
+                .iter()
+                .all(|channel| channel.line.iter().all(|&s| s == 0.0))
+        );
+    }
+
+    #[test]
+    fn channel_layout_adds_lines_for_extra_channels() {
+        let mut delay = DelayDsp::default();
+        delay.set_channel_layout(ChannelLayout::new(6, 6));
+        delay.set_sample_rate(SAMPLE_RATE);
+        assert_eq!(delay.channels.len(), 6);
+        assert!(
+            delay
+                .channels
+                .iter()
+                .all(|channel| channel.line.len() == delay.line_len())
+        );
+    }
+}
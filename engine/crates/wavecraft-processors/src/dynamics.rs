@@ -0,0 +1,151 @@
+//! Dynamics processors: a feed-forward compressor and a lookahead limiter.
+//!
+//! Both publish the gain they apply as a meter stream (see
+//! [`GainReductionMeter`]) under a [`TapId`], so the UI can draw gain
+//! reduction with `getMeterFrame`.
+
+mod compressor;
+mod limiter;
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use wavecraft_dsp::{MeteringTap, tap_id};
+use wavecraft_metering::{MeterConsumer, MeterProducer, create_meter_channel};
+use wavecraft_protocol::MeterFrame;
+
+pub use compressor::{CompressorDsp, CompressorParams};
+pub use limiter::{LIMITER_LOOKAHEAD_MS, LimiterDsp, LimiterParams};
+
+tap_id!(
+    /// Default gain reduction stream of [`CompressorDsp`].
+    pub CompressorGainReduction = "compressor_gain_reduction"
+);
+tap_id!(
+    /// Default gain reduction stream of [`LimiterDsp`].
+    pub LimiterGainReduction = "limiter_gain_reduction"
+);
+
+const GAIN_REDUCTION_CAPACITY: usize = 64;
+
+struct GainReductionOutput {
+    id: &'static str,
+    consumer: Mutex<MeterConsumer>,
+}
+
+impl MeteringTap for GainReductionOutput {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn meter_frame(&self) -> Option<MeterFrame> {
+        self.consumer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read_latest()
+    }
+}
+
+/// Publishes the gain a dynamics processor applied as meter frames.
+///
+/// Each frame carries the linear gain on both sides: `peak_*` and
+/// `true_peak_*` hold the lowest gain of the block (the deepest reduction)
+/// and `rms_*` the average, so `20 * log10(peak_l)` is the peak gain
+/// reduction in dB.
+struct GainReductionMeter {
+    producer: MeterProducer,
+    output: Arc<GainReductionOutput>,
+    lowest: f32,
+    sum: f64,
+    count: u32,
+    samples_processed: u64,
+}
+
+impl GainReductionMeter {
+    fn new(id: &'static str) -> Self {
+        let (producer, consumer) = create_meter_channel(GAIN_REDUCTION_CAPACITY);
+        Self {
+            producer,
+            output: Arc::new(GainReductionOutput {
+                id,
+                consumer: Mutex::new(consumer),
+            }),
+            lowest: 1.0,
+            sum: 0.0,
+            count: 0,
+            samples_processed: 0,
+        }
+    }
+
+    fn output(&self) -> Arc<dyn MeteringTap> {
+        self.output.clone()
+    }
+
+    #[inline]
+    fn record(&mut self, gain: f32) {
+        self.lowest = self.lowest.min(gain);
+        self.sum += f64::from(gain);
+        self.count += 1;
+    }
+
+    /// Push a frame for the samples recorded since the last call.
+    fn publish(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+
+        let average = (self.sum / f64::from(self.count)) as f32;
+        self.samples_processed += u64::from(self.count);
+        self.producer.push(MeterFrame {
+            peak_l: self.lowest,
+            peak_r: self.lowest,
+            rms_l: average,
+            rms_r: average,
+            true_peak_l: self.lowest,
+            true_peak_r: self.lowest,
+            loudness: None,
+            timestamp: self.samples_processed,
+        });
+        self.lowest = 1.0;
+        self.sum = 0.0;
+        self.count = 0;
+    }
+
+    fn reset(&mut self) {
+        self.lowest = 1.0;
+        self.sum = 0.0;
+        self.count = 0;
+        self.samples_processed = 0;
+    }
+}
+
+/// Level in dB of a linear amplitude, floored at -200 dB.
+#[inline]
+fn linear_to_db(amplitude: f32) -> f32 {
+    20.0 * amplitude.max(1.0e-10).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_reduction_meter_publishes_lowest_and_average_gain() {
+        let mut meter = GainReductionMeter::new("gr");
+        let output = meter.output();
+        meter.publish();
+        assert!(output.meter_frame().is_none(), "nothing recorded yet");
+
+        for gain in [1.0, 0.5, 0.75, 0.25] {
+            meter.record(gain);
+        }
+        meter.publish();
+
+        let frame = output.meter_frame().expect("frame should be published");
+        assert_eq!(output.id(), "gr");
+        assert_eq!(frame.peak_l, 0.25);
+        assert_eq!(frame.peak_r, 0.25);
+        assert_eq!(frame.rms_l, 0.625);
+        assert_eq!(frame.timestamp, 4);
+        assert!(frame.loudness.is_none());
+    }
+}
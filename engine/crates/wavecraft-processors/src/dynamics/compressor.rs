@@ -0,0 +1,470 @@
+//! Feed-forward compressor with soft knee and optional sidechain key.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use wavecraft_dsp::{
+    MeteringTap, NoteEvent, ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams,
+    Smoother, SmoothingStyle, TapId, Transport, time_coefficient,
+};
+use wavecraft_protocol::db_to_linear;
+
+use super::{CompressorGainReduction, GainReductionMeter, linear_to_db};
+
+const DEFAULT_THRESHOLD_DB: f32 = -18.0;
+const DEFAULT_RATIO: f32 = 4.0;
+const DEFAULT_KNEE_DB: f32 = 6.0;
+const DEFAULT_ATTACK_MS: f32 = 10.0;
+const DEFAULT_RELEASE_MS: f32 = 100.0;
+
+/// Parameters for the compressor.
+#[derive(Debug, Clone)]
+pub struct CompressorParams {
+    /// Level above which gain is reduced, in dBFS.
+    pub threshold_db: f32,
+    /// Input/output ratio above the threshold (`1.0` = no compression).
+    pub ratio: f32,
+    /// Width of the soft knee around the threshold, in dB.
+    pub knee_db: f32,
+    /// Time to reach the target gain reduction, in milliseconds.
+    pub attack_ms: f32,
+    /// Time to recover from gain reduction, in milliseconds.
+    pub release_ms: f32,
+    /// Gain applied after compression, in dB.
+    pub makeup_db: f32,
+    /// Drive the detector from the sidechain input instead of the main
+    /// input. Ignored while the host feeds no sidechain.
+    pub sidechain: bool,
+}
+
+impl Default for CompressorParams {
+    fn default() -> Self {
+        Self::from_param_defaults()
+    }
+}
+
+impl ProcessorParams for CompressorParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static KEYS: [&str; 2] = ["Input", "Sidechain"];
+        static SPECS: [ParamSpec; 7] = [
+            ParamSpec {
+                name: "Threshold",
+                id_suffix: "threshold_db",
+                range: ParamRange::Linear {
+                    min: -60.0,
+                    max: 0.0,
+                },
+                default: DEFAULT_THRESHOLD_DB as f64,
+                unit: "dB",
+                group: Some("Compressor"),
+            },
+            ParamSpec {
+                name: "Ratio",
+                id_suffix: "ratio",
+                range: ParamRange::Skewed {
+                    min: 1.0,
+                    max: 20.0,
+                    factor: 2.0,
+                },
+                default: DEFAULT_RATIO as f64,
+                unit: ":1",
+                group: Some("Compressor"),
+            },
+            ParamSpec {
+                name: "Knee",
+                id_suffix: "knee_db",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: 24.0,
+                },
+                default: DEFAULT_KNEE_DB as f64,
+                unit: "dB",
+                group: Some("Compressor"),
+            },
+            ParamSpec {
+                name: "Attack",
+                id_suffix: "attack_ms",
+                range: ParamRange::Skewed {
+                    min: 0.1,
+                    max: 200.0,
+                    factor: 2.5,
+                },
+                default: DEFAULT_ATTACK_MS as f64,
+                unit: "ms",
+                group: Some("Compressor"),
+            },
+            ParamSpec {
+                name: "Release",
+                id_suffix: "release_ms",
+                range: ParamRange::Skewed {
+                    min: 5.0,
+                    max: 2_000.0,
+                    factor: 2.5,
+                },
+                default: DEFAULT_RELEASE_MS as f64,
+                unit: "ms",
+                group: Some("Compressor"),
+            },
+            ParamSpec {
+                name: "Makeup",
+                id_suffix: "makeup_db",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: 24.0,
+                },
+                default: 0.0,
+                unit: "dB",
+                group: Some("Compressor"),
+            },
+            ParamSpec {
+                name: "Key",
+                id_suffix: "sidechain",
+                range: ParamRange::Enum { variants: &KEYS },
+                default: 0.0,
+                unit: "",
+                group: Some("Compressor"),
+            },
+        ];
+
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            threshold_db: DEFAULT_THRESHOLD_DB,
+            ratio: DEFAULT_RATIO,
+            knee_db: DEFAULT_KNEE_DB,
+            attack_ms: DEFAULT_ATTACK_MS,
+            release_ms: DEFAULT_RELEASE_MS,
+            makeup_db: 0.0,
+            sidechain: false,
+        }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(threshold_db) = values.first() {
+            self.threshold_db = *threshold_db;
+        }
+        if let Some(ratio) = values.get(1) {
+            self.ratio = *ratio;
+        }
+        if let Some(knee_db) = values.get(2) {
+            self.knee_db = *knee_db;
+        }
+        if let Some(attack_ms) = values.get(3) {
+            self.attack_ms = *attack_ms;
+        }
+        if let Some(release_ms) = values.get(4) {
+            self.release_ms = *release_ms;
+        }
+        if let Some(makeup_db) = values.get(5) {
+            self.makeup_db = *makeup_db;
+        }
+        if let Some(sidechain) = values.get(6) {
+            self.sidechain = sidechain.round() as i32 == 1;
+        }
+    }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[ParamSmoothing {
+            id_suffix: "makeup_db",
+            style: SmoothingStyle::Linear(20.0),
+        }]
+    }
+}
+
+/// Feed-forward compressor.
+///
+/// The detector follows the peak level across all channels (or all
+/// sidechain channels when keyed), so every channel gets the same gain and
+/// the stereo image is preserved. Gain reduction follows a soft-knee curve
+/// and is smoothed in the dB domain with separate attack and release
+/// times.
+///
+/// The applied gain is published under the ID of `I`
+/// ([`CompressorGainReduction`] by default). Give each compressor in a
+/// chain its own ID to meter them separately.
+pub struct CompressorDsp<I: TapId = CompressorGainReduction> {
+    sample_rate_hz: f32,
+    /// Smoothed gain reduction in dB (positive values reduce gain).
+    reduction_db: f32,
+    makeup_db: Smoother,
+    meter: GainReductionMeter,
+    _id: PhantomData<I>,
+}
+
+impl<I: TapId> Default for CompressorDsp<I> {
+    fn default() -> Self {
+        Self {
+            sample_rate_hz: 44_100.0,
+            reduction_db: 0.0,
+            makeup_db: Smoother::for_param::<CompressorParams>("makeup_db"),
+            meter: GainReductionMeter::new(I::ID),
+            _id: PhantomData,
+        }
+    }
+}
+
+impl<I: TapId> CompressorDsp<I> {
+    /// Read side of the gain reduction stream.
+    pub fn gain_reduction_output(&self) -> Arc<dyn MeteringTap> {
+        self.meter.output()
+    }
+
+    fn compress(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        key: &[&mut [f32]],
+        params: &CompressorParams,
+    ) {
+        let keyed = params.sidechain && !key.is_empty();
+        let curve = GainCurve::new(params);
+        let attack = time_coefficient(params.attack_ms, self.sample_rate_hz);
+        let release = time_coefficient(params.release_ms, self.sample_rate_hz);
+        self.makeup_db.set_target(params.makeup_db);
+
+        let num_samples = buffer.first().map_or(0, |channel| channel.len());
+        let mut gains = [0.0_f32; 64];
+        for start in (0..num_samples).step_by(gains.len()) {
+            let end = (start + gains.len()).min(num_samples);
+            let gains = &mut gains[..end - start];
+            let detector: &[&mut [f32]] = if keyed { key } else { buffer };
+
+            for (offset, gain) in gains.iter_mut().enumerate() {
+                let frame = start + offset;
+                let level = detector
+                    .iter()
+                    .filter_map(|channel| channel.get(frame))
+                    .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+                let target = curve.reduction_db(linear_to_db(level));
+                let coefficient = if target > self.reduction_db {
+                    attack
+                } else {
+                    release
+                };
+                self.reduction_db = target + coefficient * (self.reduction_db - target);
+
+                *gain = db_to_linear(-self.reduction_db);
+                self.meter.record(*gain);
+                *gain *= db_to_linear(self.makeup_db.next());
+            }
+
+            for channel in buffer.iter_mut() {
+                for (sample, gain) in channel[start..end].iter_mut().zip(gains.iter()) {
+                    *sample *= gain;
+                }
+            }
+        }
+
+        self.meter.publish();
+    }
+}
+
+impl<I: TapId> Processor for CompressorDsp<I> {
+    type Params = CompressorParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.compress(buffer, &[], params);
+    }
+
+    fn process_with_sidechain(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        sidechain: &[&mut [f32]],
+        _events: &[NoteEvent],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.compress(buffer, sidechain, params);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate_hz = sample_rate.max(1.0);
+        self.makeup_db.set_sample_rate(self.sample_rate_hz);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        taps.push(self.gain_reduction_output());
+    }
+
+    fn reset(&mut self) {
+        self.reduction_db = 0.0;
+        self.makeup_db.reset();
+        self.meter.reset();
+    }
+}
+
+/// Static soft-knee compression curve.
+#[derive(Debug, Clone, Copy)]
+struct GainCurve {
+    threshold_db: f32,
+    slope: f32,
+    knee_db: f32,
+}
+
+impl GainCurve {
+    fn new(params: &CompressorParams) -> Self {
+        Self {
+            threshold_db: params.threshold_db,
+            slope: 1.0 / params.ratio.max(1.0) - 1.0,
+            knee_db: params.knee_db.max(0.0),
+        }
+    }
+
+    /// Gain reduction in dB (`>= 0`) for an input level in dB.
+    #[inline]
+    fn reduction_db(&self, level_db: f32) -> f32 {
+        let over = level_db - self.threshold_db;
+        if 2.0 * over <= -self.knee_db {
+            0.0
+        } else if 2.0 * over.abs() < self.knee_db {
+            let into_knee = over + self.knee_db * 0.5;
+            -self.slope * into_knee * into_knee / (2.0 * self.knee_db)
+        } else {
+            -self.slope * over
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::testing::{Renderer, measure, signals};
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn hard_knee(threshold_db: f32, ratio: f32) -> CompressorParams {
+        CompressorParams {
+            threshold_db,
+            ratio,
+            knee_db: 0.0,
+            attack_ms: 1.0,
+            release_ms: 50.0,
+            ..CompressorParams::from_param_defaults()
+        }
+    }
+
+    #[test]
+    fn param_specs_use_expected_suffixes() {
+        let suffixes: Vec<_> = CompressorParams::param_specs()
+            .iter()
+            .map(|spec| spec.id_suffix)
+            .collect();
+        assert_eq!(
+            suffixes,
+            [
+                "threshold_db",
+                "ratio",
+                "knee_db",
+                "attack_ms",
+                "release_ms",
+                "makeup_db",
+                "sidechain"
+            ]
+        );
+
+        let mut params = CompressorParams::from_param_defaults();
+        params.apply_plain_values(&[-30.0, 8.0, 0.0, 5.0, 200.0, 6.0, 1.0]);
+        assert_eq!(params.ratio, 8.0);
+        assert!(params.sidechain);
+    }
+
+    #[test]
+    fn gain_curve_follows_ratio_and_knee() {
+        let hard = GainCurve::new(&hard_knee(-20.0, 4.0));
+        assert_eq!(hard.reduction_db(-30.0), 0.0);
+        assert!((hard.reduction_db(-8.0) - 9.0).abs() < 1e-5);
+
+        let soft = GainCurve::new(&CompressorParams {
+            knee_db: 10.0,
+            ..hard_knee(-20.0, 4.0)
+        });
+        assert_eq!(soft.reduction_db(-25.0), 0.0);
+        // Halfway into the knee is a quarter of the full-ratio reduction.
+        assert!((soft.reduction_db(-20.0) - 0.9375).abs() < 1e-5);
+        assert!((soft.reduction_db(-8.0) - 9.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn steady_tone_settles_at_ratio() {
+        let mut compressor = CompressorDsp::<CompressorGainReduction>::default();
+        // A 0 dBFS sine through a -20 dB threshold at 4:1 lands at -15 dB.
+        let input = signals::sine(1_000.0, SAMPLE_RATE, SAMPLE_RATE as usize);
+        let output = Renderer::new(SAMPLE_RATE).channels(1).render(
+            &mut compressor,
+            &hard_knee(-20.0, 4.0),
+            &input,
+        );
+
+        let settled = &output[0][SAMPLE_RATE as usize / 2..];
+        let level = measure::gain_to_db(measure::peak(settled));
+        assert!((level + 15.0).abs() < 0.5, "level {level}");
+    }
+
+    #[test]
+    fn publishes_gain_reduction() {
+        let mut compressor = CompressorDsp::<CompressorGainReduction>::default();
+        let output = compressor.gain_reduction_output();
+        assert_eq!(output.id(), "compressor_gain_reduction");
+
+        let mut taps = Vec::new();
+        compressor.metering_taps(&mut taps);
+        assert_eq!(taps.len(), 1);
+
+        let mut mono = vec![1.0_f32; 4_800];
+        compressor.set_sample_rate(SAMPLE_RATE);
+        compressor.process(
+            &mut [&mut mono[..]],
+            &Transport::default(),
+            &CompressorParams {
+                makeup_db: 6.0,
+                ..hard_knee(-20.0, 4.0)
+            },
+        );
+
+        let frame = output.meter_frame().expect("gain reduction frame");
+        assert!((measure::gain_to_db(frame.peak_l) + 15.0).abs() < 0.1);
+        assert!(frame.rms_l > frame.peak_l);
+        // Makeup gain is applied to the audio but not metered as reduction.
+        assert!((measure::gain_to_db(mono[4_799]) + 9.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn sidechain_keys_the_detector() {
+        let params = CompressorParams {
+            sidechain: true,
+            ..hard_knee(-20.0, 10.0)
+        };
+        let mut compressor = CompressorDsp::<CompressorGainReduction>::default();
+        compressor.set_sample_rate(SAMPLE_RATE);
+
+        let mut quiet = vec![0.05_f32; 4_800];
+        let mut key = vec![1.0_f32; 4_800];
+        compressor.process_with_sidechain(
+            &mut [&mut quiet[..]],
+            &[&mut key[..]],
+            &[],
+            &Transport::default(),
+            &params,
+        );
+        assert!(quiet[4_799] < 0.01, "loud key ducks the quiet input");
+
+        // Without a connected sidechain the main input drives the detector.
+        let mut compressor = CompressorDsp::<CompressorGainReduction>::default();
+        compressor.set_sample_rate(SAMPLE_RATE);
+        let mut quiet = vec![0.05_f32; 4_800];
+        compressor.process_with_sidechain(
+            &mut [&mut quiet[..]],
+            &[],
+            &[],
+            &Transport::default(),
+            &params,
+        );
+        assert_eq!(quiet[4_799], 0.05);
+    }
+}
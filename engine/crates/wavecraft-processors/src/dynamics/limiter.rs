@@ -0,0 +1,441 @@
+//! Brickwall lookahead limiter.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use wavecraft_dsp::{
+    ChannelLayout, MeteringTap, ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams,
+    Smoother, SmoothingStyle, TailLength, TapId, Transport, time_coefficient,
+};
+use wavecraft_protocol::db_to_linear;
+
+use super::{GainReductionMeter, LimiterGainReduction};
+
+/// Lookahead of [`LimiterDsp`], reported to the host as latency.
+pub const LIMITER_LOOKAHEAD_MS: f32 = 5.0;
+const DEFAULT_CEILING_DB: f32 = -1.0;
+const DEFAULT_RELEASE_MS: f32 = 50.0;
+/// Channels with delay lines before the host reports a layout.
+const DEFAULT_LIMITER_CHANNELS: usize = 2;
+
+/// Parameters for the limiter.
+#[derive(Debug, Clone)]
+pub struct LimiterParams {
+    /// Gain applied before limiting, in dB.
+    pub input_gain_db: f32,
+    /// Highest output level, in dBFS.
+    pub ceiling_db: f32,
+    /// Time to recover from gain reduction, in milliseconds.
+    pub release_ms: f32,
+}
+
+impl Default for LimiterParams {
+    fn default() -> Self {
+        Self::from_param_defaults()
+    }
+}
+
+impl ProcessorParams for LimiterParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static SPECS: [ParamSpec; 3] = [
+            ParamSpec {
+                name: "Input Gain",
+                id_suffix: "input_gain_db",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: 24.0,
+                },
+                default: 0.0,
+                unit: "dB",
+                group: Some("Limiter"),
+            },
+            ParamSpec {
+                name: "Ceiling",
+                id_suffix: "ceiling_db",
+                range: ParamRange::Linear {
+                    min: -24.0,
+                    max: 0.0,
+                },
+                default: DEFAULT_CEILING_DB as f64,
+                unit: "dB",
+                group: Some("Limiter"),
+            },
+            ParamSpec {
+                name: "Release",
+                id_suffix: "release_ms",
+                range: ParamRange::Skewed {
+                    min: 1.0,
+                    max: 1_000.0,
+                    factor: 2.5,
+                },
+                default: DEFAULT_RELEASE_MS as f64,
+                unit: "ms",
+                group: Some("Limiter"),
+            },
+        ];
+
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            input_gain_db: 0.0,
+            ceiling_db: DEFAULT_CEILING_DB,
+            release_ms: DEFAULT_RELEASE_MS,
+        }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(input_gain_db) = values.first() {
+            self.input_gain_db = *input_gain_db;
+        }
+        if let Some(ceiling_db) = values.get(1) {
+            self.ceiling_db = *ceiling_db;
+        }
+        if let Some(release_ms) = values.get(2) {
+            self.release_ms = *release_ms;
+        }
+    }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[ParamSmoothing {
+            id_suffix: "input_gain_db",
+            style: SmoothingStyle::Linear(20.0),
+        }]
+    }
+}
+
+/// Sliding-window minimum over the last `window` values, as a monotonic
+/// queue in a preallocated ring buffer.
+#[derive(Debug, Clone, Default)]
+struct MinimumHold {
+    /// `(sample index, value)` pairs with increasing values.
+    queue: Vec<(u64, f32)>,
+    head: usize,
+    len: usize,
+    window: u64,
+    index: u64,
+}
+
+impl MinimumHold {
+    fn new(window: usize) -> Self {
+        Self {
+            queue: vec![(0, 0.0); window + 1],
+            window: window as u64,
+            ..Self::default()
+        }
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+        self.index = 0;
+    }
+
+    #[inline]
+    fn slot(&self, offset: usize) -> usize {
+        (self.head + offset) % self.queue.len()
+    }
+
+    /// Add `value` and return the minimum of the window ending with it.
+    #[inline]
+    fn push(&mut self, value: f32) -> f32 {
+        while self.len > 0 && self.queue[self.slot(self.len - 1)].1 >= value {
+            self.len -= 1;
+        }
+        let slot = self.slot(self.len);
+        self.queue[slot] = (self.index, value);
+        self.len += 1;
+
+        if self.index - self.queue[self.head].0 >= self.window {
+            self.head = self.slot(1);
+            self.len -= 1;
+        }
+        self.index += 1;
+        self.queue[self.head].1
+    }
+}
+
+/// Moving average over the last `window` values.
+#[derive(Debug, Clone, Default)]
+struct MovingAverage {
+    values: Vec<f32>,
+    position: usize,
+    sum: f64,
+}
+
+impl MovingAverage {
+    fn new(window: usize) -> Self {
+        Self {
+            values: vec![1.0; window],
+            position: 0,
+            sum: window as f64,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.fill(1.0);
+        self.position = 0;
+        self.sum = self.values.len() as f64;
+    }
+
+    #[inline]
+    fn push(&mut self, value: f32) -> f32 {
+        self.sum += f64::from(value) - f64::from(self.values[self.position]);
+        self.values[self.position] = value;
+        self.position += 1;
+        if self.position == self.values.len() {
+            self.position = 0;
+        }
+        (self.sum / self.values.len() as f64) as f32
+    }
+}
+
+/// Brickwall lookahead limiter.
+///
+/// The audio is delayed by [`LIMITER_LOOKAHEAD_MS`] (reported through
+/// `latency_samples`) while the gain envelope ramps down ahead of each
+/// peak, so peaks are caught without distortion from instant gain changes.
+/// The envelope holds the lowest gain the lookahead window needs, releases
+/// exponentially and is smoothed by a moving average as long as the
+/// lookahead, which keeps it below the required gain at every peak. A final
+/// clip at the ceiling catches rounding errors. All channels share one
+/// gain.
+///
+/// The applied gain is published under the ID of `I`
+/// ([`LimiterGainReduction`] by default).
+pub struct LimiterDsp<I: TapId = LimiterGainReduction> {
+    sample_rate_hz: f32,
+    lookahead: usize,
+    /// One delay line of `lookahead` samples per channel.
+    lines: Vec<Vec<f32>>,
+    position: usize,
+    hold: MinimumHold,
+    released: f32,
+    average: MovingAverage,
+    input_gain_db: Smoother,
+    meter: GainReductionMeter,
+    _id: PhantomData<I>,
+}
+
+impl<I: TapId> Default for LimiterDsp<I> {
+    fn default() -> Self {
+        let mut limiter = Self {
+            sample_rate_hz: 44_100.0,
+            lookahead: 0,
+            lines: vec![Vec::new(); DEFAULT_LIMITER_CHANNELS],
+            position: 0,
+            hold: MinimumHold::default(),
+            released: 1.0,
+            average: MovingAverage::default(),
+            input_gain_db: Smoother::for_param::<LimiterParams>("input_gain_db"),
+            meter: GainReductionMeter::new(I::ID),
+            _id: PhantomData,
+        };
+        limiter.allocate();
+        limiter
+    }
+}
+
+impl<I: TapId> LimiterDsp<I> {
+    /// Read side of the gain reduction stream.
+    pub fn gain_reduction_output(&self) -> Arc<dyn MeteringTap> {
+        self.meter.output()
+    }
+
+    fn allocate(&mut self) {
+        self.lookahead =
+            ((LIMITER_LOOKAHEAD_MS * 0.001 * self.sample_rate_hz).round() as usize).max(1);
+        for line in &mut self.lines {
+            *line = vec![0.0; self.lookahead];
+        }
+        // The envelope leads the delayed audio by `lookahead` samples, so
+        // the hold and average windows span the lookahead plus the current
+        // sample.
+        self.hold = MinimumHold::new(self.lookahead + 1);
+        self.average = MovingAverage::new(self.lookahead + 1);
+        self.position = 0;
+        self.released = 1.0;
+    }
+}
+
+impl<I: TapId> Processor for LimiterDsp<I> {
+    type Params = LimiterParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        let ceiling = db_to_linear(params.ceiling_db.min(0.0));
+        let release = time_coefficient(params.release_ms, self.sample_rate_hz);
+        self.input_gain_db.set_target(params.input_gain_db);
+
+        // Channels beyond the configured layout have no delay line and pass through.
+        let channels = buffer.len().min(self.lines.len());
+        let num_samples = buffer.first().map_or(0, |channel| channel.len());
+        for frame in 0..num_samples {
+            let input_gain = db_to_linear(self.input_gain_db.next());
+            let mut peak = 0.0_f32;
+            for (samples, line) in buffer.iter_mut().zip(self.lines.iter_mut()) {
+                let input = samples[frame] * input_gain;
+                peak = peak.max(input.abs());
+                // Swap the new sample into the line and take out the one
+                // from `lookahead` samples ago.
+                samples[frame] = std::mem::replace(&mut line[self.position], input);
+            }
+
+            let required = if peak > ceiling { ceiling / peak } else { 1.0 };
+            let held = self.hold.push(required);
+            self.released = if held < self.released {
+                held
+            } else {
+                held + release * (self.released - held)
+            };
+            let gain = self.average.push(self.released);
+            self.meter.record(gain);
+
+            for samples in buffer[..channels].iter_mut() {
+                samples[frame] = (samples[frame] * gain).clamp(-ceiling, ceiling);
+            }
+
+            self.position += 1;
+            if self.position == self.lookahead {
+                self.position = 0;
+            }
+        }
+
+        self.meter.publish();
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate_hz = sample_rate.max(1.0);
+        self.input_gain_db.set_sample_rate(self.sample_rate_hz);
+        self.allocate();
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        let channels = layout.inputs.max(layout.outputs) as usize;
+        self.lines
+            .resize(channels.max(1), vec![0.0; self.lookahead]);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        taps.push(self.gain_reduction_output());
+    }
+
+    fn reset(&mut self) {
+        for line in &mut self.lines {
+            line.fill(0.0);
+        }
+        self.position = 0;
+        self.hold.clear();
+        self.released = 1.0;
+        self.average.clear();
+        self.input_gain_db.reset();
+        self.meter.reset();
+    }
+
+    fn latency_samples(&self) -> u32 {
+        self.lookahead as u32
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        TailLength::Samples(self.lookahead as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::testing::{Renderer, assert_block_size_invariant, measure, signals};
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    type Limiter = LimiterDsp<LimiterGainReduction>;
+
+    #[test]
+    fn minimum_hold_tracks_window_minimum() {
+        let mut hold = MinimumHold::new(3);
+        let held: Vec<f32> = [1.0, 0.5, 0.8, 0.9, 0.7, 1.0, 1.0, 1.0]
+            .into_iter()
+            .map(|value| hold.push(value))
+            .collect();
+        assert_eq!(held, [1.0, 0.5, 0.5, 0.5, 0.7, 0.7, 0.7, 1.0]);
+    }
+
+    #[test]
+    fn reports_lookahead_as_latency() {
+        let mut limiter = Limiter::default();
+        limiter.set_sample_rate(SAMPLE_RATE);
+        assert_eq!(limiter.latency_samples(), 240);
+
+        let input = signals::noise(4_800, 5);
+        let quiet: Vec<f32> = input.iter().map(|sample| sample * 0.1).collect();
+        let output = Renderer::new(SAMPLE_RATE).channels(1).render(
+            &mut limiter,
+            &LimiterParams::default(),
+            &quiet,
+        );
+        assert_eq!(measure::detect_latency(&quiet, &output[0], 500), Some(240));
+        // Below the ceiling the signal is only delayed.
+        assert!(measure::max_difference(&quiet[..4_560], &output[0][240..]) < 1e-6);
+    }
+
+    #[test]
+    fn output_never_exceeds_ceiling() {
+        let params = LimiterParams {
+            input_gain_db: 12.0,
+            ceiling_db: -3.0,
+            release_ms: 20.0,
+        };
+        let input = signals::noise(SAMPLE_RATE as usize / 2, 11);
+        let output = Renderer::new(SAMPLE_RATE).render(&mut Limiter::default(), &params, &input);
+
+        let ceiling = db_to_linear(-3.0);
+        for channel in &output {
+            assert!(measure::peak(channel) <= ceiling);
+        }
+        // Loud input is pushed up to the ceiling rather than limited below it.
+        assert!(measure::peak(&output[0]) > ceiling * 0.99);
+    }
+
+    #[test]
+    fn publishes_gain_reduction() {
+        let mut limiter = Limiter::default();
+        let output = limiter.gain_reduction_output();
+        assert_eq!(output.id(), "limiter_gain_reduction");
+
+        limiter.set_sample_rate(SAMPLE_RATE);
+        let mut mono = vec![1.0_f32; 4_800];
+        limiter.process(
+            &mut [&mut mono[..]],
+            &Transport::default(),
+            &LimiterParams {
+                ceiling_db: -6.0,
+                ..LimiterParams::default()
+            },
+        );
+
+        let frame = output.meter_frame().expect("gain reduction frame");
+        assert!((measure::gain_to_db(frame.peak_l) + 6.0).abs() < 0.01);
+        assert!((mono[4_799] - db_to_linear(-6.0)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn output_is_block_size_invariant() {
+        let params = LimiterParams {
+            input_gain_db: 9.0,
+            ..LimiterParams::default()
+        };
+        assert_block_size_invariant(
+            Limiter::default,
+            &params,
+            SAMPLE_RATE,
+            &signals::noise(9_600, 2),
+            &[1, 7, 512],
+            1e-6,
+        );
+    }
+}
@@ -0,0 +1,983 @@
+//! Multi-band parametric EQ.
+
+use core::f64::consts::TAU;
+use wavecraft_dsp::{
+    ChannelLayout, ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams, Smoother,
+    SmoothingStyle, Transport,
+};
+use wavecraft_protocol::ParameterInfo;
+
+use crate::biquad::{BiquadCoefficients, BiquadState};
+
+const MIN_FREQUENCY_HZ: f32 = 20.0;
+const MAX_FREQUENCY_HZ: f32 = 20_000.0;
+const MAX_GAIN_DB: f32 = 24.0;
+const MIN_Q: f32 = 0.1;
+const MAX_Q: f32 = 18.0;
+const DEFAULT_Q: f32 = 0.707;
+/// Lowest and highest default band frequency; bands in between are spaced
+/// evenly on a log scale.
+const DEFAULT_FREQUENCY_SPAN_HZ: (f32, f32) = (100.0, 8_000.0);
+/// Biquad sections in the steepest (48 dB/oct) slope.
+const MAX_STAGES: usize = 4;
+/// Channels with filter state before the host reports a layout.
+const DEFAULT_EQ_CHANNELS: usize = 8;
+/// Plain values per band: enabled, type, frequency, gain, Q.
+const VALUES_PER_BAND: usize = 5;
+/// Parameter ID suffixes of a band's plain values, after `band<n>_`.
+const BAND_VALUE_SUFFIXES: [&str; VALUES_PER_BAND] = ["enabled", "type", "freq_hz", "gain_db", "q"];
+/// Ramp time of frequency, gain and Q changes.
+const SMOOTHING_MS: f32 = 20.0;
+/// Samples between coefficient redesigns while a band is ramping.
+const REDESIGN_INTERVAL: usize = 32;
+/// Sample rate assumed for the response when the host doesn't know one.
+const DEFAULT_RESPONSE_SAMPLE_RATE_HZ: f32 = 44_100.0;
+
+/// Roll-off of the low- and high-pass band types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EqSlope {
+    /// 12 dB/oct, a single section using the band's Q.
+    Db12,
+    /// 24 dB/oct Butterworth.
+    Db24,
+    /// 48 dB/oct Butterworth.
+    Db48,
+}
+
+impl EqSlope {
+    /// Section Qs of a Butterworth cascade of this order.
+    fn butterworth_qs(self) -> &'static [f64] {
+        match self {
+            Self::Db12 => &[std::f64::consts::FRAC_1_SQRT_2],
+            Self::Db24 => &[0.541_196_1, 1.306_563],
+            Self::Db48 => &[0.509_795_6, 0.601_344_9, 0.899_976_2, 2.562_915_4],
+        }
+    }
+}
+
+/// Filter shape of an EQ band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EqBandType {
+    #[default]
+    Peak,
+    LowShelf,
+    HighShelf,
+    Notch,
+    LowPass(EqSlope),
+    HighPass(EqSlope),
+}
+
+impl EqBandType {
+    /// Band types in the order of the `type` parameter.
+    const ALL: [Self; 10] = [
+        Self::Peak,
+        Self::LowShelf,
+        Self::HighShelf,
+        Self::Notch,
+        Self::LowPass(EqSlope::Db12),
+        Self::LowPass(EqSlope::Db24),
+        Self::LowPass(EqSlope::Db48),
+        Self::HighPass(EqSlope::Db12),
+        Self::HighPass(EqSlope::Db24),
+        Self::HighPass(EqSlope::Db48),
+    ];
+
+    const NAMES: [&'static str; 10] = [
+        "Peak",
+        "Low Shelf",
+        "High Shelf",
+        "Notch",
+        "Low-pass 12",
+        "Low-pass 24",
+        "Low-pass 48",
+        "High-pass 12",
+        "High-pass 24",
+        "High-pass 48",
+    ];
+
+    fn from_index(index: i32) -> Self {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| Self::ALL.get(index).copied())
+            .unwrap_or_default()
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|band_type| *band_type == self)
+            .unwrap_or_default()
+    }
+}
+
+/// Settings of one EQ band.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EqBand {
+    /// Disabled bands pass audio through unchanged.
+    pub enabled: bool,
+    pub band_type: EqBandType,
+    /// Center, corner or cutoff frequency in Hz.
+    pub frequency_hz: f32,
+    /// Boost or cut in dB. Used by peak and shelf bands.
+    pub gain_db: f32,
+    /// Bandwidth of peak and notch bands, steepness of shelves and
+    /// resonance of 12 dB/oct passes.
+    pub q: f32,
+}
+
+impl EqBand {
+    /// Band from its plain values in parameter order.
+    fn from_plain_values(values: &[f32; VALUES_PER_BAND]) -> Self {
+        let mut band = Self {
+            enabled: true,
+            band_type: EqBandType::default(),
+            frequency_hz: 1_000.0,
+            gain_db: 0.0,
+            q: DEFAULT_Q,
+        };
+        band.apply_plain_values(values);
+        band
+    }
+
+    /// Apply plain values in parameter order; missing trailing values keep
+    /// their current setting.
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(enabled) = values.first() {
+            self.enabled = *enabled >= 0.5;
+        }
+        if let Some(band_type) = values.get(1) {
+            self.band_type = EqBandType::from_index(band_type.round() as i32);
+        }
+        if let Some(frequency_hz) = values.get(2) {
+            self.frequency_hz = *frequency_hz;
+        }
+        if let Some(gain_db) = values.get(3) {
+            self.gain_db = *gain_db;
+        }
+        if let Some(q) = values.get(4) {
+            self.q = *q;
+        }
+    }
+
+    /// Biquad sections for this band at `sample_rate_hz`. Only the first
+    /// `count` entries are used.
+    fn design(&self, sample_rate_hz: f32) -> ([BiquadCoefficients; MAX_STAGES], usize) {
+        let mut sections = [BiquadCoefficients::IDENTITY; MAX_STAGES];
+        if !self.enabled {
+            return (sections, 0);
+        }
+
+        let sample_rate = f64::from(sample_rate_hz.max(1.0));
+        let nyquist = sample_rate * 0.5;
+        let frequency = f64::from(self.frequency_hz).clamp(
+            f64::from(MIN_FREQUENCY_HZ).min(nyquist * 0.5),
+            nyquist * 0.99,
+        );
+        let omega = TAU * frequency / sample_rate;
+        let (sin, cos) = omega.sin_cos();
+        let q = f64::from(self.q.clamp(MIN_Q, MAX_Q));
+        let gain = 10_f64.powf(f64::from(self.gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB)) / 40.0);
+        let alpha = |q: f64| sin / (2.0 * q);
+
+        let count = match self.band_type {
+            EqBandType::Peak => {
+                let alpha = alpha(q);
+                sections[0] = BiquadCoefficients::from_raw(
+                    1.0 + alpha * gain,
+                    -2.0 * cos,
+                    1.0 - alpha * gain,
+                    1.0 + alpha / gain,
+                    -2.0 * cos,
+                    1.0 - alpha / gain,
+                );
+                1
+            }
+            EqBandType::LowShelf => {
+                let shelf = 2.0 * gain.sqrt() * alpha(q);
+                sections[0] = BiquadCoefficients::from_raw(
+                    gain * ((gain + 1.0) - (gain - 1.0) * cos + shelf),
+                    2.0 * gain * ((gain - 1.0) - (gain + 1.0) * cos),
+                    gain * ((gain + 1.0) - (gain - 1.0) * cos - shelf),
+                    (gain + 1.0) + (gain - 1.0) * cos + shelf,
+                    -2.0 * ((gain - 1.0) + (gain + 1.0) * cos),
+                    (gain + 1.0) + (gain - 1.0) * cos - shelf,
+                );
+                1
+            }
+            EqBandType::HighShelf => {
+                let shelf = 2.0 * gain.sqrt() * alpha(q);
+                sections[0] = BiquadCoefficients::from_raw(
+                    gain * ((gain + 1.0) + (gain - 1.0) * cos + shelf),
+                    -2.0 * gain * ((gain - 1.0) + (gain + 1.0) * cos),
+                    gain * ((gain + 1.0) + (gain - 1.0) * cos - shelf),
+                    (gain + 1.0) - (gain - 1.0) * cos + shelf,
+                    2.0 * ((gain - 1.0) - (gain + 1.0) * cos),
+                    (gain + 1.0) - (gain - 1.0) * cos - shelf,
+                );
+                1
+            }
+            EqBandType::Notch => {
+                let alpha = alpha(q);
+                sections[0] = BiquadCoefficients::from_raw(
+                    1.0,
+                    -2.0 * cos,
+                    1.0,
+                    1.0 + alpha,
+                    -2.0 * cos,
+                    1.0 - alpha,
+                );
+                1
+            }
+            EqBandType::LowPass(slope) | EqBandType::HighPass(slope) => {
+                let low_pass = matches!(self.band_type, EqBandType::LowPass(_));
+                let qs = slope.butterworth_qs();
+                for (section, &section_q) in sections.iter_mut().zip(qs) {
+                    let section_q = if slope == EqSlope::Db12 { q } else { section_q };
+                    let alpha = alpha(section_q);
+                    let (b0, b1) = if low_pass {
+                        ((1.0 - cos) * 0.5, 1.0 - cos)
+                    } else {
+                        ((1.0 + cos) * 0.5, -(1.0 + cos))
+                    };
+                    *section = BiquadCoefficients::from_raw(
+                        b0,
+                        b1,
+                        b0,
+                        1.0 + alpha,
+                        -2.0 * cos,
+                        1.0 - alpha,
+                    );
+                }
+                qs.len()
+            }
+        };
+
+        (sections, count)
+    }
+}
+
+/// Parameters for [`ParametricEqDsp`] with `BANDS` bands.
+///
+/// Each band contributes five parameters, prefixed `band<n>_` (1-based):
+/// `enabled`, `type`, `freq_hz`, `gain_db` and `q`.
+#[derive(Debug, Clone)]
+pub struct ParametricEqParams<const BANDS: usize> {
+    pub bands: [EqBand; BANDS],
+}
+
+impl<const BANDS: usize> Default for ParametricEqParams<BANDS> {
+    fn default() -> Self {
+        Self::from_param_defaults()
+    }
+}
+
+impl<const BANDS: usize> ParametricEqParams<BANDS> {
+    fn default_band(index: usize) -> EqBand {
+        let band_type = match index {
+            0 if BANDS > 1 => EqBandType::LowShelf,
+            last if BANDS > 1 && last == BANDS - 1 => EqBandType::HighShelf,
+            _ => EqBandType::Peak,
+        };
+        let (low, high) = DEFAULT_FREQUENCY_SPAN_HZ;
+        let position = if BANDS > 1 {
+            index as f32 / (BANDS - 1) as f32
+        } else {
+            0.5
+        };
+
+        EqBand {
+            enabled: true,
+            band_type,
+            frequency_hz: low * (high / low).powf(position),
+            gain_db: 0.0,
+            q: DEFAULT_Q,
+        }
+    }
+
+    /// Combined magnitude response of all enabled bands in dB, one value
+    /// per entry of `frequencies_hz`.
+    ///
+    /// Uses the same coefficients as the processor, so a UI can draw the
+    /// exact curve from the current parameter values.
+    pub fn magnitude_response_db(&self, sample_rate_hz: f32, frequencies_hz: &[f32]) -> Vec<f32> {
+        magnitude_response_db(&self.bands, sample_rate_hz, frequencies_hz)
+    }
+}
+
+fn magnitude_response_db(
+    bands: &[EqBand],
+    sample_rate_hz: f32,
+    frequencies_hz: &[f32],
+) -> Vec<f32> {
+    let designs: Vec<_> = bands
+        .iter()
+        .map(|band| band.design(sample_rate_hz))
+        .collect();
+
+    frequencies_hz
+        .iter()
+        .map(|&frequency| {
+            let magnitude: f64 = designs
+                .iter()
+                .flat_map(|(sections, count)| &sections[..*count])
+                .map(|section| section.magnitude(frequency, sample_rate_hz))
+                .product();
+            (20.0 * magnitude.max(1.0e-10).log10()) as f32
+        })
+        .collect()
+}
+
+/// Combined magnitude response in dB of a [`ParametricEqDsp`] in a plugin's
+/// parameter list, one value per entry of `frequencies_hz`.
+///
+/// `eq_id` is the ID prefix of the EQ's parameters (`eq` for
+/// `eq_band1_gain_db`); `None` picks the first EQ in `parameters`. The curve
+/// is evaluated at `sample_rate_hz`, or 44.1 kHz if the host has none yet.
+/// Returns `None` if there is no such EQ. Hosts use this to serve
+/// `getEqResponse` from the parameter values they already hold.
+pub fn eq_response_from_parameters(
+    parameters: &[ParameterInfo],
+    eq_id: Option<&str>,
+    sample_rate_hz: Option<f32>,
+    frequencies_hz: &[f32],
+) -> Option<Vec<f32>> {
+    let is_band_type = |parameter: &ParameterInfo| {
+        parameter
+            .variants
+            .as_ref()
+            .is_some_and(|variants| variants.iter().map(String::as_str).eq(EqBandType::NAMES))
+    };
+    let value = |id: &str| {
+        parameters
+            .iter()
+            .find(|parameter| parameter.id == id)
+            .map(|parameter| parameter.value)
+    };
+
+    let prefix = match eq_id {
+        Some(eq_id) => format!("{eq_id}_"),
+        None => parameters
+            .iter()
+            .filter(|parameter| is_band_type(parameter))
+            .find_map(|parameter| parameter.id.strip_suffix("band1_type"))?
+            .to_string(),
+    };
+    let band_type_id = format!("{prefix}band1_type");
+    if !parameters
+        .iter()
+        .any(|parameter| parameter.id == band_type_id && is_band_type(parameter))
+    {
+        return None;
+    }
+
+    let bands: Vec<EqBand> = (1..)
+        .map_while(|number| {
+            let mut values = [0.0; VALUES_PER_BAND];
+            for (value_slot, suffix) in values.iter_mut().zip(BAND_VALUE_SUFFIXES) {
+                *value_slot = value(&format!("{prefix}band{number}_{suffix}"))?;
+            }
+            Some(EqBand::from_plain_values(&values))
+        })
+        .collect();
+
+    Some(magnitude_response_db(
+        &bands,
+        sample_rate_hz.unwrap_or(DEFAULT_RESPONSE_SAMPLE_RATE_HZ),
+        frequencies_hz,
+    ))
+}
+
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+impl<const BANDS: usize> ProcessorParams for ParametricEqParams<BANDS> {
+    fn param_specs() -> &'static [ParamSpec] {
+        // Leaked like the combinator specs (no OnceLock, see ChainParams).
+        let mut specs = Vec::with_capacity(BANDS * VALUES_PER_BAND);
+        for index in 0..BANDS {
+            let band = Self::default_band(index);
+            let number = index + 1;
+            let group = Some(leak(format!("Band {number}")));
+            specs.extend([
+                ParamSpec {
+                    name: leak(format!("Band {number} Enabled")),
+                    id_suffix: leak(format!("band{number}_enabled")),
+                    range: ParamRange::Stepped { min: 0, max: 1 },
+                    default: 1.0,
+                    unit: "",
+                    group,
+                },
+                ParamSpec {
+                    name: leak(format!("Band {number} Type")),
+                    id_suffix: leak(format!("band{number}_type")),
+                    range: ParamRange::Enum {
+                        variants: &EqBandType::NAMES,
+                    },
+                    default: band.band_type.index() as f64,
+                    unit: "",
+                    group,
+                },
+                ParamSpec {
+                    name: leak(format!("Band {number} Frequency")),
+                    id_suffix: leak(format!("band{number}_freq_hz")),
+                    range: ParamRange::Skewed {
+                        min: MIN_FREQUENCY_HZ as f64,
+                        max: MAX_FREQUENCY_HZ as f64,
+                        factor: 2.5,
+                    },
+                    default: band.frequency_hz as f64,
+                    unit: "Hz",
+                    group,
+                },
+                ParamSpec {
+                    name: leak(format!("Band {number} Gain")),
+                    id_suffix: leak(format!("band{number}_gain_db")),
+                    range: ParamRange::Linear {
+                        min: -MAX_GAIN_DB as f64,
+                        max: MAX_GAIN_DB as f64,
+                    },
+                    default: 0.0,
+                    unit: "dB",
+                    group,
+                },
+                ParamSpec {
+                    name: leak(format!("Band {number} Q")),
+                    id_suffix: leak(format!("band{number}_q")),
+                    range: ParamRange::Skewed {
+                        min: MIN_Q as f64,
+                        max: MAX_Q as f64,
+                        factor: 2.5,
+                    },
+                    default: DEFAULT_Q as f64,
+                    unit: "Q",
+                    group,
+                },
+            ]);
+        }
+
+        Box::leak(specs.into_boxed_slice())
+    }
+
+    fn plain_value_count() -> usize {
+        BANDS * VALUES_PER_BAND
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            bands: std::array::from_fn(Self::default_band),
+        }
+    }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        // Frequency and Q ramp in the log domain so sweeps sound even across
+        // octaves; leaked like `param_specs`.
+        let smoothing: Vec<_> = (1..=BANDS)
+            .flat_map(|number| {
+                [
+                    ParamSmoothing {
+                        id_suffix: leak(format!("band{number}_freq_hz")),
+                        style: SmoothingStyle::Logarithmic(SMOOTHING_MS),
+                    },
+                    ParamSmoothing {
+                        id_suffix: leak(format!("band{number}_gain_db")),
+                        style: SmoothingStyle::Linear(SMOOTHING_MS),
+                    },
+                    ParamSmoothing {
+                        id_suffix: leak(format!("band{number}_q")),
+                        style: SmoothingStyle::Logarithmic(SMOOTHING_MS),
+                    },
+                ]
+            })
+            .collect();
+
+        Box::leak(smoothing.into_boxed_slice())
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        for (band, values) in self.bands.iter_mut().zip(values.chunks(VALUES_PER_BAND)) {
+            band.apply_plain_values(values);
+        }
+    }
+}
+
+/// Coefficients and per-channel state of one band.
+#[derive(Debug, Clone)]
+struct BandFilter {
+    /// Latest settings from the parameters.
+    target: EqBand,
+    frequency_hz: Smoother,
+    gain_db: Smoother,
+    q: Smoother,
+    /// Settings the coefficients were designed for.
+    designed_for: Option<EqBand>,
+    sections: [BiquadCoefficients; MAX_STAGES],
+    count: usize,
+    state: Vec<[BiquadState; MAX_STAGES]>,
+}
+
+impl BandFilter {
+    /// Filter for band `index` of a `BANDS`-band EQ.
+    fn new<const BANDS: usize>(index: usize, channels: usize) -> Self {
+        let number = index + 1;
+        let smoother = |suffix: &str| {
+            Smoother::for_param::<ParametricEqParams<BANDS>>(&format!("band{number}_{suffix}"))
+        };
+        Self {
+            target: ParametricEqParams::<BANDS>::default_band(index),
+            frequency_hz: smoother("freq_hz"),
+            gain_db: smoother("gain_db"),
+            q: smoother("q"),
+            designed_for: None,
+            sections: [BiquadCoefficients::IDENTITY; MAX_STAGES],
+            count: 0,
+            state: vec![[BiquadState::default(); MAX_STAGES]; channels],
+        }
+    }
+
+    fn set_target(&mut self, band: &EqBand) {
+        self.target = *band;
+        self.frequency_hz.set_target(band.frequency_hz);
+        self.gain_db.set_target(band.gain_db);
+        self.q.set_target(band.q);
+    }
+
+    fn is_smoothing(&self) -> bool {
+        self.frequency_hz.is_smoothing() || self.gain_db.is_smoothing() || self.q.is_smoothing()
+    }
+
+    /// Step the smoothers `samples` ahead, then redesign the sections if the
+    /// smoothed settings changed since the last call.
+    fn advance(&mut self, samples: usize, sample_rate_hz: f32) {
+        if self.is_smoothing() {
+            for _ in 0..samples {
+                self.frequency_hz.next();
+                self.gain_db.next();
+                self.q.next();
+            }
+        }
+
+        let band = EqBand {
+            frequency_hz: self.frequency_hz.current(),
+            gain_db: self.gain_db.current(),
+            q: self.q.current(),
+            ..self.target
+        };
+        if self.designed_for == Some(band) {
+            return;
+        }
+
+        let previous = self.designed_for.replace(band);
+        // Switching shape or turning the band on starts from clean state so
+        // stale sections don't ring; sweeping frequency, gain or Q keeps it.
+        if previous.is_none_or(|previous| {
+            previous.band_type != band.band_type || previous.enabled != band.enabled
+        }) {
+            self.clear();
+        }
+        (self.sections, self.count) = band.design(sample_rate_hz);
+    }
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], start: usize, end: usize) {
+        let sections = &self.sections[..self.count];
+        // Channels beyond the configured layout have no state and pass through.
+        for (channel, state) in buffer.iter_mut().zip(self.state.iter_mut()) {
+            for (section, state) in sections.iter().zip(state.iter_mut()) {
+                for sample in &mut channel[start..end] {
+                    *sample = state.process_sample(*sample, *section);
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        for state in &mut self.state {
+            *state = [BiquadState::default(); MAX_STAGES];
+        }
+    }
+}
+
+/// Parametric EQ with `BANDS` bands (4 by default).
+///
+/// Every band can be a peak, low/high shelf, notch, or a 12/24/48 dB/oct
+/// low- or high-pass, and can be switched off. Frequency, gain and Q changes
+/// are smoothed over 20 ms; while they ramp, coefficients are redesigned
+/// every 32 samples, and otherwise only when a band's settings (or the
+/// sample rate) change. Use [`ParametricEqParams::magnitude_response_db`] to
+/// draw the curve.
+#[derive(Debug)]
+pub struct ParametricEqDsp<const BANDS: usize = 4> {
+    sample_rate_hz: f32,
+    bands: [BandFilter; BANDS],
+}
+
+impl<const BANDS: usize> Default for ParametricEqDsp<BANDS> {
+    fn default() -> Self {
+        Self {
+            sample_rate_hz: 44_100.0,
+            bands: std::array::from_fn(|index| {
+                BandFilter::new::<BANDS>(index, DEFAULT_EQ_CHANNELS)
+            }),
+        }
+    }
+}
+
+impl<const BANDS: usize> Processor for ParametricEqDsp<BANDS> {
+    type Params = ParametricEqParams<BANDS>;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        for (filter, band) in self.bands.iter_mut().zip(&params.bands) {
+            filter.set_target(band);
+        }
+
+        let samples = buffer
+            .iter()
+            .map(|channel| channel.len())
+            .min()
+            .unwrap_or(0);
+        let mut start = 0;
+        loop {
+            let ramping = self.bands.iter().any(BandFilter::is_smoothing);
+            let end = if ramping {
+                (start + REDESIGN_INTERVAL).min(samples)
+            } else {
+                samples
+            };
+            for filter in &mut self.bands {
+                filter.advance(end - start, self.sample_rate_hz);
+                if filter.count > 0 {
+                    filter.process(buffer, start, end);
+                }
+            }
+            if end >= samples {
+                break;
+            }
+            start = end;
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate_hz = sample_rate.max(1.0);
+        for filter in &mut self.bands {
+            filter.frequency_hz.set_sample_rate(self.sample_rate_hz);
+            filter.gain_db.set_sample_rate(self.sample_rate_hz);
+            filter.q.set_sample_rate(self.sample_rate_hz);
+            filter.designed_for = None;
+        }
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        let channels = (layout.inputs.max(layout.outputs) as usize).max(1);
+        for filter in &mut self.bands {
+            filter
+                .state
+                .resize(channels, [BiquadState::default(); MAX_STAGES]);
+        }
+    }
+
+    fn reset(&mut self) {
+        for filter in &mut self.bands {
+            filter.frequency_hz.reset();
+            filter.gain_db.reset();
+            filter.q.reset();
+            filter.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::testing::{Automation, Renderer, measure, signals};
+    use wavecraft_protocol::ParameterType;
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn single_band(
+        band_type: EqBandType,
+        frequency_hz: f32,
+        gain_db: f32,
+    ) -> ParametricEqParams<1> {
+        ParametricEqParams {
+            bands: [EqBand {
+                enabled: true,
+                band_type,
+                frequency_hz,
+                gain_db,
+                q: DEFAULT_Q,
+            }],
+        }
+    }
+
+    fn response_at(params: &ParametricEqParams<1>, frequency_hz: f32) -> f32 {
+        params.magnitude_response_db(SAMPLE_RATE, &[frequency_hz])[0]
+    }
+
+    #[test]
+    fn param_specs_are_prefixed_per_band() {
+        let specs = ParametricEqParams::<3>::param_specs();
+        assert_eq!(specs.len(), 15);
+        assert_eq!(ParametricEqParams::<3>::plain_value_count(), 15);
+
+        let suffixes: Vec<_> = specs[..5].iter().map(|spec| spec.id_suffix).collect();
+        assert_eq!(
+            suffixes,
+            [
+                "band1_enabled",
+                "band1_type",
+                "band1_freq_hz",
+                "band1_gain_db",
+                "band1_q"
+            ]
+        );
+        assert_eq!(specs[14].id_suffix, "band3_q");
+        assert_eq!(specs[5].group, Some("Band 2"));
+
+        // Outer bands default to shelves, spread from 100 Hz to 8 kHz.
+        let defaults = ParametricEqParams::<3>::from_param_defaults();
+        assert_eq!(defaults.bands[0].band_type, EqBandType::LowShelf);
+        assert_eq!(defaults.bands[1].band_type, EqBandType::Peak);
+        assert_eq!(defaults.bands[2].band_type, EqBandType::HighShelf);
+        assert_eq!(specs[1].default, 1.0);
+        assert!((defaults.bands[2].frequency_hz - 8_000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn apply_plain_values_maps_band_types() {
+        let mut params = ParametricEqParams::<2>::from_param_defaults();
+        params.apply_plain_values(&[0.0, 6.0, 500.0, -3.0, 2.0, 1.0, 8.0]);
+
+        assert!(!params.bands[0].enabled);
+        assert_eq!(
+            params.bands[0].band_type,
+            EqBandType::LowPass(EqSlope::Db48)
+        );
+        assert_eq!(params.bands[0].frequency_hz, 500.0);
+        assert_eq!(params.bands[0].gain_db, -3.0);
+        assert_eq!(params.bands[0].q, 2.0);
+        assert_eq!(
+            params.bands[1].band_type,
+            EqBandType::HighPass(EqSlope::Db24)
+        );
+    }
+
+    #[test]
+    fn peak_and_shelves_reach_their_gain() {
+        let peak = single_band(EqBandType::Peak, 1_000.0, 6.0);
+        assert!((response_at(&peak, 1_000.0) - 6.0).abs() < 0.01);
+        assert!(response_at(&peak, 50.0).abs() < 0.1);
+
+        let low_shelf = single_band(EqBandType::LowShelf, 200.0, -9.0);
+        assert!((response_at(&low_shelf, 20.0) + 9.0).abs() < 0.1);
+        assert!(response_at(&low_shelf, 10_000.0).abs() < 0.1);
+
+        let high_shelf = single_band(EqBandType::HighShelf, 5_000.0, 4.0);
+        assert!((response_at(&high_shelf, 20_000.0) - 4.0).abs() < 0.2);
+        assert!(response_at(&high_shelf, 100.0).abs() < 0.1);
+
+        let notch = single_band(EqBandType::Notch, 1_000.0, 0.0);
+        assert!(response_at(&notch, 1_000.0) < -60.0);
+    }
+
+    #[test]
+    fn pass_slopes_steepen_with_order() {
+        let attenuation = |slope| {
+            let params = single_band(EqBandType::LowPass(slope), 1_000.0, 0.0);
+            // Two octaves above the cutoff.
+            -response_at(&params, 4_000.0)
+        };
+        assert!((attenuation(EqSlope::Db12) - 24.0).abs() < 2.0);
+        assert!((attenuation(EqSlope::Db24) - 48.0).abs() < 2.0);
+        assert!((attenuation(EqSlope::Db48) - 96.0).abs() < 3.0);
+
+        // Butterworth cascades are -3 dB at the cutoff.
+        let high_pass = single_band(EqBandType::HighPass(EqSlope::Db48), 1_000.0, 0.0);
+        assert!((response_at(&high_pass, 1_000.0) + 3.01).abs() < 0.05);
+    }
+
+    #[test]
+    fn processing_matches_magnitude_response() {
+        let params = ParametricEqParams {
+            bands: [
+                EqBand {
+                    gain_db: 8.0,
+                    q: 2.0,
+                    ..single_band(EqBandType::Peak, 2_000.0, 0.0).bands[0]
+                },
+                single_band(EqBandType::HighPass(EqSlope::Db24), 150.0, 0.0).bands[0],
+                EqBand {
+                    enabled: false,
+                    ..single_band(EqBandType::Notch, 1_000.0, 0.0).bands[0]
+                },
+            ],
+        };
+
+        // Halfway through, band 1 moves to 3 kHz and cuts instead of boosting.
+        let change = SAMPLE_RATE as usize / 2;
+        let mut changed = params.clone();
+        changed.bands[0].frequency_hz = 3_000.0;
+        changed.bands[0].gain_db = -8.0;
+
+        let mut automation = Automation::new();
+        for (number, band) in (1..).zip(&params.bands) {
+            for (suffix, value) in BAND_VALUE_SUFFIXES.iter().zip([
+                if band.enabled { 1.0 } else { 0.0 },
+                band.band_type.index() as f32,
+                band.frequency_hz,
+                band.gain_db,
+                band.q,
+            ]) {
+                automation = automation.set(&format!("band{number}_{suffix}"), 0, value);
+            }
+        }
+        let automation =
+            automation
+                .set("band1_freq_hz", change, 3_000.0)
+                .set("band1_gain_db", change, -8.0);
+
+        for frequency in [100.0, 1_000.0, 2_000.0, 3_000.0, 6_000.0] {
+            let input = signals::sine(frequency, SAMPLE_RATE, 2 * change);
+            let output = Renderer::new(SAMPLE_RATE).channels(1).render_automated(
+                &mut ParametricEqDsp::<3>::default(),
+                &automation,
+                &input,
+            );
+            for (params, range) in [
+                (&params, change / 2..change),
+                (&changed, 3 * change / 2..2 * change),
+            ] {
+                let measured = measure::frequency_response(
+                    &input[range.clone()],
+                    &output[0][range],
+                    frequency,
+                    SAMPLE_RATE,
+                );
+                let expected = params.magnitude_response_db(SAMPLE_RATE, &[frequency])[0];
+                assert!(
+                    (measured.gain_db - expected).abs() < 0.05,
+                    "{frequency} Hz: measured {} dB, expected {expected} dB",
+                    measured.gain_db
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parameter_changes_ramp_the_coefficients() {
+        let mut eq = ParametricEqDsp::<1>::default();
+        eq.set_sample_rate(SAMPLE_RATE);
+        let mut params = single_band(EqBandType::Peak, 1_000.0, 6.0);
+        let mut mono = [0.0_f32; REDESIGN_INTERVAL];
+
+        eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        params.bands[0].gain_db = -6.0;
+        eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        let ramping = eq.bands[0].designed_for.expect("band is designed");
+        assert!(ramping.gain_db < 6.0 && ramping.gain_db > -6.0);
+
+        let ramp_samples = (SMOOTHING_MS / 1_000.0 * SAMPLE_RATE) as usize;
+        for _ in 0..ramp_samples / REDESIGN_INTERVAL + 1 {
+            eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        }
+        assert!(!eq.bands[0].is_smoothing());
+        assert_eq!(eq.bands[0].designed_for, Some(params.bands[0]));
+    }
+
+    #[test]
+    fn response_from_parameters_matches_params() {
+        let mut params = ParametricEqParams::<2>::from_param_defaults();
+        params.bands[0].gain_db = -4.0;
+        params.bands[1].band_type = EqBandType::Peak;
+        params.bands[1].gain_db = 7.5;
+        let values = [
+            1.0,
+            params.bands[0].band_type.index() as f32,
+            params.bands[0].frequency_hz,
+            -4.0,
+            DEFAULT_Q,
+            1.0,
+            0.0,
+            params.bands[1].frequency_hz,
+            7.5,
+            DEFAULT_Q,
+        ];
+        let parameters: Vec<_> = ParametricEqParams::<2>::param_specs()
+            .iter()
+            .zip(values)
+            .map(|(spec, value)| ParameterInfo {
+                id: format!("eq_{}", spec.id_suffix),
+                name: spec.name.to_string(),
+                param_type: ParameterType::Float,
+                value,
+                default: spec.default as f32,
+                min: 0.0,
+                max: 1.0,
+                unit: None,
+                group: None,
+                variants: match spec.range {
+                    ParamRange::Enum { variants } => {
+                        Some(variants.iter().map(|name| name.to_string()).collect())
+                    }
+                    _ => None,
+                },
+            })
+            .collect();
+
+        let frequencies = [50.0, 1_000.0, 8_000.0];
+        let expected = params.magnitude_response_db(SAMPLE_RATE, &frequencies);
+        let response = |eq_id| {
+            eq_response_from_parameters(&parameters, eq_id, Some(SAMPLE_RATE), &frequencies)
+        };
+        assert_eq!(response(None), Some(expected.clone()));
+        assert_eq!(response(Some("eq")), Some(expected));
+        assert_eq!(response(Some("other")), None);
+        assert_eq!(
+            eq_response_from_parameters(&parameters[..0], None, None, &frequencies),
+            None
+        );
+    }
+
+    #[test]
+    fn coefficients_follow_parameter_changes_only() {
+        let mut eq = ParametricEqDsp::<1>::default();
+        eq.set_sample_rate(SAMPLE_RATE);
+        let mut params = single_band(EqBandType::Peak, 1_000.0, 6.0);
+        let mut mono = [0.0_f32; 16];
+
+        eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        let designed = eq.bands[0].sections;
+        eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        assert_eq!(eq.bands[0].sections, designed);
+        assert_eq!(eq.bands[0].designed_for, Some(params.bands[0]));
+
+        params.bands[0].gain_db = -6.0;
+        eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        assert_ne!(eq.bands[0].sections, designed);
+
+        params.bands[0].enabled = false;
+        eq.process(&mut [&mut mono[..]], &Transport::default(), &params);
+        assert_eq!(eq.bands[0].count, 0);
+    }
+
+    #[test]
+    fn disabled_bands_pass_audio_through() {
+        let mut params = ParametricEqParams::<4>::from_param_defaults();
+        for band in &mut params.bands {
+            band.gain_db = 12.0;
+            band.enabled = false;
+        }
+        let input = signals::noise(1_024, 9);
+        let output = Renderer::new(SAMPLE_RATE).channels(2).render(
+            &mut ParametricEqDsp::<4>::default(),
+            &params,
+            &input,
+        );
+        assert_eq!(output[1], input);
+        assert!(params.magnitude_response_db(SAMPLE_RATE, &[1_000.0])[0].abs() < 1e-6);
+    }
+}
@@ -1,6 +1,9 @@
 //! Gain processor - amplifies or attenuates audio signals.
 
-use wavecraft_dsp::{ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
+use wavecraft_dsp::{
+    ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams, Smoother, SmoothingStyle,
+    Transport,
+};
 
 /// Parameter struct for gain processor.
 #[derive(Debug, Default, Clone)]
@@ -35,15 +38,31 @@ impl ProcessorParams for GainParams {
             self.level = *level;
         }
     }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[ParamSmoothing {
+            id_suffix: "level",
+            style: SmoothingStyle::Linear(10.0),
+        }]
+    }
 }
 
 /// Gain processor - applies amplitude scaling to audio.
 ///
 /// This is a simple but essential DSP building block that multiplies
-/// all samples by a gain factor.
-#[derive(Debug, Default)]
+/// all samples by a gain factor. Level changes are ramped per sample to avoid
+/// zipper noise on automation.
+#[derive(Debug)]
 pub struct GainDsp {
-    _sample_rate: f32,
+    level: Smoother,
+}
+
+impl Default for GainDsp {
+    fn default() -> Self {
+        Self {
+            level: Smoother::for_param::<GainParams>("level"),
+        }
+    }
 }
 
 impl Processor for GainDsp {
@@ -55,19 +74,33 @@ impl Processor for GainDsp {
         _transport: &Transport,
         params: &Self::Params,
     ) {
-        let gain = params.level;
+        self.level.set_target(params.level);
+
+        if !self.level.is_smoothing() {
+            let gain = self.level.target();
+            for channel in buffer.iter_mut() {
+                apply_gain_to_channel(channel, gain);
+            }
+            return;
+        }
 
-        for channel in buffer.iter_mut() {
-            apply_gain_to_channel(channel, gain);
+        let num_samples = buffer.first().map_or(0, |channel| channel.len());
+        for sample_idx in 0..num_samples {
+            let gain = self.level.next();
+            for channel in buffer.iter_mut() {
+                if let Some(sample) = channel.get_mut(sample_idx) {
+                    *sample *= gain;
+                }
+            }
         }
     }
 
     fn set_sample_rate(&mut self, sample_rate: f32) {
-        self._sample_rate = sample_rate;
+        self.level.set_sample_rate(sample_rate);
     }
 
     fn reset(&mut self) {
-        // No state to reset
+        self.level.reset();
     }
 }
 
@@ -126,6 +159,29 @@ mod tests {
         assert_close(left[0], 0.5);
     }
 
+    #[test]
+    fn test_level_changes_are_smoothed() {
+        let mut processor = GainDsp::default();
+        processor.set_sample_rate(1000.0);
+        let transport = Transport::default();
+
+        let mut first = [1.0; 4];
+        processor.process(
+            &mut [&mut first[..]],
+            &transport,
+            &GainParams { level: 1.0 },
+        );
+        assert_close(first[3], 1.0);
+
+        // 10 ms at 1 kHz = 10-sample linear ramp from 1.0 to 0.0
+        let mut ramp = [1.0; 12];
+        processor.process(&mut [&mut ramp[..]], &transport, &GainParams { level: 0.0 });
+        assert_close(ramp[0], 0.9);
+        assert_close(ramp[4], 0.5);
+        assert_close(ramp[9], 0.0);
+        assert_close(ramp[11], 0.0);
+    }
+
     #[test]
     fn test_param_specs() {
         let specs = GainParams::param_specs();
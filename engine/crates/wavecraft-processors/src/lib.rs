@@ -1,16 +1,34 @@
 //! Reusable processor implementations for Wavecraft plugins.
 
+mod biquad;
+mod delay;
+mod dynamics;
+mod eq;
 mod gain;
 mod oscillator;
 mod oscilloscope;
 mod passthrough;
+mod reverb;
 mod saturator;
+mod spectrum;
+mod stereo_utility;
+mod taps;
 mod unified_filter;
 
 // Built-in processors and parameter surface.
+pub use delay::{DelayDsp, DelayMode, DelayParams, NoteDivision};
+pub use dynamics::{
+    CompressorDsp, CompressorGainReduction, CompressorParams, LIMITER_LOOKAHEAD_MS, LimiterDsp,
+    LimiterGainReduction, LimiterParams,
+};
+pub use eq::{
+    EqBand, EqBandType, EqSlope, ParametricEqDsp, ParametricEqParams, eq_response_from_parameters,
+};
 pub use gain::{GainDsp, GainParams};
 pub use passthrough::{PassthroughDsp, PassthroughParams};
+pub use reverb::{ReverbDsp, ReverbParams};
 pub use saturator::{SaturatorDsp, SaturatorParams};
+pub use stereo_utility::{PanLaw, StereoUtilityDsp, StereoUtilityParams};
 pub use unified_filter::{UnifiedFilterDsp, UnifiedFilterMode, UnifiedFilterParams};
 
 // Oscillator processor and parameter surface.
@@ -21,3 +39,13 @@ pub use oscilloscope::{
     OSCILLOSCOPE_FRAME_POINTS, OscilloscopeFrameConsumer, OscilloscopeFrameProducer,
     OscilloscopeFrameSnapshot, OscilloscopeTap, create_oscilloscope_channel,
 };
+
+// Spectrum analyzer processor, channel, and frame data surface.
+pub use spectrum::{
+    SPECTRUM_MAX_BANDS, SPECTRUM_MAX_FFT_SIZE, SPECTRUM_MIN_FFT_SIZE, SpectrumAnalyzer,
+    SpectrumConfig, SpectrumFrameConsumer, SpectrumFrameProducer, SpectrumFrameSnapshot,
+    SpectrumWindow, create_spectrum_channel,
+};
+
+// Named meter, oscilloscope and spectrum taps placeable anywhere in a signal chain.
+pub use taps::{MeterTap, ScopeTap, SpectrumTap};
@@ -0,0 +1,573 @@
+//! Stereo algorithmic reverb built on a feedback delay network.
+
+use wavecraft_dsp::{
+    ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams, SILENCE_THRESHOLD, Smoother,
+    SmoothingStyle, TailLength, Transport,
+};
+
+const MIN_DECAY_SECONDS: f32 = 0.1;
+const MAX_DECAY_SECONDS: f32 = 20.0;
+const MAX_PRE_DELAY_MS: f32 = 250.0;
+/// Network line lengths in milliseconds at full size. Mutually prime at
+/// common sample rates so the echoes don't pile up into a flutter.
+const LINE_LENGTHS_MS: [f32; LINES] = [29.7, 37.1, 41.1, 43.7, 53.3, 59.9, 67.7, 73.1];
+const LINES: usize = 8;
+/// Line lengths at size 0, as a fraction of [`LINE_LENGTHS_MS`].
+const MIN_SIZE_SCALE: f32 = 0.2;
+/// Glide time when the size changes, so the lines stretch instead of
+/// clicking.
+const SIZE_SMOOTHING_MS: f32 = 100.0;
+/// Strongest damping, as the one-pole coefficient of the line filters.
+const MAX_DAMPING: f32 = 0.9;
+/// Output polarity of each line for the left and right wet signals. The two
+/// patterns are orthogonal, so a mono input still produces a wide tail.
+const LEFT_TAPS: [f32; LINES] = [1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
+const RIGHT_TAPS: [f32; LINES] = [1.0, 1.0, -1.0, -1.0, 1.0, 1.0, -1.0, -1.0];
+/// Gain of the wet sum, which adds up `LINES` lines.
+const OUTPUT_GAIN: f32 = 0.35;
+const DEFAULT_SAMPLE_RATE_HZ: f32 = 44_100.0;
+
+/// Parameters for the reverb.
+#[derive(Debug, Clone)]
+pub struct ReverbParams {
+    /// Room size from 0 (small) to 1 (large); scales the network delays.
+    pub size: f32,
+    /// Time for the tail to fall by 60 dB (RT60), in seconds.
+    pub decay_s: f32,
+    /// Gap before the tail starts, in milliseconds.
+    pub pre_delay_ms: f32,
+    /// High-frequency absorption from 0 (bright) to 1 (dark).
+    pub damping: f32,
+    /// Stereo width of the tail from 0 (mono) to 1 (full).
+    pub width: f32,
+    /// Dry/wet mix from 0 (dry) to 1 (wet).
+    pub mix: f32,
+}
+
+impl Default for ReverbParams {
+    fn default() -> Self {
+        Self::from_param_defaults()
+    }
+}
+
+impl ProcessorParams for ReverbParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static SPECS: [ParamSpec; 6] = [
+            ParamSpec {
+                name: "Size",
+                id_suffix: "size",
+                range: ParamRange::Linear { min: 0.0, max: 1.0 },
+                default: 0.5,
+                unit: "",
+                group: Some("Reverb"),
+            },
+            ParamSpec {
+                name: "Decay",
+                id_suffix: "decay_s",
+                range: ParamRange::Skewed {
+                    min: MIN_DECAY_SECONDS as f64,
+                    max: MAX_DECAY_SECONDS as f64,
+                    factor: 2.5,
+                },
+                default: 2.0,
+                unit: "s",
+                group: Some("Reverb"),
+            },
+            ParamSpec {
+                name: "Pre-delay",
+                id_suffix: "pre_delay_ms",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: MAX_PRE_DELAY_MS as f64,
+                },
+                default: 10.0,
+                unit: "ms",
+                group: Some("Reverb"),
+            },
+            ParamSpec {
+                name: "Damping",
+                id_suffix: "damping",
+                range: ParamRange::Linear { min: 0.0, max: 1.0 },
+                default: 0.5,
+                unit: "",
+                group: Some("Reverb"),
+            },
+            ParamSpec {
+                name: "Width",
+                id_suffix: "width",
+                range: ParamRange::Linear { min: 0.0, max: 1.0 },
+                default: 1.0,
+                unit: "",
+                group: Some("Reverb"),
+            },
+            ParamSpec {
+                name: "Mix",
+                id_suffix: "mix",
+                range: ParamRange::Linear { min: 0.0, max: 1.0 },
+                default: 0.3,
+                unit: "",
+                group: Some("Reverb"),
+            },
+        ];
+
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            size: 0.5,
+            decay_s: 2.0,
+            pre_delay_ms: 10.0,
+            damping: 0.5,
+            width: 1.0,
+            mix: 0.3,
+        }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(size) = values.first() {
+            self.size = *size;
+        }
+        if let Some(decay_s) = values.get(1) {
+            self.decay_s = *decay_s;
+        }
+        if let Some(pre_delay_ms) = values.get(2) {
+            self.pre_delay_ms = *pre_delay_ms;
+        }
+        if let Some(damping) = values.get(3) {
+            self.damping = *damping;
+        }
+        if let Some(width) = values.get(4) {
+            self.width = *width;
+        }
+        if let Some(mix) = values.get(5) {
+            self.mix = *mix;
+        }
+    }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[
+            ParamSmoothing {
+                id_suffix: "width",
+                style: SmoothingStyle::Linear(20.0),
+            },
+            ParamSmoothing {
+                id_suffix: "mix",
+                style: SmoothingStyle::Linear(20.0),
+            },
+        ]
+    }
+}
+
+/// Circular delay line with fractional reads.
+#[derive(Debug, Clone, Default)]
+struct DelayLine {
+    buffer: Vec<f32>,
+    write_pos: usize,
+}
+
+impl DelayLine {
+    fn allocate(&mut self, len: usize) {
+        self.buffer = vec![0.0; len.max(2)];
+        self.write_pos = 0;
+    }
+
+    fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        self.write_pos = 0;
+    }
+
+    /// Linearly interpolated sample written `delay` samples ago.
+    #[inline]
+    fn read(&self, delay: f32) -> f32 {
+        let len = self.buffer.len();
+        let delay = delay.clamp(0.0, (len - 1) as f32);
+        let position = self.write_pos as f32 - delay;
+        let position = if position < 0.0 {
+            position + len as f32
+        } else {
+            position
+        };
+        let index = position as usize;
+        let fraction = position - index as f32;
+        let current = self.buffer[index % len];
+        let next = self.buffer[(index + 1) % len];
+        current + (next - current) * fraction
+    }
+
+    #[inline]
+    fn write(&mut self, sample: f32) {
+        self.buffer[self.write_pos] = sample;
+        self.write_pos += 1;
+        if self.write_pos == self.buffer.len() {
+            self.write_pos = 0;
+        }
+    }
+}
+
+/// Stereo algorithmic reverb.
+///
+/// Eight delay lines feed back into each other through a Householder
+/// matrix, which keeps the network lossless so the per-line gains alone
+/// set the decay time. Each line has a one-pole low-pass for damping. After
+/// the pre-delay, the left channel feeds the even lines and the right
+/// channel the odd ones. The wet signal goes to the first two channels (a
+/// mono buffer gets the mid of both sides) and further channels pass
+/// through.
+///
+/// All buffers are allocated in `set_sample_rate`; until then the
+/// processor passes audio through.
+#[derive(Debug)]
+pub struct ReverbDsp {
+    sample_rate_hz: f32,
+    pre_delay: [DelayLine; 2],
+    lines: [DelayLine; LINES],
+    damping_state: [f32; LINES],
+    size: Smoother,
+    width: Smoother,
+    mix: Smoother,
+    tail: TailLength,
+}
+
+impl Default for ReverbDsp {
+    fn default() -> Self {
+        let params = ReverbParams::from_param_defaults();
+        Self {
+            sample_rate_hz: DEFAULT_SAMPLE_RATE_HZ,
+            pre_delay: Default::default(),
+            lines: Default::default(),
+            damping_state: [0.0; LINES],
+            size: Smoother::new(SmoothingStyle::Exponential(SIZE_SMOOTHING_MS)),
+            width: Smoother::for_param::<ReverbParams>("width"),
+            mix: Smoother::for_param::<ReverbParams>("mix"),
+            tail: tail_length(&params, DEFAULT_SAMPLE_RATE_HZ),
+        }
+    }
+}
+
+impl ReverbDsp {
+    fn allocate(&mut self) {
+        let samples_per_ms = self.sample_rate_hz / 1000.0;
+        for line in &mut self.pre_delay {
+            line.allocate((MAX_PRE_DELAY_MS * samples_per_ms) as usize + 2);
+        }
+        for (line, length_ms) in self.lines.iter_mut().zip(LINE_LENGTHS_MS) {
+            line.allocate((length_ms * samples_per_ms) as usize + 2);
+        }
+        self.damping_state = [0.0; LINES];
+    }
+}
+
+/// Scale of the line lengths for `size`.
+#[inline]
+fn size_scale(size: f32) -> f32 {
+    MIN_SIZE_SCALE + (1.0 - MIN_SIZE_SCALE) * size.clamp(0.0, 1.0)
+}
+
+/// Time for the tail to fall below [`SILENCE_THRESHOLD`] after the input
+/// stops.
+///
+/// RT60 is the time for a 60 dB drop, so the tail needs
+/// `log10(threshold) / -3` decay times, plus the pre-delay and the longest
+/// line still to drain. Damping only shortens it.
+fn tail_length(params: &ReverbParams, sample_rate_hz: f32) -> TailLength {
+    let decay = params.decay_s.clamp(MIN_DECAY_SECONDS, MAX_DECAY_SECONDS) * sample_rate_hz;
+    let decays = SILENCE_THRESHOLD.log10() / -3.0;
+    let pre_delay = params.pre_delay_ms.clamp(0.0, MAX_PRE_DELAY_MS) * sample_rate_hz / 1000.0;
+    let longest_line =
+        LINE_LENGTHS_MS[LINES - 1] * size_scale(params.size) * sample_rate_hz / 1000.0;
+    TailLength::Samples((decays * decay + pre_delay + longest_line).ceil() as u32)
+}
+
+impl Processor for ReverbDsp {
+    type Params = ReverbParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        if self.lines[0].buffer.is_empty() {
+            return;
+        }
+
+        let sample_rate = self.sample_rate_hz;
+        let samples_per_ms = sample_rate / 1000.0;
+        let decay_s = params.decay_s.clamp(MIN_DECAY_SECONDS, MAX_DECAY_SECONDS);
+        let pre_delay = params.pre_delay_ms.clamp(0.0, MAX_PRE_DELAY_MS) * samples_per_ms;
+        let damping = params.damping.clamp(0.0, 1.0) * MAX_DAMPING;
+        self.size.set_target(size_scale(params.size));
+        self.width.set_target(params.width.clamp(0.0, 1.0));
+        self.mix.set_target(params.mix.clamp(0.0, 1.0));
+        self.tail = tail_length(params, sample_rate);
+
+        // Per-line gain for a 60 dB drop every `decay_s`, from the line
+        // lengths the size is heading to.
+        let target_scale = self.size.target();
+        let feedback = LINE_LENGTHS_MS.map(|length_ms| {
+            let length_s = length_ms * target_scale * 0.001;
+            10_f32.powf(-3.0 * length_s / decay_s)
+        });
+
+        let Some((left, rest)) = buffer.split_first_mut() else {
+            return;
+        };
+        let mut right = rest.first_mut();
+        for (frame, left) in left.iter_mut().enumerate() {
+            let scale = self.size.next();
+            let width = self.width.next();
+            let mix = self.mix.next();
+
+            let dry_left = *left;
+            let dry_right = right.as_ref().map_or(dry_left, |right| right[frame]);
+            self.pre_delay[0].write(dry_left);
+            self.pre_delay[1].write(dry_right);
+            // The sample just written is one sample back.
+            let input_left = self.pre_delay[0].read(pre_delay + 1.0);
+            let input_right = self.pre_delay[1].read(pre_delay + 1.0);
+
+            let mut outputs = [0.0_f32; LINES];
+            let mut wet_left = 0.0;
+            let mut wet_right = 0.0;
+            for (index, output) in outputs.iter_mut().enumerate() {
+                let delayed =
+                    self.lines[index].read(LINE_LENGTHS_MS[index] * scale * samples_per_ms);
+                let damped = &mut self.damping_state[index];
+                *damped = delayed + damping * (*damped - delayed);
+                wet_left += LEFT_TAPS[index] * *damped;
+                wet_right += RIGHT_TAPS[index] * *damped;
+                *output = *damped * feedback[index];
+            }
+
+            // Householder feedback matrix: reflect about the all-ones vector.
+            let reflection = outputs.iter().sum::<f32>() * (2.0 / LINES as f32);
+            for (index, (line, output)) in self.lines.iter_mut().zip(outputs).enumerate() {
+                let input = if index % 2 == 0 {
+                    input_left
+                } else {
+                    input_right
+                };
+                line.write(output - reflection + input);
+            }
+
+            let mid = 0.5 * (wet_left + wet_right) * OUTPUT_GAIN;
+            let side = 0.5 * (wet_left - wet_right) * OUTPUT_GAIN * width;
+            if let Some(right) = right.as_mut() {
+                *left = dry_left + (mid + side - dry_left) * mix;
+                right[frame] = dry_right + (mid - side - dry_right) * mix;
+            } else {
+                *left = dry_left + (mid - dry_left) * mix;
+            }
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate_hz = sample_rate.max(1.0);
+        self.size.set_sample_rate(self.sample_rate_hz);
+        self.width.set_sample_rate(self.sample_rate_hz);
+        self.mix.set_sample_rate(self.sample_rate_hz);
+        self.allocate();
+    }
+
+    fn reset(&mut self) {
+        for line in self.pre_delay.iter_mut().chain(self.lines.iter_mut()) {
+            line.clear();
+        }
+        self.damping_state = [0.0; LINES];
+        self.size.reset();
+        self.width.reset();
+        self.mix.reset();
+    }
+
+    fn tail_samples(&self) -> TailLength {
+        self.tail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::testing::{
+        Renderer, assert_block_size_invariant, assert_tail_ends, measure, signals,
+    };
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn wet(params: ReverbParams) -> ReverbParams {
+        ReverbParams { mix: 1.0, ..params }
+    }
+
+    fn first_audible(signal: &[f32]) -> Option<usize> {
+        signal.iter().position(|sample| sample.abs() > 1e-6)
+    }
+
+    #[test]
+    fn param_specs_use_expected_suffixes() {
+        let suffixes: Vec<_> = ReverbParams::param_specs()
+            .iter()
+            .map(|spec| spec.id_suffix)
+            .collect();
+        assert_eq!(
+            suffixes,
+            ["size", "decay_s", "pre_delay_ms", "damping", "width", "mix"]
+        );
+
+        let mut params = ReverbParams::from_param_defaults();
+        params.apply_plain_values(&[1.0, 4.5, 40.0]);
+        assert_eq!(params.size, 1.0);
+        assert_eq!(params.decay_s, 4.5);
+        assert_eq!(params.pre_delay_ms, 40.0);
+        assert_eq!(params.mix, 0.3);
+    }
+
+    #[test]
+    fn passes_audio_through_before_allocation_and_when_dry() {
+        let mut reverb = ReverbDsp::default();
+        let mut mono = [0.5_f32, -0.25];
+        reverb.process(
+            &mut [&mut mono[..]],
+            &Transport::default(),
+            &ReverbParams::default(),
+        );
+        assert_eq!(mono, [0.5, -0.25]);
+
+        let input = signals::noise(4_800, 4);
+        let params = ReverbParams {
+            mix: 0.0,
+            ..ReverbParams::default()
+        };
+        let output = Renderer::new(SAMPLE_RATE).render(&mut ReverbDsp::default(), &params, &input);
+        assert_eq!(output[0], input);
+        assert_eq!(output[1], input);
+    }
+
+    #[test]
+    fn tail_starts_after_pre_delay() {
+        let params = wet(ReverbParams {
+            size: 0.0,
+            pre_delay_ms: 50.0,
+            ..ReverbParams::default()
+        });
+        let output = Renderer::new(SAMPLE_RATE).render(
+            &mut ReverbDsp::default(),
+            &params,
+            &signals::impulse(SAMPLE_RATE as usize / 4),
+        );
+
+        // Pre-delay plus the shortest line at size 0.
+        let expected = 2_400 + (LINE_LENGTHS_MS[0] * MIN_SIZE_SCALE * 48.0) as usize;
+        let onset = first_audible(&output[0]).expect("reverb should ring");
+        assert!(
+            (expected..expected + 2).contains(&onset),
+            "onset at {onset}"
+        );
+    }
+
+    #[test]
+    fn decay_follows_rt60() {
+        let params = wet(ReverbParams {
+            decay_s: 1.0,
+            damping: 0.0,
+            pre_delay_ms: 0.0,
+            ..ReverbParams::default()
+        });
+        let output = Renderer::new(SAMPLE_RATE).channels(1).render(
+            &mut ReverbDsp::default(),
+            &params,
+            &signals::impulse(SAMPLE_RATE as usize * 2),
+        );
+
+        let window = |start: f32| {
+            let start = (start * SAMPLE_RATE) as usize;
+            measure::rms(&output[0][start..start + 4_800])
+        };
+        let drop = measure::gain_to_db(window(1.2) / window(0.2));
+        assert!(
+            (drop + 60.0).abs() < 6.0,
+            "dropped {drop} dB in one decay time"
+        );
+    }
+
+    #[test]
+    fn damping_darkens_the_tail() {
+        let input = signals::noise(SAMPLE_RATE as usize / 2, 8);
+        let render = |damping| {
+            let params = wet(ReverbParams {
+                damping,
+                ..ReverbParams::default()
+            });
+            Renderer::new(SAMPLE_RATE).channels(1).render(
+                &mut ReverbDsp::default(),
+                &params,
+                &input,
+            )
+        };
+        let roughness = |signal: &[f32]| -> f32 {
+            let level = measure::rms(signal);
+            signal
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).abs())
+                .sum::<f32>()
+                / level
+        };
+
+        let tail = input.len() / 2..;
+        assert!(roughness(&render(1.0)[0][tail.clone()]) < roughness(&render(0.0)[0][tail]) * 0.8);
+    }
+
+    #[test]
+    fn width_zero_collapses_to_mono() {
+        let params = wet(ReverbParams {
+            width: 0.0,
+            ..ReverbParams::default()
+        });
+        let input = signals::noise(9_600, 6);
+        let output = Renderer::new(SAMPLE_RATE).render(&mut ReverbDsp::default(), &params, &input);
+        assert!(measure::max_difference(&output[0], &output[1]) < 1e-6);
+
+        let wide = Renderer::new(SAMPLE_RATE).render(
+            &mut ReverbDsp::default(),
+            &wet(ReverbParams::default()),
+            &input,
+        );
+        assert!(measure::max_difference(&wide[0], &wide[1]) > 0.01);
+    }
+
+    #[test]
+    fn declared_tail_covers_ring_out_and_reset_clears_it() {
+        let params = wet(ReverbParams {
+            decay_s: 0.5,
+            ..ReverbParams::default()
+        });
+        let mut reverb = ReverbDsp::default();
+        reverb.set_sample_rate(SAMPLE_RATE);
+        let mut stereo = [signals::impulse(512), signals::impulse(512)];
+        let [left, right] = &mut stereo;
+        reverb.process(
+            &mut [&mut left[..], &mut right[..]],
+            &Transport::default(),
+            &params,
+        );
+        assert_tail_ends(&mut reverb, &params, 2, 512);
+
+        let mut noise = signals::noise(4_800, 2);
+        reverb.process(&mut [&mut noise[..]], &Transport::default(), &params);
+        reverb.reset();
+        let mut silence = vec![0.0_f32; 4_800];
+        reverb.process(&mut [&mut silence[..]], &Transport::default(), &params);
+        assert!(silence.iter().all(|sample| *sample == 0.0));
+    }
+
+    #[test]
+    fn output_is_block_size_invariant() {
+        assert_block_size_invariant(
+            ReverbDsp::default,
+            &ReverbParams::default(),
+            SAMPLE_RATE,
+            &signals::noise(9_600, 3),
+            &[1, 7, 512],
+            1e-5,
+        );
+    }
+}
@@ -0,0 +1,668 @@
+//! Spectrum analyzer processor and lock-free frame transport.
+//!
+//! Like the oscilloscope tap, the spectrum analyzer is observation-only: it never
+//! modifies audio samples. It runs a windowed FFT every half FFT length and
+//! publishes log-spaced band magnitudes for EQ and analyzer displays.
+
+use wavecraft_dsp::{Processor, Transport};
+use wavecraft_protocol::SpectrumFrame;
+
+/// Maximum number of log-spaced bands per spectrum frame.
+pub const SPECTRUM_MAX_BANDS: usize = 512;
+/// Smallest supported FFT length.
+pub const SPECTRUM_MIN_FFT_SIZE: usize = 256;
+/// Largest supported FFT length.
+pub const SPECTRUM_MAX_FFT_SIZE: usize = 16_384;
+/// Magnitude reported for silent bands, in dBFS.
+const SPECTRUM_FLOOR_DB: f32 = -120.0;
+const SPECTRUM_FLOOR_POWER: f32 = 1e-12;
+
+/// Analysis window applied before the FFT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectrumWindow {
+    /// Good general-purpose choice.
+    #[default]
+    Hann,
+    /// Lower sidelobes (-92 dB) for a wider main lobe; suits high dynamic
+    /// range displays.
+    BlackmanHarris,
+    /// No windowing; sharpest bins but heavy leakage.
+    Rectangular,
+}
+
+impl SpectrumWindow {
+    fn coefficient(self, index: usize, len: usize) -> f32 {
+        let phase = std::f64::consts::TAU * index as f64 / len as f64;
+        let value = match self {
+            Self::Hann => 0.5 - 0.5 * phase.cos(),
+            Self::BlackmanHarris => {
+                0.35875 - 0.48829 * phase.cos() + 0.14128 * (2.0 * phase).cos()
+                    - 0.01168 * (3.0 * phase).cos()
+            }
+            Self::Rectangular => 1.0,
+        };
+        value as f32
+    }
+}
+
+/// Spectrum analyzer settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumConfig {
+    /// FFT length in samples; rounded up to a power of two within
+    /// [`SPECTRUM_MIN_FFT_SIZE`]..=[`SPECTRUM_MAX_FFT_SIZE`]. Frames are
+    /// published every `fft_size / 2` samples.
+    pub fft_size: usize,
+    /// Analysis window.
+    pub window: SpectrumWindow,
+    /// Time constant of the exponential averaging applied to band power, in
+    /// milliseconds. `0.0` disables averaging.
+    pub averaging_ms: f32,
+    /// Number of log-spaced bands (at most [`SPECTRUM_MAX_BANDS`]).
+    pub bands: usize,
+    /// Lowest band edge in Hz.
+    pub min_hz: f32,
+    /// Highest band edge in Hz; clamped to Nyquist.
+    pub max_hz: f32,
+}
+
+impl Default for SpectrumConfig {
+    fn default() -> Self {
+        Self {
+            fft_size: 2048,
+            window: SpectrumWindow::Hann,
+            averaging_ms: 100.0,
+            bands: 256,
+            min_hz: 20.0,
+            max_hz: 20_000.0,
+        }
+    }
+}
+
+impl SpectrumConfig {
+    fn normalized(self) -> Self {
+        let min_hz = if self.min_hz.is_finite() {
+            self.min_hz.max(1.0)
+        } else {
+            1.0
+        };
+        Self {
+            fft_size: self
+                .fft_size
+                .clamp(SPECTRUM_MIN_FFT_SIZE, SPECTRUM_MAX_FFT_SIZE)
+                .next_power_of_two(),
+            averaging_ms: if self.averaging_ms.is_finite() {
+                self.averaging_ms.max(0.0)
+            } else {
+                0.0
+            },
+            bands: self.bands.clamp(1, SPECTRUM_MAX_BANDS),
+            min_hz,
+            max_hz: if self.max_hz > min_hz {
+                self.max_hz
+            } else {
+                min_hz * 2.0
+            },
+            ..self
+        }
+    }
+}
+
+/// Internal snapshot format with fixed-size arrays (no heap allocations).
+#[derive(Clone)]
+pub struct SpectrumFrameSnapshot {
+    pub frequencies: [f32; SPECTRUM_MAX_BANDS],
+    pub magnitudes_l: [f32; SPECTRUM_MAX_BANDS],
+    pub magnitudes_r: [f32; SPECTRUM_MAX_BANDS],
+    /// Number of valid entries in each array.
+    pub bands: usize,
+    pub sample_rate: f32,
+    pub fft_size: usize,
+    pub timestamp: u64,
+}
+
+impl SpectrumFrameSnapshot {
+    /// Convert fixed-size snapshot into IPC frame payload.
+    pub fn to_protocol_frame(&self) -> SpectrumFrame {
+        SpectrumFrame {
+            frequencies: self.frequencies[..self.bands].to_vec(),
+            magnitudes_l: self.magnitudes_l[..self.bands].to_vec(),
+            magnitudes_r: self.magnitudes_r[..self.bands].to_vec(),
+            sample_rate: self.sample_rate,
+            fft_size: self.fft_size as u32,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Producer side of spectrum frame channel.
+pub struct SpectrumFrameProducer {
+    producer: rtrb::Producer<SpectrumFrameSnapshot>,
+}
+
+impl SpectrumFrameProducer {
+    /// Push the latest frame. If the channel is full, the frame is dropped.
+    pub fn push(&mut self, frame: SpectrumFrameSnapshot) {
+        let _ = self.producer.push(frame);
+    }
+}
+
+/// Consumer side of spectrum frame channel.
+pub struct SpectrumFrameConsumer {
+    consumer: rtrb::Consumer<SpectrumFrameSnapshot>,
+}
+
+impl SpectrumFrameConsumer {
+    /// Read and return the most recent available frame.
+    pub fn read_latest(&mut self) -> Option<SpectrumFrameSnapshot> {
+        let mut latest = None;
+        while let Ok(frame) = self.consumer.pop() {
+            latest = Some(frame);
+        }
+        latest
+    }
+}
+
+/// Create a lock-free spectrum frame channel.
+pub fn create_spectrum_channel(capacity: usize) -> (SpectrumFrameProducer, SpectrumFrameConsumer) {
+    let (producer, consumer) = rtrb::RingBuffer::new(capacity);
+    (
+        SpectrumFrameProducer { producer },
+        SpectrumFrameConsumer { consumer },
+    )
+}
+
+/// In-place iterative radix-2 complex FFT with precomputed tables.
+struct Fft {
+    cos: Vec<f32>,
+    sin: Vec<f32>,
+    bit_reverse: Vec<usize>,
+}
+
+impl Fft {
+    fn new(len: usize) -> Self {
+        let bits = len.trailing_zeros();
+        let (cos, sin) = (0..len / 2)
+            .map(|k| {
+                let angle = -std::f64::consts::TAU * k as f64 / len as f64;
+                (angle.cos() as f32, angle.sin() as f32)
+            })
+            .unzip();
+        let bit_reverse = (0..len)
+            .map(|index| {
+                if bits == 0 {
+                    0
+                } else {
+                    index.reverse_bits() >> (usize::BITS - bits)
+                }
+            })
+            .collect();
+        Self {
+            cos,
+            sin,
+            bit_reverse,
+        }
+    }
+
+    fn process(&self, re: &mut [f32], im: &mut [f32]) {
+        let len = re.len();
+        for index in 0..len {
+            let reversed = self.bit_reverse[index];
+            if reversed > index {
+                re.swap(index, reversed);
+                im.swap(index, reversed);
+            }
+        }
+
+        let mut size = 2;
+        while size <= len {
+            let half = size / 2;
+            let stride = len / size;
+            for start in (0..len).step_by(size) {
+                for k in 0..half {
+                    let (w_re, w_im) = (self.cos[k * stride], self.sin[k * stride]);
+                    let a = start + k;
+                    let b = a + half;
+                    let t_re = re[b] * w_re - im[b] * w_im;
+                    let t_im = re[b] * w_im + im[b] * w_re;
+                    re[b] = re[a] - t_re;
+                    im[b] = im[a] - t_im;
+                    re[a] += t_re;
+                    im[a] += t_im;
+                }
+            }
+            size *= 2;
+        }
+    }
+}
+
+/// Observation-only spectrum analyzer processor.
+///
+/// Configure it with [`SpectrumAnalyzer::set_config`] outside the audio thread;
+/// capture and publishing never allocate.
+pub struct SpectrumAnalyzer {
+    config: SpectrumConfig,
+    sample_rate: f32,
+    fft: Fft,
+    window: Vec<f32>,
+    /// Converts an FFT bin magnitude to sine amplitude.
+    amplitude_scale: f32,
+    history_l: Vec<f32>,
+    history_r: Vec<f32>,
+    write_pos: usize,
+    samples_until_frame: usize,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    power: Vec<f32>,
+    band_first_bin: [usize; SPECTRUM_MAX_BANDS],
+    band_last_bin: [usize; SPECTRUM_MAX_BANDS],
+    band_center_bin: [f32; SPECTRUM_MAX_BANDS],
+    frequencies: [f32; SPECTRUM_MAX_BANDS],
+    average_l: [f32; SPECTRUM_MAX_BANDS],
+    average_r: [f32; SPECTRUM_MAX_BANDS],
+    averaging_coefficient: f32,
+    has_average: bool,
+    timestamp: u64,
+    output: Option<SpectrumFrameProducer>,
+}
+
+impl Default for SpectrumAnalyzer {
+    fn default() -> Self {
+        Self::with_config(SpectrumConfig::default())
+    }
+}
+
+impl SpectrumAnalyzer {
+    /// Create a new spectrum analyzer with default settings and no output channel.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new spectrum analyzer with the given settings.
+    pub fn with_config(config: SpectrumConfig) -> Self {
+        let config = config.normalized();
+        let fft_size = config.fft_size;
+        let mut tap = Self {
+            config,
+            sample_rate: 44_100.0,
+            fft: Fft::new(fft_size),
+            window: Vec::new(),
+            amplitude_scale: 0.0,
+            history_l: vec![0.0; fft_size],
+            history_r: vec![0.0; fft_size],
+            write_pos: 0,
+            samples_until_frame: fft_size,
+            re: vec![0.0; fft_size],
+            im: vec![0.0; fft_size],
+            power: vec![0.0; fft_size / 2 + 1],
+            band_first_bin: [0; SPECTRUM_MAX_BANDS],
+            band_last_bin: [0; SPECTRUM_MAX_BANDS],
+            band_center_bin: [0.0; SPECTRUM_MAX_BANDS],
+            frequencies: [0.0; SPECTRUM_MAX_BANDS],
+            average_l: [0.0; SPECTRUM_MAX_BANDS],
+            average_r: [0.0; SPECTRUM_MAX_BANDS],
+            averaging_coefficient: 0.0,
+            has_average: false,
+            timestamp: 0,
+            output: None,
+        };
+        tap.window = (0..fft_size)
+            .map(|index| config.window.coefficient(index, fft_size))
+            .collect();
+        let window_sum: f32 = tap.window.iter().sum();
+        tap.amplitude_scale = 2.0 / window_sum;
+        tap.update_bands();
+        tap
+    }
+
+    /// Create a new spectrum analyzer with frame output channel.
+    pub fn with_output(output: SpectrumFrameProducer) -> Self {
+        Self {
+            output: Some(output),
+            ..Self::default()
+        }
+    }
+
+    /// Attach or replace the output channel.
+    pub fn set_output(&mut self, output: SpectrumFrameProducer) {
+        self.output = Some(output);
+    }
+
+    /// Current settings (after normalization).
+    pub fn config(&self) -> SpectrumConfig {
+        self.config
+    }
+
+    /// Replace the analyzer settings, keeping the output channel and sample
+    /// rate.
+    ///
+    /// Reallocates the FFT buffers; call from `initialize` or the UI
+    /// thread, not from the audio callback.
+    pub fn set_config(&mut self, config: SpectrumConfig) {
+        let mut tap = Self::with_config(config);
+        tap.output = self.output.take();
+        tap.timestamp = self.timestamp;
+        tap.sample_rate = self.sample_rate;
+        tap.update_bands();
+        *self = tap;
+    }
+
+    /// Set the sample rate used for band frequencies and averaging.
+    pub fn set_sample_rate_hz(&mut self, sample_rate: f32) {
+        if sample_rate > 0.0 {
+            self.sample_rate = sample_rate;
+            self.update_bands();
+        }
+    }
+
+    /// Capture stereo slices, publishing a frame for every half FFT length
+    /// of input.
+    pub fn capture_stereo(&mut self, left: &[f32], right: &[f32]) {
+        let right = if right.is_empty() { left } else { right };
+        let fft_size = self.config.fft_size;
+
+        for (&l, &r) in left.iter().zip(right) {
+            self.history_l[self.write_pos] = l;
+            self.history_r[self.write_pos] = r;
+            self.write_pos = (self.write_pos + 1) % fft_size;
+
+            self.samples_until_frame -= 1;
+            if self.samples_until_frame == 0 {
+                self.samples_until_frame = fft_size / 2;
+                self.analyze_and_publish();
+            }
+        }
+    }
+
+    fn update_bands(&mut self) {
+        let config = self.config;
+        let nyquist = self.sample_rate / 2.0;
+        let max_hz = config.max_hz.min(nyquist);
+        let min_hz = config.min_hz.min(max_hz * 0.5);
+        let bin_hz = self.sample_rate / config.fft_size as f32;
+        let last_bin = config.fft_size / 2;
+        let ratio = (max_hz / min_hz).powf(1.0 / config.bands as f32);
+
+        for band in 0..config.bands {
+            let low = min_hz * ratio.powi(band as i32);
+            let high = low * ratio;
+            let center = (low * high).sqrt();
+            self.frequencies[band] = center;
+            self.band_first_bin[band] = ((low / bin_hz).ceil() as usize).min(last_bin);
+            self.band_last_bin[band] = ((high / bin_hz).floor() as usize).min(last_bin);
+            self.band_center_bin[band] = (center / bin_hz).min(last_bin as f32);
+        }
+
+        let hop_seconds = (config.fft_size / 2) as f32 / self.sample_rate;
+        self.averaging_coefficient = if config.averaging_ms > 0.0 {
+            (-hop_seconds * 1000.0 / config.averaging_ms).exp()
+        } else {
+            0.0
+        };
+    }
+
+    fn analyze_and_publish(&mut self) {
+        self.analyze_channel(false);
+        self.analyze_channel(true);
+        self.has_average = true;
+
+        let mut frame = SpectrumFrameSnapshot {
+            frequencies: self.frequencies,
+            magnitudes_l: [SPECTRUM_FLOOR_DB; SPECTRUM_MAX_BANDS],
+            magnitudes_r: [SPECTRUM_FLOOR_DB; SPECTRUM_MAX_BANDS],
+            bands: self.config.bands,
+            sample_rate: self.sample_rate,
+            fft_size: self.config.fft_size,
+            timestamp: self.timestamp,
+        };
+        for band in 0..self.config.bands {
+            frame.magnitudes_l[band] = power_to_db(self.average_l[band]);
+            frame.magnitudes_r[band] = power_to_db(self.average_r[band]);
+        }
+
+        self.timestamp = self.timestamp.wrapping_add(1);
+
+        if let Some(output) = self.output.as_mut() {
+            output.push(frame);
+        }
+    }
+
+    fn analyze_channel(&mut self, right: bool) {
+        let fft_size = self.config.fft_size;
+        let history = if right {
+            &self.history_r
+        } else {
+            &self.history_l
+        };
+
+        // Oldest sample first.
+        for index in 0..fft_size {
+            let sample = history[(self.write_pos + index) % fft_size];
+            self.re[index] = sample * self.window[index];
+            self.im[index] = 0.0;
+        }
+        self.fft.process(&mut self.re, &mut self.im);
+
+        for (bin, power) in self.power.iter_mut().enumerate() {
+            let amplitude = (self.re[bin] * self.re[bin] + self.im[bin] * self.im[bin]).sqrt()
+                * self.amplitude_scale;
+            *power = amplitude * amplitude;
+        }
+
+        let coefficient = if self.has_average {
+            self.averaging_coefficient
+        } else {
+            0.0
+        };
+        let average = if right {
+            &mut self.average_r
+        } else {
+            &mut self.average_l
+        };
+        for (band, average) in average[..self.config.bands].iter_mut().enumerate() {
+            let first = self.band_first_bin[band];
+            let last = self.band_last_bin[band];
+            let power = if last >= first {
+                // Wide band: show the strongest bin so narrow peaks keep
+                // their level.
+                self.power[first..=last].iter().copied().fold(0.0, f32::max)
+            } else {
+                // Band narrower than a bin: interpolate at its centre.
+                let position = self.band_center_bin[band];
+                let index = position as usize;
+                let next = (index + 1).min(self.power.len() - 1);
+                let fraction = position - index as f32;
+                self.power[index] + (self.power[next] - self.power[index]) * fraction
+            };
+            *average = power + (*average - power) * coefficient;
+        }
+    }
+}
+
+fn power_to_db(power: f32) -> f32 {
+    if power <= SPECTRUM_FLOOR_POWER {
+        SPECTRUM_FLOOR_DB
+    } else {
+        (10.0 * power.log10()).max(SPECTRUM_FLOOR_DB)
+    }
+}
+
+impl Processor for SpectrumAnalyzer {
+    type Params = ();
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.set_sample_rate_hz(sample_rate);
+    }
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        _params: &Self::Params,
+    ) {
+        if buffer.is_empty() {
+            return;
+        }
+
+        let left = &*buffer[0];
+        let right = if buffer.len() > 1 { &*buffer[1] } else { left };
+
+        // Observation-only capture. Audio data is never modified.
+        self.capture_stereo(left, right);
+    }
+
+    fn reset(&mut self) {
+        self.history_l.fill(0.0);
+        self.history_r.fill(0.0);
+        self.write_pos = 0;
+        self.samples_until_frame = self.config.fft_size;
+        self.average_l = [0.0; SPECTRUM_MAX_BANDS];
+        self.average_r = [0.0; SPECTRUM_MAX_BANDS];
+        self.has_average = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, amplitude: f32, sample_rate: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|n| amplitude * (std::f32::consts::TAU * frequency * n as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    fn tap_with_channel(config: SpectrumConfig) -> (SpectrumAnalyzer, SpectrumFrameConsumer) {
+        let (producer, consumer) = create_spectrum_channel(8);
+        let mut tap = SpectrumAnalyzer::with_config(config);
+        tap.set_output(producer);
+        tap.set_sample_rate_hz(48_000.0);
+        (tap, consumer)
+    }
+
+    #[test]
+    fn passthrough_invariance() {
+        let mut tap = SpectrumAnalyzer::new();
+
+        let mut left = [0.25_f32, -0.1, 0.4, -0.3];
+        let mut right = [-0.2_f32, 0.5, -0.4, 0.1];
+        let expected_left = left;
+        let expected_right = right;
+        let mut buffer = [&mut left[..], &mut right[..]];
+
+        tap.process(&mut buffer, &Transport::default(), &());
+
+        assert_eq!(left, expected_left);
+        assert_eq!(right, expected_right);
+    }
+
+    #[test]
+    fn fft_matches_direct_dft() {
+        let len = 16;
+        let fft = Fft::new(len);
+        let input: Vec<f32> = (0..len).map(|n| ((n * 7) % 5) as f32 - 2.0).collect();
+        let mut re = input.clone();
+        let mut im = vec![0.0; len];
+        fft.process(&mut re, &mut im);
+
+        for k in 0..len {
+            let (mut dft_re, mut dft_im) = (0.0_f32, 0.0_f32);
+            for (n, &x) in input.iter().enumerate() {
+                let angle = -std::f32::consts::TAU * (k * n) as f32 / len as f32;
+                dft_re += x * angle.cos();
+                dft_im += x * angle.sin();
+            }
+            assert!((re[k] - dft_re).abs() < 1e-3, "bin {k}");
+            assert!((im[k] - dft_im).abs() < 1e-3, "bin {k}");
+        }
+    }
+
+    #[test]
+    fn sine_peaks_at_its_frequency_near_full_scale() {
+        let (mut tap, mut consumer) = tap_with_channel(SpectrumConfig {
+            averaging_ms: 0.0,
+            ..SpectrumConfig::default()
+        });
+        let left = sine(1000.0, 1.0, 48_000.0, 4096);
+        let right = vec![0.0; 4096];
+        tap.capture_stereo(&left, &right);
+
+        let frame = consumer.read_latest().expect("frame should exist");
+        assert_eq!(frame.bands, 256);
+        let (peak_band, peak_db) = frame.magnitudes_l[..frame.bands]
+            .iter()
+            .copied()
+            .enumerate()
+            .fold(
+                (0, f32::MIN),
+                |best, (band, db)| {
+                    if db > best.1 { (band, db) } else { best }
+                },
+            );
+
+        let peak_hz = frame.frequencies[peak_band];
+        assert!(
+            (peak_hz / 1000.0 - 1.0).abs() < 0.05,
+            "peak at {peak_hz} Hz"
+        );
+        assert!(peak_db > -2.0 && peak_db < 0.5, "peak level {peak_db} dB");
+        assert!(frame.magnitudes_l[10] < -60.0);
+        assert!(
+            frame.magnitudes_r[..frame.bands]
+                .iter()
+                .all(|&db| db == SPECTRUM_FLOOR_DB)
+        );
+    }
+
+    #[test]
+    fn frames_publish_every_half_fft() {
+        let (mut tap, mut consumer) = tap_with_channel(SpectrumConfig {
+            fft_size: 1024,
+            ..SpectrumConfig::default()
+        });
+        let silence = [0.0_f32; 1023];
+        tap.capture_stereo(&silence, &silence);
+        assert!(consumer.read_latest().is_none());
+
+        tap.capture_stereo(&silence[..1], &silence[..1]);
+        assert_eq!(consumer.read_latest().expect("first frame").timestamp, 0);
+
+        tap.capture_stereo(&silence[..512], &silence[..512]);
+        let frame = consumer.read_latest().expect("second frame");
+        assert_eq!(frame.timestamp, 1);
+        assert_eq!(frame.to_protocol_frame().fft_size, 1024);
+    }
+
+    #[test]
+    fn averaging_smooths_level_changes() {
+        let (mut tap, mut consumer) = tap_with_channel(SpectrumConfig {
+            averaging_ms: 500.0,
+            ..SpectrumConfig::default()
+        });
+        let tone = sine(1000.0, 1.0, 48_000.0, 2048);
+        tap.capture_stereo(&tone, &tone);
+        let loud = consumer.read_latest().expect("frame");
+        let band = loud.frequencies[..loud.bands]
+            .iter()
+            .position(|&hz| hz >= 1000.0)
+            .expect("band near 1 kHz");
+
+        let silence = vec![0.0_f32; 1024];
+        tap.capture_stereo(&silence, &silence);
+        let decaying = consumer.read_latest().expect("frame");
+
+        assert!(decaying.magnitudes_l[band] < loud.magnitudes_l[band]);
+        assert!(decaying.magnitudes_l[band] > -20.0);
+    }
+
+    #[test]
+    fn config_is_normalized() {
+        let tap = SpectrumAnalyzer::with_config(SpectrumConfig {
+            fft_size: 3000,
+            bands: 10_000,
+            window: SpectrumWindow::BlackmanHarris,
+            ..SpectrumConfig::default()
+        });
+        assert_eq!(tap.config().fft_size, 4096);
+        assert_eq!(tap.config().bands, SPECTRUM_MAX_BANDS);
+    }
+}
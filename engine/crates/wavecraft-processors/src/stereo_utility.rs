@@ -0,0 +1,495 @@
+//! Stereo utility: width, balance, polarity, channel swap, mono and DC blocking.
+
+use core::f32::consts::{FRAC_PI_2, TAU};
+use wavecraft_dsp::{
+    ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams, Smoother, SmoothingStyle,
+    Transport,
+};
+
+const MAX_WIDTH: f32 = 2.0;
+/// Corner frequency of the DC blocker. Low enough to leave sub-bass alone.
+const DC_BLOCK_HZ: f32 = 5.0;
+
+/// How balance distributes level between the channels.
+///
+/// Named after the level of a centered signal relative to a hard-panned
+/// one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanLaw {
+    /// 0 dB: the center is untouched and turning the balance only
+    /// attenuates the opposite side.
+    #[default]
+    Balance,
+    /// -3 dB: sine/cosine law with constant power across positions.
+    ConstantPower,
+    /// -4.5 dB: halfway between constant power and linear.
+    Compromise,
+    /// -6 dB: linear crossfade with constant amplitude across positions.
+    Linear,
+}
+
+impl PanLaw {
+    const NAMES: [&'static str; 4] = ["0 dB", "-3 dB", "-4.5 dB", "-6 dB"];
+
+    fn from_index(index: i32) -> Self {
+        match index {
+            1 => Self::ConstantPower,
+            2 => Self::Compromise,
+            3 => Self::Linear,
+            _ => Self::Balance,
+        }
+    }
+
+    /// Left and right gains for a balance from -1 (left) to 1 (right).
+    #[inline]
+    fn gains(self, balance: f32) -> (f32, f32) {
+        let position = (balance.clamp(-1.0, 1.0) + 1.0) * 0.5;
+        let (sin, cos) = (position * FRAC_PI_2).sin_cos();
+        match self {
+            Self::Balance => ((2.0 - 2.0 * position).min(1.0), (2.0 * position).min(1.0)),
+            Self::ConstantPower => (cos, sin),
+            Self::Compromise => (((1.0 - position) * cos).sqrt(), (position * sin).sqrt()),
+            Self::Linear => (1.0 - position, position),
+        }
+    }
+}
+
+/// Parameters for the stereo utility.
+#[derive(Debug, Clone)]
+pub struct StereoUtilityParams {
+    /// Stereo width: 0 is mono, 1 unchanged and 2 doubles the side signal.
+    pub width: f32,
+    /// Balance from -1 (left) to 1 (right).
+    pub balance: f32,
+    pub pan_law: PanLaw,
+    /// Flip the polarity of the left channel.
+    pub invert_left: bool,
+    /// Flip the polarity of the right channel.
+    pub invert_right: bool,
+    /// Exchange the left and right channels.
+    pub swap: bool,
+    /// Sum both channels to mono before balance is applied.
+    pub mono: bool,
+    /// Remove DC offset with a 5 Hz high-pass.
+    pub dc_block: bool,
+}
+
+impl Default for StereoUtilityParams {
+    fn default() -> Self {
+        Self::from_param_defaults()
+    }
+}
+
+impl ProcessorParams for StereoUtilityParams {
+    fn param_specs() -> &'static [ParamSpec] {
+        static SPECS: [ParamSpec; 8] = [
+            ParamSpec {
+                name: "Width",
+                id_suffix: "width",
+                range: ParamRange::Linear {
+                    min: 0.0,
+                    max: MAX_WIDTH as f64,
+                },
+                default: 1.0,
+                unit: "x",
+                group: Some("Stereo"),
+            },
+            ParamSpec {
+                name: "Balance",
+                id_suffix: "balance",
+                range: ParamRange::Linear {
+                    min: -1.0,
+                    max: 1.0,
+                },
+                default: 0.0,
+                unit: "",
+                group: Some("Stereo"),
+            },
+            ParamSpec {
+                name: "Pan Law",
+                id_suffix: "pan_law",
+                range: ParamRange::Enum {
+                    variants: &PanLaw::NAMES,
+                },
+                default: 0.0,
+                unit: "",
+                group: Some("Stereo"),
+            },
+            ParamSpec {
+                name: "Invert Left",
+                id_suffix: "invert_left",
+                range: ParamRange::Stepped { min: 0, max: 1 },
+                default: 0.0,
+                unit: "",
+                group: Some("Channels"),
+            },
+            ParamSpec {
+                name: "Invert Right",
+                id_suffix: "invert_right",
+                range: ParamRange::Stepped { min: 0, max: 1 },
+                default: 0.0,
+                unit: "",
+                group: Some("Channels"),
+            },
+            ParamSpec {
+                name: "Swap",
+                id_suffix: "swap",
+                range: ParamRange::Stepped { min: 0, max: 1 },
+                default: 0.0,
+                unit: "",
+                group: Some("Channels"),
+            },
+            ParamSpec {
+                name: "Mono",
+                id_suffix: "mono",
+                range: ParamRange::Stepped { min: 0, max: 1 },
+                default: 0.0,
+                unit: "",
+                group: Some("Channels"),
+            },
+            ParamSpec {
+                name: "DC Block",
+                id_suffix: "dc_block",
+                range: ParamRange::Stepped { min: 0, max: 1 },
+                default: 0.0,
+                unit: "",
+                group: Some("Channels"),
+            },
+        ];
+
+        &SPECS
+    }
+
+    fn from_param_defaults() -> Self {
+        Self {
+            width: 1.0,
+            balance: 0.0,
+            pan_law: PanLaw::Balance,
+            invert_left: false,
+            invert_right: false,
+            swap: false,
+            mono: false,
+            dc_block: false,
+        }
+    }
+
+    fn apply_plain_values(&mut self, values: &[f32]) {
+        if let Some(width) = values.first() {
+            self.width = *width;
+        }
+        if let Some(balance) = values.get(1) {
+            self.balance = *balance;
+        }
+        if let Some(pan_law) = values.get(2) {
+            self.pan_law = PanLaw::from_index(pan_law.round() as i32);
+        }
+        if let Some(invert_left) = values.get(3) {
+            self.invert_left = *invert_left >= 0.5;
+        }
+        if let Some(invert_right) = values.get(4) {
+            self.invert_right = *invert_right >= 0.5;
+        }
+        if let Some(swap) = values.get(5) {
+            self.swap = *swap >= 0.5;
+        }
+        if let Some(mono) = values.get(6) {
+            self.mono = *mono >= 0.5;
+        }
+        if let Some(dc_block) = values.get(7) {
+            self.dc_block = *dc_block >= 0.5;
+        }
+    }
+
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[
+            ParamSmoothing {
+                id_suffix: "width",
+                style: SmoothingStyle::Linear(20.0),
+            },
+            ParamSmoothing {
+                id_suffix: "balance",
+                style: SmoothingStyle::Linear(20.0),
+            },
+        ]
+    }
+}
+
+/// First-order DC-blocking high-pass for one channel.
+#[derive(Debug, Clone, Copy, Default)]
+struct DcBlocker {
+    previous_input: f32,
+    previous_output: f32,
+}
+
+impl DcBlocker {
+    #[inline]
+    fn process(&mut self, input: f32, pole: f32) -> f32 {
+        let output = input - self.previous_input + pole * self.previous_output;
+        self.previous_input = input;
+        self.previous_output = output;
+        output
+    }
+}
+
+/// Stereo utility processor.
+///
+/// Works on the first two channels in this order: swap, polarity invert,
+/// DC block, width (mid/side), mono sum and balance. A mono buffer only
+/// gets the left polarity switch and DC blocking; channels beyond the
+/// second pass through. Width and balance changes are ramped to avoid
+/// zipper noise.
+#[derive(Debug)]
+pub struct StereoUtilityDsp {
+    sample_rate_hz: f32,
+    width: Smoother,
+    balance: Smoother,
+    dc_blockers: [DcBlocker; 2],
+}
+
+impl Default for StereoUtilityDsp {
+    fn default() -> Self {
+        Self {
+            sample_rate_hz: 44_100.0,
+            width: Smoother::for_param::<StereoUtilityParams>("width"),
+            balance: Smoother::for_param::<StereoUtilityParams>("balance"),
+            dc_blockers: [DcBlocker::default(); 2],
+        }
+    }
+}
+
+impl Processor for StereoUtilityDsp {
+    type Params = StereoUtilityParams;
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        params: &Self::Params,
+    ) {
+        self.width.set_target(params.width.clamp(0.0, MAX_WIDTH));
+        self.balance.set_target(params.balance.clamp(-1.0, 1.0));
+
+        let Some((left, rest)) = buffer.split_first_mut() else {
+            return;
+        };
+        let mut right = rest.first_mut();
+        if params.swap
+            && let Some(right) = right.as_mut()
+        {
+            left.swap_with_slice(right);
+        }
+
+        let polarity = |invert: bool| if invert { -1.0 } else { 1.0 };
+        let (left_polarity, right_polarity) =
+            (polarity(params.invert_left), polarity(params.invert_right));
+        let dc_pole = (-TAU * DC_BLOCK_HZ / self.sample_rate_hz).exp();
+        let mut gains = params.pan_law.gains(self.balance.target());
+
+        for (frame, left) in left.iter_mut().enumerate() {
+            let width = self.width.next();
+            if self.balance.is_smoothing() {
+                gains = params.pan_law.gains(self.balance.next());
+            }
+
+            let mut left_sample = *left * left_polarity;
+            if params.dc_block {
+                left_sample = self.dc_blockers[0].process(left_sample, dc_pole);
+            }
+
+            let Some(right) = right.as_mut() else {
+                *left = left_sample;
+                continue;
+            };
+
+            let mut right_sample = right[frame] * right_polarity;
+            if params.dc_block {
+                right_sample = self.dc_blockers[1].process(right_sample, dc_pole);
+            }
+
+            let mid = 0.5 * (left_sample + right_sample);
+            let side = if params.mono {
+                0.0
+            } else {
+                0.5 * (left_sample - right_sample) * width
+            };
+            *left = (mid + side) * gains.0;
+            right[frame] = (mid - side) * gains.1;
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate_hz = sample_rate.max(1.0);
+        self.width.set_sample_rate(self.sample_rate_hz);
+        self.balance.set_sample_rate(self.sample_rate_hz);
+    }
+
+    fn reset(&mut self) {
+        self.width.reset();
+        self.balance.reset();
+        self.dc_blockers = [DcBlocker::default(); 2];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::testing::{Renderer, assert_block_size_invariant, measure, signals};
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    fn process_stereo(params: &StereoUtilityParams, left: &[f32], right: &[f32]) -> [Vec<f32>; 2] {
+        let mut utility = StereoUtilityDsp::default();
+        utility.set_sample_rate(SAMPLE_RATE);
+        let (mut left, mut right) = (left.to_vec(), right.to_vec());
+        utility.process(
+            &mut [&mut left[..], &mut right[..]],
+            &Transport::default(),
+            params,
+        );
+        [left, right]
+    }
+
+    #[test]
+    fn param_specs_and_plain_values() {
+        let suffixes: Vec<_> = StereoUtilityParams::param_specs()
+            .iter()
+            .map(|spec| spec.id_suffix)
+            .collect();
+        assert_eq!(
+            suffixes,
+            [
+                "width",
+                "balance",
+                "pan_law",
+                "invert_left",
+                "invert_right",
+                "swap",
+                "mono",
+                "dc_block"
+            ]
+        );
+
+        let mut params = StereoUtilityParams::from_param_defaults();
+        params.apply_plain_values(&[0.5, -0.25, 2.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(params.width, 0.5);
+        assert_eq!(params.balance, -0.25);
+        assert_eq!(params.pan_law, PanLaw::Compromise);
+        assert!(params.invert_left && !params.invert_right);
+        assert!(params.swap && !params.mono && params.dc_block);
+    }
+
+    #[test]
+    fn defaults_are_transparent() {
+        let left = signals::noise(512, 1);
+        let right = signals::noise(512, 2);
+        let [out_left, out_right] = process_stereo(&StereoUtilityParams::default(), &left, &right);
+        assert!(measure::max_difference(&left, &out_left) < 1e-6);
+        assert!(measure::max_difference(&right, &out_right) < 1e-6);
+    }
+
+    #[test]
+    fn swap_then_invert() {
+        let params = StereoUtilityParams {
+            swap: true,
+            invert_right: true,
+            ..StereoUtilityParams::default()
+        };
+        let [left, right] = process_stereo(&params, &[0.5, 0.25], &[-0.125, 0.375]);
+        assert_eq!(left, [-0.125, 0.375]);
+        assert_eq!(right, [-0.5, -0.25]);
+    }
+
+    #[test]
+    fn width_and_mono_control_the_side_signal() {
+        let (left, right) = ([1.0_f32, 0.5], [0.0_f32, 0.5]);
+        let narrow = StereoUtilityParams {
+            width: 0.0,
+            ..StereoUtilityParams::default()
+        };
+        assert_eq!(
+            process_stereo(&narrow, &left, &right),
+            [[0.5, 0.5], [0.5, 0.5]]
+        );
+
+        let wide = StereoUtilityParams {
+            width: 2.0,
+            ..StereoUtilityParams::default()
+        };
+        assert_eq!(
+            process_stereo(&wide, &left, &right),
+            [[1.5, 0.5], [-0.5, 0.5]]
+        );
+
+        let mono = StereoUtilityParams { mono: true, ..wide };
+        assert_eq!(
+            process_stereo(&mono, &left, &right),
+            [[0.5, 0.5], [0.5, 0.5]]
+        );
+    }
+
+    #[test]
+    fn pan_laws_set_the_center_level() {
+        let center_db = |pan_law: PanLaw| {
+            let (left, right) = pan_law.gains(0.0);
+            assert!((left - right).abs() < 1e-6);
+            measure::gain_to_db(left)
+        };
+        assert!(center_db(PanLaw::Balance).abs() < 1e-4);
+        assert!((center_db(PanLaw::ConstantPower) + 3.01).abs() < 0.01);
+        assert!((center_db(PanLaw::Compromise) + 4.52).abs() < 0.01);
+        assert!((center_db(PanLaw::Linear) + 6.02).abs() < 0.01);
+
+        for pan_law in [
+            PanLaw::Balance,
+            PanLaw::ConstantPower,
+            PanLaw::Compromise,
+            PanLaw::Linear,
+        ] {
+            let (left, right) = pan_law.gains(-1.0);
+            assert!((left - 1.0).abs() < 1e-6 && right.abs() < 1e-6);
+            let (left, right) = pan_law.gains(1.0);
+            assert!(left.abs() < 1e-6 && (right - 1.0).abs() < 1e-6);
+        }
+
+        let (left, right) = PanLaw::ConstantPower.gains(0.3);
+        assert!((left * left + right * right - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn dc_blocker_removes_offset() {
+        let params = StereoUtilityParams {
+            dc_block: true,
+            ..StereoUtilityParams::default()
+        };
+        let input: Vec<f32> = signals::sine(1_000.0, SAMPLE_RATE, SAMPLE_RATE as usize)
+            .into_iter()
+            .map(|sample| 0.5 * sample + 0.3)
+            .collect();
+        let output =
+            Renderer::new(SAMPLE_RATE).render(&mut StereoUtilityDsp::default(), &params, &input);
+
+        let settled = &output[0][SAMPLE_RATE as usize / 2..];
+        let mean = settled.iter().sum::<f32>() / settled.len() as f32;
+        assert!(mean.abs() < 1e-3, "mean {mean}");
+        assert!((measure::peak(settled) - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn output_is_block_size_invariant() {
+        let params = StereoUtilityParams {
+            width: 1.6,
+            balance: 0.4,
+            pan_law: PanLaw::ConstantPower,
+            dc_block: true,
+            ..StereoUtilityParams::default()
+        };
+        assert_block_size_invariant(
+            StereoUtilityDsp::default,
+            &params,
+            SAMPLE_RATE,
+            &signals::noise(4_800, 5),
+            &[1, 7, 512],
+            1e-6,
+        );
+    }
+}
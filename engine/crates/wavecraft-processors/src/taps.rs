@@ -0,0 +1,390 @@
+//! Named meter, oscilloscope and spectrum taps for use inside a signal chain.
+
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use wavecraft_dsp::{ChannelLayout, MeteringTap, Processor, TapId, Transport};
+use wavecraft_metering::{MeterConsumer, MeterEngine, MeterProducer, create_meter_channel};
+use wavecraft_protocol::{MeterFrame, OscilloscopeFrame, SpectrumFrame};
+
+use crate::oscilloscope::{
+    OscilloscopeFrameConsumer, OscilloscopeTap, create_oscilloscope_channel,
+};
+use crate::spectrum::{
+    SpectrumAnalyzer, SpectrumConfig, SpectrumFrameConsumer, create_spectrum_channel,
+};
+
+const METER_TAP_CAPACITY: usize = 64;
+const SCOPE_TAP_CAPACITY: usize = 8;
+const SPECTRUM_TAP_CAPACITY: usize = 4;
+
+struct MeterTapOutput {
+    id: &'static str,
+    consumer: Mutex<MeterConsumer>,
+}
+
+impl MeteringTap for MeterTapOutput {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn meter_frame(&self) -> Option<MeterFrame> {
+        self.consumer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read_latest()
+    }
+}
+
+/// Meters the signal at its position in the chain and publishes the frames
+/// under the ID of `I`.
+///
+/// Audio passes through unchanged. Select the stream from the UI with
+/// `getMeterFrame` and the tap's ID.
+pub struct MeterTap<I: TapId> {
+    meters: MeterEngine,
+    sample_rate: f32,
+    channels: usize,
+    producer: MeterProducer,
+    output: Arc<MeterTapOutput>,
+    _id: PhantomData<I>,
+}
+
+impl<I: TapId> Default for MeterTap<I> {
+    fn default() -> Self {
+        let (producer, consumer) = create_meter_channel(METER_TAP_CAPACITY);
+        Self {
+            meters: MeterEngine::new(44_100.0, 2),
+            sample_rate: 44_100.0,
+            channels: 2,
+            producer,
+            output: Arc::new(MeterTapOutput {
+                id: I::ID,
+                consumer: Mutex::new(consumer),
+            }),
+            _id: PhantomData,
+        }
+    }
+}
+
+impl<I: TapId> MeterTap<I> {
+    /// Create a meter tap with its own output stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read side of the tap's stream.
+    pub fn output(&self) -> Arc<dyn MeteringTap> {
+        self.output.clone()
+    }
+}
+
+impl<I: TapId> Processor for MeterTap<I> {
+    type Params = ();
+
+    fn process(
+        &mut self,
+        buffer: &mut [&mut [f32]],
+        _transport: &Transport,
+        _params: &Self::Params,
+    ) {
+        if buffer.is_empty() {
+            return;
+        }
+
+        self.meters.process(buffer);
+        self.producer.push(self.meters.frame());
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.meters = MeterEngine::new(self.sample_rate, self.channels);
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        self.channels = layout.outputs as usize;
+        self.meters = MeterEngine::new(self.sample_rate, self.channels);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        taps.push(self.output());
+    }
+
+    fn reset(&mut self) {
+        self.meters.reset();
+    }
+}
+
+struct ScopeTapOutput {
+    id: &'static str,
+    consumer: Mutex<OscilloscopeFrameConsumer>,
+}
+
+impl MeteringTap for ScopeTapOutput {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn oscilloscope_frame(&self) -> Option<OscilloscopeFrame> {
+        self.consumer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read_latest()
+            .map(|frame| frame.to_protocol_frame())
+    }
+}
+
+/// [`OscilloscopeTap`] that publishes its waveform under the ID of `I`.
+///
+/// Audio passes through unchanged. Select the stream from the UI with
+/// `getOscilloscopeFrame` and the tap's ID.
+pub struct ScopeTap<I: TapId> {
+    tap: OscilloscopeTap,
+    output: Arc<ScopeTapOutput>,
+    _id: PhantomData<I>,
+}
+
+impl<I: TapId> Default for ScopeTap<I> {
+    fn default() -> Self {
+        let (producer, consumer) = create_oscilloscope_channel(SCOPE_TAP_CAPACITY);
+        Self {
+            tap: OscilloscopeTap::with_output(producer),
+            output: Arc::new(ScopeTapOutput {
+                id: I::ID,
+                consumer: Mutex::new(consumer),
+            }),
+            _id: PhantomData,
+        }
+    }
+}
+
+impl<I: TapId> ScopeTap<I> {
+    /// Create an oscilloscope tap with its own output stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read side of the tap's stream.
+    pub fn output(&self) -> Arc<dyn MeteringTap> {
+        self.output.clone()
+    }
+}
+
+impl<I: TapId> Processor for ScopeTap<I> {
+    type Params = ();
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.tap.process(buffer, transport, params);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.tap.set_sample_rate_hz(sample_rate);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        taps.push(self.output());
+    }
+}
+
+struct SpectrumTapOutput {
+    id: &'static str,
+    consumer: Mutex<SpectrumFrameConsumer>,
+}
+
+impl MeteringTap for SpectrumTapOutput {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn spectrum_frame(&self) -> Option<SpectrumFrame> {
+        self.consumer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read_latest()
+            .map(|frame| frame.to_protocol_frame())
+    }
+}
+
+/// [`SpectrumAnalyzer`] that publishes its spectrum under the ID of `I`.
+///
+/// Audio passes through unchanged. Select the stream from the UI with
+/// `getSpectrumFrame` and the tap's ID.
+pub struct SpectrumTap<I: TapId> {
+    analyzer: SpectrumAnalyzer,
+    output: Arc<SpectrumTapOutput>,
+    _id: PhantomData<I>,
+}
+
+impl<I: TapId> Default for SpectrumTap<I> {
+    fn default() -> Self {
+        let (producer, consumer) = create_spectrum_channel(SPECTRUM_TAP_CAPACITY);
+        Self {
+            analyzer: SpectrumAnalyzer::with_output(producer),
+            output: Arc::new(SpectrumTapOutput {
+                id: I::ID,
+                consumer: Mutex::new(consumer),
+            }),
+            _id: PhantomData,
+        }
+    }
+}
+
+impl<I: TapId> SpectrumTap<I> {
+    /// Create a spectrum tap with its own output stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a spectrum tap with custom analyzer settings.
+    pub fn with_config(config: SpectrumConfig) -> Self {
+        let mut tap = Self::default();
+        tap.set_config(config);
+        tap
+    }
+
+    /// Current analyzer settings.
+    pub fn config(&self) -> SpectrumConfig {
+        self.analyzer.config()
+    }
+
+    /// Replace the analyzer settings (see [`SpectrumAnalyzer::set_config`]).
+    pub fn set_config(&mut self, config: SpectrumConfig) {
+        self.analyzer.set_config(config);
+    }
+
+    /// Read side of the tap's stream.
+    pub fn output(&self) -> Arc<dyn MeteringTap> {
+        self.output.clone()
+    }
+}
+
+impl<I: TapId> Processor for SpectrumTap<I> {
+    type Params = ();
+
+    fn process(&mut self, buffer: &mut [&mut [f32]], transport: &Transport, params: &Self::Params) {
+        self.analyzer.process(buffer, transport, params);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.analyzer.set_sample_rate_hz(sample_rate);
+    }
+
+    fn metering_taps(&self, taps: &mut Vec<Arc<dyn MeteringTap>>) {
+        taps.push(self.output());
+    }
+
+    fn reset(&mut self) {
+        self.analyzer.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft_dsp::{SignalChain, tap_id};
+
+    tap_id!(Input = "input");
+    tap_id!(Output = "output");
+
+    #[derive(Default)]
+    struct Halve;
+
+    impl Processor for Halve {
+        type Params = ();
+
+        fn process(
+            &mut self,
+            buffer: &mut [&mut [f32]],
+            _transport: &Transport,
+            _params: &Self::Params,
+        ) {
+            for channel in buffer.iter_mut() {
+                channel.iter_mut().for_each(|sample| *sample *= 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn meter_tap_passes_audio_and_publishes_frames() {
+        let mut tap = MeterTap::<Input>::new();
+        let output = tap.output();
+        assert_eq!(output.id(), "input");
+        assert!(output.meter_frame().is_none());
+
+        let mut left = [0.25_f32, -0.5, 0.1, 0.0];
+        let mut right = [0.0_f32, 0.125, -0.2, 0.0];
+        let expected = (left, right);
+        tap.process(
+            &mut [&mut left[..], &mut right[..]],
+            &Transport::default(),
+            &(),
+        );
+        assert_eq!((left, right), expected);
+
+        let frame = output.meter_frame().expect("frame should be published");
+        assert_eq!(frame.peak_l, 0.5);
+        assert_eq!(frame.peak_r, 0.2);
+        assert!(output.oscilloscope_frame().is_none());
+    }
+
+    #[test]
+    fn taps_in_a_chain_publish_separate_streams() {
+        let mut chain = <SignalChain![MeterTap<Input>, Halve, MeterTap<Output>]>::default();
+        let mut taps = Vec::new();
+        chain.metering_taps(&mut taps);
+        let ids: Vec<_> = taps.iter().map(|tap| tap.id().to_string()).collect();
+        assert_eq!(ids, ["input", "output"]);
+
+        let mut left = [0.8_f32; 64];
+        let mut right = [0.8_f32; 64];
+        chain.process(
+            &mut [&mut left[..], &mut right[..]],
+            &Transport::default(),
+            &Default::default(),
+        );
+
+        let input = taps[0].meter_frame().expect("input frame");
+        let output = taps[1].meter_frame().expect("output frame");
+        assert_eq!(input.peak_l, 0.8);
+        assert_eq!(output.peak_l, 0.4);
+    }
+
+    #[test]
+    fn scope_tap_publishes_waveform() {
+        let mut tap = ScopeTap::<Output>::new();
+        let output = tap.output();
+
+        let mut mono = [0.5_f32; 128];
+        tap.process(&mut [&mut mono[..]], &Transport::default(), &());
+
+        let frame = output
+            .oscilloscope_frame()
+            .expect("frame should be published");
+        assert_eq!(output.id(), "output");
+        assert_eq!(frame.points_l, frame.points_r);
+        assert!(output.meter_frame().is_none());
+    }
+
+    #[test]
+    fn spectrum_tap_publishes_spectrum_in_a_chain() {
+        let mut chain = <SignalChain![SpectrumTap<Input>, Halve]>::default();
+        let mut taps = Vec::new();
+        chain.metering_taps(&mut taps);
+        assert_eq!(taps.len(), 1);
+        assert_eq!(taps[0].id(), "input");
+        assert!(taps[0].spectrum_frame().is_none());
+
+        let mut left = [0.5_f32; 4096];
+        let mut right = [0.5_f32; 4096];
+        chain.process(
+            &mut [&mut left[..], &mut right[..]],
+            &Transport::default(),
+            &Default::default(),
+        );
+        assert_eq!(left[0], 0.25);
+
+        let frame = taps[0].spectrum_frame().expect("frame should be published");
+        assert!(!frame.magnitudes_l.is_empty());
+        assert!(taps[0].meter_frame().is_none());
+    }
+}
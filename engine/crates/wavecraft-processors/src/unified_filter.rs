@@ -1,14 +1,22 @@
 //! Unified filter processor (LP/HP/BP) with enum mode selection.
 
 use core::f32::consts::PI;
-use wavecraft_dsp::{ParamRange, ParamSpec, Processor, ProcessorParams, Transport};
+use wavecraft_dsp::{
+    ChannelLayout, ParamRange, ParamSmoothing, ParamSpec, Processor, ProcessorParams, Smoother,
+    SmoothingStyle, Transport,
+};
+
+use crate::biquad::{BiquadCoefficients, BiquadState};
 
 const MIN_CUTOFF_HZ: f32 = 20.0;
 const MIN_Q: f32 = 0.1;
 const MAX_Q: f32 = 10.0;
 const DEFAULT_CUTOFF_HZ: f32 = 1_000.0;
 const DEFAULT_Q: f32 = 0.707;
-const MAX_FILTER_CHANNELS: usize = 8;
+/// Channels with filter state before the host reports a layout.
+const DEFAULT_FILTER_CHANNELS: usize = 8;
+/// Samples between coefficient updates while cutoff or resonance is ramping.
+const COEFFICIENT_UPDATE_INTERVAL: usize = 16;
 
 /// Unified filter mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -105,53 +113,41 @@ impl ProcessorParams for UnifiedFilterParams {
             self.resonance_q = *resonance_q;
         }
     }
-}
-
-#[derive(Debug, Clone, Copy, Default)]
-struct BiquadState {
-    x1: f32,
-    x2: f32,
-    y1: f32,
-    y2: f32,
-}
-
-#[derive(Debug, Clone, Copy)]
-struct BiquadCoefficients {
-    b0: f32,
-    b1: f32,
-    b2: f32,
-    a1: f32,
-    a2: f32,
-}
-
-impl BiquadState {
-    #[inline]
-    fn process_sample(&mut self, input: f32, coeffs: BiquadCoefficients) -> f32 {
-        let output = coeffs.b0 * input + coeffs.b1 * self.x1 + coeffs.b2 * self.x2
-            - coeffs.a1 * self.y1
-            - coeffs.a2 * self.y2;
-
-        self.x2 = self.x1;
-        self.x1 = input;
-        self.y2 = self.y1;
-        self.y1 = output;
 
-        output
+    fn param_smoothing() -> &'static [ParamSmoothing] {
+        &[
+            ParamSmoothing {
+                id_suffix: "cutoff_hz",
+                style: SmoothingStyle::Logarithmic(20.0),
+            },
+            ParamSmoothing {
+                id_suffix: "resonance_q",
+                style: SmoothingStyle::Linear(20.0),
+            },
+        ]
     }
 }
 
 /// Unified biquad filter DSP processor.
+///
+/// Cutoff and resonance changes are ramped, with the coefficients recomputed
+/// every few samples while a ramp is in progress, so automation does not click.
 #[derive(Debug)]
 pub struct UnifiedFilterDsp {
     sample_rate_hz: f32,
-    state: [BiquadState; MAX_FILTER_CHANNELS],
+    cutoff_hz: Smoother,
+    resonance_q: Smoother,
+    /// One filter state per channel, sized from the active channel layout.
+    state: Vec<BiquadState>,
 }
 
 impl Default for UnifiedFilterDsp {
     fn default() -> Self {
         Self {
             sample_rate_hz: 44_100.0,
-            state: [BiquadState::default(); MAX_FILTER_CHANNELS],
+            cutoff_hz: Smoother::for_param::<UnifiedFilterParams>("cutoff_hz"),
+            resonance_q: Smoother::for_param::<UnifiedFilterParams>("resonance_q"),
+            state: vec![BiquadState::default(); DEFAULT_FILTER_CHANNELS],
         }
     }
 }
@@ -169,29 +165,58 @@ impl Processor for UnifiedFilterDsp {
             return;
         }
 
-        let coeffs = compute_coefficients(
-            self.sample_rate_hz,
-            params.cutoff_hz,
-            params.resonance_q,
-            params.mode,
-        );
-
-        for (channel_index, channel) in buffer.iter_mut().enumerate() {
-            let state_index = channel_index.min(MAX_FILTER_CHANNELS - 1);
-            let state = &mut self.state[state_index];
-
-            for sample in channel.iter_mut() {
-                *sample = state.process_sample(*sample, coeffs);
+        self.cutoff_hz.set_target(params.cutoff_hz);
+        self.resonance_q.set_target(params.resonance_q);
+
+        let num_samples = buffer.first().map_or(0, |channel| channel.len());
+        let mut start = 0;
+        while start < num_samples {
+            // Without a ramp in progress the whole remainder shares one set
+            // of coefficients.
+            let end = if self.cutoff_hz.is_smoothing() || self.resonance_q.is_smoothing() {
+                let end = (start + COEFFICIENT_UPDATE_INTERVAL).min(num_samples);
+                for _ in start..end {
+                    self.cutoff_hz.next();
+                    self.resonance_q.next();
+                }
+                end
+            } else {
+                num_samples
+            };
+
+            let coeffs = compute_coefficients(
+                self.sample_rate_hz,
+                self.cutoff_hz.current(),
+                self.resonance_q.current(),
+                params.mode,
+            );
+
+            // Channels beyond the configured layout have no state and pass through.
+            for (channel, state) in buffer.iter_mut().zip(self.state.iter_mut()) {
+                let end = end.min(channel.len());
+                for sample in channel[start.min(end)..end].iter_mut() {
+                    *sample = state.process_sample(*sample, coeffs);
+                }
             }
+            start = end;
         }
     }
 
     fn set_sample_rate(&mut self, sample_rate: f32) {
         self.sample_rate_hz = sample_rate.max(1.0);
+        self.cutoff_hz.set_sample_rate(self.sample_rate_hz);
+        self.resonance_q.set_sample_rate(self.sample_rate_hz);
+    }
+
+    fn set_channel_layout(&mut self, layout: ChannelLayout) {
+        let channels = layout.inputs.max(layout.outputs) as usize;
+        self.state.resize(channels.max(1), BiquadState::default());
     }
 
     fn reset(&mut self) {
-        self.state = [BiquadState::default(); MAX_FILTER_CHANNELS];
+        self.state.fill(BiquadState::default());
+        self.cutoff_hz.reset();
+        self.resonance_q.reset();
     }
 }
 
@@ -336,4 +361,65 @@ mod tests {
         let tail = output.last().copied().unwrap_or_default().abs();
         assert!(tail < 0.1);
     }
+
+    #[test]
+    fn channel_layout_sizes_filter_state_per_channel() {
+        let mut filter = UnifiedFilterDsp::default();
+        filter.set_sample_rate(48_000.0);
+        filter.set_channel_layout(ChannelLayout::new(10, 10));
+
+        // Every channel is filtered independently, including those past 8.
+        let mut channels = vec![vec![1.0_f32; 256]; 10];
+        channels[9].fill(0.0);
+        let mut buffer: Vec<&mut [f32]> = channels.iter_mut().map(Vec::as_mut_slice).collect();
+        let params = UnifiedFilterParams {
+            mode: UnifiedFilterMode::HighPass,
+            cutoff_hz: 800.0,
+            resonance_q: 0.707,
+        };
+        filter.process(&mut buffer, &Transport::default(), &params);
+
+        assert!(channels[8][255].abs() < 0.1);
+        assert!(channels[9].iter().all(|&sample| sample == 0.0));
+    }
+
+    #[test]
+    fn cutoff_changes_are_ramped() {
+        let mut filter = UnifiedFilterDsp::default();
+        filter.set_sample_rate(48_000.0);
+        let input = vec![0.0_f32; 480];
+
+        // The first block snaps to the initial cutoff.
+        process_mono(
+            &mut filter,
+            UnifiedFilterMode::LowPass,
+            1_000.0,
+            0.707,
+            &input,
+        );
+        assert_eq!(filter.cutoff_hz.current(), 1_000.0);
+        assert!(!filter.cutoff_hz.is_smoothing());
+
+        // Half of the 20 ms ramp: a logarithmic ramp sits at the geometric mean.
+        process_mono(
+            &mut filter,
+            UnifiedFilterMode::LowPass,
+            10_000.0,
+            0.707,
+            &input,
+        );
+        let halfway = filter.cutoff_hz.current();
+        assert!(filter.cutoff_hz.is_smoothing());
+        assert!((halfway - 3_162.3).abs() < 10.0, "halfway cutoff {halfway}");
+
+        process_mono(
+            &mut filter,
+            UnifiedFilterMode::LowPass,
+            10_000.0,
+            0.707,
+            &input,
+        );
+        assert_eq!(filter.cutoff_hz.current(), 10_000.0);
+        assert!(!filter.cutoff_hz.is_smoothing());
+    }
 }
@@ -57,7 +57,8 @@ pub struct DevProcessorVTable {
     /// # Arguments
     /// - `instance`: Opaque processor pointer from `create`
     /// - `channels`: Pointer to an array of `num_channels` mutable f32 pointers
-    /// - `num_channels`: Number of audio channels (typically 2)
+    /// - `num_channels`: Number of audio channels (at most
+    ///   [`DEV_PROCESSOR_MAX_CHANNELS`])
     /// - `num_samples`: Number of samples per channel
     ///
     /// # Safety
@@ -96,13 +97,52 @@ pub struct DevProcessorVTable {
     /// - Must not be called more than once for the same pointer
     /// - No other vtable function may be called after `drop`
     pub drop: extern "C" fn(instance: *mut c_void),
+
+    /// Process audio with an auxiliary sidechain input.
+    ///
+    /// Same as `process`, plus `num_sidechain_channels` read-only sidechain
+    /// channels of `num_samples` each. `num_sidechain_channels` may be 0, in
+    /// which case `sidechain` may be null.
+    ///
+    /// # Safety
+    /// - Same requirements as `process`
+    /// - `sidechain[0..num_sidechain_channels]` must each point to
+    ///   `num_samples` valid f32s that do not alias `channels`
+    pub process_with_sidechain: extern "C" fn(
+        instance: *mut c_void,
+        channels: *mut *mut f32,
+        num_channels: u32,
+        sidechain: *mut *mut f32,
+        num_sidechain_channels: u32,
+        num_samples: u32,
+    ),
+
+    /// Main input channels of the plugin's default (first declared) layout.
+    ///
+    /// `0` for instruments. New instances start in the default layout.
+    pub default_num_inputs: u32,
+
+    /// Main output channels of the plugin's default (first declared) layout.
+    pub default_num_outputs: u32,
+
+    /// Select the main-bus channel layout.
+    ///
+    /// Like a host, call this before `set_sample_rate` and only when the
+    /// layout changes. Neither count may exceed [`DEV_PROCESSOR_MAX_CHANNELS`].
+    pub set_channel_layout: extern "C" fn(instance: *mut c_void, num_inputs: u32, num_outputs: u32),
 }
 
 /// Current vtable version.
 ///
 /// v2 adds `apply_plain_values` to support block-boundary parameter injection
-/// in dev FFI mode.
-pub const DEV_PROCESSOR_VTABLE_VERSION: u32 = 2;
+/// in dev FFI mode. v3 adds `process_with_sidechain` so `wavecraft start` can
+/// feed a second input source to sidechain-aware processors. v4 adds the
+/// default layout and `set_channel_layout` so callers no longer assume stereo.
+pub const DEV_PROCESSOR_VTABLE_VERSION: u32 = 4;
+
+/// Widest bus (main or sidechain) passed through the vtable, matching 7.1
+/// surround.
+pub const DEV_PROCESSOR_MAX_CHANNELS: u32 = 8;
 
 /// FFI symbol name exported by `wavecraft_plugin!` macro.
 pub const DEV_PROCESSOR_SYMBOL: &[u8] = b"wavecraft_dev_create_processor\0";
@@ -26,19 +26,27 @@ mod methods;
 pub use envelope::{IpcNotification, IpcRequest, IpcResponse, RequestId};
 pub use errors::{
     ERROR_INTERNAL, ERROR_INVALID_PARAMS, ERROR_INVALID_REQUEST, ERROR_METHOD_NOT_FOUND,
-    ERROR_PARAM_NOT_FOUND, ERROR_PARAM_OUT_OF_RANGE, ERROR_PARSE, IpcError,
+    ERROR_PARAM_NOT_FOUND, ERROR_PARAM_OUT_OF_RANGE, ERROR_PARSE, ERROR_PRESET_NOT_FOUND, IpcError,
 };
 pub use methods::{
     AudioDiagnostic, AudioDiagnosticCode, AudioRuntimePhase, AudioRuntimeStatus,
-    GetAllParametersResult, GetAudioStatusResult, GetMeterFrameResult, GetOscilloscopeFrameResult,
-    GetParameterParams, GetParameterResult, METHOD_GET_ALL_PARAMETERS, METHOD_GET_AUDIO_STATUS,
-    METHOD_GET_METER_FRAME, METHOD_GET_OSCILLOSCOPE_FRAME, METHOD_GET_PARAMETER,
-    METHOD_REGISTER_AUDIO, METHOD_REQUEST_RESIZE, METHOD_SET_PARAMETER, MeterFrame,
-    MeterUpdateNotification, NOTIFICATION_AUDIO_STATUS_CHANGED, NOTIFICATION_METER_UPDATE,
-    NOTIFICATION_PARAMETER_CHANGED, OscilloscopeChannelView, OscilloscopeFrame,
-    OscilloscopeTriggerMode, ParameterChangedNotification, ParameterInfo, ParameterType,
-    ProcessorInfo, RegisterAudioParams, RegisterAudioResult, RequestResizeParams,
-    RequestResizeResult, SetParameterParams, SetParameterResult,
+    DeletePresetParams, DeletePresetResult, GetAllParametersResult, GetAudioStatusResult,
+    GetEqResponseParams, GetEqResponseResult, GetMeterFrameParams, GetMeterFrameResult,
+    GetModulationFrameResult, GetModulationResult, GetOscilloscopeFrameParams,
+    GetOscilloscopeFrameResult, GetParameterParams, GetParameterResult, GetSpectrumFrameParams,
+    GetSpectrumFrameResult, ListPresetsResult, LoadPresetParams, LoadPresetResult, LoudnessFrame,
+    METHOD_DELETE_PRESET, METHOD_GET_ALL_PARAMETERS, METHOD_GET_AUDIO_STATUS,
+    METHOD_GET_EQ_RESPONSE, METHOD_GET_METER_FRAME, METHOD_GET_MODULATION,
+    METHOD_GET_MODULATION_FRAME, METHOD_GET_OSCILLOSCOPE_FRAME, METHOD_GET_PARAMETER,
+    METHOD_GET_SPECTRUM_FRAME, METHOD_LIST_PRESETS, METHOD_LOAD_PRESET, METHOD_REGISTER_AUDIO,
+    METHOD_REQUEST_RESIZE, METHOD_SAVE_PRESET, METHOD_SET_MODULATION, METHOD_SET_PARAMETER,
+    MeterFrame, MeterUpdateNotification, NOTIFICATION_AUDIO_STATUS_CHANGED,
+    NOTIFICATION_METER_UPDATE, NOTIFICATION_PARAMETER_CHANGED, NOTIFICATION_SPECTRUM_UPDATE,
+    OscilloscopeChannelView, OscilloscopeFrame, OscilloscopeTriggerMode,
+    ParameterChangedNotification, ParameterInfo, ParameterType, ProcessorInfo, RegisterAudioParams,
+    RegisterAudioResult, RequestResizeParams, RequestResizeResult, SavePresetParams,
+    SavePresetResult, SetModulationParams, SetModulationResult, SetParameterParams,
+    SetParameterResult, SpectrumFrame,
 };
 
 #[cfg(test)]
@@ -144,6 +152,9 @@ mod tests {
                 left_rms: 0.3,
                 right_peak: 0.6,
                 right_rms: 0.4,
+                left_true_peak: 0.55,
+                right_true_peak: 0.65,
+                loudness: None,
             },
         );
 
@@ -152,6 +163,27 @@ mod tests {
         assert!(json.contains("\"left_peak\":0.5"));
     }
 
+    #[test]
+    fn test_meter_frame_loudness_is_optional() {
+        let frame: MeterFrame = serde_json::from_str(
+            r#"{"peak_l":0.5,"peak_r":0.5,"rms_l":0.2,"rms_r":0.2,"timestamp":7}"#,
+        )
+        .expect("frame without loudness fields should parse");
+        assert_eq!(frame.true_peak_l, 0.0);
+        assert!(frame.loudness.is_none());
+
+        let frame = MeterFrame {
+            loudness: Some(LoudnessFrame {
+                momentary_lufs: Some(-14.0),
+                ..LoudnessFrame::default()
+            }),
+            ..frame
+        };
+        let json = serde_json::to_string(&frame).unwrap();
+        assert!(json.contains("\"momentary_lufs\":-14.0"));
+        assert!(json.contains("\"integrated_lufs\":null"));
+    }
+
     #[test]
     fn test_audio_status_serialization() {
         let result = GetAudioStatusResult {
@@ -169,6 +201,24 @@ mod tests {
         assert!(json.contains("\"sample_rate\":44100"));
     }
 
+    #[test]
+    fn test_audio_diagnostic_count_serialization() {
+        let diagnostic = AudioDiagnostic {
+            code: AudioDiagnosticCode::OutputSanitized,
+            message: "Output sanitizer replaced 3 non-finite samples".to_string(),
+            hint: None,
+            count: Some(3),
+        };
+
+        let json = serde_json::to_string(&diagnostic).expect("diagnostic should serialize");
+        assert!(json.contains("\"code\":\"outputSanitized\""));
+        assert!(json.contains("\"count\":3"));
+
+        let parsed: AudioDiagnostic =
+            serde_json::from_str(r#"{"code":"unknown","message":"boom"}"#).unwrap();
+        assert_eq!(parsed.count, None);
+    }
+
     #[test]
     fn test_oscilloscope_frame_serialization() {
         let result = GetOscilloscopeFrameResult {
@@ -187,6 +237,24 @@ mod tests {
         assert!(json.contains("\"trigger_mode\":\"risingZeroCrossing\""));
     }
 
+    #[test]
+    fn test_spectrum_frame_serialization() {
+        let result = GetSpectrumFrameResult {
+            frame: Some(SpectrumFrame {
+                frequencies: vec![100.0, 1000.0],
+                magnitudes_l: vec![-60.0, -6.0],
+                magnitudes_r: vec![-60.0, -12.0],
+                sample_rate: 48000.0,
+                fft_size: 2048,
+                timestamp: 3,
+            }),
+        };
+
+        let json = serde_json::to_string(&result).expect("spectrum result should serialize");
+        assert!(json.contains("\"fft_size\":2048"));
+        assert!(json.contains("\"magnitudes_r\":[-60.0,-12.0]"));
+    }
+
     #[test]
     fn parameter_info_with_variants_serializes_correctly() {
         let info = ParameterInfo {
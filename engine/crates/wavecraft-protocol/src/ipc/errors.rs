@@ -32,6 +32,8 @@ pub const ERROR_INTERNAL: i32 = -32603;
 pub const ERROR_PARAM_NOT_FOUND: i32 = -32000;
 /// Parameter value out of valid range
 pub const ERROR_PARAM_OUT_OF_RANGE: i32 = -32001;
+/// Preset not found
+pub const ERROR_PRESET_NOT_FOUND: i32 = -32002;
 
 impl IpcError {
     /// Create a new error
@@ -118,4 +120,12 @@ impl IpcError {
             format!("Parameter '{}' value {} out of range", id.as_ref(), value),
         )
     }
+
+    /// Preset not found error
+    pub fn preset_not_found(name: impl AsRef<str>) -> Self {
+        Self::new(
+            ERROR_PRESET_NOT_FOUND,
+            format!("Preset not found: {}", name.as_ref()),
+        )
+    }
 }
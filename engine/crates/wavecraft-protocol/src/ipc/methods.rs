@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::modulation::{ModulationConfig, ModulationFrame};
+use crate::presets::{PresetSource, PresetSummary};
+
 // ============================================================================
 // Method-Specific Types
 // ============================================================================
@@ -87,6 +90,9 @@ pub struct ParameterInfo {
 pub struct ProcessorInfo {
     /// Canonical processor ID (snake_case type-derived identifier).
     pub id: String,
+    /// Processing latency in samples reported by a default instance.
+    #[serde(rename = "latencySamples", default)]
+    pub latency_samples: u32,
 }
 
 /// Parameter type discriminator
@@ -98,6 +104,96 @@ pub enum ParameterType {
     Enum,
 }
 
+// ----------------------------------------------------------------------------
+// Presets
+// ----------------------------------------------------------------------------
+
+/// Result of listPresets request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPresetsResult {
+    /// Factory presets first, then user presets, each sorted by name
+    pub presets: Vec<PresetSummary>,
+}
+
+/// Parameters for loadPreset request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadPresetParams {
+    /// Preset name
+    pub name: String,
+    /// Whether to load the factory or the user preset of that name
+    pub source: PresetSource,
+}
+
+/// Result of loadPreset request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadPresetResult {
+    /// Parameter IDs in the preset that this plugin doesn't have
+    pub skipped: Vec<String>,
+}
+
+/// Parameters for savePreset request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavePresetParams {
+    /// Preset name; saving over an existing user preset replaces it
+    pub name: String,
+    /// Optional category
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Optional author credit
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Optional description
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Result of savePreset request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavePresetResult {
+    /// The saved user preset
+    pub preset: PresetSummary,
+}
+
+/// Parameters for deletePreset request (user presets only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletePresetParams {
+    /// Name of the user preset to delete
+    pub name: String,
+}
+
+/// Result of deletePreset request (empty success)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletePresetResult {}
+
+// ----------------------------------------------------------------------------
+// Modulation
+// ----------------------------------------------------------------------------
+
+/// Result of getModulation request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetModulationResult {
+    /// Current modulation sources and routings
+    pub config: ModulationConfig,
+}
+
+/// Parameters for setModulation request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetModulationParams {
+    /// Replacement modulation sources and routings
+    pub config: ModulationConfig,
+}
+
+/// Result of setModulation request (empty success)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetModulationResult {}
+
+/// Result of getModulationFrame request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetModulationFrameResult {
+    /// Latest modulated values, or None if no audio has been processed yet
+    pub frame: Option<ModulationFrame>,
+}
+
 // ----------------------------------------------------------------------------
 // Notification: parameterChanged
 // ----------------------------------------------------------------------------
@@ -125,16 +221,36 @@ pub const METHOD_GET_ALL_PARAMETERS: &str = "getAllParameters";
 pub const METHOD_GET_METER_FRAME: &str = "getMeterFrame";
 /// Method: Get current oscilloscope frame (1024-point waveform)
 pub const METHOD_GET_OSCILLOSCOPE_FRAME: &str = "getOscilloscopeFrame";
+/// Method: Get current spectrum analyzer frame
+pub const METHOD_GET_SPECTRUM_FRAME: &str = "getSpectrumFrame";
+/// Method: Get the magnitude response of a parametric EQ
+pub const METHOD_GET_EQ_RESPONSE: &str = "getEqResponse";
 /// Method: Get current audio runtime status
 pub const METHOD_GET_AUDIO_STATUS: &str = "getAudioStatus";
 /// Method: Request resize of editor window
 pub const METHOD_REQUEST_RESIZE: &str = "requestResize";
+/// Method: List factory and user presets
+pub const METHOD_LIST_PRESETS: &str = "listPresets";
+/// Method: Apply a preset's parameter values
+pub const METHOD_LOAD_PRESET: &str = "loadPreset";
+/// Method: Save current parameter values as a user preset
+pub const METHOD_SAVE_PRESET: &str = "savePreset";
+/// Method: Delete a user preset
+pub const METHOD_DELETE_PRESET: &str = "deletePreset";
+/// Method: Get modulation sources and routings
+pub const METHOD_GET_MODULATION: &str = "getModulation";
+/// Method: Replace modulation sources and routings
+pub const METHOD_SET_MODULATION: &str = "setModulation";
+/// Method: Get the latest modulated parameter values
+pub const METHOD_GET_MODULATION_FRAME: &str = "getModulationFrame";
 /// Method: Register audio client with dev server
 pub const METHOD_REGISTER_AUDIO: &str = "registerAudio";
 /// Notification: Parameter changed (push from Rust to UI)
 pub const NOTIFICATION_PARAMETER_CHANGED: &str = "parameterChanged";
 /// Notification: Meter update from audio binary (push to browser)
 pub const NOTIFICATION_METER_UPDATE: &str = "meterUpdate";
+/// Notification: Spectrum frame from audio binary (push to browser)
+pub const NOTIFICATION_SPECTRUM_UPDATE: &str = "spectrumUpdate";
 /// Notification: Audio runtime status changed
 pub const NOTIFICATION_AUDIO_STATUS_CHANGED: &str = "audioStatusChanged";
 
@@ -144,7 +260,8 @@ pub const NOTIFICATION_AUDIO_STATUS_CHANGED: &str = "audioStatusChanged";
 
 /// Meter frame data for UI visualization.
 ///
-/// All values are in linear scale (not dB).
+/// Peak and RMS values are in linear scale (not dB); loudness values are in
+/// LUFS/LU.
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct MeterFrame {
     /// Left channel peak (linear, 0.0 to 1.0+)
@@ -155,10 +272,43 @@ pub struct MeterFrame {
     pub rms_l: f32,
     /// Right channel RMS (linear, 0.0 to 1.0+)
     pub rms_r: f32,
+    /// Left channel true peak, 4x oversampled (linear, 0.0 to 1.0+)
+    #[serde(default)]
+    pub true_peak_l: f32,
+    /// Right channel true peak, 4x oversampled (linear, 0.0 to 1.0+)
+    #[serde(default)]
+    pub true_peak_r: f32,
+    /// ITU-R BS.1770 loudness, if the producer measures it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<LoudnessFrame>,
     /// Sample timestamp (monotonic)
     pub timestamp: u64,
 }
 
+/// ITU-R BS.1770 / EBU R128 loudness measurements.
+///
+/// Each value is `None` until enough audio has been measured (or, for gated
+/// values, while everything measured so far is below the -70 LUFS gate).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessFrame {
+    /// Momentary loudness over the last 400 ms (LUFS)
+    pub momentary_lufs: Option<f32>,
+    /// Short-term loudness over the last 3 s (LUFS)
+    pub short_term_lufs: Option<f32>,
+    /// Gated integrated loudness since the last reset (LUFS)
+    pub integrated_lufs: Option<f32>,
+    /// Loudness range (EBU Tech 3342) since the last reset (LU)
+    pub loudness_range_lu: Option<f32>,
+}
+
+/// Parameters for getMeterFrame method (optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetMeterFrameParams {
+    /// ID of a named meter tap; omit for the plugin's output meter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tap_id: Option<String>,
+}
+
 /// Result for getMeterFrame method
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetMeterFrameResult {
@@ -203,6 +353,14 @@ pub struct OscilloscopeFrame {
     pub trigger_mode: OscilloscopeTriggerMode,
 }
 
+/// Parameters for getOscilloscopeFrame method (optional).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetOscilloscopeFrameParams {
+    /// ID of a named oscilloscope tap; omit for the plugin's output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tap_id: Option<String>,
+}
+
 /// Result for getOscilloscopeFrame method.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetOscilloscopeFrameResult {
@@ -210,6 +368,69 @@ pub struct GetOscilloscopeFrameResult {
     pub frame: Option<OscilloscopeFrame>,
 }
 
+// ============================================================================
+// Spectrum Types
+// ============================================================================
+
+/// Spectrum analyzer frame data for UI visualization.
+///
+/// Magnitudes are given per log-spaced frequency band in dBFS, so a
+/// full-scale sine reads 0 dB in the band containing it. Also sent as the
+/// `spectrumUpdate` notification payload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumFrame {
+    /// Band centre frequencies in Hz, ascending.
+    pub frequencies: Vec<f32>,
+    /// Left channel band magnitudes in dBFS (one per frequency).
+    pub magnitudes_l: Vec<f32>,
+    /// Right channel band magnitudes in dBFS (one per frequency).
+    pub magnitudes_r: Vec<f32>,
+    /// Sample rate in Hz used to capture the frame.
+    pub sample_rate: f32,
+    /// FFT length in samples.
+    pub fft_size: u32,
+    /// Frame counter (monotonic).
+    pub timestamp: u64,
+}
+
+/// Parameters for getSpectrumFrame method (optional).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetSpectrumFrameParams {
+    /// ID of a named spectrum tap; omit for the plugin's output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tap_id: Option<String>,
+}
+
+/// Result for getSpectrumFrame method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSpectrumFrameResult {
+    /// Latest spectrum frame, or null if no data available.
+    pub frame: Option<SpectrumFrame>,
+}
+
+// ============================================================================
+// EQ Response Types
+// ============================================================================
+
+/// Parameters for getEqResponse method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetEqResponseParams {
+    /// Frequencies in Hz to evaluate the response at.
+    pub frequencies: Vec<f32>,
+    /// Parameter ID prefix of the EQ (`eq` for `eq_band1_gain_db`); omit for
+    /// the plugin's first EQ.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eq_id: Option<String>,
+}
+
+/// Result for getEqResponse method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetEqResponseResult {
+    /// Combined response in dB, one value per requested frequency, or null
+    /// if the plugin has no such EQ.
+    pub magnitudes_db: Option<Vec<f32>>,
+}
+
 // ----------------------------------------------------------------------------
 // getAudioStatus
 // ----------------------------------------------------------------------------
@@ -237,6 +458,8 @@ pub enum AudioDiagnosticCode {
     InputPermissionDenied,
     NoOutputDevice,
     StreamStartFailed,
+    /// The output sanitizer replaced NaN/Inf or clamped runaway samples.
+    OutputSanitized,
     Unknown,
 }
 
@@ -250,6 +473,9 @@ pub struct AudioDiagnostic {
     /// Optional actionable hint for the user.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hint: Option<String>,
+    /// How many times the issue occurred, for repeating runtime events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
 }
 
 /// Current audio runtime status for browser dev mode.
@@ -336,4 +562,13 @@ pub struct MeterUpdateNotification {
     pub right_peak: f32,
     /// Right channel RMS (linear scale)
     pub right_rms: f32,
+    /// Left channel true peak (linear scale)
+    #[serde(default)]
+    pub left_true_peak: f32,
+    /// Right channel true peak (linear scale)
+    #[serde(default)]
+    pub right_true_peak: f32,
+    /// Loudness measurements, if available
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loudness: Option<LoudnessFrame>,
 }
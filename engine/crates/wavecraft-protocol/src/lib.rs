@@ -6,27 +6,48 @@
 pub mod dev_audio_ffi;
 pub mod ipc;
 pub mod macros;
+pub mod modulation;
 pub mod params;
+pub mod presets;
 
 pub use params::{
     PARAM_SPECS, ParamId, ParamSet, ParamSpec, WavecraftParamId, WavecraftParams, db_to_linear,
 };
 
+pub use modulation::{
+    LfoRate, LfoShape, MAX_MODULATION_ROUTINGS, MAX_MODULATION_SOURCES, ModulatedValue,
+    ModulationConfig, ModulationFrame, ModulationPolarity, ModulationRouting, ModulationSource,
+};
+
+pub use presets::{PRESET_FORMAT_VERSION, Preset, PresetCatalog, PresetSource, PresetSummary};
+
 // Re-export dev audio FFI types for convenience
-pub use dev_audio_ffi::{DEV_PROCESSOR_SYMBOL, DEV_PROCESSOR_VTABLE_VERSION, DevProcessorVTable};
+pub use dev_audio_ffi::{
+    DEV_PROCESSOR_MAX_CHANNELS, DEV_PROCESSOR_SYMBOL, DEV_PROCESSOR_VTABLE_VERSION,
+    DevProcessorVTable,
+};
 
 // Re-export key IPC types for convenience
 pub use ipc::{
-    AudioDiagnostic, AudioDiagnosticCode, AudioRuntimePhase, AudioRuntimeStatus, ERROR_INTERNAL,
-    ERROR_INVALID_PARAMS, ERROR_INVALID_REQUEST, ERROR_METHOD_NOT_FOUND, ERROR_PARAM_NOT_FOUND,
-    ERROR_PARAM_OUT_OF_RANGE, ERROR_PARSE, GetAllParametersResult, GetAudioStatusResult,
-    GetMeterFrameResult, GetOscilloscopeFrameResult, GetParameterParams, GetParameterResult,
-    IpcError, IpcNotification, IpcRequest, IpcResponse, METHOD_GET_ALL_PARAMETERS,
-    METHOD_GET_AUDIO_STATUS, METHOD_GET_METER_FRAME, METHOD_GET_OSCILLOSCOPE_FRAME,
-    METHOD_GET_PARAMETER, METHOD_REGISTER_AUDIO, METHOD_REQUEST_RESIZE, METHOD_SET_PARAMETER,
-    MeterFrame, MeterUpdateNotification, NOTIFICATION_AUDIO_STATUS_CHANGED,
-    NOTIFICATION_METER_UPDATE, NOTIFICATION_PARAMETER_CHANGED, OscilloscopeChannelView,
+    AudioDiagnostic, AudioDiagnosticCode, AudioRuntimePhase, AudioRuntimeStatus,
+    DeletePresetParams, DeletePresetResult, ERROR_INTERNAL, ERROR_INVALID_PARAMS,
+    ERROR_INVALID_REQUEST, ERROR_METHOD_NOT_FOUND, ERROR_PARAM_NOT_FOUND, ERROR_PARAM_OUT_OF_RANGE,
+    ERROR_PARSE, ERROR_PRESET_NOT_FOUND, GetAllParametersResult, GetAudioStatusResult,
+    GetEqResponseParams, GetEqResponseResult, GetMeterFrameParams, GetMeterFrameResult,
+    GetModulationFrameResult, GetModulationResult, GetOscilloscopeFrameParams,
+    GetOscilloscopeFrameResult, GetParameterParams, GetParameterResult, GetSpectrumFrameParams,
+    GetSpectrumFrameResult, IpcError, IpcNotification, IpcRequest, IpcResponse, ListPresetsResult,
+    LoadPresetParams, LoadPresetResult, LoudnessFrame, METHOD_DELETE_PRESET,
+    METHOD_GET_ALL_PARAMETERS, METHOD_GET_AUDIO_STATUS, METHOD_GET_EQ_RESPONSE,
+    METHOD_GET_METER_FRAME, METHOD_GET_MODULATION, METHOD_GET_MODULATION_FRAME,
+    METHOD_GET_OSCILLOSCOPE_FRAME, METHOD_GET_PARAMETER, METHOD_GET_SPECTRUM_FRAME,
+    METHOD_LIST_PRESETS, METHOD_LOAD_PRESET, METHOD_REGISTER_AUDIO, METHOD_REQUEST_RESIZE,
+    METHOD_SAVE_PRESET, METHOD_SET_MODULATION, METHOD_SET_PARAMETER, MeterFrame,
+    MeterUpdateNotification, NOTIFICATION_AUDIO_STATUS_CHANGED, NOTIFICATION_METER_UPDATE,
+    NOTIFICATION_PARAMETER_CHANGED, NOTIFICATION_SPECTRUM_UPDATE, OscilloscopeChannelView,
     OscilloscopeFrame, OscilloscopeTriggerMode, ParameterChangedNotification, ParameterInfo,
     ParameterType, ProcessorInfo, RegisterAudioParams, RegisterAudioResult, RequestId,
-    RequestResizeParams, RequestResizeResult, SetParameterParams, SetParameterResult,
+    RequestResizeParams, RequestResizeResult, SavePresetParams, SavePresetResult,
+    SetModulationParams, SetModulationResult, SetParameterParams, SetParameterResult,
+    SpectrumFrame,
 };
@@ -0,0 +1,255 @@
+//! Modulation routing contract shared by the DSP runtime, the plugin state
+//! and the modulation IPC methods.
+//!
+//! A [`ModulationConfig`] lists modulation sources (LFOs, envelope followers)
+//! and routings from a source to a parameter ID. It is saved with the plugin
+//! state and edited from the UI with `getModulation`/`setModulation`:
+//!
+//! ```json
+//! {
+//!   "sources": [
+//!     { "type": "lfo", "shape": "sine", "rate": { "type": "hz", "hz": 2.0 } },
+//!     { "type": "envelopeFollower", "attack_ms": 5.0, "release_ms": 120.0 }
+//!   ],
+//!   "routings": [
+//!     { "source": 0, "target": "filter_cutoff", "depth": 0.25, "polarity": "bipolar" }
+//!   ]
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of modulation sources in a [`ModulationConfig`].
+pub const MAX_MODULATION_SOURCES: usize = 8;
+
+/// Maximum number of routings in a [`ModulationConfig`].
+pub const MAX_MODULATION_ROUTINGS: usize = 32;
+
+/// LFO waveform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LfoShape {
+    #[default]
+    Sine,
+    Triangle,
+    /// Rising ramp.
+    Saw,
+    Square,
+    /// A new random value at the start of each cycle.
+    SampleAndHold,
+}
+
+/// LFO speed, free-running or synced to the host tempo.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LfoRate {
+    /// Cycles per second.
+    Hz { hz: f32 },
+    /// Cycle length in quarter notes (`1.0` = one beat, `0.5` = an eighth
+    /// note, `4.0` = a bar of 4/4). Follows the host tempo and, while the
+    /// host is playing, its song position.
+    Beats { beats: f32 },
+}
+
+impl Default for LfoRate {
+    fn default() -> Self {
+        Self::Hz { hz: 1.0 }
+    }
+}
+
+/// A modulation source.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ModulationSource {
+    /// Low-frequency oscillator with output in `-1.0..=1.0`.
+    Lfo {
+        #[serde(default)]
+        shape: LfoShape,
+        #[serde(default)]
+        rate: LfoRate,
+        /// Start phase as a fraction of a cycle (`0.0..1.0`).
+        #[serde(default)]
+        phase: f32,
+    },
+    /// Follows the level of the plugin's main input, with output in
+    /// `0.0..=1.0`.
+    EnvelopeFollower {
+        /// Time to rise towards a louder level, in milliseconds.
+        attack_ms: f32,
+        /// Time to fall towards a quieter level, in milliseconds.
+        release_ms: f32,
+    },
+}
+
+/// How a source's output is mapped before scaling by the routing depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModulationPolarity {
+    /// Swings around the parameter's value (`-1.0..=1.0`).
+    #[default]
+    Bipolar,
+    /// Only moves away from the parameter's value in the direction of the
+    /// depth (`0.0..=1.0`).
+    Unipolar,
+}
+
+/// Routes a source to a parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModulationRouting {
+    /// Index into [`ModulationConfig::sources`].
+    pub source: usize,
+    /// Parameter ID to modulate.
+    pub target: String,
+    /// Modulation amount as a fraction of the parameter's range
+    /// (`-1.0..=1.0`; negative values invert the source).
+    pub depth: f32,
+    #[serde(default)]
+    pub polarity: ModulationPolarity,
+}
+
+/// Modulation sources and their routings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModulationConfig {
+    #[serde(default)]
+    pub sources: Vec<ModulationSource>,
+    #[serde(default)]
+    pub routings: Vec<ModulationRouting>,
+}
+
+impl ModulationConfig {
+    /// Checks limits, source indices and value ranges.
+    ///
+    /// Routing targets aren't checked here since this crate doesn't know the
+    /// plugin's parameters.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sources.len() > MAX_MODULATION_SOURCES {
+            return Err(format!(
+                "at most {MAX_MODULATION_SOURCES} modulation sources are supported"
+            ));
+        }
+        if self.routings.len() > MAX_MODULATION_ROUTINGS {
+            return Err(format!(
+                "at most {MAX_MODULATION_ROUTINGS} modulation routings are supported"
+            ));
+        }
+
+        for (index, source) in self.sources.iter().enumerate() {
+            let valid = match *source {
+                ModulationSource::Lfo { rate, phase, .. } => {
+                    let rate = match rate {
+                        LfoRate::Hz { hz } => hz,
+                        LfoRate::Beats { beats } => beats,
+                    };
+                    rate.is_finite() && rate > 0.0 && (0.0..1.0).contains(&phase)
+                }
+                ModulationSource::EnvelopeFollower {
+                    attack_ms,
+                    release_ms,
+                } => {
+                    attack_ms.is_finite()
+                        && attack_ms >= 0.0
+                        && release_ms.is_finite()
+                        && release_ms >= 0.0
+                }
+            };
+            if !valid {
+                return Err(format!("modulation source {index} has an invalid setting"));
+            }
+        }
+
+        for routing in &self.routings {
+            if routing.source >= self.sources.len() {
+                return Err(format!(
+                    "routing to '{}' uses unknown source {}",
+                    routing.target, routing.source
+                ));
+            }
+            if !(-1.0..=1.0).contains(&routing.depth) {
+                return Err(format!(
+                    "routing to '{}' has depth {} outside -1.0..=1.0",
+                    routing.target, routing.depth
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Current value of a modulated parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModulatedValue {
+    /// Value the processor sees, in the parameter's declared range.
+    pub value: f32,
+    /// Modulation offset added to the parameter's own value.
+    pub offset: f32,
+}
+
+/// Snapshot of modulated parameter values for UI modulation rings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModulationFrame {
+    /// Modulated parameters keyed by parameter ID. Parameters without a
+    /// routing are omitted.
+    pub values: BTreeMap<String, ModulatedValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lfo() -> ModulationSource {
+        ModulationSource::Lfo {
+            shape: LfoShape::Sine,
+            rate: LfoRate::Hz { hz: 2.0 },
+            phase: 0.0,
+        }
+    }
+
+    #[test]
+    fn config_parses_documented_json() {
+        let config: ModulationConfig = serde_json::from_str(
+            r#"{
+                "sources": [
+                    { "type": "lfo", "shape": "sine", "rate": { "type": "beats", "beats": 0.5 } },
+                    { "type": "envelopeFollower", "attack_ms": 5.0, "release_ms": 120.0 }
+                ],
+                "routings": [
+                    { "source": 1, "target": "filter_cutoff", "depth": -0.25 }
+                ]
+            }"#,
+        )
+        .expect("config should parse");
+
+        assert_eq!(config.sources.len(), 2);
+        assert_eq!(config.routings[0].polarity, ModulationPolarity::Bipolar);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_routings() {
+        let mut config = ModulationConfig {
+            sources: vec![lfo()],
+            routings: vec![ModulationRouting {
+                source: 1,
+                target: "gain".to_string(),
+                depth: 0.5,
+                polarity: ModulationPolarity::Unipolar,
+            }],
+        };
+        assert!(config.validate().unwrap_err().contains("unknown source"));
+
+        config.routings[0].source = 0;
+        config.routings[0].depth = 1.5;
+        assert!(config.validate().unwrap_err().contains("outside"));
+
+        config.routings[0].depth = 1.0;
+        config.sources[0] = ModulationSource::Lfo {
+            shape: LfoShape::Square,
+            rate: LfoRate::Hz { hz: 0.0 },
+            phase: 0.0,
+        };
+        assert!(config.validate().unwrap_err().contains("invalid setting"));
+    }
+}
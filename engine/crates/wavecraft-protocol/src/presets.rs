@@ -0,0 +1,142 @@
+//! Preset file format shared by factory presets, user presets and the IPC
+//! preset methods.
+//!
+//! A preset is a JSON document mapping parameter IDs to values in each
+//! parameter's declared range, plus descriptive metadata:
+//!
+//! ```json
+//! {
+//!   "format_version": 1,
+//!   "name": "Warm Drive",
+//!   "plugin_version": "0.3.0",
+//!   "author": "Sound Design",
+//!   "category": "Drive",
+//!   "parameters": { "input_gain_level": -6.0, "saturator_drive_db": 12.0 }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Current version of the preset file format.
+pub const PRESET_FORMAT_VERSION: u32 = 1;
+
+fn default_format_version() -> u32 {
+    PRESET_FORMAT_VERSION
+}
+
+/// A named snapshot of parameter values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    /// Preset file format version (see [`PRESET_FORMAT_VERSION`]).
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
+    /// Display name, unique within its source.
+    pub name: String,
+    /// Version of the plugin that saved the preset.
+    #[serde(default)]
+    pub plugin_version: String,
+    /// Optional author credit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Optional category used to group presets in the UI (e.g. "Bass").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Optional free-form description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Parameter values keyed by parameter ID, in each parameter's declared
+    /// range. Parameters missing here keep their current value on load.
+    pub parameters: BTreeMap<String, f32>,
+}
+
+/// Where a preset comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PresetSource {
+    /// Shipped with the plugin via `wavecraft_plugin!`; read-only.
+    Factory,
+    /// Saved by the user.
+    User,
+}
+
+/// Preset listing entry returned by `listPresets`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresetSummary {
+    /// Preset name.
+    pub name: String,
+    /// Factory or user preset.
+    pub source: PresetSource,
+    /// Optional category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Optional author credit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+impl Preset {
+    /// Summarize this preset for a listing.
+    pub fn summary(&self, source: PresetSource) -> PresetSummary {
+        PresetSummary {
+            name: self.name.clone(),
+            source,
+            category: self.category.clone(),
+            author: self.author.clone(),
+        }
+    }
+}
+
+/// Presets a plugin exposes to the editor and to `wavecraft start`.
+///
+/// Returned by the `wavecraft_get_presets_json` FFI export generated by
+/// `wavecraft_plugin!`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PresetCatalog {
+    /// Plugin version stamped into newly saved presets.
+    #[serde(default)]
+    pub plugin_version: String,
+    /// Directory user presets are stored in, if one could be determined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_dir: Option<String>,
+    /// Factory presets embedded in the plugin.
+    #[serde(default)]
+    pub factory: Vec<Preset>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_preset_defaults_metadata() {
+        let preset: Preset = serde_json::from_str(r#"{"name":"Init","parameters":{"gain":0.5}}"#)
+            .expect("minimal preset should parse");
+
+        assert_eq!(preset.format_version, PRESET_FORMAT_VERSION);
+        assert!(preset.plugin_version.is_empty());
+        assert_eq!(preset.category, None);
+        assert_eq!(preset.parameters.get("gain"), Some(&0.5));
+    }
+
+    #[test]
+    fn preset_round_trips_and_omits_empty_metadata() {
+        let preset = Preset {
+            format_version: PRESET_FORMAT_VERSION,
+            name: "Warm".to_string(),
+            plugin_version: "1.2.0".to_string(),
+            author: None,
+            category: Some("Drive".to_string()),
+            description: None,
+            parameters: BTreeMap::from([("drive".to_string(), 12.0)]),
+        };
+
+        let json = serde_json::to_string(&preset).expect("preset should serialize");
+        assert!(!json.contains("author"));
+        assert!(json.contains("\"category\":\"Drive\""));
+
+        let parsed: Preset = serde_json::from_str(&json).expect("preset should parse");
+        assert_eq!(parsed, preset);
+    }
+}
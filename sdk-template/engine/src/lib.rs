@@ -18,6 +18,7 @@ use processors::ExampleProcessor;
 wavecraft_processor!(InputTrim => Gain);
 wavecraft_processor!(ToneFilter => Filter);
 wavecraft_processor!(SoftClip => Saturator);
+wavecraft_processor!(Room => Reverb);
 wavecraft_processor!(OutputGain => Gain);
 
 // ---------------------------------------------------------------------------
@@ -32,6 +33,7 @@ wavecraft_plugin! {
         ExampleProcessor,
         ToneFilter,
         SoftClip,
+        Room,
         OutputGain,
         OscilloscopeTap,
     ],
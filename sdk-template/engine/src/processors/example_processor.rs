@@ -39,3 +39,56 @@ impl Processor for ExampleProcessor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wavecraft::testing::{self, measure, signals, Renderer};
+
+    const SAMPLE_RATE: f32 = 48_000.0;
+
+    #[test]
+    fn passes_audio_through_unchanged() {
+        let input = signals::sine(1_000.0, SAMPLE_RATE, 4_800);
+        let output = Renderer::new(SAMPLE_RATE).render(
+            &mut ExampleProcessor,
+            &ExampleProcessorParams,
+            &input,
+        );
+
+        let response = measure::frequency_response(&input, &output[0], 1_000.0, SAMPLE_RATE);
+        assert!(response.gain_db.abs() < 0.01, "{response:?}");
+        assert_eq!(measure::detect_latency(&input, &output[0], 64), Some(0));
+    }
+
+    #[test]
+    fn output_does_not_depend_on_block_size() {
+        let input = signals::noise(8_192, 1);
+        testing::assert_block_size_invariant(
+            ExampleProcessor::default,
+            &ExampleProcessorParams,
+            SAMPLE_RATE,
+            &input,
+            testing::BLOCK_SIZES,
+            1e-6,
+        );
+    }
+
+    // Compares against tests/golden/example_processor.json and fails if it
+    // is missing. After an intentional DSP change, rerun with
+    // WAVECRAFT_UPDATE_GOLDEN=1 to record the new output, and commit it.
+    #[test]
+    fn matches_golden_output() {
+        let input = signals::sweep(20.0, 20_000.0, SAMPLE_RATE, 2_048);
+        let output = Renderer::new(SAMPLE_RATE).render(
+            &mut ExampleProcessor,
+            &ExampleProcessorParams,
+            &input,
+        );
+        testing::assert_golden(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/example_processor.json"),
+            &output,
+            1e-5,
+        );
+    }
+}
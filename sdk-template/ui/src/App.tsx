@@ -53,6 +53,7 @@ export function App(): JSX.Element {
             <SmartProcessor id="input_trim" title="Input Trim" hideWhenNotInSignalChain />
             <SmartProcessor id="tone_filter" title="Tone Filter" hideWhenNotInSignalChain />
             <SmartProcessor id="soft_clip" title="Soft Clip" hideWhenNotInSignalChain />
+            <SmartProcessor id="room" title="Room" hideWhenNotInSignalChain />
             <ExampleProcessor hideWhenNotInSignalChain />
             <SmartProcessor id="output_gain" title="Output Gain" hideWhenNotInSignalChain />
             <OscilloscopeProcessor hideWhenNotInSignalChain />
@@ -15,6 +15,7 @@ declare global {
     oscillator: true;
     oscilloscope_tap: true;
     output_gain: true;
+    room: true;
     soft_clip: true;
     tone_filter: true;
   }
@@ -26,6 +27,7 @@ const PROCESSOR_IDS = [
   'oscillator',
   'oscilloscope_tap',
   'output_gain',
+  'room',
   'soft_clip',
   'tone_filter',
 ] as const;
@@ -41,6 +41,26 @@ describe('ConnectionStatus', () => {
     expect(screen.getByText('(noOutputDevice)')).toBeInTheDocument();
   });
 
+  it('shows the occurrence count of repeating diagnostics', () => {
+    render(
+      <ConnectionStatus
+        connected
+        transport="websocket"
+        phase="degraded"
+        diagnostic={{
+          code: 'outputSanitized',
+          message: 'Output sanitizer replaced 12 NaN/Inf sample(s)',
+          count: 12,
+        }}
+        isReady={false}
+        isDegraded
+      />
+    );
+
+    expect(screen.getByText('(outputSanitized)')).toBeInTheDocument();
+    expect(screen.getByText('×12')).toBeInTheDocument();
+  });
+
   it('shows connecting and initializing labels while startup is in progress', () => {
     render(
       <ConnectionStatus
@@ -83,6 +83,9 @@ export function ConnectionStatus({
         {isDegraded && diagnostic?.code && (
           <span className="text-xs opacity-70">({diagnostic.code})</span>
         )}
+        {isDegraded && diagnostic?.count !== undefined && (
+          <span className="text-xs opacity-70">×{diagnostic.count}</span>
+        )}
       </div>
     </div>
   );
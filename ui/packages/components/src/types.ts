@@ -34,6 +34,7 @@ export interface AudioDiagnostic {
   code: string;
   message: string;
   hint?: string;
+  count?: number;
 }
 
 export interface OscilloscopeFrame {
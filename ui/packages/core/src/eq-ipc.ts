@@ -0,0 +1,27 @@
+/**
+ * Parametric EQ response API for drawing EQ curves (IPC-based)
+ */
+
+import { IpcBridge } from './ipc/IpcBridge';
+import { METHOD_GET_EQ_RESPONSE } from './types/ipc';
+import type { GetEqResponseParams, GetEqResponseResult } from './types/eq';
+
+/**
+ * Get the combined magnitude response of a parametric EQ in dB
+ *
+ * Evaluated by the engine from the EQ's current parameter values with the
+ * same filter design as the audio path.
+ *
+ * @param frequencies - Frequencies in Hz, e.g. log-spaced across the plot
+ * @param eqId - Parameter ID prefix of the EQ; omit for the plugin's first EQ
+ * @returns One dB value per frequency, or null if the plugin has no such EQ
+ */
+export async function getEqResponse(
+  frequencies: number[],
+  eqId?: string
+): Promise<number[] | null> {
+  const bridge = IpcBridge.getInstance();
+  const params: GetEqResponseParams = eqId ? { frequencies, eq_id: eqId } : { frequencies };
+  const result = await bridge.invoke<GetEqResponseResult>(METHOD_GET_EQ_RESPONSE, params);
+  return result.magnitudes_db;
+}
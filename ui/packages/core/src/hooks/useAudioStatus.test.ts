@@ -46,6 +46,42 @@ describe('useAudioStatus', () => {
     });
   });
 
+  it('re-fetches status to pick up runtime diagnostics', async () => {
+    vi.useFakeTimers();
+    try {
+      mockTransport.setConnected(true);
+
+      const getAudioStatus = vi
+        .spyOn(IpcBridge.prototype, 'getAudioStatus')
+        .mockResolvedValue(null);
+
+      const { result } = renderHook(() => useAudioStatus());
+      await act(async () => {
+        await Promise.resolve();
+      });
+      const initialCalls = getAudioStatus.mock.calls.length;
+
+      getAudioStatus.mockResolvedValue({
+        phase: 'degraded',
+        diagnostic: {
+          code: 'outputSanitized',
+          message: 'Output sanitizer replaced 3 NaN/Inf sample(s)',
+          count: 3,
+        },
+        updated_at_ms: 456,
+      });
+      await act(async () => {
+        await vi.advanceTimersByTimeAsync(1000);
+      });
+
+      expect(getAudioStatus.mock.calls.length).toBeGreaterThan(initialCalls);
+      expect(result.current.phase).toBe('degraded');
+      expect(result.current.diagnostic?.count).toBe(3);
+    } finally {
+      vi.useRealTimers();
+    }
+  });
+
   it('surfaces explicit failed status when getAudioStatus fails while connected', async () => {
     mockTransport.setConnected(true);
 
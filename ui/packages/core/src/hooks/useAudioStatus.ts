@@ -3,6 +3,8 @@
  *
  * `useConnectionStatus()` reports transport connectivity only. This hook adds
  * runtime audio phase and diagnostics from `getAudioStatus` + `audioStatusChanged`.
+ * Status is also re-fetched periodically, because plugin hosts report runtime
+ * diagnostics (e.g. sanitized output) without pushing notifications.
  */
 
 import { useMemo, useState } from 'react';
@@ -17,6 +19,8 @@ import {
 } from '../types/ipc';
 import { _usePollingSubscription } from './_usePollingSubscription';
 
+const STATUS_REFRESH_INTERVAL_MS = 1000;
+
 export interface UseAudioStatusResult {
   /** Runtime audio phase. `null` means status unavailable/disconnected. */
   phase: AudioRuntimePhase | null;
@@ -92,9 +96,11 @@ export function useAudioStatus(): UseAudioStatusResult {
 
     // Initial fetch for already-connected transports.
     void fetchStatus();
+    const intervalId = setInterval(() => void fetchStatus(), STATUS_REFRESH_INTERVAL_MS);
 
     return () => {
       mounted = false;
+      clearInterval(intervalId);
       unsubscribeConnection();
       unsubscribeStatus();
     };
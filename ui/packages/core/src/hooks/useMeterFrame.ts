@@ -5,27 +5,32 @@
 import { useState } from 'react';
 import { IpcBridge } from '../ipc/IpcBridge';
 import { IpcMethods } from '../ipc/constants';
-import type { MeterFrame, GetMeterFrameResult } from '../types/metering';
+import type { MeterFrame, GetMeterFrameParams, GetMeterFrameResult } from '../types/metering';
 import { _usePollingSubscription } from './_usePollingSubscription';
 
 /**
  * Hook to poll meter frames at a specified interval
  *
  * @param intervalMs - Polling interval in milliseconds (default: 50ms = 20fps)
+ * @param tapId - ID of a named meter tap; omit for the output meter
  * @returns Current meter frame or null if not available
  */
-export function useMeterFrame(intervalMs = 50): MeterFrame | null {
+export function useMeterFrame(intervalMs = 50, tapId?: string): MeterFrame | null {
   const [frame, setFrame] = useState<MeterFrame | null>(null);
 
   _usePollingSubscription(() => {
     let isMounted = true;
     const bridge = IpcBridge.getInstance();
+    const params: GetMeterFrameParams | undefined = tapId ? { tap_id: tapId } : undefined;
 
     async function fetchFrame(): Promise<void> {
       if (!bridge.isConnected()) return;
 
       try {
-        const result = await bridge.invoke<GetMeterFrameResult>(IpcMethods.GET_METER_FRAME);
+        const result = await bridge.invoke<GetMeterFrameResult>(
+          IpcMethods.GET_METER_FRAME,
+          params
+        );
         if (isMounted && result.frame) {
           setFrame(result.frame);
         }
@@ -44,7 +49,7 @@ export function useMeterFrame(intervalMs = 50): MeterFrame | null {
       isMounted = false;
       clearInterval(intervalId);
     };
-  }, [intervalMs]);
+  }, [intervalMs, tapId]);
 
   return frame;
 }
@@ -0,0 +1,49 @@
+/**
+ * useModulationFrame - Hook for polling modulated parameter values
+ */
+
+import { useState } from 'react';
+import { IpcBridge } from '../ipc/IpcBridge';
+import { IpcMethods } from '../ipc/constants';
+import type { GetModulationFrameResult, ModulationFrame } from '../types/modulation';
+import { _usePollingSubscription } from './_usePollingSubscription';
+
+/**
+ * Hook to poll modulated parameter values, e.g. to draw modulation rings
+ *
+ * @param intervalMs - Polling interval in milliseconds (default: 50ms = 20fps)
+ * @returns Current modulation frame or null if not available
+ */
+export function useModulationFrame(intervalMs = 50): ModulationFrame | null {
+  const [frame, setFrame] = useState<ModulationFrame | null>(null);
+
+  _usePollingSubscription(() => {
+    let isMounted = true;
+    const bridge = IpcBridge.getInstance();
+
+    async function fetchFrame(): Promise<void> {
+      if (!bridge.isConnected()) return;
+
+      try {
+        const result = await bridge.invoke<GetModulationFrameResult>(
+          IpcMethods.GET_MODULATION_FRAME
+        );
+        if (isMounted && result.frame) {
+          setFrame(result.frame);
+        }
+      } catch {
+        // Silently ignore modulation fetch errors
+      }
+    }
+
+    fetchFrame();
+    const intervalId = setInterval(fetchFrame, intervalMs);
+
+    return (): void => {
+      isMounted = false;
+      clearInterval(intervalId);
+    };
+  }, [intervalMs]);
+
+  return frame;
+}
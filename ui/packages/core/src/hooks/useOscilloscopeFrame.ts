@@ -5,15 +5,21 @@
 import { useState } from 'react';
 import { IpcBridge } from '../ipc/IpcBridge';
 import { METHOD_GET_OSCILLOSCOPE_FRAME } from '../types/ipc';
-import type { GetOscilloscopeFrameResult, OscilloscopeFrame } from '../types/oscilloscope';
+import type {
+  GetOscilloscopeFrameParams,
+  GetOscilloscopeFrameResult,
+  OscilloscopeFrame,
+} from '../types/oscilloscope';
 import { _usePollingSubscription } from './_usePollingSubscription';
 
 /**
  * Hook to poll oscilloscope frames on the browser animation frame cadence.
  *
  * Uses single in-flight request semantics to avoid transport backpressure.
+ *
+ * @param tapId - ID of a named oscilloscope tap; omit for the plugin output
  */
-export function useOscilloscopeFrame(): OscilloscopeFrame | null {
+export function useOscilloscopeFrame(tapId?: string): OscilloscopeFrame | null {
   const [frame, setFrame] = useState<OscilloscopeFrame | null>(null);
 
   _usePollingSubscription(() => {
@@ -21,6 +27,7 @@ export function useOscilloscopeFrame(): OscilloscopeFrame | null {
     let inFlight = false;
     let rafId: number | null = null;
     const bridge = IpcBridge.getInstance();
+    const params: GetOscilloscopeFrameParams | undefined = tapId ? { tap_id: tapId } : undefined;
 
     const tick = async (): Promise<void> => {
       if (!isMounted) {
@@ -31,7 +38,8 @@ export function useOscilloscopeFrame(): OscilloscopeFrame | null {
         inFlight = true;
         try {
           const result = await bridge.invoke<GetOscilloscopeFrameResult>(
-            METHOD_GET_OSCILLOSCOPE_FRAME
+            METHOD_GET_OSCILLOSCOPE_FRAME,
+            params
           );
           if (isMounted && result.frame) {
             setFrame(result.frame);
@@ -56,7 +64,7 @@ export function useOscilloscopeFrame(): OscilloscopeFrame | null {
         cancelAnimationFrame(rafId);
       }
     };
-  }, []);
+  }, [tapId]);
 
   return frame;
 }
@@ -0,0 +1,70 @@
+/**
+ * useSpectrumFrame - Hook for requestAnimationFrame-driven spectrum polling
+ */
+
+import { useState } from 'react';
+import { IpcBridge } from '../ipc/IpcBridge';
+import { METHOD_GET_SPECTRUM_FRAME } from '../types/ipc';
+import type {
+  GetSpectrumFrameParams,
+  GetSpectrumFrameResult,
+  SpectrumFrame,
+} from '../types/spectrum';
+import { _usePollingSubscription } from './_usePollingSubscription';
+
+/**
+ * Hook to poll spectrum frames on the browser animation frame cadence.
+ *
+ * Uses single in-flight request semantics to avoid transport backpressure.
+ *
+ * @param tapId - ID of a named spectrum tap; omit for the plugin output
+ */
+export function useSpectrumFrame(tapId?: string): SpectrumFrame | null {
+  const [frame, setFrame] = useState<SpectrumFrame | null>(null);
+
+  _usePollingSubscription(() => {
+    let isMounted = true;
+    let inFlight = false;
+    let rafId: number | null = null;
+    const bridge = IpcBridge.getInstance();
+    const params: GetSpectrumFrameParams | undefined = tapId ? { tap_id: tapId } : undefined;
+
+    const tick = async (): Promise<void> => {
+      if (!isMounted) {
+        return;
+      }
+
+      if (bridge.isConnected() && !inFlight) {
+        inFlight = true;
+        try {
+          const result = await bridge.invoke<GetSpectrumFrameResult>(
+            METHOD_GET_SPECTRUM_FRAME,
+            params
+          );
+          if (isMounted && result.frame) {
+            setFrame(result.frame);
+          }
+        } catch {
+          // Silently ignore spectrum fetch errors
+        } finally {
+          inFlight = false;
+        }
+      }
+
+      rafId = requestAnimationFrame(() => {
+        void tick();
+      });
+    };
+
+    void tick();
+
+    return (): void => {
+      isMounted = false;
+      if (rafId !== null) {
+        cancelAnimationFrame(rafId);
+      }
+    };
+  }, [tapId]);
+
+  return frame;
+}
@@ -50,17 +50,60 @@ export type {
 export type {
   // Metering types
   MeterFrame,
+  LoudnessFrame,
+  GetMeterFrameParams,
   GetMeterFrameResult,
 } from './types/metering';
 
+export type {
+  // Preset types
+  PresetSource,
+  PresetSummary,
+  ListPresetsResult,
+  LoadPresetParams,
+  LoadPresetResult,
+  SavePresetParams,
+  SavePresetResult,
+  DeletePresetParams,
+} from './types/presets';
+
+export type {
+  // Modulation types
+  LfoShape,
+  LfoRate,
+  ModulationSource,
+  ModulationPolarity,
+  ModulationRouting,
+  ModulationConfig,
+  ModulatedValue,
+  ModulationFrame,
+  GetModulationResult,
+  SetModulationParams,
+  GetModulationFrameResult,
+} from './types/modulation';
+
 export type {
   // Oscilloscope types
   OscilloscopeTriggerMode,
   OscilloscopeChannelView,
   OscilloscopeFrame,
+  GetOscilloscopeFrameParams,
   GetOscilloscopeFrameResult,
 } from './types/oscilloscope';
 
+export type {
+  // Spectrum types
+  SpectrumFrame,
+  GetSpectrumFrameParams,
+  GetSpectrumFrameResult,
+} from './types/spectrum';
+
+export type {
+  // Parametric EQ types
+  GetEqResponseParams,
+  GetEqResponseResult,
+} from './types/eq';
+
 // IPC error codes
 export {
   ERROR_PARSE,
@@ -70,9 +113,13 @@ export {
   ERROR_INTERNAL,
   ERROR_PARAM_NOT_FOUND,
   ERROR_PARAM_OUT_OF_RANGE,
+  ERROR_PRESET_NOT_FOUND,
   METHOD_GET_AUDIO_STATUS,
   METHOD_GET_OSCILLOSCOPE_FRAME,
+  METHOD_GET_SPECTRUM_FRAME,
+  METHOD_GET_EQ_RESPONSE,
   NOTIFICATION_AUDIO_STATUS_CHANGED,
+  NOTIFICATION_SPECTRUM_UPDATE,
   isAudioRuntimeStatus,
   isIpcResponse,
   isIpcNotification,
@@ -123,7 +170,9 @@ export { useLatencyMonitor } from './hooks/useLatencyMonitor';
 export type { UseLatencyMonitorResult } from './hooks/useLatencyMonitor';
 
 export { useMeterFrame } from './hooks/useMeterFrame';
+export { useModulationFrame } from './hooks/useModulationFrame';
 export { useOscilloscopeFrame } from './hooks/useOscilloscopeFrame';
+export { useSpectrumFrame } from './hooks/useSpectrumFrame';
 export { useAudioStatus } from './hooks/useAudioStatus';
 export type { UseAudioStatusResult } from './hooks/useAudioStatus';
 
@@ -149,6 +198,18 @@ export {
 // =============================================================================
 export { getMeterFrame } from './meter-ipc';
 export { getOscilloscopeFrame } from './oscilloscope-ipc';
+export { getSpectrumFrame } from './spectrum-ipc';
+export { getEqResponse } from './eq-ipc';
+
+// =============================================================================
+// Presets API
+// =============================================================================
+export { listPresets, loadPreset, savePreset, deletePreset } from './presets-ipc';
+
+// =============================================================================
+// Modulation API
+// =============================================================================
+export { getModulation, setModulation, getModulationFrame } from './modulation-ipc';
 export { linearToDb, dbToLinear } from './utils/audio-math';
 
 // =============================================================================
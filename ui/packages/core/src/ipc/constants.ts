@@ -5,7 +5,16 @@ export const IpcMethods = {
   GET_METER_FRAME: 'getMeterFrame',
   GET_AUDIO_STATUS: 'getAudioStatus',
   GET_OSCILLOSCOPE_FRAME: 'getOscilloscopeFrame',
+  GET_SPECTRUM_FRAME: 'getSpectrumFrame',
+  GET_EQ_RESPONSE: 'getEqResponse',
   REQUEST_RESIZE: 'requestResize',
+  LIST_PRESETS: 'listPresets',
+  LOAD_PRESET: 'loadPreset',
+  SAVE_PRESET: 'savePreset',
+  DELETE_PRESET: 'deletePreset',
+  GET_MODULATION: 'getModulation',
+  SET_MODULATION: 'setModulation',
+  GET_MODULATION_FRAME: 'getModulationFrame',
   PING: 'ping',
 } as const;
 
@@ -15,6 +24,7 @@ export const IpcEvents = {
   AUDIO_STATUS_CHANGED: 'audioStatusChanged',
   PARAM_UPDATE: 'paramUpdate',
   METER_FRAME: 'meterFrame',
+  SPECTRUM_UPDATE: 'spectrumUpdate',
   PARAMETER_CHANGED: 'parameterChanged',
   PARAMETERS_CHANGED: 'parametersChanged',
 } as const;
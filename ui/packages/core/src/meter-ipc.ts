@@ -4,13 +4,16 @@
 
 import { IpcBridge } from './ipc/IpcBridge';
 import { IpcMethods } from './ipc/constants';
-import type { MeterFrame, GetMeterFrameResult } from './types/metering';
+import type { MeterFrame, GetMeterFrameParams, GetMeterFrameResult } from './types/metering';
 
 /**
  * Get the latest meter frame from the audio engine
+ *
+ * @param tapId - ID of a named meter tap; omit for the output meter
  */
-export async function getMeterFrame(): Promise<MeterFrame | null> {
+export async function getMeterFrame(tapId?: string): Promise<MeterFrame | null> {
   const bridge = IpcBridge.getInstance();
-  const result = await bridge.invoke<GetMeterFrameResult>(IpcMethods.GET_METER_FRAME);
+  const params: GetMeterFrameParams | undefined = tapId ? { tap_id: tapId } : undefined;
+  const result = await bridge.invoke<GetMeterFrameResult>(IpcMethods.GET_METER_FRAME, params);
   return result.frame;
 }
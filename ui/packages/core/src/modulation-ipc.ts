@@ -0,0 +1,42 @@
+/**
+ * Modulation API (IPC-based)
+ */
+
+import { IpcBridge } from './ipc/IpcBridge';
+import { IpcMethods } from './ipc/constants';
+import type {
+  GetModulationFrameResult,
+  GetModulationResult,
+  ModulationConfig,
+  ModulationFrame,
+  SetModulationParams,
+} from './types/modulation';
+
+/**
+ * Get the modulation sources and routings
+ */
+export async function getModulation(): Promise<ModulationConfig> {
+  const bridge = IpcBridge.getInstance();
+  const result = await bridge.invoke<GetModulationResult>(IpcMethods.GET_MODULATION);
+  return result.config;
+}
+
+/**
+ * Replace the modulation sources and routings.
+ *
+ * Rejects with an invalid-params error for unknown targets or out-of-range settings.
+ */
+export async function setModulation(config: ModulationConfig): Promise<void> {
+  const bridge = IpcBridge.getInstance();
+  const params: SetModulationParams = { config };
+  await bridge.invoke(IpcMethods.SET_MODULATION, params);
+}
+
+/**
+ * Get the latest modulated parameter values
+ */
+export async function getModulationFrame(): Promise<ModulationFrame | null> {
+  const bridge = IpcBridge.getInstance();
+  const result = await bridge.invoke<GetModulationFrameResult>(IpcMethods.GET_MODULATION_FRAME);
+  return result.frame;
+}
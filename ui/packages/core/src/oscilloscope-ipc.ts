@@ -4,13 +4,23 @@
 
 import { IpcBridge } from './ipc/IpcBridge';
 import { METHOD_GET_OSCILLOSCOPE_FRAME } from './types/ipc';
-import type { GetOscilloscopeFrameResult, OscilloscopeFrame } from './types/oscilloscope';
+import type {
+  GetOscilloscopeFrameParams,
+  GetOscilloscopeFrameResult,
+  OscilloscopeFrame,
+} from './types/oscilloscope';
 
 /**
  * Get the latest oscilloscope frame from the audio engine
+ *
+ * @param tapId - ID of a named oscilloscope tap; omit for the plugin output
  */
-export async function getOscilloscopeFrame(): Promise<OscilloscopeFrame | null> {
+export async function getOscilloscopeFrame(tapId?: string): Promise<OscilloscopeFrame | null> {
   const bridge = IpcBridge.getInstance();
-  const result = await bridge.invoke<GetOscilloscopeFrameResult>(METHOD_GET_OSCILLOSCOPE_FRAME);
+  const params: GetOscilloscopeFrameParams | undefined = tapId ? { tap_id: tapId } : undefined;
+  const result = await bridge.invoke<GetOscilloscopeFrameResult>(
+    METHOD_GET_OSCILLOSCOPE_FRAME,
+    params
+  );
   return result.frame;
 }
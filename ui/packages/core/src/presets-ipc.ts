@@ -0,0 +1,55 @@
+/**
+ * Preset API (IPC-based)
+ */
+
+import { IpcBridge } from './ipc/IpcBridge';
+import { IpcMethods } from './ipc/constants';
+import type {
+  DeletePresetParams,
+  ListPresetsResult,
+  LoadPresetParams,
+  LoadPresetResult,
+  PresetSource,
+  PresetSummary,
+  SavePresetParams,
+  SavePresetResult,
+} from './types/presets';
+
+/**
+ * List factory and user presets
+ */
+export async function listPresets(): Promise<PresetSummary[]> {
+  const bridge = IpcBridge.getInstance();
+  const result = await bridge.invoke<ListPresetsResult>(IpcMethods.LIST_PRESETS);
+  return result.presets;
+}
+
+/**
+ * Apply a preset's parameter values.
+ *
+ * Resolves to the preset's parameter IDs that the plugin no longer has.
+ */
+export async function loadPreset(name: string, source: PresetSource): Promise<string[]> {
+  const bridge = IpcBridge.getInstance();
+  const params: LoadPresetParams = { name, source };
+  const result = await bridge.invoke<LoadPresetResult>(IpcMethods.LOAD_PRESET, params);
+  return result.skipped;
+}
+
+/**
+ * Save the current parameter values as a user preset
+ */
+export async function savePreset(params: SavePresetParams): Promise<PresetSummary> {
+  const bridge = IpcBridge.getInstance();
+  const result = await bridge.invoke<SavePresetResult>(IpcMethods.SAVE_PRESET, params);
+  return result.preset;
+}
+
+/**
+ * Delete a user preset
+ */
+export async function deletePreset(name: string): Promise<void> {
+  const bridge = IpcBridge.getInstance();
+  const params: DeletePresetParams = { name };
+  await bridge.invoke(IpcMethods.DELETE_PRESET, params);
+}
@@ -0,0 +1,23 @@
+/**
+ * Spectrum polling API for frequency-domain visualization (IPC-based)
+ */
+
+import { IpcBridge } from './ipc/IpcBridge';
+import { METHOD_GET_SPECTRUM_FRAME } from './types/ipc';
+import type {
+  GetSpectrumFrameParams,
+  GetSpectrumFrameResult,
+  SpectrumFrame,
+} from './types/spectrum';
+
+/**
+ * Get the latest spectrum frame from the audio engine
+ *
+ * @param tapId - ID of a named spectrum tap; omit for the plugin output
+ */
+export async function getSpectrumFrame(tapId?: string): Promise<SpectrumFrame | null> {
+  const bridge = IpcBridge.getInstance();
+  const params: GetSpectrumFrameParams | undefined = tapId ? { tap_id: tapId } : undefined;
+  const result = await bridge.invoke<GetSpectrumFrameResult>(METHOD_GET_SPECTRUM_FRAME, params);
+  return result.frame;
+}
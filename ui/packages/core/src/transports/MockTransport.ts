@@ -102,6 +102,8 @@ export class MockTransport implements Transport {
             peak_r: 0,
             rms_l: 0,
             rms_r: 0,
+            true_peak_l: 0,
+            true_peak_r: 0,
             timestamp: Date.now(),
           },
         };
@@ -118,6 +120,21 @@ export class MockTransport implements Transport {
           },
         };
 
+      case IpcMethods.GET_SPECTRUM_FRAME:
+        return {
+          frame: {
+            frequencies: [],
+            magnitudes_l: [],
+            magnitudes_r: [],
+            sample_rate: 44100,
+            fft_size: 2048,
+            timestamp: Date.now(),
+          },
+        };
+
+      case IpcMethods.GET_EQ_RESPONSE:
+        return { magnitudes_db: null };
+
       case IpcMethods.REQUEST_RESIZE:
         return { accepted: true };
 
@@ -0,0 +1,15 @@
+/**
+ * Parametric EQ types
+ */
+
+export interface GetEqResponseParams {
+  /** Frequencies in Hz to evaluate the response at. */
+  frequencies: number[];
+  /** Parameter ID prefix of the EQ (`eq` for `eq_band1_gain_db`); omit for the first EQ. */
+  eq_id?: string;
+}
+
+export interface GetEqResponseResult {
+  /** Combined response in dB, one value per requested frequency. */
+  magnitudes_db: number[] | null;
+}
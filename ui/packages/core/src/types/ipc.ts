@@ -58,12 +58,15 @@ export type AudioDiagnosticCode =
   | 'inputPermissionDenied'
   | 'noOutputDevice'
   | 'streamStartFailed'
+  | 'outputSanitized'
   | 'unknown';
 
 export interface AudioDiagnostic {
   code: AudioDiagnosticCode;
   message: string;
   hint?: string;
+  /** Number of occurrences, for repeating runtime events such as sanitized output. */
+  count?: number;
 }
 
 export interface AudioRuntimeStatus {
@@ -80,7 +83,10 @@ export interface GetAudioStatusResult {
 
 export const METHOD_GET_AUDIO_STATUS = IpcMethods.GET_AUDIO_STATUS;
 export const METHOD_GET_OSCILLOSCOPE_FRAME = IpcMethods.GET_OSCILLOSCOPE_FRAME;
+export const METHOD_GET_SPECTRUM_FRAME = IpcMethods.GET_SPECTRUM_FRAME;
+export const METHOD_GET_EQ_RESPONSE = IpcMethods.GET_EQ_RESPONSE;
 export const NOTIFICATION_AUDIO_STATUS_CHANGED = IpcEvents.AUDIO_STATUS_CHANGED;
+export const NOTIFICATION_SPECTRUM_UPDATE = IpcEvents.SPECTRUM_UPDATE;
 
 // ============================================================================
 // Error Codes (matching Rust constants)
@@ -93,6 +99,7 @@ export const ERROR_INVALID_PARAMS = -32602;
 export const ERROR_INTERNAL = -32603;
 export const ERROR_PARAM_NOT_FOUND = -32000;
 export const ERROR_PARAM_OUT_OF_RANGE = -32001;
+export const ERROR_PRESET_NOT_FOUND = -32002;
 
 // ============================================================================
 // Injected IPC Primitives (from Rust)
@@ -5,16 +5,40 @@
  */
 
 /**
- * Meter frame data (all values in linear scale, not dB)
+ * ITU-R BS.1770 / EBU R128 loudness (LUFS, range in LU).
+ *
+ * Each value is null until enough audio has been measured.
+ */
+export interface LoudnessFrame {
+  momentary_lufs: number | null;
+  short_term_lufs: number | null;
+  integrated_lufs: number | null;
+  loudness_range_lu: number | null;
+}
+
+/**
+ * Meter frame data (peak and RMS values in linear scale, not dB)
  */
 export interface MeterFrame {
   peak_l: number;
   peak_r: number;
   rms_l: number;
   rms_r: number;
+  /** 4x-oversampled true peak (0 when the host doesn't measure it) */
+  true_peak_l?: number;
+  true_peak_r?: number;
+  /** Present when the host measures loudness */
+  loudness?: LoudnessFrame;
   timestamp: number;
 }
 
+/**
+ * Parameters for getMeterFrame method. Omit `tap_id` for the output meter.
+ */
+export interface GetMeterFrameParams {
+  tap_id?: string;
+}
+
 /**
  * Result from getMeterFrame method
  */
@@ -0,0 +1,86 @@
+/**
+ * Modulation Types
+ *
+ * Types for the modulation IPC methods (matching engine/crates/wavecraft-protocol/src/modulation.rs).
+ */
+
+/**
+ * LFO waveform
+ */
+export type LfoShape = 'sine' | 'triangle' | 'saw' | 'square' | 'sampleAndHold';
+
+/**
+ * LFO speed: free-running in Hz, or a cycle length in quarter notes synced to the host tempo
+ */
+export type LfoRate = { type: 'hz'; hz: number } | { type: 'beats'; beats: number };
+
+/**
+ * A modulation source. LFOs output -1..1, envelope followers 0..1.
+ */
+export type ModulationSource =
+  | { type: 'lfo'; shape?: LfoShape; rate?: LfoRate; phase?: number }
+  | { type: 'envelopeFollower'; attack_ms: number; release_ms: number };
+
+/**
+ * How a source's output is mapped before scaling by the routing depth
+ */
+export type ModulationPolarity = 'bipolar' | 'unipolar';
+
+/**
+ * Routes a source to a parameter
+ */
+export interface ModulationRouting {
+  /** Index into ModulationConfig.sources */
+  source: number;
+  /** Parameter ID to modulate */
+  target: string;
+  /** Fraction of the parameter's range (-1..1) */
+  depth: number;
+  polarity?: ModulationPolarity;
+}
+
+/**
+ * Modulation sources and their routings (at most 8 sources and 32 routings)
+ */
+export interface ModulationConfig {
+  sources: ModulationSource[];
+  routings: ModulationRouting[];
+}
+
+/**
+ * Current value of a modulated parameter
+ */
+export interface ModulatedValue {
+  /** Value the processor sees, in the parameter's declared range */
+  value: number;
+  /** Modulation offset added to the parameter's own value */
+  offset: number;
+}
+
+/**
+ * Latest modulated values keyed by parameter ID (unmodulated parameters are omitted)
+ */
+export interface ModulationFrame {
+  values: Record<string, ModulatedValue>;
+}
+
+/**
+ * Result from getModulation method
+ */
+export interface GetModulationResult {
+  config: ModulationConfig;
+}
+
+/**
+ * Parameters for setModulation method
+ */
+export interface SetModulationParams {
+  config: ModulationConfig;
+}
+
+/**
+ * Result from getModulationFrame method
+ */
+export interface GetModulationFrameResult {
+  frame: ModulationFrame | null;
+}
@@ -15,6 +15,11 @@ export interface OscilloscopeFrame {
   trigger_mode: OscilloscopeTriggerMode;
 }
 
+/** Omit `tap_id` for the plugin's output waveform. */
+export interface GetOscilloscopeFrameParams {
+  tap_id?: string;
+}
+
 export interface GetOscilloscopeFrameResult {
   frame: OscilloscopeFrame | null;
 }
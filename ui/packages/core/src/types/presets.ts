@@ -0,0 +1,67 @@
+/**
+ * Preset Types
+ *
+ * Types for the preset IPC methods (matching engine/crates/wavecraft-protocol/src/presets.rs).
+ */
+
+/**
+ * Where a preset comes from: shipped with the plugin or saved by the user
+ */
+export type PresetSource = 'factory' | 'user';
+
+/**
+ * Preset listing entry
+ */
+export interface PresetSummary {
+  name: string;
+  source: PresetSource;
+  category?: string;
+  author?: string;
+}
+
+/**
+ * Result from listPresets method (factory presets first, then user presets)
+ */
+export interface ListPresetsResult {
+  presets: PresetSummary[];
+}
+
+/**
+ * Parameters for loadPreset method
+ */
+export interface LoadPresetParams {
+  name: string;
+  source: PresetSource;
+}
+
+/**
+ * Result from loadPreset method
+ */
+export interface LoadPresetResult {
+  /** Parameter IDs in the preset that the plugin no longer has */
+  skipped: string[];
+}
+
+/**
+ * Parameters for savePreset method (captures the current parameter values)
+ */
+export interface SavePresetParams {
+  name: string;
+  category?: string;
+  author?: string;
+  description?: string;
+}
+
+/**
+ * Result from savePreset method
+ */
+export interface SavePresetResult {
+  preset: PresetSummary;
+}
+
+/**
+ * Parameters for deletePreset method (user presets only)
+ */
+export interface DeletePresetParams {
+  name: string;
+}
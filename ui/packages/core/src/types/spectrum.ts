@@ -0,0 +1,24 @@
+/**
+ * Spectrum analyzer types
+ */
+
+export interface SpectrumFrame {
+  /** Band centre frequencies in Hz, log-spaced. */
+  frequencies: number[];
+  /** Left channel band magnitudes in dBFS. */
+  magnitudes_l: number[];
+  /** Right channel band magnitudes in dBFS. */
+  magnitudes_r: number[];
+  sample_rate: number;
+  fft_size: number;
+  timestamp: number;
+}
+
+/** Omit `tap_id` for the plugin's output spectrum. */
+export interface GetSpectrumFrameParams {
+  tap_id?: string;
+}
+
+export interface GetSpectrumFrameResult {
+  frame: SpectrumFrame | null;
+}